@@ -25,10 +25,12 @@ use supports_color::Stream;
 mod mcp_cmd;
 mod pre_main_hardening;
 mod subagents_cmd;
+mod workflow_cmd;
 
 use crate::mcp_cmd::McpCli;
 use crate::proto::ProtoCli;
 use crate::subagents_cmd::SubagentsCli;
+use crate::workflow_cmd::WorkflowCli;
 
 /// Codex CLI
 ///
@@ -90,6 +92,9 @@ enum Subcommand {
     /// Inspect and run subagents locally.
     Subagents(SubagentsCli),
 
+    /// List and run declarative subagent workflows.
+    Workflow(WorkflowCli),
+
     /// Internal: generate TypeScript protocol bindings.
     #[clap(hide = true)]
     GenerateTs(GenerateTsCommand),
@@ -264,6 +269,13 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             );
             subagents_cli.run().await?;
         }
+        Some(Subcommand::Workflow(mut workflow_cli)) => {
+            prepend_config_flags(
+                &mut workflow_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            workflow_cli.run().await?;
+        }
         Some(Subcommand::Exec(mut exec_cli)) => {
             prepend_config_flags(
                 &mut exec_cli.config_overrides,
@@ -1,5 +1,7 @@
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Context;
 use anyhow::anyhow;
@@ -10,14 +12,58 @@ use codex_core::AuthManager;
 use codex_core::ConversationManager;
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
+use codex_core::git_info::git_hooks_dir;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::SubAgentOutcome;
+use codex_core::protocol::TokenUsage;
 use codex_core::subagents::SubagentInvocation;
 use codex_core::subagents::SubagentOrchestrator;
+use codex_core::subagents::project_agents_dir;
+use codex_core::subagents::subagent_registry;
+use codex_core::subagents::user_agents_dir;
+use codex_exec::ensemble::EnsembleOptions;
+use codex_exec::ensemble::run_ensemble;
+use codex_exec::eval::EvalOptions;
+use codex_exec::eval::run_evals;
+use codex_exec::hook::HookRunOptions;
+use codex_exec::hook::run_hook;
+use codex_exec::watch::WatchOptions;
+use codex_exec::watch::run_watch;
+use codex_protocol::num_format::format_with_separators;
+use codex_subagents::ContextAttachment;
+use codex_subagents::HookError;
 use codex_subagents::RegistrySnapshot;
-use codex_subagents::SubagentRegistry;
+use codex_subagents::SubagentMetadata;
+use codex_subagents::SubagentSpec;
+use codex_subagents::TaskContextSnapshot;
+use codex_subagents::WorkflowCatalog;
+use codex_subagents::checkpoint::RunCheckpoint;
+use codex_subagents::checkpoint::RunStatus;
+use codex_subagents::checkpoint::RunStore;
+use codex_subagents::checkpoint::SlotCodecs;
+use codex_subagents::hooks::HookConfig;
+use codex_subagents::hooks::HookStage;
+use codex_subagents::hooks::HookState;
+use codex_subagents::hooks::hook_state;
+use codex_subagents::hooks::install_hook;
+use codex_subagents::hooks::parse_push_updates;
+use codex_subagents::hooks::uninstall_hook;
+use codex_subagents::memory::AgentMemory;
+use codex_subagents::memory::memory_dir;
+use codex_subagents::packs::AgentsLock;
+use codex_subagents::packs::LockedPack;
+use codex_subagents::packs::PackSource;
+use codex_subagents::packs::PackStore;
+use codex_subagents::pipeline::Severity;
+use codex_subagents::pipeline::TestResults;
+use codex_subagents::pipeline::TraceabilityReport;
+use codex_subagents::sarif::SarifLog;
+use codex_subagents::sarif::SarifRun;
+use codex_subagents::test_reports::to_junit_xml;
 use owo_colors::OwoColorize;
 
+use crate::workflow_cmd::run_checkpointed;
+
 #[derive(Debug, Parser)]
 pub(crate) struct SubagentsCli {
     #[clap(flatten)]
@@ -32,152 +78,1022 @@ pub(crate) enum SubagentsCommand {
     /// List discovered subagents from the project and user registries.
     List,
 
-    /// Run a subagent manually with an optional prompt payload.
+    /// Run one or more subagents with an optional prompt payload. Several
+    /// names fan out in parallel, bounded by `subagents.max_concurrency`.
     Run {
-        #[arg(value_name = "NAME")]
-        name: String,
+        #[arg(value_name = "NAME", required = true, num_args = 1..)]
+        names: Vec<String>,
 
         /// Optional prompt text forwarded to the subagent.
         #[arg(long = "prompt", value_name = "TEXT")]
         prompt: Option<String>,
+
+        /// Attach input to each agent's first message: `git_diff`,
+        /// `git_diff=<base>`, `files=<glob>[,<glob>...]`,
+        /// `parent_last_messages=<n>`, or `plan`. Repeatable.
+        #[arg(long = "context", value_name = "ATTACHMENT")]
+        context: Vec<ContextAttachment>,
+
+        /// Write review findings from the agents' final messages to FILE as
+        /// SARIF 2.1.0.
+        #[arg(long = "sarif", value_name = "FILE")]
+        sarif: Option<PathBuf>,
+
+        /// Run N samples of a single agent, each in its own git worktree,
+        /// and apply only the diff the judge picks.
+        #[arg(long = "samples", value_name = "N", conflicts_with = "sarif")]
+        samples: Option<usize>,
+
+        /// Models for the samples, assigned in turn. Without `--samples`,
+        /// runs one sample per model.
+        #[arg(long = "models", value_name = "MODEL", value_delimiter = ',')]
+        models: Vec<String>,
+
+        /// Sampling temperatures for the samples, assigned in turn. Without
+        /// `--samples`, runs one sample per temperature (or per model, if
+        /// there are more models).
+        #[arg(long = "temperatures", value_name = "T", value_delimiter = ',')]
+        temperatures: Vec<f64>,
+
+        /// Agent that ranks the samples.
+        #[arg(long = "judge", value_name = "NAME", default_value = "reviewer")]
+        judge: String,
+
+        /// Command run in each sample's worktree after the agent finishes;
+        /// its result is shown to the judge.
+        #[arg(long = "test", value_name = "COMMAND")]
+        test_command: Option<String>,
+    },
+
+    /// Rerun an agent in a read-only sandbox whenever watched files change,
+    /// with the changed files and their diff as context.
+    Watch {
+        #[arg(value_name = "NAME")]
+        agent: String,
+
+        /// Globs of files to watch, relative to the working directory.
+        /// Repeatable or comma-separated; defaults to every file git does not
+        /// ignore.
+        #[arg(long = "paths", value_name = "GLOB", value_delimiter = ',')]
+        paths: Vec<String>,
+
+        /// How long files must stay unchanged before a run, e.g. `500ms`,
+        /// `2s` or `1m`.
+        #[arg(long = "debounce", value_name = "DURATION", default_value = "2s", value_parser = parse_duration)]
+        debounce: Duration,
+
+        /// Instructions sent after the changed files and diff.
+        #[arg(long = "prompt", value_name = "TEXT")]
+        prompt: Option<String>,
+    },
+
+    /// Install an agent pack from a directory, a tarball, or
+    /// `<git-url>[@rev]` into `.codex/agents/packs/` and record it in
+    /// `.codex/agents/agents.lock`.
+    Install {
+        #[arg(value_name = "SOURCE")]
+        source: String,
+
+        /// Install into `$CODEX_HOME/agents` instead of the project.
+        #[arg(long = "user", default_value_t = false)]
+        user: bool,
+    },
+
+    /// Re-fetch installed packs from their recorded sources and refresh the
+    /// lockfile. Updates every pack when NAME is omitted.
+    Update {
+        #[arg(value_name = "NAME")]
+        name: Option<String>,
+
+        /// Move a git pack to another branch, tag or commit.
+        #[arg(long = "rev", value_name = "REV", requires = "name")]
+        rev: Option<String>,
+
+        /// Update packs in `$CODEX_HOME/agents` instead of the project.
+        #[arg(long = "user", default_value_t = false)]
+        user: bool,
+    },
+
+    /// Uninstall an agent pack and drop it from the lockfile.
+    Remove {
+        #[arg(value_name = "NAME")]
+        name: String,
+
+        /// Remove from `$CODEX_HOME/agents` instead of the project.
+        #[arg(long = "user", default_value_t = false)]
+        user: bool,
+    },
+
+    /// Run the eval cases in `.codex/agents/evals/<agent>/*.yaml`, each on a
+    /// scratch copy of its fixture, and print a pass/fail table.
+    Eval {
+        /// Only run this agent's cases.
+        #[arg(value_name = "NAME")]
+        agent: Option<String>,
+
+        /// Where to write the JSON report.
+        #[arg(
+            long = "report",
+            value_name = "FILE",
+            default_value = ".codex/agents/evals/report.json"
+        )]
+        report: PathBuf,
+    },
+
+    /// Review staged changes or pushed commits with an agent from a git hook.
+    Hook {
+        #[command(subcommand)]
+        action: HookCommand,
+    },
+
+    /// Continue a workflow run saved under `$CODEX_HOME/subagents/runs` from
+    /// its last completed step.
+    Resume {
+        #[arg(value_name = "RUN_ID")]
+        run_id: String,
+    },
+
+    /// Inspect or reset the notes agents with `memory: true` keep for this
+    /// project.
+    Memory {
+        #[command(subcommand)]
+        action: MemoryCommand,
+    },
+
+    /// Print a requirements traceability report from a saved task-context
+    /// snapshot.
+    Report {
+        #[arg(value_name = "CONTEXT_SNAPSHOT")]
+        snapshot: PathBuf,
+
+        /// Emit JSON instead of Markdown.
+        #[arg(long = "json", default_value_t = false)]
+        json: bool,
+
+        /// Also write the tester's results to FILE as JUnit XML.
+        #[arg(long = "junit", value_name = "FILE")]
+        junit: Option<PathBuf>,
+
+        /// Replace a test task's outcome with the per-test results in its
+        /// runner's report (libtest JSON, JUnit XML or jest JSON). Repeatable.
+        #[arg(long = "test-report", value_name = "TASK=FILE", value_parser = parse_test_report_arg)]
+        test_reports: Vec<(String, PathBuf)>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub(crate) enum MemoryCommand {
+    /// List agents with saved notes, or print one agent's notes.
+    List {
+        #[arg(value_name = "NAME")]
+        name: Option<String>,
+    },
+
+    /// Open an agent's notes in `$VISUAL` or `$EDITOR`.
+    Edit {
+        #[arg(value_name = "NAME")]
+        name: String,
+    },
+
+    /// Delete an agent's notes.
+    Clear {
+        #[arg(value_name = "NAME")]
+        name: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub(crate) enum HookCommand {
+    /// Write a git hook that runs an agent in a read-only sandbox and fails
+    /// on findings at or above `--severity`. `--no-verify` skips it.
+    Install {
+        #[arg(long = "stage", value_name = "STAGE", default_value = "pre-commit")]
+        stage: HookStage,
+
+        #[arg(long = "agent", value_name = "NAME")]
+        agent: String,
+
+        /// Lowest severity that blocks: info, low, medium, high or critical.
+        #[arg(long = "severity", value_name = "SEVERITY", default_value = "high")]
+        severity: Severity,
+
+        /// Replace a hook installed by another tool. It is restored on
+        /// uninstall.
+        #[arg(long = "force", default_value_t = false)]
+        force: bool,
+    },
+
+    /// Remove the codex hook for a stage, or for every stage when omitted.
+    Uninstall {
+        #[arg(long = "stage", value_name = "STAGE")]
+        stage: Option<HookStage>,
+    },
+
+    /// Show which git hooks run an agent.
+    Status,
+
+    /// Entry point of installed hooks.
+    #[command(hide = true)]
+    Run {
+        #[arg(long = "stage", value_name = "STAGE")]
+        stage: HookStage,
+
+        #[arg(long = "agent", value_name = "NAME")]
+        agent: String,
+
+        #[arg(long = "severity", value_name = "SEVERITY")]
+        severity: Severity,
+
+        /// Arguments git passes to the hook.
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        git_args: Vec<String>,
     },
 }
 
 impl SubagentsCli {
     pub(crate) async fn run(self) -> anyhow::Result<()> {
         let config = load_config(&self.config_overrides)?;
-        let project_agents = config.cwd.join(".codex/agents");
-        let user_agents = config.codex_home.join("agents");
 
         match self.command {
             SubagentsCommand::List => {
-                let snapshot = load_snapshot(project_agents, user_agents)?;
-                render_snapshot(&snapshot);
+                let snapshot = load_snapshot(&config)?;
+                render_snapshot(&snapshot, &config.cwd);
                 Ok(())
             }
-            SubagentsCommand::Run { name, prompt } => {
+            SubagentsCommand::Report {
+                snapshot,
+                json,
+                junit,
+                test_reports,
+            } => print_report(&snapshot, json, junit.as_deref(), &test_reports),
+            SubagentsCommand::Watch {
+                agent,
+                paths,
+                debounce,
+                prompt,
+            } => {
+                run_watch(
+                    config,
+                    WatchOptions {
+                        agent,
+                        paths,
+                        debounce,
+                        prompt,
+                    },
+                )
+                .await
+            }
+            SubagentsCommand::Install { source, user } => {
+                let store = pack_store(&config, user);
+                let source = PackSource::parse(&source, &config.cwd)?;
+                let locked = store.install(&source)?;
+                println!(
+                    "{} Installed pack {} from {source}",
+                    "✓".green(),
+                    describe_pack(&locked).cyan().bold()
+                );
+                print_pack_details(&locked, store.agents_dir());
+                Ok(())
+            }
+            SubagentsCommand::Update { name, rev, user } => {
+                let store = pack_store(&config, user);
+                let names = match name {
+                    Some(name) => vec![name],
+                    None => store
+                        .lock()?
+                        .packs
+                        .into_iter()
+                        .map(|pack| pack.name)
+                        .collect(),
+                };
+                if names.is_empty() {
+                    println!("{}", "No agent packs installed.".yellow());
+                    return Ok(());
+                }
+                for name in names {
+                    let (previous, current) = store.update(&name, rev.clone())?;
+                    if previous.hash == current.hash {
+                        println!(
+                            "  {} {} {}",
+                            "=".dimmed(),
+                            describe_pack(&current).cyan(),
+                            "already up to date".dimmed()
+                        );
+                    } else {
+                        println!(
+                            "  {} {} → {}",
+                            "✓".green(),
+                            describe_pack(&previous).cyan(),
+                            describe_pack(&current).cyan().bold()
+                        );
+                    }
+                }
+                Ok(())
+            }
+            SubagentsCommand::Remove { name, user } => {
+                let store = pack_store(&config, user);
+                let removed = store.remove(&name)?;
+                println!(
+                    "Removed pack {} ({}).",
+                    describe_pack(&removed).cyan(),
+                    removed.agents.join(", ")
+                );
+                Ok(())
+            }
+            SubagentsCommand::Eval { agent, report } => {
+                let report = run_evals(config, EvalOptions { agent, report }).await?;
+                if report.failed > 0 {
+                    anyhow::bail!(
+                        "{} of {} eval cases failed",
+                        report.failed,
+                        report.cases.len()
+                    );
+                }
+                Ok(())
+            }
+            SubagentsCommand::Hook { action } => run_hook_command(config, action).await,
+            SubagentsCommand::Resume { run_id } => resume_run(&config, &run_id).await,
+            SubagentsCommand::Memory { action } => run_memory_command(&config.cwd, action),
+            SubagentsCommand::Run {
+                names,
+                prompt,
+                context,
+                sarif,
+                samples,
+                models,
+                temperatures,
+                judge,
+                test_command,
+            } => {
                 if !config.subagents.enabled {
                     anyhow::bail!(
                         "Subagents feature is disabled in this configuration. Enable `subagents.enabled` to run subagents."
                     );
                 }
+                if samples.is_some() || !models.is_empty() || !temperatures.is_empty() {
+                    let [agent] = <[String; 1]>::try_from(names).map_err(|_| {
+                        anyhow!("`--samples`, `--models` and `--temperatures` run a single agent.")
+                    })?;
+                    if let Some(t) = temperatures.iter().find(|t| !(0.0..=2.0).contains(*t)) {
+                        anyhow::bail!("Temperature {t} is outside 0 to 2.");
+                    }
+                    let options = EnsembleOptions {
+                        agent,
+                        prompt,
+                        samples: samples.unwrap_or(models.len().max(temperatures.len())),
+                        models,
+                        temperatures,
+                        judge,
+                        test_command,
+                        context,
+                    };
+                    return run_ensemble(config, options).await;
+                }
 
-                let snapshot = load_snapshot(project_agents, user_agents)?;
-                let handle = snapshot
-                    .agents
+                let snapshot = load_snapshot(&config)?;
+                let specs = names
                     .iter()
-                    .find(|agent| agent.spec.metadata.name.eq_ignore_ascii_case(&name))
-                    .ok_or_else(|| anyhow!("Subagent '{}' not found.", name))?;
+                    .map(|name| {
+                        snapshot
+                            .agents
+                            .iter()
+                            .find(|agent| agent.spec.metadata.name.eq_ignore_ascii_case(name))
+                            .map(|handle| handle.spec.clone())
+                            .ok_or_else(|| anyhow!("Subagent '{}' not found.", name))
+                    })
+                    .collect::<anyhow::Result<Vec<SubagentSpec>>>()?;
 
                 let auth_manager = AuthManager::shared(config.codex_home.clone());
                 let conversation_manager = Arc::new(ConversationManager::new(auth_manager));
-                let orchestrator = SubagentOrchestrator::new(conversation_manager);
-                let spec = handle.spec.clone();
-                let agent_display = spec.metadata.name.clone();
+                let agents: Vec<SubagentSpec> = snapshot
+                    .agents
+                    .into_iter()
+                    .map(|handle| handle.spec)
+                    .collect();
+                let orchestrator = SubagentOrchestrator::new(conversation_manager)
+                    .with_handoff_agents(agents.clone())
+                    .with_context_attachments(context);
 
-                println!(
-                    "{} Starting subagent {}",
-                    "→".cyan(),
-                    agent_display.cyan().bold()
-                );
+                if let [spec] = specs.as_slice() {
+                    run_single(&orchestrator, &config, spec, &agents, prompt, sarif).await
+                } else {
+                    run_many(&orchestrator, &config, &specs, &agents, prompt, sarif).await
+                }
+            }
+        }
+    }
+}
 
-                let run_state = orchestrator
-                    .run_subagent(
-                        &config,
-                        SubagentInvocation {
-                            spec: &spec,
-                            parent_submit_id: format!("cli-subagent-{agent_display}"),
-                        },
-                        prompt,
-                        |msg| match msg {
-                            EventMsg::SubAgentStarted(ev) => {
-                                let runtime_model =
-                                    describe_model(&spec.metadata, ev.model.as_deref());
-                                println!(
-                                    "  {} {}",
-                                    "started".dimmed(),
-                                    format!("model: {runtime_model}").dimmed()
-                                );
-                            }
-                            EventMsg::SubAgentMessage(ev) => {
-                                for (idx, line) in ev.message.lines().enumerate() {
-                                    if idx == 0 {
-                                        println!("  {line}");
-                                    } else {
-                                        println!("    {line}");
-                                    }
-                                }
-                            }
-                            EventMsg::SubAgentCompleted(ev) => match ev.outcome {
-                                SubAgentOutcome::Success => {
-                                    let mut message = "Subagent completed successfully".to_string();
-                                    if let Some(ms) = ev.duration_ms {
-                                        message.push_str(&format!(" in {}", format_duration(ms)));
-                                    }
-                                    println!("{} {}", "✓".green(), message.green());
-                                }
-                                SubAgentOutcome::Error => {
-                                    let mut base = "Subagent failed".to_string();
-                                    if let Some(ms) = ev.duration_ms {
-                                        base.push_str(&format!(" after {}", format_duration(ms)));
-                                    }
-                                    if let Some(message) = ev.error.as_ref() {
-                                        println!(
-                                            "{} {}",
-                                            "✗".red(),
-                                            format!("{base}: {message}").red()
-                                        );
-                                    } else {
-                                        println!("{} {}", "✗".red(), base.red());
-                                    }
-                                }
-                            },
-                            EventMsg::Error(err) => {
-                                println!(
-                                    "{} {}",
-                                    "✗".red(),
-                                    format!("Subagent error: {}", err.message).red()
-                                );
-                            }
-                            EventMsg::StreamError(stream_err) => {
-                                println!(
-                                    "{} {}",
-                                    "!".magenta(),
-                                    format!("Stream warning: {}", stream_err.message).magenta()
-                                );
-                            }
-                            _ => {}
-                        },
-                    )
-                    .await?;
-
-                let duration_ms = run_state.duration.as_millis().min(u128::from(u64::MAX)) as u64;
+async fn resume_run(config: &Config, run_id: &str) -> anyhow::Result<()> {
+    if !config.subagents.enabled {
+        anyhow::bail!(
+            "Subagents feature is disabled in this configuration. Enable `subagents.enabled` to resume runs."
+        );
+    }
+
+    let mut checkpoint = RunCheckpoint::resume(
+        RunStore::for_codex_home(&config.codex_home),
+        SlotCodecs::pipeline(),
+        run_id,
+    )?;
+    let manifest = checkpoint.manifest();
+    if manifest.status == RunStatus::Succeeded {
+        anyhow::bail!(
+            "Run {run_id} of workflow '{}' already succeeded.",
+            manifest.workflow
+        );
+    }
+    let catalog = WorkflowCatalog::load(
+        &config.cwd.join(".codex/workflows"),
+        &config.codex_home.join("workflows"),
+    )
+    .context("failed to load workflows")?;
+    let workflow = catalog
+        .get(&manifest.workflow)
+        .ok_or_else(|| anyhow!("Workflow '{}' not found.", manifest.workflow))?;
+
+    println!(
+        "{} Resuming workflow {} {}",
+        "→".cyan(),
+        workflow.name.cyan().bold(),
+        format!("(run {run_id})").dimmed()
+    );
+    run_checkpointed(config, workflow, &mut checkpoint).await
+}
+
+async fn run_single(
+    orchestrator: &SubagentOrchestrator,
+    config: &Config,
+    spec: &SubagentSpec,
+    agents: &[SubagentSpec],
+    prompt: Option<String>,
+    sarif: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    let agent_display = spec.metadata.name.clone();
+
+    println!(
+        "{} Starting subagent {}",
+        "→".cyan(),
+        agent_display.cyan().bold()
+    );
+
+    let run_state = orchestrator
+        .run_subagent(
+            config,
+            SubagentInvocation {
+                spec,
+                parent_submit_id: format!("cli-subagent-{agent_display}"),
+            },
+            prompt,
+            |msg| {
+                // Agents reached through a handoff are labelled with their name.
+                match subagent_event_agent(&msg) {
+                    Some(name) if name != agent_display => {
+                        if let Some(next) = agents.iter().find(|agent| agent.metadata.name == name)
+                        {
+                            print_subagent_event(&msg, &next.metadata, &format!("[{name}] "));
+                        }
+                    }
+                    _ => print_subagent_event(&msg, &spec.metadata, ""),
+                }
+            },
+        )
+        .await?;
+
+    let duration_ms = run_state.duration.as_millis().min(u128::from(u64::MAX)) as u64;
+    println!(
+        "{}",
+        format!("Duration: {}", format_duration(duration_ms)).dimmed()
+    );
+    if let Some(tokens) = format_token_usage(&run_state.token_usage) {
+        println!("{}", format!("Tokens: {tokens}").dimmed());
+    }
+
+    match run_state.outcome {
+        SubAgentOutcome::Error => {
+            let detail = run_state
+                .error
+                .or(run_state.last_message.clone())
+                .unwrap_or_else(|| "unknown error".to_string());
+            Err(anyhow!(detail))
+        }
+        SubAgentOutcome::Success => {
+            if let Some(path) = sarif {
+                write_sarif(
+                    &path,
+                    &config.cwd,
+                    [(agent_display.as_str(), run_state.last_message.as_deref())],
+                )?;
+            }
+            if let Some(message) = run_state.last_message {
+                println!("{}", format!("Last message: {message}").dimmed());
+            }
+            Ok(())
+        }
+    }
+}
+
+/// `1,234 (1,000 input + 234 output)`, or `None` when nothing was used.
+fn format_token_usage(usage: &TokenUsage) -> Option<String> {
+    if usage.is_zero() {
+        return None;
+    }
+    Some(format!(
+        "{} ({} input + {} output)",
+        format_with_separators(usage.blended_total()),
+        format_with_separators(usage.non_cached_input()),
+        format_with_separators(usage.output_tokens)
+    ))
+}
+
+async fn run_many(
+    orchestrator: &SubagentOrchestrator,
+    config: &Config,
+    specs: &[SubagentSpec],
+    agents: &[SubagentSpec],
+    prompt: Option<String>,
+    sarif: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    let names: Vec<&str> = specs
+        .iter()
+        .map(|spec| spec.metadata.name.as_str())
+        .collect();
+    println!(
+        "{} Starting {} subagents in parallel (max {} at once): {}",
+        "→".cyan(),
+        specs.len(),
+        config.subagents.max_concurrency,
+        names.join(", ").cyan().bold()
+    );
+
+    let invocations = specs
+        .iter()
+        .map(|spec| SubagentInvocation {
+            spec,
+            parent_submit_id: format!("cli-subagent-{}", spec.metadata.name),
+        })
+        .collect();
+
+    let batch = orchestrator
+        .run_parallel(config, invocations, prompt, |msg| {
+            let Some(spec) = subagent_event_agent(&msg)
+                .and_then(|name| agents.iter().find(|spec| spec.metadata.name == name))
+            else {
+                // Errors that no single agent reported.
+                print_error_event(&msg, "[batch] ");
+                return;
+            };
+            let prefix = format!("[{}] ", spec.metadata.name);
+            print_subagent_event(&msg, &spec.metadata, &prefix);
+        })
+        .await;
+
+    println!("\n{}", "Summary".bold());
+    for entry in &batch.runs {
+        match &entry.result {
+            Ok(state) => {
+                let duration_ms = state.duration.as_millis().min(u128::from(u64::MAX)) as u64;
+                let duration = format_duration(duration_ms);
+                match state.outcome {
+                    SubAgentOutcome::Success => println!(
+                        "  {} {} {}",
+                        "✓".green(),
+                        entry.agent_name.cyan(),
+                        duration.dimmed()
+                    ),
+                    SubAgentOutcome::Error => println!(
+                        "  {} {} {} {}",
+                        "✗".red(),
+                        entry.agent_name.cyan(),
+                        duration.dimmed(),
+                        state.error.clone().unwrap_or_default().red()
+                    ),
+                }
+            }
+            Err(err) => println!("  {} {} {}", "✗".red(), entry.agent_name.cyan(), err.red()),
+        }
+    }
+    if let Some(tokens) = format_token_usage(&batch.token_usage()) {
+        println!("{}", format!("Tokens: {tokens}").dimmed());
+    }
+
+    if let Some(path) = sarif {
+        let messages = batch.runs.iter().filter_map(|entry| match &entry.result {
+            Ok(state) if state.outcome == SubAgentOutcome::Success => {
+                Some((entry.agent_name.as_str(), state.last_message.as_deref()))
+            }
+            _ => None,
+        });
+        write_sarif(&path, &config.cwd, messages)?;
+    }
+
+    if batch.all_succeeded() {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "{} of {} subagents failed",
+            batch.failed(),
+            batch.runs.len()
+        ))
+    }
+}
+
+/// Collect review findings from each agent's final message into one SARIF
+/// log, one run per agent. Agents whose message has no findings JSON are
+/// skipped with a warning.
+fn write_sarif<'a>(
+    path: &Path,
+    root: &Path,
+    messages: impl IntoIterator<Item = (&'a str, Option<&'a str>)>,
+) -> anyhow::Result<()> {
+    let mut log = SarifLog::default();
+    for (agent_name, message) in messages {
+        match message.and_then(|message| SarifRun::from_agent_message(agent_name, message, root)) {
+            Some(run) => log = log.with_run(run),
+            None => eprintln!(
+                "{} {agent_name} did not return review findings; leaving it out of {}",
+                "warning:".yellow(),
+                path.display()
+            ),
+        }
+    }
+    log.write_to(path)
+        .with_context(|| format!("failed to write {}", path.display()))?;
+    println!(
+        "{}",
+        format!("SARIF written to {}", path.display()).dimmed()
+    );
+    Ok(())
+}
+
+/// Name of the agent a subagent lifecycle event belongs to.
+pub(crate) fn subagent_event_agent(msg: &EventMsg) -> Option<&str> {
+    match msg {
+        EventMsg::SubAgentStarted(ev) => Some(ev.agent_name.as_str()),
+        EventMsg::SubAgentMessage(ev) => Some(ev.agent_name.as_str()),
+        EventMsg::SubAgentCompleted(ev) => Some(ev.agent_name.as_str()),
+        EventMsg::SubAgentHandoff(ev) => Some(ev.agent_name.as_str()),
+        EventMsg::SubAgentActivity(ev) => Some(ev.agent_name.as_str()),
+        _ => None,
+    }
+}
+
+pub(crate) fn print_subagent_event(msg: &EventMsg, metadata: &SubagentMetadata, prefix: &str) {
+    match msg {
+        EventMsg::SubAgentStarted(ev) => {
+            let runtime_model = describe_model(metadata, ev.model.as_deref());
+            println!(
+                "  {prefix}{} {}",
+                "started".dimmed(),
+                format!("model: {runtime_model}").dimmed()
+            );
+        }
+        EventMsg::SubAgentMessage(ev) => {
+            for (idx, line) in ev.message.lines().enumerate() {
+                if idx == 0 {
+                    println!("  {prefix}{line}");
+                } else {
+                    println!("    {prefix}{line}");
+                }
+            }
+        }
+        EventMsg::SubAgentCompleted(ev) => match ev.outcome {
+            SubAgentOutcome::Success => {
+                let mut message = "Subagent completed successfully".to_string();
+                if let Some(ms) = ev.duration_ms {
+                    message.push_str(&format!(" in {}", format_duration(ms)));
+                }
+                println!("{prefix}{} {}", "✓".green(), message.green());
+            }
+            SubAgentOutcome::Error => {
+                let mut base = "Subagent failed".to_string();
+                if let Some(ms) = ev.duration_ms {
+                    base.push_str(&format!(" after {}", format_duration(ms)));
+                }
+                if let Some(message) = ev.error.as_ref() {
+                    println!(
+                        "{prefix}{} {}",
+                        "✗".red(),
+                        format!("{base}: {message}").red()
+                    );
+                } else {
+                    println!("{prefix}{} {}", "✗".red(), base.red());
+                }
+            }
+        },
+        EventMsg::SubAgentHandoff(ev) => {
+            println!(
+                "  {prefix}{} {}",
+                "↪".cyan(),
+                format!("handing off to {}: {}", ev.target_agent, ev.note).cyan()
+            );
+        }
+        _ => print_error_event(msg, prefix),
+    }
+}
+
+/// Print `Error` and `StreamError` events; anything else is ignored.
+fn print_error_event(msg: &EventMsg, prefix: &str) {
+    match msg {
+        EventMsg::Error(err) => {
+            println!(
+                "{prefix}{} {}",
+                "✗".red(),
+                format!("Subagent error: {}", err.message).red()
+            );
+        }
+        EventMsg::StreamError(stream_err) => {
+            println!(
+                "{prefix}{} {}",
+                "!".magenta(),
+                format!("Stream warning: {}", stream_err.message).magenta()
+            );
+        }
+        _ => {}
+    }
+}
+
+fn pack_store(config: &Config, user: bool) -> PackStore {
+    if user {
+        PackStore::new(user_agents_dir(config))
+    } else {
+        PackStore::new(project_agents_dir(config))
+    }
+}
+
+/// `name 1.2.0 @ 0123abc`, leaving out what the pack does not record.
+fn describe_pack(pack: &LockedPack) -> String {
+    let mut label = pack.name.clone();
+    if let Some(version) = pack.version.as_deref() {
+        label.push_str(&format!(" {version}"));
+    }
+    if let Some(revision) = pack.revision.as_deref() {
+        label.push_str(&format!(" @ {}", &revision[..revision.len().min(7)]));
+    }
+    label
+}
+
+fn print_pack_details(pack: &LockedPack, agents_dir: &Path) {
+    println!("      agents: {}", pack.agents.join(", "));
+    println!("      hash: {}", pack.hash);
+    println!(
+        "{}",
+        format!("      locked in {}", AgentsLock::path(agents_dir).display()).dimmed()
+    );
+}
+
+async fn run_hook_command(config: Config, action: HookCommand) -> anyhow::Result<()> {
+    match action {
+        HookCommand::Install {
+            stage,
+            agent,
+            severity,
+            force,
+        } => {
+            let hooks_dir = hooks_dir(&config).await?;
+            let snapshot = load_snapshot(&config)?;
+            let Some(handle) = snapshot
+                .agents
+                .iter()
+                .find(|handle| handle.spec.metadata.name.eq_ignore_ascii_case(&agent))
+            else {
+                anyhow::bail!("Subagent '{agent}' not found.");
+            };
+            let hook = HookConfig {
+                stage,
+                agent: handle.spec.metadata.name.clone(),
+                severity,
+            };
+            let backup = install_hook(&hooks_dir, &hook, force).map_err(|err| match err {
+                HookError::ForeignHook(path) => anyhow!(
+                    "{} was not installed by codex; pass --force to replace it.",
+                    path.display()
+                ),
+                err => err.into(),
+            })?;
+            println!(
+                "{} Installed {stage} hook: {} blocks on {severity} or higher findings.",
+                "✓".green(),
+                hook.agent.cyan().bold()
+            );
+            if let Some(backup) = backup {
                 println!(
                     "{}",
-                    format!("Duration: {}", format_duration(duration_ms)).dimmed()
+                    format!("      previous hook moved to {}", backup.display()).dimmed()
                 );
+            }
+            println!(
+                "{}",
+                format!("      skip it once with `{}`", stage.bypass_hint()).dimmed()
+            );
+        }
+        HookCommand::Uninstall { stage } => {
+            let hooks_dir = hooks_dir(&config).await?;
+            let stages = match stage {
+                Some(stage) => vec![stage],
+                None => HookStage::ALL
+                    .into_iter()
+                    .filter(|stage| {
+                        matches!(hook_state(&hooks_dir, *stage), Ok(HookState::Installed(_)))
+                    })
+                    .collect(),
+            };
+            if stages.is_empty() {
+                println!("{}", "No codex git hooks installed.".yellow());
+            }
+            for stage in stages {
+                let restored = uninstall_hook(&hooks_dir, stage)?;
+                println!("Removed {stage} hook.");
+                if let Some(restored) = restored {
+                    println!(
+                        "{}",
+                        format!("      restored previous hook at {}", restored.display()).dimmed()
+                    );
+                }
+            }
+        }
+        HookCommand::Status => {
+            let hooks_dir = hooks_dir(&config).await?;
+            println!("{}", format!("Hooks in {}", hooks_dir.display()).dimmed());
+            for stage in HookStage::ALL {
+                let state = match hook_state(&hooks_dir, stage)? {
+                    HookState::Installed(hook) => format!(
+                        "{} (blocks on {} or higher)",
+                        hook.agent.cyan().bold(),
+                        hook.severity
+                    ),
+                    HookState::NotInstalled => "not installed".dimmed().to_string(),
+                    HookState::Foreign => "installed by another tool".yellow().to_string(),
+                };
+                println!("  {:<10} {state}", stage.as_str());
+            }
+        }
+        HookCommand::Run {
+            stage,
+            agent,
+            severity,
+            git_args: _,
+        } => {
+            let push_updates = match stage {
+                HookStage::PrePush => {
+                    parse_push_updates(&std::io::read_to_string(std::io::stdin())?)
+                }
+                HookStage::PreCommit => Vec::new(),
+            };
+            let options = HookRunOptions {
+                stage,
+                agent,
+                severity,
+                push_updates,
+            };
+            if !run_hook(config, options).await? {
+                std::process::exit(1);
+            }
+        }
+    }
+    Ok(())
+}
 
-                match run_state.outcome {
-                    SubAgentOutcome::Error => {
-                        let detail = run_state
-                            .error
-                            .or(run_state.last_message.clone())
-                            .unwrap_or_else(|| "unknown error".to_string());
-                        Err(anyhow!(detail))
-                    }
-                    SubAgentOutcome::Success => {
-                        if let Some(message) = run_state.last_message {
-                            println!("{}", format!("Last message: {message}").dimmed());
-                        }
-                        Ok(())
-                    }
+async fn hooks_dir(config: &Config) -> anyhow::Result<PathBuf> {
+    git_hooks_dir(&config.cwd)
+        .await
+        .ok_or_else(|| anyhow!("`codex subagents hook` needs a git repository."))
+}
+
+fn run_memory_command(project_root: &Path, action: MemoryCommand) -> anyhow::Result<()> {
+    match action {
+        MemoryCommand::List { name: None } => {
+            let dir = memory_dir(project_root);
+            let mut names: Vec<String> = match std::fs::read_dir(&dir) {
+                Ok(entries) => entries
+                    .filter_map(Result::ok)
+                    .map(|entry| entry.path())
+                    .filter(|path| path.extension().is_some_and(|ext| ext == "md"))
+                    .filter_map(|path| {
+                        path.file_stem()
+                            .and_then(|stem| stem.to_str())
+                            .map(str::to_string)
+                    })
+                    .collect(),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+                Err(err) => {
+                    return Err(err).with_context(|| format!("failed to read {}", dir.display()));
                 }
+            };
+            names.sort();
+            if names.is_empty() {
+                println!("{}", "No agent memory saved for this project.".yellow());
+                return Ok(());
             }
+            println!("{}", format!("Agent memory in {}:", dir.display()).bold());
+            for name in names {
+                let notes = AgentMemory::for_agent(project_root, &name)
+                    .and_then(|memory| memory.notes())
+                    .map(|notes| notes.len())
+                    .unwrap_or_default();
+                println!(
+                    "  • {} {}",
+                    name.cyan().bold(),
+                    format!("{notes} note(s)").dimmed()
+                );
+            }
+            Ok(())
+        }
+        MemoryCommand::List { name: Some(name) } => {
+            let memory = AgentMemory::for_agent(project_root, &name)?;
+            let notes = memory.notes()?;
+            if notes.is_empty() {
+                println!("{}", format!("No notes saved for {name}.").yellow());
+            } else {
+                for note in notes {
+                    println!("- {note}");
+                }
+            }
+            Ok(())
+        }
+        MemoryCommand::Edit { name } => {
+            let memory = AgentMemory::for_agent(project_root, &name)?;
+            memory.ensure_file()?;
+            let editor = std::env::var("VISUAL")
+                .or_else(|_| std::env::var("EDITOR"))
+                .ok()
+                .filter(|editor| !editor.trim().is_empty())
+                .ok_or_else(|| anyhow!("Set $VISUAL or $EDITOR to edit agent memory."))?;
+            let mut parts = editor.split_whitespace();
+            let program = parts.next().unwrap_or_default();
+            let status = std::process::Command::new(program)
+                .args(parts)
+                .arg(memory.path())
+                .status()
+                .with_context(|| format!("failed to launch {editor}"))?;
+            if !status.success() {
+                anyhow::bail!("{editor} exited with {status}");
+            }
+            Ok(())
+        }
+        MemoryCommand::Clear { name } => {
+            let memory = AgentMemory::for_agent(project_root, &name)?;
+            if memory.clear()? {
+                println!("Cleared memory for {}.", name.cyan());
+            } else {
+                println!("{}", format!("No notes saved for {name}.").yellow());
+            }
+            Ok(())
+        }
+    }
+}
+
+fn print_report(
+    path: &Path,
+    json: bool,
+    junit: Option<&Path>,
+    test_reports: &[(String, PathBuf)],
+) -> anyhow::Result<()> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let mut snapshot: TaskContextSnapshot = serde_json::from_str(&contents)
+        .with_context(|| format!("{} is not a task-context snapshot", path.display()))?;
+    let results: Option<TestResults> =
+        snapshot.scratchpad_field("subagents.tester.output", "results")?;
+    if !test_reports.is_empty() {
+        let mut results =
+            results.ok_or_else(|| anyhow!("{} has no tester results", path.display()))?;
+        for (task, report) in test_reports {
+            let text = std::fs::read_to_string(report)
+                .with_context(|| format!("failed to read {}", report.display()))?;
+            results
+                .import_report(task, &text, None)
+                .with_context(|| format!("failed to import {}", report.display()))?;
+        }
+        snapshot.set_scratchpad_field("subagents.tester.output", "results", &results)?;
+    }
+    if let Some(junit) = junit {
+        let results: TestResults = snapshot
+            .scratchpad_field("subagents.tester.output", "results")?
+            .ok_or_else(|| anyhow!("{} has no tester results", path.display()))?;
+        std::fs::write(junit, to_junit_xml(&results))
+            .with_context(|| format!("failed to write {}", junit.display()))?;
+    }
+    let report = TraceabilityReport::from_snapshot(&snapshot)?;
+    if json {
+        println!("{}", report.to_json()?);
+    } else {
+        print!("{}", report.to_markdown());
+    }
+    Ok(())
+}
+
+/// Parse `--test-report TASK=FILE`.
+fn parse_test_report_arg(value: &str) -> Result<(String, PathBuf), String> {
+    match value.split_once('=') {
+        Some((task, file)) if !task.trim().is_empty() && !file.is_empty() => {
+            Ok((task.trim().to_string(), PathBuf::from(file)))
         }
+        _ => Err(format!("expected TASK=FILE, got `{value}`")),
     }
 }
 
-fn load_config(overrides: &CliConfigOverrides) -> anyhow::Result<Config> {
+pub(crate) fn load_config(overrides: &CliConfigOverrides) -> anyhow::Result<Config> {
     let cli_overrides = overrides
         .parse_overrides()
         .map_err(|err| anyhow::anyhow!("failed to parse -c overrides: {err}"))?;
@@ -186,18 +1102,35 @@ fn load_config(overrides: &CliConfigOverrides) -> anyhow::Result<Config> {
         .context("failed to load Codex configuration")
 }
 
-fn load_snapshot(
-    project_agents: PathBuf,
-    user_agents: PathBuf,
-) -> anyhow::Result<RegistrySnapshot> {
-    let mut registry = SubagentRegistry::new(project_agents, user_agents);
+pub(crate) fn load_snapshot(config: &Config) -> anyhow::Result<RegistrySnapshot> {
+    let mut registry = subagent_registry(config);
     let snapshot = registry
         .reload()
         .context("failed to load subagent registry")?;
     Ok(snapshot.clone())
 }
 
-fn format_duration(ms: u64) -> String {
+/// Parse `<n>ms`, `<n>s` or `<n>m`; a bare number is seconds.
+fn parse_duration(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+    let amount: u64 = amount
+        .parse()
+        .map_err(|_| format!("invalid duration `{value}`; expected e.g. 500ms, 2s or 1m"))?;
+    match unit {
+        "ms" => Ok(Duration::from_millis(amount)),
+        "" | "s" => Ok(Duration::from_secs(amount)),
+        "m" => Ok(Duration::from_secs(amount * 60)),
+        _ => Err(format!(
+            "invalid duration unit `{unit}`; expected ms, s or m"
+        )),
+    }
+}
+
+pub(crate) fn format_duration(ms: u64) -> String {
     if ms >= 60_000 {
         let minutes = ms / 60_000;
         let seconds = (ms % 60_000) / 1_000;
@@ -218,10 +1151,7 @@ fn format_duration(ms: u64) -> String {
     }
 }
 
-fn describe_model(
-    metadata: &codex_subagents::SubagentMetadata,
-    runtime_model: Option<&str>,
-) -> String {
+fn describe_model(metadata: &SubagentMetadata, runtime_model: Option<&str>) -> String {
     let provider = metadata
         .model_config
         .as_ref()
@@ -246,7 +1176,7 @@ fn describe_model(
     summary
 }
 
-fn render_snapshot(snapshot: &RegistrySnapshot) {
+fn render_snapshot(snapshot: &RegistrySnapshot, cwd: &Path) {
     if snapshot.agents.is_empty() {
         println!("{}", "No subagents found.".yellow());
     } else {
@@ -264,6 +1194,18 @@ fn render_snapshot(snapshot: &RegistrySnapshot) {
                 metadata.tools.join(", ")
             };
             println!("  • {} [{}]", metadata.name.cyan().bold(), source);
+            if let Some(path) = handle.spec.source_path.as_deref() {
+                let path = path.strip_prefix(cwd).unwrap_or(path);
+                println!("      from: {}", path.display());
+            }
+            if !handle.overrides.is_empty() {
+                let overridden: Vec<&str> = handle
+                    .overrides
+                    .iter()
+                    .map(|source| source.describe())
+                    .collect();
+                println!("      overrides: {}", overridden.join(", "));
+            }
             if let Some(desc) = metadata.description.as_ref() {
                 println!("      {desc}");
             }
@@ -289,7 +1231,9 @@ fn render_snapshot(snapshot: &RegistrySnapshot) {
 #[cfg(test)]
 mod tests {
     use super::format_duration;
+    use super::parse_duration;
     use pretty_assertions::assert_eq;
+    use std::time::Duration;
 
     #[test]
     fn format_duration_formats_human_readable_values() {
@@ -298,4 +1242,14 @@ mod tests {
         assert_eq!(format_duration(1_250), "1.2s");
         assert_eq!(format_duration(75_000), "1m 15s");
     }
+
+    #[test]
+    fn parse_duration_accepts_common_units() {
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("2s"), Ok(Duration::from_secs(2)));
+        assert_eq!(parse_duration("3"), Ok(Duration::from_secs(3)));
+        assert_eq!(parse_duration("1m"), Ok(Duration::from_secs(60)));
+        assert!(parse_duration("2h").is_err());
+        assert!(parse_duration("fast").is_err());
+    }
 }
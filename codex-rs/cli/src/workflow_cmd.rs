@@ -0,0 +1,269 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use anyhow::Context;
+use anyhow::anyhow;
+use clap::Parser;
+use clap::Subcommand;
+use codex_common::CliConfigOverrides;
+use codex_core::AuthManager;
+use codex_core::ConversationManager;
+use codex_core::config::Config;
+use codex_core::protocol::EventMsg;
+use codex_core::subagents::SubagentOrchestrator;
+use codex_subagents::StepStatus;
+use codex_subagents::SubagentSpec;
+use codex_subagents::WorkflowCatalog;
+use codex_subagents::WorkflowDefinition;
+use codex_subagents::WorkflowReport;
+use codex_subagents::WorkflowRun;
+use codex_subagents::checkpoint::RunCheckpoint;
+use codex_subagents::checkpoint::RunStore;
+use codex_subagents::checkpoint::SlotCodecs;
+use owo_colors::OwoColorize;
+
+use crate::subagents_cmd::load_config;
+use crate::subagents_cmd::load_snapshot;
+use crate::subagents_cmd::print_subagent_event;
+use crate::subagents_cmd::subagent_event_agent;
+
+#[derive(Debug, Parser)]
+pub(crate) struct WorkflowCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    pub command: WorkflowCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub(crate) enum WorkflowCommand {
+    /// List workflows from `.codex/workflows` and `$CODEX_HOME/workflows`.
+    List,
+
+    /// Run a workflow by name.
+    Run {
+        #[arg(value_name = "NAME")]
+        name: String,
+
+        /// Workflow input as `key=value`; repeat for several inputs.
+        #[arg(long = "input", short = 'i', value_name = "KEY=VALUE", value_parser = parse_input)]
+        inputs: Vec<(String, String)>,
+    },
+}
+
+impl WorkflowCli {
+    pub(crate) async fn run(self) -> anyhow::Result<()> {
+        let config = load_config(&self.config_overrides)?;
+        let catalog = WorkflowCatalog::load(
+            &config.cwd.join(".codex/workflows"),
+            &config.codex_home.join("workflows"),
+        )
+        .context("failed to load workflows")?;
+
+        match self.command {
+            WorkflowCommand::List => {
+                render_catalog(&catalog);
+                Ok(())
+            }
+            WorkflowCommand::Run { name, inputs } => {
+                if !config.subagents.enabled {
+                    anyhow::bail!(
+                        "Subagents feature is disabled in this configuration. Enable `subagents.enabled` to run workflows."
+                    );
+                }
+
+                let workflow = catalog
+                    .get(&name)
+                    .ok_or_else(|| anyhow!("Workflow '{}' not found.", name))?;
+                let inputs: BTreeMap<String, String> = inputs.into_iter().collect();
+                // Reject bad inputs before a run directory is created for them.
+                WorkflowRun::new(workflow, inputs.clone())?;
+                let mut checkpoint = RunCheckpoint::start(
+                    RunStore::for_codex_home(&config.codex_home),
+                    SlotCodecs::pipeline(),
+                    &workflow.name,
+                    inputs,
+                )
+                .context("failed to create run checkpoint")?;
+
+                println!(
+                    "{} Running workflow {} {}",
+                    "→".cyan(),
+                    workflow.name.cyan().bold(),
+                    format!("(run {})", checkpoint.id()).dimmed()
+                );
+                run_checkpointed(&config, workflow, &mut checkpoint).await
+            }
+        }
+    }
+}
+
+/// Run `workflow` from wherever `checkpoint` left off and print its summary.
+pub(crate) async fn run_checkpointed(
+    config: &Config,
+    workflow: &WorkflowDefinition,
+    checkpoint: &mut RunCheckpoint,
+) -> anyhow::Result<()> {
+    let snapshot = load_snapshot(config)?;
+    let agents: Vec<SubagentSpec> = snapshot
+        .agents
+        .into_iter()
+        .map(|handle| handle.spec)
+        .collect();
+    let missing: Vec<&str> = workflow
+        .agent_names()
+        .into_iter()
+        .filter(|agent| {
+            !agents
+                .iter()
+                .any(|spec| spec.metadata.name.eq_ignore_ascii_case(agent))
+        })
+        .collect();
+    if !missing.is_empty() {
+        anyhow::bail!(
+            "Workflow '{}' references unknown subagents: {}",
+            workflow.name,
+            missing.join(", ")
+        );
+    }
+
+    let auth_manager = AuthManager::shared(config.codex_home.clone());
+    let conversation_manager = Arc::new(ConversationManager::new(auth_manager));
+    let orchestrator =
+        SubagentOrchestrator::new(conversation_manager).with_handoff_agents(agents.clone());
+
+    let report = orchestrator
+        .run_workflow_checkpointed(config, workflow, &agents, checkpoint, |msg| match &msg {
+            EventMsg::BackgroundEvent(ev) => {
+                println!("{} {}", "→".cyan(), ev.message.dimmed());
+            }
+            _ => {
+                if let Some(agent_name) = subagent_event_agent(&msg) {
+                    print_agent_event(&msg, agent_name, &agents);
+                }
+            }
+        })
+        .await?;
+
+    render_report(&report);
+    match report.status {
+        StepStatus::Failed => {
+            println!(
+                "\n{}",
+                format!(
+                    "Fix the problem and continue with `codex subagents resume {}`.",
+                    checkpoint.id()
+                )
+                .dimmed()
+            );
+            Err(anyhow!("Workflow '{}' failed", report.workflow))
+        }
+        StepStatus::Succeeded | StepStatus::Skipped => Ok(()),
+    }
+}
+
+fn parse_input(raw: &str) -> Result<(String, String), String> {
+    match raw.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.to_string()))
+        }
+        _ => Err(format!("expected KEY=VALUE, got `{raw}`")),
+    }
+}
+
+fn print_agent_event(msg: &EventMsg, agent_name: &str, agents: &[SubagentSpec]) {
+    if let Some(spec) = agents.iter().find(|spec| spec.metadata.name == agent_name) {
+        print_subagent_event(msg, &spec.metadata, &format!("[{agent_name}] "));
+    }
+}
+
+fn render_catalog(catalog: &WorkflowCatalog) {
+    if catalog.workflows.is_empty() {
+        println!("{}", "No workflows found.".yellow());
+    } else {
+        println!(
+            "{}",
+            format!("Discovered {} workflow(s):", catalog.workflows.len()).bold()
+        );
+        for workflow in &catalog.workflows {
+            println!("  • {}", workflow.name.cyan().bold());
+            if let Some(desc) = workflow.description.as_ref() {
+                println!("      {desc}");
+            }
+            let steps: Vec<&str> = workflow.steps.iter().map(|step| step.id.as_str()).collect();
+            println!("      steps: {}", steps.join(" → "));
+            if !workflow.inputs.is_empty() {
+                let inputs: Vec<String> = workflow
+                    .inputs
+                    .iter()
+                    .map(|input| match input.default.as_ref() {
+                        Some(default) => format!("{}={default}", input.name),
+                        None => input.name.clone(),
+                    })
+                    .collect();
+                println!("      inputs: {}", inputs.join(", "));
+            }
+        }
+    }
+
+    if !catalog.parse_errors.is_empty() {
+        println!("\n{}", "Parse errors:".red().bold());
+        for err in &catalog.parse_errors {
+            println!("  - {}\n      {}", err.path.display(), err.message);
+        }
+    }
+}
+
+fn render_report(report: &WorkflowReport) {
+    println!("\n{}", "Summary".bold());
+    for record in &report.steps {
+        let label = match record.agent.as_deref() {
+            Some(agent) => format!("{} ({agent})", record.step_id),
+            None => record.step_id.clone(),
+        };
+        let attempt = if record.attempt > 1 {
+            format!(" attempt {}", record.attempt)
+        } else {
+            String::new()
+        };
+        match record.result.status {
+            StepStatus::Succeeded => {
+                println!("  {} {}{}", "✓".green(), label.cyan(), attempt.dimmed())
+            }
+            StepStatus::Failed => println!(
+                "  {} {}{} {}",
+                "✗".red(),
+                label.cyan(),
+                attempt.dimmed(),
+                record.result.error.clone().unwrap_or_default().red()
+            ),
+            StepStatus::Skipped => {
+                println!("  {} {}", "-".dimmed(), format!("{label} skipped").dimmed())
+            }
+        }
+    }
+    if let Some(output) = report
+        .output
+        .as_deref()
+        .filter(|text| !text.trim().is_empty())
+    {
+        println!("\n{}\n{output}", "Output".bold());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_input;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse_input_splits_on_first_equals() {
+        assert_eq!(
+            parse_input("issue=a=b"),
+            Ok(("issue".to_string(), "a=b".to_string()))
+        );
+        assert!(parse_input("=value").is_err());
+        assert!(parse_input("novalue").is_err());
+    }
+}
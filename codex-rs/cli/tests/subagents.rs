@@ -3,6 +3,7 @@ use std::path::Path;
 
 use anyhow::Result;
 use assert_cmd::Command;
+use predicates::prelude::PredicateBooleanExt;
 use predicates::str::contains;
 use tempfile::TempDir;
 
@@ -39,6 +40,32 @@ fn list_displays_discovered_agents() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn list_shows_where_each_agent_came_from() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let project_dir = TempDir::new()?;
+    let agents_dir = project_dir.path().join(".codex/agents");
+    fs::create_dir_all(&agents_dir)?;
+    fs::write(
+        agents_dir.join("reviewer.toml"),
+        "name = \"reviewer\"\ninstructions = \"Review the diff.\"\n",
+    )?;
+    fs::write(
+        codex_home.path().join("config.toml"),
+        "[subagents.agents.summarizer]\ninstructions = \"Summarize.\"\n",
+    )?;
+
+    let mut cmd = codex_command(codex_home.path(), project_dir.path())?;
+    cmd.args(["subagents", "list"]).assert().success().stdout(
+        contains("[project]")
+            .and(contains("from: .codex/agents/reviewer.toml"))
+            .and(contains("overrides: builtin"))
+            .and(contains("[config]"))
+            .and(contains("summarizer")),
+    );
+    Ok(())
+}
+
 #[test]
 fn run_requires_feature_flag() -> Result<()> {
     let codex_home = TempDir::new()?;
@@ -54,3 +81,421 @@ fn run_requires_feature_flag() -> Result<()> {
     assert!(stderr.contains("Subagents feature is disabled"));
     Ok(())
 }
+
+#[test]
+fn run_rejects_unknown_context_attachment() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let project_dir = TempDir::new()?;
+
+    let mut cmd = codex_command(codex_home.path(), project_dir.path())?;
+    let output = cmd
+        .args([
+            "subagents",
+            "run",
+            "reviewer",
+            "--context",
+            "parent_last_messages=0",
+        ])
+        .output()?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(
+        stderr.contains("parent_last_messages must be at least 1"),
+        "{stderr}"
+    );
+    Ok(())
+}
+
+#[test]
+fn watch_requires_a_git_repository() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let project_dir = TempDir::new()?;
+    fs::write(
+        codex_home.path().join("config.toml"),
+        "[subagents]\nenabled = true\n",
+    )?;
+
+    codex_command(codex_home.path(), project_dir.path())?
+        .args([
+            "subagents",
+            "watch",
+            "reviewer",
+            "--paths",
+            "src/**",
+            "--debounce",
+            "500ms",
+        ])
+        .assert()
+        .failure()
+        .stderr(contains("needs a git repository"));
+    codex_command(codex_home.path(), project_dir.path())?
+        .args(["subagents", "watch", "reviewer", "--debounce", "soon"])
+        .assert()
+        .failure()
+        .stderr(contains("invalid duration"));
+    Ok(())
+}
+
+#[test]
+fn ensemble_run_validates_arguments() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let project_dir = TempDir::new()?;
+    fs::write(
+        codex_home.path().join("config.toml"),
+        "[subagents]\nenabled = true\n",
+    )?;
+
+    codex_command(codex_home.path(), project_dir.path())?
+        .args(["subagents", "run", "reviewer", "planner", "--samples", "2"])
+        .assert()
+        .failure()
+        .stderr(contains("run a single agent"));
+    codex_command(codex_home.path(), project_dir.path())?
+        .args(["subagents", "run", "reviewer", "--samples", "1"])
+        .assert()
+        .failure()
+        .stderr(contains("at least two samples"));
+    codex_command(codex_home.path(), project_dir.path())?
+        .args(["subagents", "run", "reviewer", "--temperatures", "0.2,3"])
+        .assert()
+        .failure()
+        .stderr(contains("Temperature 3 is outside 0 to 2."));
+    codex_command(codex_home.path(), project_dir.path())?
+        .args([
+            "subagents",
+            "run",
+            "reviewer",
+            "--models",
+            "gpt-5,gpt-5-codex",
+        ])
+        .assert()
+        .failure()
+        .stderr(contains("need a git repository"));
+    Ok(())
+}
+
+#[test]
+fn eval_reports_missing_cases() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let project_dir = TempDir::new()?;
+
+    codex_command(codex_home.path(), project_dir.path())?
+        .args(["subagents", "eval"])
+        .assert()
+        .failure()
+        .stderr(contains("No eval cases found"));
+    codex_command(codex_home.path(), project_dir.path())?
+        .args(["subagents", "eval", "reviewer"])
+        .assert()
+        .failure()
+        .stderr(contains("no eval cases for agent `reviewer`"));
+    Ok(())
+}
+
+#[test]
+fn report_renders_traceability_from_snapshot() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let project_dir = TempDir::new()?;
+    let snapshot = project_dir.path().join("context.json");
+    fs::write(
+        &snapshot,
+        r#"{
+  "typed_slots": [],
+  "scratchpads": {
+    "subagents.spec_parser.output": {
+      "format": "markdown",
+      "spec": {
+        "title": "Export",
+        "overview": "Export reports",
+        "requirements": [{
+          "id": "REQ-001",
+          "summary": "Export as CSV",
+          "acceptance_criteria": [{ "id": "AC-001", "text": "CSV is written" }]
+        }]
+      }
+    },
+    "subagents.tester.output": {
+      "results": {
+        "plan": {
+          "summary": "1 task",
+          "tasks": [{ "name": "cargo test", "command": "cargo test", "related_requirements": ["REQ-001"] }]
+        },
+        "outcomes": [{ "task_name": "cargo test", "status": "passed" }]
+      }
+    }
+  },
+  "diagnostics": []
+}"#,
+    )?;
+
+    let mut cmd = codex_command(codex_home.path(), project_dir.path())?;
+    cmd.args(["subagents", "report"])
+        .arg(&snapshot)
+        .assert()
+        .success()
+        .stdout(contains("# Traceability: Export"))
+        .stdout(contains("| AC-001: CSV is written | no |"));
+
+    let junit = project_dir.path().join("junit.xml");
+    let mut cmd = codex_command(codex_home.path(), project_dir.path())?;
+    cmd.args(["subagents", "report", "--json", "--junit"])
+        .arg(&junit)
+        .arg(&snapshot)
+        .assert()
+        .success()
+        .stdout(contains("\"coverage_percent\": 0"));
+    assert!(
+        fs::read_to_string(&junit)?
+            .contains(r#"<testcase name="cargo test" classname="cargo test"/>"#)
+    );
+
+    let libtest = project_dir.path().join("libtest.json");
+    fs::write(
+        &libtest,
+        r#"{ "type": "test", "event": "ok", "name": "export::writes_csv" }
+{ "type": "test", "event": "failed", "name": "export::writes_header", "stdout": "missing header" }
+"#,
+    )?;
+    let mut cmd = codex_command(codex_home.path(), project_dir.path())?;
+    cmd.args(["subagents", "report", "--junit"])
+        .arg(&junit)
+        .arg("--test-report")
+        .arg(format!("cargo test={}", libtest.display()))
+        .arg(&snapshot)
+        .assert()
+        .success()
+        .stdout(contains("- `cargo test`: failed"));
+    assert!(fs::read_to_string(&junit)?.contains(r#"name="export::writes_header""#));
+
+    let mut cmd = codex_command(codex_home.path(), project_dir.path())?;
+    cmd.args(["subagents", "report", "--test-report"])
+        .arg(format!("lint={}", libtest.display()))
+        .arg(&snapshot)
+        .assert()
+        .failure()
+        .stderr(contains("no test task named `lint`"));
+    Ok(())
+}
+
+#[test]
+fn resume_rejects_unknown_run() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let project_dir = TempDir::new()?;
+    fs::write(
+        codex_home.path().join("config.toml"),
+        "[subagents]\nenabled = true\n",
+    )?;
+
+    let mut cmd = codex_command(codex_home.path(), project_dir.path())?;
+    let output = cmd.args(["subagents", "resume", "missing-run"]).output()?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(stderr.contains("no saved run `missing-run`"), "{stderr}");
+    Ok(())
+}
+
+#[test]
+fn memory_lists_and_clears_agent_notes() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let project_dir = TempDir::new()?;
+    let memory_dir = project_dir.path().join(".codex/agents/.memory");
+    fs::create_dir_all(&memory_dir)?;
+    fs::write(
+        memory_dir.join("reviewer.md"),
+        "- Errors use thiserror\n- Tests use pretty_assertions\n",
+    )?;
+
+    codex_command(codex_home.path(), project_dir.path())?
+        .args(["subagents", "memory", "list"])
+        .assert()
+        .success()
+        .stdout(contains("reviewer").and(contains("2 note(s)")));
+    codex_command(codex_home.path(), project_dir.path())?
+        .args(["subagents", "memory", "list", "reviewer"])
+        .assert()
+        .success()
+        .stdout(contains("- Tests use pretty_assertions"));
+    codex_command(codex_home.path(), project_dir.path())?
+        .args(["subagents", "memory", "clear", "reviewer"])
+        .assert()
+        .success()
+        .stdout(contains("Cleared memory for"));
+    assert!(!memory_dir.join("reviewer.md").exists());
+    Ok(())
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = std::process::Command::new("git")
+        .args([
+            "-c",
+            "user.name=Test",
+            "-c",
+            "user.email=test@example.com",
+            "-c",
+            "init.defaultBranch=main",
+        ])
+        .args(args)
+        .current_dir(dir)
+        .output()?;
+    anyhow::ensure!(
+        output.status.success(),
+        "git {args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Ok(String::from_utf8(output.stdout)?.trim().to_string())
+}
+
+#[test]
+fn install_update_and_remove_pack_from_git() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let project_dir = TempDir::new()?;
+    let remote = TempDir::new()?;
+    let bare = remote.path().join("team-agents.git");
+    let work = remote.path().join("work");
+    fs::create_dir_all(&bare)?;
+    git(&bare, &["init", "--bare", "--quiet"])?;
+    git(
+        remote.path(),
+        &["clone", "--quiet", "team-agents.git", "work"],
+    )?;
+    fs::write(
+        work.join("pack.toml"),
+        "name = \"team-agents\"\nversion = \"1.0.0\"\n",
+    )?;
+    write_agent(&work.join("linter.md"), "linter")?;
+    git(&work, &["add", "."])?;
+    git(&work, &["commit", "--quiet", "-m", "v1"])?;
+    git(&work, &["tag", "v1"])?;
+    git(&work, &["push", "--quiet", "origin", "HEAD:main", "v1"])?;
+    let v1 = git(&work, &["rev-parse", "HEAD"])?;
+
+    codex_command(codex_home.path(), project_dir.path())?
+        .args(["subagents", "install"])
+        .arg(format!("{}@v1", bare.display()))
+        .assert()
+        .success()
+        .stdout(contains("Installed pack").and(contains("agents: linter")));
+    let agents_dir = project_dir.path().join(".codex/agents");
+    let lock = fs::read_to_string(agents_dir.join("agents.lock"))?;
+    assert!(lock.contains("name = \"team-agents\""), "{lock}");
+    assert!(lock.contains("rev = \"v1\""), "{lock}");
+    assert!(lock.contains(&format!("revision = \"{v1}\"")), "{lock}");
+    assert!(lock.contains("hash = \"sha256:"), "{lock}");
+    codex_command(codex_home.path(), project_dir.path())?
+        .args(["subagents", "list"])
+        .assert()
+        .success()
+        .stdout(contains("from: .codex/agents/packs/team-agents/linter.md"));
+
+    let installed = agents_dir.join("packs/team-agents/linter.md");
+    fs::write(&installed, "---\nname: linter\n---\nTampered.\n")?;
+    codex_command(codex_home.path(), project_dir.path())?
+        .args(["subagents", "list"])
+        .assert()
+        .success()
+        .stdout(
+            contains("does not match agents.lock").and(contains("from: .codex/agents/packs").not()),
+        );
+
+    write_agent(&work.join("formatter.md"), "formatter")?;
+    git(&work, &["add", "."])?;
+    git(&work, &["commit", "--quiet", "-m", "v2"])?;
+    git(&work, &["push", "--quiet", "origin", "HEAD:main"])?;
+    let v2 = git(&work, &["rev-parse", "HEAD"])?;
+
+    codex_command(codex_home.path(), project_dir.path())?
+        .args(["subagents", "update"])
+        .assert()
+        .success()
+        .stdout(contains(&v1[..7]));
+    assert!(fs::read_to_string(&installed)?.contains("Perform test task."));
+    codex_command(codex_home.path(), project_dir.path())?
+        .args(["subagents", "update", "team-agents", "--rev", "main"])
+        .assert()
+        .success()
+        .stdout(contains(&v2[..7]));
+    let lock = fs::read_to_string(agents_dir.join("agents.lock"))?;
+    assert!(lock.contains("rev = \"main\""), "{lock}");
+    assert!(lock.contains(&format!("revision = \"{v2}\"")), "{lock}");
+    assert!(
+        lock.contains("agents = [\"formatter\", \"linter\"]"),
+        "{lock}"
+    );
+
+    codex_command(codex_home.path(), project_dir.path())?
+        .args(["subagents", "remove", "team-agents"])
+        .assert()
+        .success()
+        .stdout(contains("Removed pack"));
+    assert!(!agents_dir.join("packs/team-agents").exists());
+    let lock = fs::read_to_string(agents_dir.join("agents.lock"))?;
+    assert!(!lock.contains("team-agents"), "{lock}");
+    codex_command(codex_home.path(), project_dir.path())?
+        .args(["subagents", "remove", "team-agents"])
+        .assert()
+        .failure()
+        .stderr(contains("pack `team-agents` is not installed"));
+    Ok(())
+}
+
+#[test]
+fn hook_install_status_and_uninstall() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let project_dir = TempDir::new()?;
+    let repo = project_dir.path();
+    git(repo, &["init", "--quiet"])?;
+    let agents_dir = repo.join(".codex/agents");
+    fs::create_dir_all(&agents_dir)?;
+    write_agent(&agents_dir.join("reviewer.md"), "reviewer")?;
+    let hooks_dir = repo.join(".git/hooks");
+    fs::create_dir_all(&hooks_dir)?;
+    fs::write(hooks_dir.join("pre-push"), "#!/bin/sh\nexit 0\n")?;
+
+    codex_command(codex_home.path(), repo)?
+        .args(["subagents", "hook", "install", "--agent", "reviewer"])
+        .args(["--severity", "medium"])
+        .assert()
+        .success()
+        .stdout(contains("Installed pre-commit hook"));
+    let script = fs::read_to_string(hooks_dir.join("pre-commit"))?;
+    assert!(script.contains("--stage pre-commit --agent reviewer --severity medium"));
+    codex_command(codex_home.path(), repo)?
+        .args(["subagents", "hook", "install", "--stage", "pre-push"])
+        .args(["--agent", "reviewer"])
+        .assert()
+        .failure()
+        .stderr(contains("pass --force to replace it"));
+    codex_command(codex_home.path(), repo)?
+        .args(["subagents", "hook", "install", "--agent", "missing"])
+        .assert()
+        .failure()
+        .stderr(contains("Subagent 'missing' not found."));
+    codex_command(codex_home.path(), repo)?
+        .args(["subagents", "hook", "status"])
+        .assert()
+        .success()
+        .stdout(contains("blocks on medium or higher").and(contains("installed by another tool")));
+
+    // The installed hook lets commits without staged changes through.
+    let output = std::process::Command::new("git")
+        .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+        .args(["commit", "--allow-empty", "--quiet", "-m", "empty"])
+        .env("CODEX_HOME", codex_home.path())
+        .env("CODEX_BIN", assert_cmd::cargo::cargo_bin("codex"))
+        .current_dir(repo)
+        .output()?;
+    assert!(output.status.success(), "{output:?}");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("nothing to review"));
+
+    codex_command(codex_home.path(), repo)?
+        .args(["subagents", "hook", "uninstall"])
+        .assert()
+        .success()
+        .stdout(contains("Removed pre-commit hook."));
+    assert!(!hooks_dir.join("pre-commit").exists());
+    assert_eq!(
+        fs::read_to_string(hooks_dir.join("pre-push"))?,
+        "#!/bin/sh\nexit 0\n"
+    );
+    Ok(())
+}
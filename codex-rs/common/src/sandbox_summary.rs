@@ -9,11 +9,14 @@ pub fn summarize_sandbox_policy(sandbox_policy: &SandboxPolicy) -> String {
             network_access,
             exclude_tmpdir_env_var,
             exclude_slash_tmp,
+            exclude_cwd,
         } => {
             let mut summary = "workspace-write".to_string();
 
             let mut writable_entries = Vec::<String>::new();
-            writable_entries.push("workdir".to_string());
+            if !*exclude_cwd {
+                writable_entries.push("workdir".to_string());
+            }
             if !*exclude_slash_tmp {
                 writable_entries.push("/tmp".to_string());
             }
@@ -32,6 +32,7 @@ use codex_protocol::models::ResponseItem;
 pub(crate) async fn stream_chat_completions(
     prompt: &Prompt,
     model_family: &ModelFamily,
+    temperature: Option<f64>,
     client: &reqwest::Client,
     provider: &ModelProviderInfo,
 ) -> Result<ResponseStream> {
@@ -274,12 +275,15 @@ pub(crate) async fn stream_chat_completions(
     }
 
     let tools_json = create_tools_json_for_chat_completions_api(&prompt.tools)?;
-    let payload = json!({
+    let mut payload = json!({
         "model": model_family.slug,
         "messages": messages,
         "stream": true,
         "tools": tools_json,
     });
+    if let Some(temperature) = temperature {
+        payload["temperature"] = json!(temperature);
+    }
 
     debug!(
         "POST to {}: {}",
@@ -31,6 +31,7 @@ use crate::client_common::ResponseStream;
 use crate::client_common::ResponsesApiRequest;
 use crate::client_common::create_reasoning_param_for_request;
 use crate::client_common::create_text_param_for_request;
+use crate::client_common::temperature_for_request;
 use crate::config::Config;
 use crate::default_client::create_client;
 use crate::error::CodexErr;
@@ -129,6 +130,10 @@ impl ModelClient {
                 let response_stream = stream_chat_completions(
                     prompt,
                     &self.config.model_family,
+                    temperature_for_request(
+                        &self.config.model_family,
+                        self.config.model_temperature,
+                    ),
                     &self.client,
                     &self.provider,
                 )
@@ -227,6 +232,10 @@ impl ModelClient {
             include,
             prompt_cache_key: Some(self.conversation_id.to_string()),
             text,
+            temperature: temperature_for_request(
+                &self.config.model_family,
+                self.config.model_temperature,
+            ),
         };
 
         let mut payload_json = serde_json::to_value(&payload)?;
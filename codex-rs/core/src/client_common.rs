@@ -158,6 +158,8 @@ pub(crate) struct ResponsesApiRequest<'a> {
     pub(crate) prompt_cache_key: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) text: Option<TextControls>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) temperature: Option<f64>,
 }
 
 pub(crate) fn create_reasoning_param_for_request(
@@ -175,6 +177,18 @@ pub(crate) fn create_reasoning_param_for_request(
     })
 }
 
+/// Reasoning models reject `temperature`, so it is only sent to model
+/// families without reasoning support.
+pub(crate) fn temperature_for_request(
+    model_family: &ModelFamily,
+    temperature: Option<f64>,
+) -> Option<f64> {
+    if model_family.supports_reasoning_summaries {
+        return None;
+    }
+    temperature
+}
+
 pub(crate) fn create_text_param_for_request(
     verbosity: Option<VerbosityConfig>,
     output_schema: &Option<Value>,
@@ -269,6 +283,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn temperature_is_dropped_for_reasoning_models() {
+        for (slug, expected) in [
+            ("gpt-5", None),
+            ("gpt-5-codex", None),
+            ("o3", None),
+            ("gpt-4o", Some(0.7)),
+            ("gpt-oss:120b", Some(0.7)),
+        ] {
+            let model_family = find_family_for_model(slug).expect("known model slug");
+            assert_eq!(
+                temperature_for_request(&model_family, Some(0.7)),
+                expected,
+                "{slug}"
+            );
+        }
+    }
+
     #[test]
     fn serializes_text_verbosity_when_set() {
         let input: Vec<ResponseItem> = vec![];
@@ -289,6 +321,7 @@ mod tests {
                 verbosity: Some(OpenAiVerbosity::Low),
                 format: None,
             }),
+            temperature: None,
         };
 
         let v = serde_json::to_value(&req).expect("json");
@@ -327,6 +360,7 @@ mod tests {
             include: vec![],
             prompt_cache_key: None,
             text: Some(text_controls),
+            temperature: None,
         };
 
         let v = serde_json::to_value(&req).expect("json");
@@ -363,6 +397,7 @@ mod tests {
             include: vec![],
             prompt_cache_key: None,
             text: None,
+            temperature: None,
         };
 
         let v = serde_json::to_value(&req).expect("json");
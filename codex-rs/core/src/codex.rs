@@ -118,6 +118,14 @@ use crate::safety::assess_safety_for_untrusted_command;
 use crate::shell;
 use crate::state::ActiveTurn;
 use crate::state::SessionServices;
+use crate::subagents::handoff::HANDOFF_TOOL_NAME;
+use crate::subagents::handoff::create_handoff_tool;
+use crate::subagents::handoff::handle_handoff;
+use crate::subagents::memory::RECALL_TOOL_NAME;
+use crate::subagents::memory::REMEMBER_TOOL_NAME;
+use crate::subagents::memory::create_memory_tools;
+use crate::subagents::memory::handle_recall;
+use crate::subagents::memory::handle_remember;
 use crate::tasks::CompactTask;
 use crate::tasks::RegularTask;
 use crate::tasks::ReviewTask;
@@ -137,6 +145,7 @@ use codex_protocol::models::ResponseInputItem;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::models::ShellToolCallParams;
 use codex_protocol::protocol::InitialHistory;
+use codex_subagents::memory::AgentMemory;
 
 pub mod compact;
 use self::compact::build_compacted_history;
@@ -287,6 +296,8 @@ pub(crate) struct TurnContext {
     pub(crate) final_output_json_schema: Option<Value>,
     pub(crate) subagent_name: Option<String>,
     pub(crate) subagent_tool_allowlist: Option<Vec<String>>,
+    pub(crate) subagent_handoff_targets: Vec<String>,
+    pub(crate) subagent_memory: Option<AgentMemory>,
 }
 
 impl TurnContext {
@@ -457,6 +468,8 @@ impl Session {
             final_output_json_schema: None,
             subagent_name: config.subagents.active_agent.clone(),
             subagent_tool_allowlist: config.subagents.tool_allowlist.clone(),
+            subagent_handoff_targets: config.subagents.handoff_to.clone(),
+            subagent_memory: config.subagents.memory_file.clone().map(AgentMemory::at),
         };
         let services = SessionServices {
             mcp_connection_manager,
@@ -1217,6 +1230,8 @@ async fn submission_loop(
                     final_output_json_schema: None,
                     subagent_name: prev.subagent_name.clone(),
                     subagent_tool_allowlist: prev.subagent_tool_allowlist.clone(),
+                    subagent_handoff_targets: prev.subagent_handoff_targets.clone(),
+                    subagent_memory: prev.subagent_memory.clone(),
                 };
 
                 // Install the new persistent context for subsequent tasks/turns.
@@ -1304,6 +1319,8 @@ async fn submission_loop(
                         final_output_json_schema,
                         subagent_name: turn_context.subagent_name.clone(),
                         subagent_tool_allowlist: turn_context.subagent_tool_allowlist.clone(),
+                        subagent_handoff_targets: turn_context.subagent_handoff_targets.clone(),
+                        subagent_memory: turn_context.subagent_memory.clone(),
                     };
 
                     // if the environment context has changed, record it in the conversation history
@@ -1556,6 +1573,8 @@ async fn spawn_review_thread(
         final_output_json_schema: None,
         subagent_name: parent_turn_context.subagent_name.clone(),
         subagent_tool_allowlist: parent_turn_context.subagent_tool_allowlist.clone(),
+        subagent_handoff_targets: Vec::new(),
+        subagent_memory: None,
     };
 
     // Seed the child task with the review prompt as the initial user message.
@@ -1907,10 +1926,16 @@ async fn run_turn(
     sub_id: String,
     input: Vec<ResponseItem>,
 ) -> CodexResult<TurnRunResult> {
-    let tools = get_openai_tools(
+    let mut tools = get_openai_tools(
         &turn_context.tools_config,
         Some(sess.services.mcp_connection_manager.list_all_tools()),
     );
+    if !turn_context.subagent_handoff_targets.is_empty() {
+        tools.push(create_handoff_tool(&turn_context.subagent_handoff_targets));
+    }
+    if turn_context.subagent_memory.is_some() {
+        tools.extend(create_memory_tools());
+    }
 
     let prompt = Prompt {
         input,
@@ -2247,7 +2272,12 @@ async fn handle_response_item(
             ..
         } => {
             info!("FunctionCall: {name}({arguments})");
-            if !tool_is_allowed(tool_allowlist, name.as_str()) {
+            // `handoff` is granted by `handoff_to` and the memory tools by
+            // `memory: true`, independently of the tools allowlist.
+            let is_granted = name == HANDOFF_TOOL_NAME
+                || (turn_context.subagent_memory.is_some()
+                    && matches!(name.as_str(), REMEMBER_TOOL_NAME | RECALL_TOOL_NAME));
+            if !is_granted && !tool_is_allowed(tool_allowlist, name.as_str()) {
                 let message = tool_denied_message(name.as_str(), subagent_name);
                 send_tool_denied_event(sess, sub_id, &message, subagent_name).await;
                 let call_id_string = call_id.clone();
@@ -2562,6 +2592,18 @@ async fn handle_function_call(
             .await
         }
         "update_plan" => handle_update_plan(sess, arguments, sub_id, call_id).await,
+        HANDOFF_TOOL_NAME => {
+            handle_handoff(
+                sess,
+                turn_context.subagent_name.as_deref(),
+                &turn_context.subagent_handoff_targets,
+                arguments,
+                sub_id,
+            )
+            .await
+        }
+        REMEMBER_TOOL_NAME => handle_remember(turn_context.subagent_memory.as_ref(), &arguments),
+        RECALL_TOOL_NAME => handle_recall(turn_context.subagent_memory.as_ref(), &arguments),
         EXEC_COMMAND_TOOL_NAME => {
             // TODO(mbolin): Sandbox check.
             let exec_params: ExecCommandParams = serde_json::from_str(&arguments).map_err(|e| {
@@ -3562,6 +3604,8 @@ mod tests {
             final_output_json_schema: None,
             subagent_name: config.subagents.active_agent.clone(),
             subagent_tool_allowlist: config.subagents.tool_allowlist.clone(),
+            subagent_handoff_targets: config.subagents.handoff_to.clone(),
+            subagent_memory: config.subagents.memory_file.clone().map(AgentMemory::at),
         };
         let services = SessionServices {
             mcp_connection_manager: McpConnectionManager::default(),
@@ -3631,6 +3675,8 @@ mod tests {
             final_output_json_schema: None,
             subagent_name: config.subagents.active_agent.clone(),
             subagent_tool_allowlist: config.subagents.tool_allowlist.clone(),
+            subagent_handoff_targets: config.subagents.handoff_to.clone(),
+            subagent_memory: config.subagents.memory_file.clone().map(AgentMemory::at),
         });
         let services = SessionServices {
             mcp_connection_manager: McpConnectionManager::default(),
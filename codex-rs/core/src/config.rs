@@ -25,6 +25,7 @@ use codex_protocol::config_types::SandboxMode;
 use codex_protocol::config_types::Verbosity;
 use codex_protocol::mcp_protocol::Tools;
 use codex_protocol::mcp_protocol::UserSavedConfig;
+use codex_subagents::AgentDefinition;
 use dirs::home_dir;
 use serde::Deserialize;
 use std::collections::BTreeMap;
@@ -40,6 +41,10 @@ use toml_edit::Table as TomlTable;
 
 const OPENAI_DEFAULT_MODEL: &str = "gpt-5-codex";
 const OPENAI_DEFAULT_REVIEW_MODEL: &str = "gpt-5-codex";
+/// Default number of subagents that may run at once during a parallel fan-out.
+pub const DEFAULT_SUBAGENT_MAX_CONCURRENCY: usize = 4;
+/// Default number of `handoff` hops a chain of subagents may take.
+pub const DEFAULT_SUBAGENT_MAX_HANDOFFS: usize = 3;
 pub const GPT_5_CODEX_MEDIUM_MODEL: &str = "gpt-5-codex";
 
 /// Maximum number of bytes of the documentation that will be embedded. Larger
@@ -169,6 +174,11 @@ pub struct Config {
     /// Optional verbosity control for GPT-5 models (Responses API `text.verbosity`).
     pub model_verbosity: Option<Verbosity>,
 
+    /// Sampling temperature sent with each request. Not read from
+    /// `config.toml`; subagent model bindings set it through
+    /// `parameters.temperature`.
+    pub model_temperature: Option<f64>,
+
     /// Base URL for requests to ChatGPT (as opposed to the OpenAI API).
     pub chatgpt_base_url: String,
 
@@ -202,12 +212,43 @@ pub struct Config {
     /// or placeholder replacement will occur for fast keypress bursts.
     pub disable_paste_burst: bool,
 }
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SubagentSettings {
     pub enabled: bool,
     pub auto_route: bool,
+    /// Upper bound on concurrently running children for `run_parallel`.
+    pub max_concurrency: usize,
     pub active_agent: Option<String>,
     pub tool_allowlist: Option<Vec<String>>,
+    /// Advertise every registered agent as an `agent_<name>` tool from `codex mcp`.
+    pub mcp_tools: bool,
+    /// Agents a child session may pass control to with the `handoff` tool.
+    pub handoff_to: Vec<String>,
+    /// Upper bound on handoff hops after the first agent in a chain.
+    pub max_handoffs: usize,
+    /// Notes file behind the `remember`/`recall` tools, for children whose
+    /// agent sets `memory: true`.
+    pub memory_file: Option<PathBuf>,
+    /// Agents defined in `[subagents.agents]`, with the active profile's
+    /// definitions applied on top.
+    pub agents: BTreeMap<String, AgentDefinition>,
+}
+
+impl Default for SubagentSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            auto_route: false,
+            max_concurrency: DEFAULT_SUBAGENT_MAX_CONCURRENCY,
+            active_agent: None,
+            tool_allowlist: None,
+            mcp_tools: false,
+            handoff_to: Vec::new(),
+            max_handoffs: DEFAULT_SUBAGENT_MAX_HANDOFFS,
+            memory_file: None,
+            agents: BTreeMap::new(),
+        }
+    }
 }
 
 impl Config {
@@ -766,6 +807,11 @@ pub struct ProjectConfig {
 pub struct SubagentsToml {
     pub enabled: Option<bool>,
     pub auto_route: Option<bool>,
+    pub max_concurrency: Option<usize>,
+    pub mcp_tools: Option<bool>,
+    pub max_handoffs: Option<usize>,
+    /// Agents defined inline, keyed by name.
+    pub agents: BTreeMap<String, AgentDefinition>,
 }
 
 #[derive(Deserialize, Debug, Clone, Default, PartialEq)]
@@ -806,6 +852,7 @@ impl ConfigToml {
                     network_access: *network_access,
                     exclude_tmpdir_env_var: *exclude_tmpdir_env_var,
                     exclude_slash_tmp: *exclude_slash_tmp,
+                    exclude_cwd: false,
                 },
                 None => SandboxPolicy::new_workspace_write_policy(),
             },
@@ -990,11 +1037,30 @@ impl Config {
             Err(_) => None,
         };
         let subagents_settings = SubagentSettings {
-            enabled: env_subagents_enabled
-                .unwrap_or_else(|| subagents_cfg.enabled.unwrap_or(true)),
+            enabled: env_subagents_enabled.unwrap_or_else(|| subagents_cfg.enabled.unwrap_or(true)),
             auto_route: subagents_cfg.auto_route.unwrap_or(false),
+            max_concurrency: subagents_cfg
+                .max_concurrency
+                .unwrap_or(DEFAULT_SUBAGENT_MAX_CONCURRENCY)
+                .max(1),
             active_agent: None,
             tool_allowlist: None,
+            mcp_tools: subagents_cfg.mcp_tools.unwrap_or(false),
+            handoff_to: Vec::new(),
+            max_handoffs: subagents_cfg
+                .max_handoffs
+                .unwrap_or(DEFAULT_SUBAGENT_MAX_HANDOFFS),
+            memory_file: None,
+            agents: subagents_cfg
+                .agents
+                .into_iter()
+                .chain(
+                    config_profile
+                        .subagents
+                        .map(|profile| profile.agents)
+                        .unwrap_or_default(),
+                )
+                .collect(),
         };
 
         let tools_web_search_request = override_tools_web_search_request
@@ -1092,6 +1158,7 @@ impl Config {
                 .or(cfg.model_reasoning_summary)
                 .unwrap_or_default(),
             model_verbosity: config_profile.model_verbosity.or(cfg.model_verbosity),
+            model_temperature: None,
             chatgpt_base_url: config_profile
                 .chatgpt_base_url
                 .or(cfg.chatgpt_base_url)
@@ -1277,6 +1344,44 @@ persistence = "none"
         assert_eq!(tui.notifications, Notifications::Enabled(false));
     }
 
+    #[test]
+    fn profile_subagents_replace_top_level_agents() -> std::io::Result<()> {
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+profile = "ci"
+
+[subagents.agents.reviewer]
+instructions = "Review carefully."
+
+[subagents.agents.summarizer]
+instructions = "Summarize."
+
+[profiles.ci.subagents.agents.reviewer]
+model = "gpt-5"
+instructions = "Review only the diff."
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+        let codex_home = TempDir::new()?;
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        let agents = &config.subagents.agents;
+        assert_eq!(
+            agents.keys().collect::<Vec<_>>(),
+            vec!["reviewer", "summarizer"]
+        );
+        assert_eq!(agents["reviewer"].model.as_deref(), Some("gpt-5"));
+        assert_eq!(
+            agents["reviewer"].instructions.as_deref(),
+            Some("Review only the diff.")
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_sandbox_config_parsing() {
         let sandbox_full_access = r#"
@@ -1328,6 +1433,7 @@ exclude_slash_tmp = true
                 network_access: false,
                 exclude_tmpdir_env_var: true,
                 exclude_slash_tmp: true,
+                exclude_cwd: false,
             },
             sandbox_workspace_write_cfg.derive_sandbox_policy(sandbox_mode_override)
         );
@@ -1844,6 +1950,7 @@ model_verbosity = "high"
                 model_reasoning_effort: Some(ReasoningEffort::High),
                 model_reasoning_summary: ReasoningSummary::Detailed,
                 model_verbosity: None,
+                model_temperature: None,
                 chatgpt_base_url: "https://chatgpt.com/backend-api/".to_string(),
                 base_instructions: None,
                 include_plan_tool: false,
@@ -1904,6 +2011,7 @@ model_verbosity = "high"
             model_reasoning_effort: None,
             model_reasoning_summary: ReasoningSummary::default(),
             model_verbosity: None,
+            model_temperature: None,
             chatgpt_base_url: "https://chatgpt.com/backend-api/".to_string(),
             base_instructions: None,
             include_plan_tool: false,
@@ -1979,6 +2087,7 @@ model_verbosity = "high"
             model_reasoning_effort: None,
             model_reasoning_summary: ReasoningSummary::default(),
             model_verbosity: None,
+            model_temperature: None,
             chatgpt_base_url: "https://chatgpt.com/backend-api/".to_string(),
             base_instructions: None,
             include_plan_tool: false,
@@ -2040,6 +2149,7 @@ model_verbosity = "high"
             model_reasoning_effort: Some(ReasoningEffort::High),
             model_reasoning_summary: ReasoningSummary::Detailed,
             model_verbosity: Some(Verbosity::High),
+            model_temperature: None,
             chatgpt_base_url: "https://chatgpt.com/backend-api/".to_string(),
             base_instructions: None,
             include_plan_tool: false,
@@ -1,4 +1,6 @@
+use codex_subagents::AgentDefinition;
 use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 use crate::protocol::AskForApproval;
@@ -20,6 +22,16 @@ pub struct ConfigProfile {
     pub model_verbosity: Option<Verbosity>,
     pub chatgpt_base_url: Option<String>,
     pub experimental_instructions_file: Option<PathBuf>,
+    pub subagents: Option<ProfileSubagentsToml>,
+}
+
+/// Subagent settings a profile may layer over the top-level `[subagents]`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProfileSubagentsToml {
+    /// Agents added by this profile; they replace top-level agents of the
+    /// same name.
+    pub agents: BTreeMap<String, AgentDefinition>,
 }
 
 impl From<ConfigProfile> for codex_protocol::mcp_protocol::Profile {
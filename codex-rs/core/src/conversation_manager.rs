@@ -10,8 +10,12 @@ use crate::config::Config;
 use crate::config::SubagentSettings;
 use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
+use crate::git_info::get_git_repo_root;
+use crate::model_family::derive_default_model_family;
+use crate::model_family::find_family_for_model;
 use crate::protocol::Event;
 use crate::protocol::EventMsg;
+use crate::protocol::SandboxPolicy;
 use crate::protocol::SessionConfiguredEvent;
 use crate::rollout::RolloutRecorder;
 use codex_protocol::mcp_protocol::ConversationId;
@@ -20,7 +24,10 @@ use codex_protocol::protocol::InitialHistory;
 use codex_protocol::protocol::RolloutItem;
 use codex_subagents::ModelBinding;
 use codex_subagents::SubagentSpec;
+use codex_subagents::memory::AgentMemory;
+use codex_subagents::scope::AgentScope;
 use std::collections::HashMap;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -64,32 +71,7 @@ impl ConversationManager {
         parent_config: &Config,
         spec: &SubagentSpec,
     ) -> CodexResult<NewConversation> {
-        let mut child_config = parent_config.clone();
-        if let Some(binding) = spec.metadata.model_config.as_ref() {
-            apply_model_binding(&mut child_config, binding);
-        } else if let Some(model) = spec.metadata.model.as_ref() {
-            child_config.model = model.clone();
-            child_config.review_model = model.clone();
-        }
-
-        apply_tool_policy_from_spec(&mut child_config, spec);
-
-        let merged_instructions = merge_subagent_instructions(
-            parent_config.base_instructions.as_deref(),
-            &spec.instructions,
-        );
-        child_config.base_instructions = Some(merged_instructions);
-        child_config.subagents = SubagentSettings {
-            enabled: false,
-            auto_route: false,
-            active_agent: Some(spec.metadata.name.clone()),
-            tool_allowlist: if spec.metadata.tools.is_empty() {
-                None
-            } else {
-                Some(spec.metadata.tools.clone())
-            },
-        };
-
+        let child_config = subagent_config(parent_config, spec)?;
         self.spawn_conversation(child_config, self.auth_manager.clone())
             .await
     }
@@ -199,6 +181,65 @@ impl ConversationManager {
     }
 }
 
+/// Config for a child running `spec`: the parent's config with the agent's
+/// model, tools, scope, memory and instructions applied.
+fn subagent_config(parent_config: &Config, spec: &SubagentSpec) -> CodexResult<Config> {
+    let mut child_config = parent_config.clone();
+    if let Some(binding) = spec.metadata.model_config.as_ref() {
+        apply_model_binding(&mut child_config, binding);
+    } else if let Some(model) = spec.metadata.model.as_ref() {
+        child_config.model = model.clone();
+        child_config.review_model = model.clone();
+    }
+
+    apply_tool_policy_from_spec(&mut child_config, spec);
+    let project_root =
+        get_git_repo_root(&parent_config.cwd).unwrap_or_else(|| parent_config.cwd.clone());
+    apply_agent_scope(&mut child_config, &project_root, spec)?;
+
+    let memory = if spec.metadata.memory {
+        Some(AgentMemory::for_agent(
+            &parent_config.cwd,
+            &spec.metadata.name,
+        )?)
+    } else {
+        None
+    };
+    let agent_instructions = match memory.as_ref().map(AgentMemory::prompt_section) {
+        Some(Ok(Some(notes))) => format!("{notes}\n{}", spec.instructions),
+        Some(Err(err)) => {
+            tracing::warn!(
+                "failed to read memory for subagent {}: {err}",
+                spec.metadata.name
+            );
+            spec.instructions.clone()
+        }
+        Some(Ok(None)) | None => spec.instructions.clone(),
+    };
+    let merged_instructions = merge_subagent_instructions(
+        parent_config.base_instructions.as_deref(),
+        &agent_instructions,
+    );
+    child_config.base_instructions = Some(merged_instructions);
+    child_config.subagents = SubagentSettings {
+        enabled: false,
+        auto_route: false,
+        max_concurrency: parent_config.subagents.max_concurrency,
+        active_agent: Some(spec.metadata.name.clone()),
+        tool_allowlist: if spec.metadata.tools.is_empty() {
+            None
+        } else {
+            Some(spec.metadata.tools.clone())
+        },
+        mcp_tools: false,
+        handoff_to: spec.metadata.handoff_to.clone(),
+        max_handoffs: parent_config.subagents.max_handoffs,
+        memory_file: memory.map(|memory| memory.path().to_path_buf()),
+        agents: parent_config.subagents.agents.clone(),
+    };
+    Ok(child_config)
+}
+
 fn apply_model_binding(config: &mut Config, binding: &ModelBinding) {
     if let Some(provider_id) = binding.provider_id.as_ref() {
         if let Some(mut provider) = config.model_providers.get(provider_id).cloned() {
@@ -227,6 +268,17 @@ fn apply_model_binding(config: &mut Config, binding: &ModelBinding) {
     if let Some(model) = binding.model.as_ref() {
         config.model = model.clone();
         config.review_model = model.clone();
+        // The family decides which request parameters (reasoning,
+        // temperature) the bound model accepts.
+        config.model_family =
+            find_family_for_model(model).unwrap_or_else(|| derive_default_model_family(model));
+    }
+    if let Some(temperature) = binding
+        .parameters
+        .get("temperature")
+        .and_then(serde_json::Value::as_f64)
+    {
+        config.model_temperature = Some(temperature);
     }
 }
 
@@ -258,6 +310,38 @@ fn apply_tool_policy_from_spec(config: &mut Config, spec: &SubagentSpec) {
     }
 }
 
+/// Apply the agent's `cwd`, `writable_paths` and `env`. Paths are relative to
+/// `project_root` and must stay inside it. `writable_paths` replace the roots
+/// of a workspace-write sandbox, and the child's working directory is no
+/// longer writable unless it is one of them.
+fn apply_agent_scope(
+    config: &mut Config,
+    project_root: &Path,
+    spec: &SubagentSpec,
+) -> std::io::Result<()> {
+    let scope = AgentScope::resolve(&spec.metadata, project_root)?;
+    if let Some(cwd) = scope.cwd {
+        config.cwd = cwd;
+    }
+    if !spec.metadata.writable_paths.is_empty()
+        && let SandboxPolicy::WorkspaceWrite {
+            writable_roots,
+            exclude_cwd,
+            ..
+        } = &mut config.sandbox_policy
+    {
+        *writable_roots = scope.writable_roots;
+        *exclude_cwd = true;
+    }
+    config.shell_environment_policy.r#set.extend(
+        spec.metadata
+            .env
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone())),
+    );
+    Ok(())
+}
+
 fn tool_name_matches(entry: &str, candidate: &str) -> bool {
     entry == candidate
         || match entry {
@@ -443,14 +527,20 @@ mod tests {
             provider_id: Some("oss".to_string()),
             model: Some("gpt-oss:9b".to_string()),
             endpoint: Some("http://localhost:11434/v1".to_string()),
-            parameters: std::collections::BTreeMap::new(),
+            parameters: std::collections::BTreeMap::from([(
+                "temperature".to_string(),
+                serde_json::json!(0.7),
+            )]),
         };
 
+        assert_eq!(config.model_temperature, None);
         apply_model_binding(&mut config, &binding);
 
         assert_eq!(config.model_provider_id, "oss");
         assert_eq!(config.model, "gpt-oss:9b");
         assert_eq!(config.review_model, "gpt-oss:9b");
+        assert_eq!(config.model_family.family, "gpt-oss");
+        assert_eq!(config.model_temperature, Some(0.7));
         let provider = config.model_providers.get("oss").expect("provider");
         assert_eq!(
             provider.base_url.as_deref(),
@@ -458,6 +548,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn apply_model_binding_drops_temperature_for_reasoning_models() {
+        let codex_home = tempdir().expect("tempdir");
+        let mut config = Config::load_from_base_config_with_overrides(
+            ConfigToml {
+                model: Some("gpt-4o".to_string()),
+                ..Default::default()
+            },
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+        .expect("load config");
+        let binding = ModelBinding {
+            provider_id: None,
+            model: Some("gpt-5-codex".to_string()),
+            endpoint: None,
+            parameters: std::collections::BTreeMap::from([(
+                "temperature".to_string(),
+                serde_json::json!(0.7),
+            )]),
+        };
+
+        apply_model_binding(&mut config, &binding);
+
+        assert!(config.model_family.supports_reasoning_summaries);
+        assert_eq!(
+            crate::client_common::temperature_for_request(
+                &config.model_family,
+                config.model_temperature
+            ),
+            None
+        );
+    }
+
     #[test]
     fn apply_model_binding_endpoint_only_updates_base_url() {
         let codex_home = tempdir().expect("tempdir");
@@ -519,4 +643,110 @@ mod tests {
         assert!(config.tools_web_search_request);
         assert!(!config.include_view_image_tool);
     }
+
+    #[test]
+    fn apply_agent_scope_confines_cwd_sandbox_and_env() {
+        let codex_home = tempdir().expect("tempdir");
+        let project = tempdir().expect("tempdir");
+        let root = project.path().canonicalize().expect("canonical root");
+        std::fs::create_dir_all(root.join("web/src")).expect("mkdir");
+        let mut config = scoped_test_config(&codex_home, &root);
+
+        let spec = SubagentBuilder::new("frontend-dev")
+            .cwd(PathBuf::from("web"))
+            .writable_paths(["web/src"])
+            .env([("NODE_ENV".to_string(), "development".to_string())].into())
+            .instructions("instr")
+            .build()
+            .expect("spec");
+        super::apply_agent_scope(&mut config, &root, &spec).expect("scope");
+
+        assert_eq!(config.cwd, root.join("web"));
+        assert_eq!(writable_roots(&config), vec![root.join("web/src")]);
+        assert_eq!(
+            config
+                .shell_environment_policy
+                .r#set
+                .get("NODE_ENV")
+                .map(String::as_str),
+            Some("development")
+        );
+
+        // Without `cwd`, the project root is not writable either.
+        let mut config = scoped_test_config(&codex_home, &root);
+        let spec = SubagentBuilder::new("frontend-dev")
+            .writable_paths(["web/src"])
+            .instructions("instr")
+            .build()
+            .expect("spec");
+        super::apply_agent_scope(&mut config, &root, &spec).expect("scope");
+        assert_eq!(config.cwd, root);
+        assert_eq!(writable_roots(&config), vec![root.join("web/src")]);
+
+        let missing = SubagentBuilder::new("backend-dev")
+            .cwd(PathBuf::from("api"))
+            .instructions("instr")
+            .build()
+            .expect("spec");
+        assert!(super::apply_agent_scope(&mut config, &root, &missing).is_err());
+    }
+
+    #[test]
+    fn subagent_scope_is_relative_to_the_repo_root() {
+        let codex_home = tempdir().expect("tempdir");
+        let project = tempdir().expect("tempdir");
+        let root = project.path().canonicalize().expect("canonical root");
+        std::fs::create_dir_all(root.join(".git")).expect("mkdir");
+        std::fs::create_dir_all(root.join("web/src")).expect("mkdir");
+        std::fs::create_dir_all(root.join("api")).expect("mkdir");
+        // Launched from a subdirectory of the repo.
+        let parent = scoped_test_config(&codex_home, &root.join("web"));
+
+        let spec = SubagentBuilder::new("frontend-dev")
+            .cwd(PathBuf::from("web"))
+            .writable_paths(["web/src"])
+            .instructions("instr")
+            .build()
+            .expect("spec");
+        let child = super::subagent_config(&parent, &spec).expect("child config");
+        assert_eq!(child.cwd, root.join("web"));
+        assert_eq!(writable_roots(&child), vec![root.join("web/src")]);
+
+        let spec = SubagentBuilder::new("backend-dev")
+            .cwd(PathBuf::from("api"))
+            .instructions("instr")
+            .build()
+            .expect("spec");
+        let child = super::subagent_config(&parent, &spec).expect("child config");
+        assert_eq!(child.cwd, root.join("api"));
+    }
+
+    /// Config with a workspace-write sandbox whose only default root is `cwd`.
+    fn scoped_test_config(codex_home: &tempfile::TempDir, cwd: &Path) -> Config {
+        let mut config = Config::load_from_base_config_with_overrides(
+            ConfigToml::default(),
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+        .expect("load config");
+        config.cwd = cwd.to_path_buf();
+        config.sandbox_policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![cwd.to_path_buf()],
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+            exclude_cwd: false,
+        };
+        config
+    }
+
+    /// Roots the sandbox actually lets the child write to.
+    fn writable_roots(config: &Config) -> Vec<PathBuf> {
+        config
+            .sandbox_policy
+            .get_writable_roots_with_cwd(&config.cwd)
+            .into_iter()
+            .map(|root| root.root)
+            .collect()
+    }
 }
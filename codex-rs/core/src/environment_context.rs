@@ -186,6 +186,7 @@ mod tests {
             network_access,
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
+            exclude_cwd: false,
         }
     }
 
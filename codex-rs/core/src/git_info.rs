@@ -44,6 +44,9 @@ pub fn get_git_repo_root(base_dir: &Path) -> Option<PathBuf> {
 /// Timeout for git commands to prevent freezing on large repositories
 const GIT_COMMAND_TIMEOUT: TokioDuration = TokioDuration::from_secs(5);
 
+/// The object id of an empty tree, a diff base for root commits.
+const EMPTY_TREE_SHA: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct GitDiffToRemote {
     pub sha: GitSha,
@@ -178,6 +181,87 @@ pub async fn git_diff_to_remote(cwd: &Path) -> Option<GitDiffToRemote> {
     })
 }
 
+/// Diff of the working tree, untracked files included, against `HEAD` or,
+/// when `base` is set, against the merge base of `HEAD` and `base`.
+pub async fn git_diff_working_tree(cwd: &Path, base: Option<&str>) -> Option<String> {
+    let sha = match base {
+        Some(base) => {
+            let output = run_git_command_with_timeout(&["merge-base", "HEAD", base], cwd).await?;
+            if !output.status.success() {
+                return None;
+            }
+            GitSha::new(String::from_utf8(output.stdout).ok()?.trim())
+        }
+        None => GitSha::new("HEAD"),
+    };
+    diff_against_sha(cwd, &sha).await
+}
+
+/// Diff of the index against `HEAD`: what `git commit` would record.
+pub async fn git_staged_diff(cwd: &Path) -> Option<String> {
+    let output =
+        run_git_command_with_timeout(&["diff", "--cached", "--no-textconv", "--no-ext-diff"], cwd)
+            .await?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
+/// Diff of the commits reachable from `head` but not from `base`. Without a
+/// `base`, the diff covers the commits not yet on any remote, as for a branch
+/// pushed for the first time.
+pub async fn git_diff_commits(cwd: &Path, base: Option<&str>, head: &str) -> Option<String> {
+    let base = match base {
+        Some(base) => base.to_string(),
+        None => {
+            let output = run_git_command_with_timeout(
+                &["rev-list", "--reverse", head, "--not", "--remotes"],
+                cwd,
+            )
+            .await?;
+            if !output.status.success() {
+                return None;
+            }
+            let stdout = String::from_utf8(output.stdout).ok()?;
+            let Some(oldest) = stdout.lines().next() else {
+                return Some(String::new());
+            };
+            let parent = format!("{oldest}^");
+            match run_git_command_with_timeout(&["rev-parse", "--verify", "--quiet", &parent], cwd)
+                .await
+            {
+                Some(output) if output.status.success() => parent,
+                // The oldest unpushed commit is a root commit.
+                _ => EMPTY_TREE_SHA.to_string(),
+            }
+        }
+    };
+    let output = run_git_command_with_timeout(
+        &["diff", "--no-textconv", "--no-ext-diff", &base, head],
+        cwd,
+    )
+    .await?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
+/// The directory git runs hooks from, honoring `core.hooksPath`.
+pub async fn git_hooks_dir(cwd: &Path) -> Option<PathBuf> {
+    let output = run_git_command_with_timeout(
+        &["rev-parse", "--path-format=absolute", "--git-path", "hooks"],
+        cwd,
+    )
+    .await?;
+    if !output.status.success() {
+        return None;
+    }
+    let path = String::from_utf8(output.stdout).ok()?;
+    Some(PathBuf::from(path.trim()))
+}
+
 /// Run a git command with a timeout to prevent blocking on large repositories
 async fn run_git_command_with_timeout(args: &[&str], cwd: &Path) -> Option<std::process::Output> {
     let result = timeout(
@@ -974,6 +1058,40 @@ mod tests {
         assert!(resolve_root_git_project_for_trust(tmp.path()).is_none());
     }
 
+    #[tokio::test]
+    async fn test_staged_and_unpushed_diffs() {
+        skip_if_sandbox!();
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let repo_path = create_test_git_repo(&temp_dir).await;
+        let git = |args: &[&str]| {
+            std::process::Command::new("git")
+                .args(args)
+                .current_dir(&repo_path)
+                .output()
+                .expect("git")
+        };
+
+        fs::write(repo_path.join("test.txt"), "staged").unwrap();
+        fs::write(repo_path.join("other.txt"), "unstaged").unwrap();
+        git(&["add", "test.txt"]);
+        let staged = git_staged_diff(&repo_path).await.unwrap();
+        assert!(staged.contains("+staged"));
+        assert!(!staged.contains("other.txt"));
+
+        git(&["commit", "-m", "second"]);
+        let last = git_diff_commits(&repo_path, Some("HEAD~1"), "HEAD")
+            .await
+            .unwrap();
+        assert!(last.contains("+staged") && !last.contains("+test content"));
+        // Nothing is on a remote, so the root commit is included.
+        let unpushed = git_diff_commits(&repo_path, None, "HEAD").await.unwrap();
+        assert!(unpushed.contains("+staged"));
+        assert!(unpushed.contains("new file mode"));
+
+        let hooks = git_hooks_dir(&repo_path).await.unwrap();
+        assert!(hooks.ends_with(".git/hooks"));
+    }
+
     #[tokio::test]
     async fn resolve_root_git_project_for_trust_regular_repo_returns_repo_root() {
         let temp_dir = TempDir::new().expect("Failed to create temp dir");
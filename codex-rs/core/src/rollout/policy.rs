@@ -45,8 +45,10 @@ pub(crate) fn should_persist_event_msg(ev: &EventMsg) -> bool {
         | EventMsg::TurnAborted(_)
         | EventMsg::SubAgentStarted(_)
         | EventMsg::SubAgentMessage(_)
-        | EventMsg::SubAgentCompleted(_) => true,
+        | EventMsg::SubAgentCompleted(_)
+        | EventMsg::SubAgentHandoff(_) => true,
         EventMsg::Error(_)
+        | EventMsg::SubAgentActivity(_)
         | EventMsg::TaskStarted(_)
         | EventMsg::TaskComplete(_)
         | EventMsg::AgentMessageDelta(_)
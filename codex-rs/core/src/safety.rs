@@ -293,6 +293,7 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            exclude_cwd: false,
         };
 
         assert!(is_write_patch_constrained_to_writable_paths(
@@ -314,6 +315,7 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            exclude_cwd: false,
         };
         assert!(is_write_patch_constrained_to_writable_paths(
             &add_outside,
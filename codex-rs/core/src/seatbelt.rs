@@ -158,6 +158,7 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            exclude_cwd: false,
         };
 
         let args = create_seatbelt_command_args(
@@ -233,6 +234,7 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
+            exclude_cwd: false,
         };
 
         let args = create_seatbelt_command_args(
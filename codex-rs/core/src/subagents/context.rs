@@ -0,0 +1,365 @@
+//! Rendering of [`ContextAttachment`]s into the tagged blocks that open a
+//! child's first user message.
+
+use std::collections::VecDeque;
+use std::path::Path;
+
+use codex_protocol::plan_tool::StepStatus;
+use codex_protocol::plan_tool::UpdatePlanArgs;
+use codex_subagents::ContextAttachment;
+use tokio::process::Command;
+use wildmatch::WildMatch;
+
+use crate::git_info::git_diff_working_tree;
+use crate::protocol::AgentMessageEvent;
+use crate::protocol::EventMsg;
+use crate::protocol::InputMessageKind;
+use crate::protocol::UserMessageEvent;
+use crate::truncate::truncate_middle;
+
+const MAX_GIT_DIFF_BYTES: usize = 64 * 1024;
+const MAX_FILE_BYTES: usize = 16 * 1024;
+const MAX_FILES_TOTAL_BYTES: usize = 64 * 1024;
+const MAX_FILES: usize = 32;
+/// Directory entries looked at when listing files outside a git repository.
+const MAX_WALK_ENTRIES: usize = 20_000;
+const MAX_PARENT_MESSAGES_BYTES: usize = 16 * 1024;
+const MAX_PLAN_BYTES: usize = 8 * 1024;
+/// Messages kept by [`ParentContext::observe`]; `parent_last_messages`
+/// cannot reach further back than this.
+const MAX_TRACKED_MESSAGES: usize = 50;
+
+/// What the launching conversation can hand to a child: its recent messages
+/// and current plan. Front ends feed it the parent's events through
+/// [`ParentContext::observe`].
+#[derive(Debug, Clone, Default)]
+pub struct ParentContext {
+    messages: VecDeque<ParentMessage>,
+    plan: Option<UpdatePlanArgs>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ParentMessage {
+    role: &'static str,
+    text: String,
+}
+
+impl ParentContext {
+    /// Record a parent event if it is a user or agent message or a plan
+    /// update; everything else is ignored.
+    pub fn observe(&mut self, msg: &EventMsg) {
+        match msg {
+            EventMsg::UserMessage(UserMessageEvent {
+                message,
+                kind: None | Some(InputMessageKind::Plain),
+                ..
+            }) => {
+                self.push_message("user", message);
+            }
+            EventMsg::AgentMessage(AgentMessageEvent { message }) => {
+                self.push_message("assistant", message);
+            }
+            EventMsg::PlanUpdate(plan) => self.plan = Some(plan.clone()),
+            _ => {}
+        }
+    }
+
+    fn push_message(&mut self, role: &'static str, text: &str) {
+        let text = text.trim();
+        if text.is_empty() {
+            return;
+        }
+        if self.messages.len() == MAX_TRACKED_MESSAGES {
+            self.messages.pop_front();
+        }
+        self.messages.push_back(ParentMessage {
+            role,
+            text: text.to_string(),
+        });
+    }
+}
+
+/// Render `attachments` as tagged blocks, one per attachment, separated by
+/// blank lines. Returns an empty string when there is nothing to attach.
+pub(crate) async fn render_context(
+    attachments: &[ContextAttachment],
+    cwd: &Path,
+    parent: &ParentContext,
+) -> String {
+    let mut blocks = Vec::with_capacity(attachments.len());
+    for attachment in attachments {
+        blocks.push(match attachment {
+            ContextAttachment::GitDiff { base } => render_git_diff(cwd, base.as_deref()).await,
+            ContextAttachment::Files(globs) => render_files(cwd, globs).await,
+            ContextAttachment::ParentLastMessages(n) => render_parent_messages(parent, *n),
+            ContextAttachment::Plan => render_plan(parent.plan.as_ref()),
+        });
+    }
+    blocks.join("\n\n")
+}
+
+fn tagged(tag: &str, attrs: &str, body: &str, max_bytes: usize) -> String {
+    let (body, _) = truncate_middle(body.trim_end(), max_bytes);
+    format!("<{tag}{attrs}>\n{body}\n</{tag}>")
+}
+
+async fn render_git_diff(cwd: &Path, base: Option<&str>) -> String {
+    let attrs = format!(" base=\"{}\"", base.unwrap_or("HEAD"));
+    let body = match git_diff_working_tree(cwd, base).await {
+        Some(diff) if diff.trim().is_empty() => "(no changes)".to_string(),
+        Some(diff) => diff,
+        None => "(git diff unavailable)".to_string(),
+    };
+    tagged("git_diff", &attrs, &body, MAX_GIT_DIFF_BYTES)
+}
+
+async fn render_files(cwd: &Path, globs: &[String]) -> String {
+    let matchers: Vec<WildMatch> = globs.iter().map(|glob| WildMatch::new(glob)).collect();
+    let mut paths: Vec<String> = list_files(cwd)
+        .await
+        .into_iter()
+        .filter(|path| matchers.iter().any(|matcher| matcher.matches(path)))
+        .collect();
+    paths.sort();
+    if paths.is_empty() {
+        let attrs = format!(" pattern=\"{}\"", globs.join(","));
+        return tagged("files", &attrs, "(no matching files)", MAX_FILE_BYTES);
+    }
+
+    let omitted = paths.len().saturating_sub(MAX_FILES);
+    let mut budget = MAX_FILES_TOTAL_BYTES;
+    let mut blocks = Vec::new();
+    for path in paths.iter().take(MAX_FILES) {
+        let attrs = format!(" path=\"{path}\"");
+        let body = match tokio::fs::read(cwd.join(path)).await {
+            Ok(bytes) => match String::from_utf8(bytes) {
+                Ok(text) => text,
+                Err(_) => "(binary file omitted)".to_string(),
+            },
+            Err(err) => format!("(failed to read: {err})"),
+        };
+        let block = tagged("file", &attrs, &body, MAX_FILE_BYTES.min(budget));
+        budget = budget.saturating_sub(block.len());
+        blocks.push(block);
+        if budget == 0 {
+            break;
+        }
+    }
+    let skipped = omitted + paths.len().min(MAX_FILES) - blocks.len();
+    if skipped > 0 {
+        blocks.push(format!("({skipped} more matching files omitted)"));
+    }
+    blocks.join("\n")
+}
+
+/// Files under `cwd` relative to it: tracked and untracked-but-not-ignored
+/// files inside a git repository, otherwise every non-hidden file.
+async fn list_files(cwd: &Path) -> Vec<String> {
+    let output = Command::new("git")
+        .args(["ls-files", "--cached", "--others", "--exclude-standard"])
+        .current_dir(cwd)
+        .output()
+        .await;
+    if let Ok(output) = output
+        && output.status.success()
+    {
+        return String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect();
+    }
+
+    let root = cwd.to_path_buf();
+    tokio::task::spawn_blocking(move || walk_files(&root))
+        .await
+        .unwrap_or_default()
+}
+
+/// Non-hidden files under `root`, without following symlinks, stopping after
+/// `MAX_WALK_ENTRIES` directory entries.
+fn walk_files(root: &Path) -> Vec<String> {
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    let mut seen = 0;
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            seen += 1;
+            if seen > MAX_WALK_ENTRIES {
+                return files;
+            }
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let path = entry.path();
+            if file_type.is_dir() {
+                pending.push(path);
+            } else if file_type.is_file()
+                && let Ok(relative) = path.strip_prefix(root)
+            {
+                files.push(relative.to_string_lossy().replace('\\', "/"));
+            }
+        }
+    }
+    files
+}
+
+fn render_parent_messages(parent: &ParentContext, n: usize) -> String {
+    let start = parent.messages.len().saturating_sub(n);
+    let body = if parent.messages.is_empty() {
+        "(no parent messages)".to_string()
+    } else {
+        parent
+            .messages
+            .iter()
+            .skip(start)
+            .map(|message| format!("{}: {}", message.role, message.text))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    };
+    let attrs = format!(" count=\"{}\"", parent.messages.len() - start);
+    tagged("parent_messages", &attrs, &body, MAX_PARENT_MESSAGES_BYTES)
+}
+
+fn render_plan(plan: Option<&UpdatePlanArgs>) -> String {
+    let Some(plan) = plan else {
+        return tagged("plan", "", "(no plan has been set)", MAX_PLAN_BYTES);
+    };
+    let mut lines = Vec::new();
+    if let Some(explanation) = plan.explanation.as_deref().map(str::trim)
+        && !explanation.is_empty()
+    {
+        lines.push(explanation.to_string());
+    }
+    for item in &plan.plan {
+        let mark = match item.status {
+            StepStatus::Completed => "[x]",
+            StepStatus::InProgress => "[~]",
+            StepStatus::Pending => "[ ]",
+        };
+        lines.push(format!("- {mark} {}", item.step));
+    }
+    tagged("plan", "", &lines.join("\n"), MAX_PLAN_BYTES)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::plan_tool::PlanItemArg;
+    use pretty_assertions::assert_eq;
+
+    #[cfg(unix)]
+    #[test]
+    fn walk_skips_symlinks_and_hidden_entries() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src/nested")).expect("mkdir");
+        std::fs::create_dir_all(root.join(".git")).expect("mkdir");
+        std::fs::write(root.join("src/lib.rs"), "").expect("write");
+        std::fs::write(root.join("src/nested/mod.rs"), "").expect("write");
+        std::fs::write(root.join(".git/config"), "").expect("write");
+        std::os::unix::fs::symlink(root, root.join("src/loop")).expect("symlink");
+        std::os::unix::fs::symlink(root.join("src/lib.rs"), root.join("link.rs")).expect("symlink");
+
+        let mut files = walk_files(root);
+        files.sort();
+        assert_eq!(files, vec!["src/lib.rs", "src/nested/mod.rs"]);
+    }
+
+    fn user(message: &str) -> EventMsg {
+        EventMsg::UserMessage(UserMessageEvent {
+            message: message.to_string(),
+            kind: None,
+            images: None,
+        })
+    }
+
+    fn agent(message: &str) -> EventMsg {
+        EventMsg::AgentMessage(AgentMessageEvent {
+            message: message.to_string(),
+        })
+    }
+
+    #[tokio::test]
+    async fn renders_parent_messages_and_plan() {
+        let mut parent = ParentContext::default();
+        parent.observe(&user("first"));
+        parent.observe(&agent("reply"));
+        parent.observe(&EventMsg::UserMessage(UserMessageEvent {
+            message: "<environment_context/>".to_string(),
+            kind: Some(InputMessageKind::EnvironmentContext),
+            images: None,
+        }));
+        parent.observe(&user("second"));
+        parent.observe(&EventMsg::PlanUpdate(UpdatePlanArgs {
+            explanation: Some("Ship it".to_string()),
+            plan: vec![
+                PlanItemArg {
+                    step: "write".to_string(),
+                    status: StepStatus::Completed,
+                },
+                PlanItemArg {
+                    step: "test".to_string(),
+                    status: StepStatus::Pending,
+                },
+            ],
+        }));
+
+        let cwd = tempfile::tempdir().unwrap();
+        let rendered = render_context(
+            &[
+                ContextAttachment::ParentLastMessages(2),
+                ContextAttachment::Plan,
+            ],
+            cwd.path(),
+            &parent,
+        )
+        .await;
+        assert_eq!(
+            rendered,
+            "<parent_messages count=\"2\">\nassistant: reply\n\nuser: second\n</parent_messages>\n\n<plan>\nShip it\n- [x] write\n- [ ] test\n</plan>"
+        );
+    }
+
+    #[tokio::test]
+    async fn renders_matching_files_with_byte_caps() {
+        let cwd = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(cwd.path().join("src/nested")).unwrap();
+        std::fs::write(cwd.path().join("src/lib.rs"), "pub fn lib() {}\n").unwrap();
+        std::fs::write(
+            cwd.path().join("src/nested/big.rs"),
+            "x\n".repeat(MAX_FILE_BYTES),
+        )
+        .unwrap();
+        std::fs::write(cwd.path().join("README.md"), "readme\n").unwrap();
+
+        let rendered = render_context(
+            &[ContextAttachment::Files(vec!["src/*.rs".to_string()])],
+            cwd.path(),
+            &ParentContext::default(),
+        )
+        .await;
+        assert!(rendered.starts_with("<file path=\"src/lib.rs\">\npub fn lib() {}\n</file>\n"));
+        assert!(rendered.contains("<file path=\"src/nested/big.rs\">"));
+        assert!(rendered.contains("tokens truncated"));
+        assert!(!rendered.contains("readme"));
+        assert!(rendered.len() < MAX_FILE_BYTES * 2);
+
+        let rendered = render_context(
+            &[ContextAttachment::Files(vec!["docs/**".to_string()])],
+            cwd.path(),
+            &ParentContext::default(),
+        )
+        .await;
+        assert_eq!(
+            rendered,
+            "<files pattern=\"docs/**\">\n(no matching files)\n</files>"
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+use crate::codex::Session;
+use crate::function_tool::FunctionCallError;
+use crate::openai_tools::JsonSchema;
+use crate::openai_tools::OpenAiTool;
+use crate::openai_tools::ResponsesApiTool;
+use crate::protocol::Event;
+use crate::protocol::EventMsg;
+use crate::protocol::SubAgentHandoffEvent;
+
+pub(crate) const HANDOFF_TOOL_NAME: &str = "handoff";
+
+#[derive(Debug, Deserialize)]
+struct HandoffArgs {
+    agent: String,
+    note: String,
+}
+
+/// Tool offered to subagents whose spec lists `handoff_to` targets.
+pub(crate) fn create_handoff_tool(targets: &[String]) -> OpenAiTool {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "agent".to_string(),
+        JsonSchema::String {
+            description: Some(format!("One of: {}", targets.join(", "))),
+        },
+    );
+    properties.insert(
+        "note".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "What the next agent should do and anything it needs to know.".to_string(),
+            ),
+        },
+    );
+
+    OpenAiTool::Function(ResponsesApiTool {
+        name: HANDOFF_TOOL_NAME.to_string(),
+        description: r#"Passes control to another agent once your turn ends.
+Call this when the remaining work belongs to a different agent, then finish your turn with a short summary of what you did; the summary and note are given to the next agent.
+"#
+        .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["agent".to_string(), "note".to_string()]),
+            additional_properties: Some(false),
+        },
+    })
+}
+
+/// Records the handoff request as a [`SubAgentHandoffEvent`]; the
+/// orchestrator driving this child starts the target agent after the turn
+/// completes.
+pub(crate) async fn handle_handoff(
+    session: &Session,
+    agent_name: Option<&str>,
+    targets: &[String],
+    arguments: String,
+    sub_id: String,
+) -> Result<String, FunctionCallError> {
+    let Some(agent_name) = agent_name.filter(|_| !targets.is_empty()) else {
+        return Err(FunctionCallError::RespondToModel(format!(
+            "unsupported call: {HANDOFF_TOOL_NAME}"
+        )));
+    };
+    let args = parse_handoff_arguments(&arguments, targets)?;
+    session
+        .send_event(Event {
+            id: sub_id,
+            msg: EventMsg::SubAgentHandoff(SubAgentHandoffEvent {
+                agent_name: agent_name.to_string(),
+                sub_conversation_id: *session.conversation_id(),
+                target_agent: args.agent.clone(),
+                note: args.note,
+            }),
+        })
+        .await;
+    Ok(format!(
+        "Handoff to `{}` recorded. Finish your turn with a brief summary of your work.",
+        args.agent
+    ))
+}
+
+fn parse_handoff_arguments(
+    arguments: &str,
+    targets: &[String],
+) -> Result<HandoffArgs, FunctionCallError> {
+    let mut args = serde_json::from_str::<HandoffArgs>(arguments).map_err(|e| {
+        FunctionCallError::RespondToModel(format!("failed to parse function arguments: {e}"))
+    })?;
+    args.agent = args.agent.trim().to_string();
+    if !targets.contains(&args.agent) {
+        return Err(FunctionCallError::RespondToModel(format!(
+            "cannot hand off to `{}`; allowed agents: {}",
+            args.agent,
+            targets.join(", ")
+        )));
+    }
+    Ok(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn handoff_arguments_must_name_an_allowed_agent() {
+        let targets = vec!["tester".to_string(), "reviewer".to_string()];
+
+        let args = parse_handoff_arguments(
+            r#"{"agent": " tester ", "note": "3 tests fail in parser.rs"}"#,
+            &targets,
+        )
+        .expect("valid handoff");
+        assert_eq!(args.agent, "tester");
+        assert_eq!(args.note, "3 tests fail in parser.rs");
+
+        let Err(FunctionCallError::RespondToModel(message)) =
+            parse_handoff_arguments(r#"{"agent": "deployer", "note": "ship it"}"#, &targets)
+        else {
+            panic!("expected unknown target to be rejected");
+        };
+        assert_eq!(
+            message,
+            "cannot hand off to `deployer`; allowed agents: tester, reviewer"
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use std::collections::BTreeMap;
+
+use codex_subagents::memory::AgentMemory;
+use codex_subagents::memory::MAX_NOTE_BYTES;
+use codex_subagents::memory::RememberOutcome;
+use serde::Deserialize;
+
+use crate::function_tool::FunctionCallError;
+use crate::openai_tools::JsonSchema;
+use crate::openai_tools::OpenAiTool;
+use crate::openai_tools::ResponsesApiTool;
+
+pub(crate) const REMEMBER_TOOL_NAME: &str = "remember";
+pub(crate) const RECALL_TOOL_NAME: &str = "recall";
+
+const DEFAULT_RECALL_LIMIT: usize = 10;
+
+#[derive(Debug, Deserialize)]
+struct RememberArgs {
+    note: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RecallArgs {
+    #[serde(default)]
+    query: String,
+    #[serde(default)]
+    limit: Option<usize>,
+}
+
+/// Tools offered to subagents whose spec sets `memory: true`.
+pub(crate) fn create_memory_tools() -> Vec<OpenAiTool> {
+    let mut remember_properties = BTreeMap::new();
+    remember_properties.insert(
+        "note".to_string(),
+        JsonSchema::String {
+            description: Some(format!(
+                "One self-contained fact, at most {MAX_NOTE_BYTES} bytes."
+            )),
+        },
+    );
+    let remember = OpenAiTool::Function(ResponsesApiTool {
+        name: REMEMBER_TOOL_NAME.to_string(),
+        description: r#"Saves a note for your future runs on this project.
+Use it for durable project conventions and facts you had to discover, not for details of the current task.
+"#
+        .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties: remember_properties,
+            required: Some(vec!["note".to_string()]),
+            additional_properties: Some(false),
+        },
+    });
+
+    let mut recall_properties = BTreeMap::new();
+    recall_properties.insert(
+        "query".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Words to look for; leave empty for the most recent notes.".to_string(),
+            ),
+        },
+    );
+    recall_properties.insert(
+        "limit".to_string(),
+        JsonSchema::Number {
+            description: Some(format!(
+                "Maximum number of notes to return (default {DEFAULT_RECALL_LIMIT})."
+            )),
+        },
+    );
+    let recall = OpenAiTool::Function(ResponsesApiTool {
+        name: RECALL_TOOL_NAME.to_string(),
+        description:
+            "Searches the notes you saved with `remember` in earlier runs on this project."
+                .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties: recall_properties,
+            required: Some(Vec::new()),
+            additional_properties: Some(false),
+        },
+    });
+
+    vec![remember, recall]
+}
+
+pub(crate) fn handle_remember(
+    memory: Option<&AgentMemory>,
+    arguments: &str,
+) -> Result<String, FunctionCallError> {
+    let memory = memory_or_unsupported(memory, REMEMBER_TOOL_NAME)?;
+    let args: RememberArgs = parse_arguments(arguments)?;
+    match memory.remember(&args.note) {
+        Ok(RememberOutcome::Saved { dropped: 0 }) => Ok("Saved.".to_string()),
+        Ok(RememberOutcome::Saved { dropped }) => Ok(format!(
+            "Saved. Dropped the {dropped} oldest note(s) to stay within the size limit."
+        )),
+        Ok(RememberOutcome::AlreadyKnown) => Ok("Already remembered.".to_string()),
+        Err(err) => Err(FunctionCallError::RespondToModel(format!(
+            "failed to save note: {err}"
+        ))),
+    }
+}
+
+pub(crate) fn handle_recall(
+    memory: Option<&AgentMemory>,
+    arguments: &str,
+) -> Result<String, FunctionCallError> {
+    let memory = memory_or_unsupported(memory, RECALL_TOOL_NAME)?;
+    let args: RecallArgs = if arguments.trim().is_empty() {
+        RecallArgs {
+            query: String::new(),
+            limit: None,
+        }
+    } else {
+        parse_arguments(arguments)?
+    };
+    let limit = args.limit.unwrap_or(DEFAULT_RECALL_LIMIT).max(1);
+    let notes = memory
+        .recall(&args.query, limit)
+        .map_err(|err| FunctionCallError::RespondToModel(format!("failed to read notes: {err}")))?;
+    if notes.is_empty() {
+        return Ok("No matching notes.".to_string());
+    }
+    Ok(notes
+        .iter()
+        .map(|note| format!("- {note}"))
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+fn memory_or_unsupported<'a>(
+    memory: Option<&'a AgentMemory>,
+    tool: &str,
+) -> Result<&'a AgentMemory, FunctionCallError> {
+    memory.ok_or_else(|| FunctionCallError::RespondToModel(format!("unsupported call: {tool}")))
+}
+
+fn parse_arguments<'de, T: Deserialize<'de>>(arguments: &'de str) -> Result<T, FunctionCallError> {
+    serde_json::from_str(arguments).map_err(|e| {
+        FunctionCallError::RespondToModel(format!("failed to parse function arguments: {e}"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn remember_and_recall_round_trip() {
+        let project = TempDir::new().expect("tempdir");
+        let memory = AgentMemory::for_agent(project.path(), "reviewer").expect("memory");
+
+        assert_eq!(
+            handle_remember(
+                Some(&memory),
+                r#"{"note": "CI runs clippy with -D warnings"}"#
+            )
+            .expect("remember"),
+            "Saved."
+        );
+        assert_eq!(
+            handle_remember(
+                Some(&memory),
+                r#"{"note": "ci runs clippy with -D warnings"}"#
+            )
+            .expect("remember"),
+            "Already remembered."
+        );
+        assert_eq!(
+            handle_recall(Some(&memory), r#"{"query": "clippy lints"}"#).expect("recall"),
+            "- CI runs clippy with -D warnings"
+        );
+        assert_eq!(
+            handle_recall(Some(&memory), r#"{"query": "database"}"#).expect("recall"),
+            "No matching notes."
+        );
+
+        let Err(FunctionCallError::RespondToModel(message)) =
+            handle_recall(None, r#"{"query": "clippy"}"#)
+        else {
+            panic!("expected recall without memory to be rejected");
+        };
+        assert_eq!(message, "unsupported call: recall");
+    }
+}
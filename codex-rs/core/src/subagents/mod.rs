@@ -1,9 +1,21 @@
+mod context;
+pub(crate) mod handoff;
+pub(crate) mod memory;
 pub mod orchestrator;
+mod registry;
 pub mod router;
+pub mod workflow;
 
+pub use context::ParentContext;
+pub use orchestrator::SubagentBatchEntry;
+pub use orchestrator::SubagentBatchResult;
 pub use orchestrator::SubagentInvocation;
 pub use orchestrator::SubagentOrchestrator;
 pub use orchestrator::SubagentRunState;
+pub use orchestrator::requires_exclusive_workspace;
+pub use registry::project_agents_dir;
+pub use registry::subagent_registry;
+pub use registry::user_agents_dir;
 pub use router::RouteCandidate;
 pub use router::RouteIntent;
 pub use router::SubagentRoute;
@@ -1,7 +1,10 @@
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 use std::time::Instant;
 
+use super::context::ParentContext;
+use super::context::render_context;
 use crate::ConversationManager;
 use crate::NewConversation;
 use crate::config::Config;
@@ -11,17 +14,42 @@ use crate::protocol::EventMsg;
 use crate::protocol::InputItem;
 use crate::protocol::Op;
 use crate::protocol::StreamErrorEvent;
+use crate::protocol::SubAgentActivityEvent;
 use crate::protocol::SubAgentCompletedEvent;
 use crate::protocol::SubAgentMessageEvent;
 use crate::protocol::SubAgentOutcome;
 use crate::protocol::SubAgentStartedEvent;
 use crate::protocol::TaskCompleteEvent;
+use crate::protocol::TokenCountEvent;
+use crate::protocol::TokenUsage;
 use crate::protocol::TurnAbortReason;
 use crate::protocol::TurnAbortedEvent;
 use codex_protocol::mcp_protocol::ConversationId;
+use codex_subagents::ContextAttachment;
 use codex_subagents::SubagentSpec;
 use codex_subagents::TaskContext;
 use codex_subagents::TaskContextError;
+use futures::future::join_all;
+use tokio::sync::AcquireError;
+use tokio::sync::Mutex;
+use tokio::sync::MutexGuard;
+use tokio::sync::Semaphore;
+use tokio::sync::SemaphorePermit;
+
+/// Tools that let a child mutate the shared workspace, including `*`, which
+/// grants all of them. Children that can use any of them are serialized
+/// during a parallel fan-out.
+const WORKSPACE_WRITE_TOOLS: &[&str] = &[
+    "*",
+    "apply_patch",
+    "shell",
+    "container.exec",
+    "local_shell",
+    "exec",
+    "exec_command",
+    "write_stdin",
+    "unified_exec",
+];
 
 #[derive(Debug)]
 pub struct SubagentInvocation<'a> {
@@ -37,27 +65,143 @@ pub struct SubagentRunState {
     pub error: Option<String>,
     pub last_message: Option<String>,
     pub duration: Duration,
+    /// Tokens used by the run, summed over every agent in a handoff chain.
+    pub token_usage: TokenUsage,
+}
+
+/// Outcome of a single child launched by [`SubagentOrchestrator::run_parallel`].
+#[derive(Debug, Clone)]
+pub struct SubagentBatchEntry {
+    pub agent_name: String,
+    pub result: Result<SubagentRunState, String>,
+}
+
+impl SubagentBatchEntry {
+    pub fn succeeded(&self) -> bool {
+        matches!(
+            &self.result,
+            Ok(SubagentRunState {
+                outcome: SubAgentOutcome::Success,
+                ..
+            })
+        )
+    }
+}
+
+/// Combined result of a parallel fan-out, in the order agents were requested.
+#[derive(Debug, Clone, Default)]
+pub struct SubagentBatchResult {
+    pub runs: Vec<SubagentBatchEntry>,
+}
+
+impl SubagentBatchResult {
+    pub fn succeeded(&self) -> usize {
+        self.runs.iter().filter(|entry| entry.succeeded()).count()
+    }
+
+    pub fn failed(&self) -> usize {
+        self.runs.len() - self.succeeded()
+    }
+
+    pub fn all_succeeded(&self) -> bool {
+        self.runs.iter().all(SubagentBatchEntry::succeeded)
+    }
+
+    /// Tokens used by every child that started.
+    pub fn token_usage(&self) -> TokenUsage {
+        let mut total = TokenUsage::default();
+        for state in self
+            .runs
+            .iter()
+            .filter_map(|entry| entry.result.as_ref().ok())
+        {
+            total.add_assign(&state.token_usage);
+        }
+        total
+    }
+}
+
+/// Handoff requested by a child through the `handoff` tool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PendingHandoff {
+    agent: String,
+    note: String,
 }
 
 #[derive(Clone)]
 pub struct SubagentOrchestrator {
     conversation_manager: Arc<ConversationManager>,
+    /// Agents children may hand off to; usually the registry snapshot.
+    handoff_agents: Arc<Vec<SubagentSpec>>,
+    /// Attachments requested at launch, on top of each agent's own `context`.
+    context: Arc<Vec<ContextAttachment>>,
+    parent_context: Arc<ParentContext>,
 }
 
 impl SubagentOrchestrator {
     pub fn new(conversation_manager: Arc<ConversationManager>) -> Self {
         Self {
             conversation_manager,
+            handoff_agents: Arc::new(Vec::new()),
+            context: Arc::new(Vec::new()),
+            parent_context: Arc::new(ParentContext::default()),
         }
     }
 
+    /// Make `agents` available as `handoff` targets. Without them children
+    /// are not offered the `handoff` tool.
+    pub fn with_handoff_agents(mut self, agents: Vec<SubagentSpec>) -> Self {
+        self.handoff_agents = Arc::new(agents);
+        self
+    }
+
+    /// Attach `context` to the first user message of every launched child.
+    /// Agents reached through a handoff only get their own `context`.
+    pub fn with_context_attachments(mut self, context: Vec<ContextAttachment>) -> Self {
+        self.context = Arc::new(context);
+        self
+    }
+
+    /// Messages and plan that `parent_last_messages` and `plan` attachments
+    /// draw from. Without it those attachments render as empty.
+    pub fn with_parent_context(mut self, parent: ParentContext) -> Self {
+        self.parent_context = Arc::new(parent);
+        self
+    }
+
     pub async fn spawn_child(
         &self,
         parent_config: &Config,
         invocation: &SubagentInvocation<'_>,
     ) -> CodexResult<NewConversation> {
+        self.spawn_hop(parent_config, invocation.spec, 0).await
+    }
+
+    /// Spawn a child `hops` handoffs into a chain. The `handoff` tool only
+    /// lists targets that can be resolved and disappears once the chain has
+    /// reached `subagents.max_handoffs`.
+    async fn spawn_hop(
+        &self,
+        parent_config: &Config,
+        spec: &SubagentSpec,
+        hops: usize,
+    ) -> CodexResult<NewConversation> {
+        let targets = available_handoff_targets(
+            spec,
+            &self.handoff_agents,
+            hops,
+            parent_config.subagents.max_handoffs,
+        );
+        if targets == spec.metadata.handoff_to {
+            return self
+                .conversation_manager
+                .spawn_subagent_conversation(parent_config, spec)
+                .await;
+        }
+        let mut spec = spec.clone();
+        spec.metadata.handoff_to = targets;
         self.conversation_manager
-            .spawn_subagent_conversation(parent_config, invocation.spec)
+            .spawn_subagent_conversation(parent_config, &spec)
             .await
     }
 
@@ -71,12 +215,14 @@ impl SubagentOrchestrator {
         invocation: &SubagentInvocation<'_>,
         conversation_id: ConversationId,
         model: Option<String>,
+        handoff_from: Option<ConversationId>,
     ) -> EventMsg {
         EventMsg::SubAgentStarted(SubAgentStartedEvent {
             agent_name: invocation.spec.metadata.name.clone(),
             parent_submit_id: invocation.parent_submit_id.clone(),
             sub_conversation_id: conversation_id,
             model,
+            handoff_from,
         })
     }
 
@@ -92,6 +238,19 @@ impl SubagentOrchestrator {
         })
     }
 
+    pub fn build_activity_event(
+        spec: &SubagentSpec,
+        conversation_id: ConversationId,
+        msg: EventMsg,
+    ) -> EventMsg {
+        EventMsg::SubAgentActivity(SubAgentActivityEvent {
+            agent_name: spec.metadata.name.clone(),
+            sub_conversation_id: conversation_id,
+            msg: Box::new(msg),
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn build_completed_event(
         spec: &SubagentSpec,
         conversation_id: ConversationId,
@@ -99,6 +258,8 @@ impl SubagentOrchestrator {
         error: Option<String>,
         model: Option<String>,
         duration: Duration,
+        handoff_to: Option<String>,
+        token_usage: Option<TokenUsage>,
     ) -> EventMsg {
         let duration_ms = duration.as_millis();
         let duration_ms = duration_ms.min(u128::from(u64::MAX)) as u64;
@@ -109,6 +270,8 @@ impl SubagentOrchestrator {
             error,
             model,
             duration_ms: Some(duration_ms),
+            handoff_to,
+            token_usage,
         })
     }
 
@@ -118,33 +281,132 @@ impl SubagentOrchestrator {
         parent_config: &Config,
         invocation: SubagentInvocation<'_>,
         prompt: Option<String>,
+        on_event: F,
+    ) -> CodexResult<SubagentRunState>
+    where
+        F: FnMut(EventMsg) + Send,
+    {
+        let child = self.spawn_child(parent_config, &invocation).await?;
+        self.run_spawned(parent_config, invocation, child, prompt, on_event)
+            .await
+    }
+
+    /// Drive a child conversation created by [`Self::spawn_child`]. Callers
+    /// that need the child's `SessionConfigured` event before the run starts
+    /// (e.g. `codex exec --agent`) spawn first and then hand the child over.
+    ///
+    /// When the child hands off, the target agent is started with the handoff
+    /// note and the previous agent's final message; the returned state is the
+    /// last agent's in the chain.
+    pub async fn run_spawned<F>(
+        &self,
+        parent_config: &Config,
+        invocation: SubagentInvocation<'_>,
+        child: NewConversation,
+        prompt: Option<String>,
+        on_event: F,
+    ) -> CodexResult<SubagentRunState>
+    where
+        F: FnMut(EventMsg) + Send,
+    {
+        self.run_chain(parent_config, invocation, child, prompt, None, on_event)
+            .await
+    }
+
+    /// [`Self::run_spawned`] for a child of a batch, which holds `slot`. Each
+    /// handoff target that can write to the workspace takes the batch's write
+    /// lock before it starts.
+    async fn run_chain<F>(
+        &self,
+        parent_config: &Config,
+        invocation: SubagentInvocation<'_>,
+        child: NewConversation,
+        prompt: Option<String>,
+        mut slot: Option<&mut BatchSlot<'_>>,
         mut on_event: F,
     ) -> CodexResult<SubagentRunState>
     where
         F: FnMut(EventMsg) + Send,
     {
-        let SubagentInvocation {
-            spec,
-            parent_submit_id,
-        } = invocation;
-        let invocation_ref = SubagentInvocation {
-            spec,
-            parent_submit_id: parent_submit_id.clone(),
-        };
+        let (mut state, mut handoff) = self
+            .run_hop(
+                &parent_config.cwd,
+                &invocation,
+                child,
+                prompt,
+                None,
+                &mut on_event,
+            )
+            .await?;
+        let mut from_agent = invocation.spec.metadata.name.clone();
+        let mut hops = 0;
+        while let Some(PendingHandoff { agent, note }) = handoff.take() {
+            let Some(next_spec) = self.find_handoff_agent(&agent) else {
+                break;
+            };
+            hops += 1;
+            let next = SubagentInvocation {
+                spec: next_spec,
+                parent_submit_id: invocation.parent_submit_id.clone(),
+            };
+            let prompt = handoff_prompt(&from_agent, &note, state.last_message.as_deref());
+            if let Some(slot) = slot.as_deref_mut() {
+                slot.acquire(next_spec).await.map_err(|err| {
+                    std::io::Error::other(format!("subagent scheduler closed: {err}"))
+                })?;
+            }
+            let child = self.spawn_hop(parent_config, next_spec, hops).await?;
+            let mut token_usage = state.token_usage;
+            (state, handoff) = self
+                .run_hop(
+                    &parent_config.cwd,
+                    &next,
+                    child,
+                    Some(prompt),
+                    Some(state.conversation_id),
+                    &mut on_event,
+                )
+                .await?;
+            token_usage.add_assign(&state.token_usage);
+            state.token_usage = token_usage;
+            from_agent = next_spec.metadata.name.clone();
+        }
+        Ok(state)
+    }
+
+    fn find_handoff_agent(&self, name: &str) -> Option<&SubagentSpec> {
+        self.handoff_agents
+            .iter()
+            .find(|spec| spec.metadata.name == name)
+    }
 
+    async fn run_hop<F>(
+        &self,
+        cwd: &Path,
+        invocation: &SubagentInvocation<'_>,
+        child: NewConversation,
+        prompt: Option<String>,
+        handoff_from: Option<ConversationId>,
+        on_event: &mut F,
+    ) -> CodexResult<(SubagentRunState, Option<PendingHandoff>)>
+    where
+        F: FnMut(EventMsg) + Send,
+    {
+        let spec = invocation.spec;
         let started_at = Instant::now();
 
         let NewConversation {
             conversation_id,
             conversation,
             session_configured,
-        } = self.spawn_child(parent_config, &invocation_ref).await?;
+        } = child;
 
         let model = Some(session_configured.model.clone());
         on_event(Self::build_started_event(
-            &invocation_ref,
+            invocation,
             conversation_id,
             model.clone(),
+            handoff_from,
         ));
 
         let default_prompt = "Please execute your standard workflow.".to_string();
@@ -170,6 +432,27 @@ impl SubagentOrchestrator {
             on_event(Self::build_message_event(spec, conversation_id, shortened));
         }
 
+        let mut attachments = spec.metadata.context.clone();
+        if handoff_from.is_none() {
+            for attachment in self.context.iter() {
+                if !attachments.contains(attachment) {
+                    attachments.push(attachment.clone());
+                }
+            }
+        }
+        let prompt_text = if attachments.is_empty() {
+            prompt_text
+        } else {
+            let labels: Vec<String> = attachments.iter().map(ToString::to_string).collect();
+            on_event(Self::build_message_event(
+                spec,
+                conversation_id,
+                format!("context: {}", labels.join(", ")),
+            ));
+            let blocks = render_context(&attachments, cwd, &self.parent_context).await;
+            format!("{blocks}\n\n{prompt_text}")
+        };
+
         conversation
             .submit(Op::UserInput {
                 items: vec![InputItem::Text { text: prompt_text }],
@@ -179,6 +462,8 @@ impl SubagentOrchestrator {
         let mut last_message: Option<String> = None;
         let mut outcome = SubAgentOutcome::Success;
         let mut error_text: Option<String> = None;
+        let mut handoff: Option<PendingHandoff> = None;
+        let mut token_usage: Option<TokenUsage> = None;
 
         loop {
             match conversation.next_event().await {
@@ -192,6 +477,18 @@ impl SubagentOrchestrator {
                         ));
                     }
                     EventMsg::AgentMessageDelta(_) => {}
+                    EventMsg::TokenCount(TokenCountEvent {
+                        info: Some(info), ..
+                    }) => {
+                        token_usage = Some(info.total_token_usage);
+                    }
+                    EventMsg::SubAgentHandoff(event) => {
+                        handoff = Some(PendingHandoff {
+                            agent: event.target_agent.clone(),
+                            note: event.note.clone(),
+                        });
+                        on_event(EventMsg::SubAgentHandoff(event));
+                    }
                     EventMsg::TaskComplete(TaskCompleteEvent { last_agent_message }) => {
                         if let Some(message) = last_agent_message
                             .filter(|msg| !msg.trim().is_empty())
@@ -233,6 +530,16 @@ impl SubagentOrchestrator {
                     EventMsg::ShutdownComplete => {
                         break;
                     }
+                    msg @ (EventMsg::ExecCommandBegin(_)
+                    | EventMsg::ExecCommandEnd(_)
+                    | EventMsg::ExecApprovalRequest(_)
+                    | EventMsg::ApplyPatchApprovalRequest(_)
+                    | EventMsg::PatchApplyBegin(_)
+                    | EventMsg::PatchApplyEnd(_)
+                    | EventMsg::McpToolCallBegin(_)
+                    | EventMsg::McpToolCallEnd(_)) => {
+                        on_event(Self::build_activity_event(spec, conversation_id, msg));
+                    }
                     _ => {}
                 },
                 Err(err) => {
@@ -246,6 +553,8 @@ impl SubagentOrchestrator {
             }
         }
 
+        // A failed child does not pass control on.
+        let handoff = handoff.filter(|_| outcome == SubAgentOutcome::Success);
         let duration = started_at.elapsed();
         on_event(Self::build_completed_event(
             spec,
@@ -254,6 +563,8 @@ impl SubagentOrchestrator {
             error_text.clone(),
             model.clone(),
             duration,
+            handoff.as_ref().map(|handoff| handoff.agent.clone()),
+            token_usage.clone(),
         ));
 
         crate::telemetry::record_subagent_run(
@@ -267,17 +578,163 @@ impl SubagentOrchestrator {
             .remove_conversation(&conversation_id)
             .await;
 
-        Ok(SubagentRunState {
-            conversation_id,
-            model,
-            outcome,
-            error: error_text,
-            last_message,
-            duration,
-        })
+        Ok((
+            SubagentRunState {
+                conversation_id,
+                model,
+                outcome,
+                error: error_text,
+                last_message,
+                duration,
+                token_usage: token_usage.unwrap_or_default(),
+            },
+            handoff,
+        ))
+    }
+
+    /// Run several subagents concurrently, bounded by
+    /// `subagents.max_concurrency`. Events from every child are forwarded to
+    /// `on_event` as they arrive, each tagged with its own
+    /// `sub_conversation_id`. Children that may write to the workspace,
+    /// including agents reached through a handoff, are run one at a time so
+    /// their edits cannot clobber each other.
+    pub async fn run_parallel<F>(
+        &self,
+        parent_config: &Config,
+        invocations: Vec<SubagentInvocation<'_>>,
+        prompt: Option<String>,
+        on_event: F,
+    ) -> SubagentBatchResult
+    where
+        F: Fn(EventMsg) + Send + Sync,
+    {
+        let runs = invocations
+            .into_iter()
+            .map(|invocation| (invocation, prompt.clone()))
+            .collect();
+        self.run_batch(parent_config, runs, on_event).await
+    }
+
+    /// Same as [`Self::run_parallel`], but each child receives its own prompt.
+    pub async fn run_batch<F>(
+        &self,
+        parent_config: &Config,
+        runs: Vec<(SubagentInvocation<'_>, Option<String>)>,
+        on_event: F,
+    ) -> SubagentBatchResult
+    where
+        F: Fn(EventMsg) + Send + Sync,
+    {
+        let permits = Semaphore::new(parent_config.subagents.max_concurrency.max(1));
+        let write_lock = Mutex::new(());
+        let on_event = &on_event;
+
+        let runs = runs.into_iter().map(|(invocation, prompt)| {
+            let permits = &permits;
+            let write_lock = &write_lock;
+            async move {
+                let agent_name = invocation.spec.metadata.name.clone();
+                let mut slot = BatchSlot {
+                    permits,
+                    write_lock,
+                    permit: None,
+                    write_guard: None,
+                };
+                let result = match slot.acquire(invocation.spec).await {
+                    Ok(()) => {
+                        let run = async {
+                            let child = self.spawn_child(parent_config, &invocation).await?;
+                            self.run_chain(
+                                parent_config,
+                                invocation,
+                                child,
+                                prompt,
+                                Some(&mut slot),
+                                on_event,
+                            )
+                            .await
+                        };
+                        run.await.map_err(|err| err.to_string())
+                    }
+                    Err(err) => Err(format!("subagent scheduler closed: {err}")),
+                };
+                SubagentBatchEntry { agent_name, result }
+            }
+        });
+
+        SubagentBatchResult {
+            runs: join_all(runs).await,
+        }
+    }
+}
+
+/// Concurrency permit and workspace write lock held by one child of a batch,
+/// carried along its handoff chain.
+struct BatchSlot<'a> {
+    permits: &'a Semaphore,
+    write_lock: &'a Mutex<()>,
+    permit: Option<SemaphorePermit<'a>>,
+    write_guard: Option<MutexGuard<'a, ()>>,
+}
+
+impl BatchSlot<'_> {
+    /// Make sure the slot can run `spec`: take the write lock if `spec` can
+    /// write and it is not held yet, then a permit. The lock comes first so
+    /// queued writers do not hold concurrency slots that read-only children
+    /// could use, and the permit is given back while waiting for it so the
+    /// writer holding the lock can still get one.
+    async fn acquire(&mut self, spec: &SubagentSpec) -> Result<(), AcquireError> {
+        if self.write_guard.is_none() && requires_exclusive_workspace(spec) {
+            self.permit = None;
+            self.write_guard = Some(self.write_lock.lock().await);
+        }
+        if self.permit.is_none() {
+            self.permit = Some(self.permits.acquire().await?);
+        }
+        Ok(())
     }
 }
 
+/// Handoff targets offered to a child that is `hops` handoffs into a chain:
+/// the spec's `handoff_to` entries that resolve to a known agent, or none once
+/// the chain has reached `max_handoffs`.
+fn available_handoff_targets(
+    spec: &SubagentSpec,
+    agents: &[SubagentSpec],
+    hops: usize,
+    max_handoffs: usize,
+) -> Vec<String> {
+    if hops >= max_handoffs {
+        return Vec::new();
+    }
+    spec.metadata
+        .handoff_to
+        .iter()
+        .filter(|target| agents.iter().any(|agent| &agent.metadata.name == *target))
+        .cloned()
+        .collect()
+}
+
+fn handoff_prompt(from_agent: &str, note: &str, summary: Option<&str>) -> String {
+    let mut prompt =
+        format!("The `{from_agent}` agent handed this task to you.\n\nHandoff note:\n{note}");
+    if let Some(summary) = summary.filter(|summary| !summary.trim().is_empty()) {
+        prompt.push_str(&format!("\n\nSummary from `{from_agent}`:\n{summary}"));
+    }
+    prompt
+}
+
+/// Returns true when the spec grants tools that can modify the workspace. An
+/// empty allowlist means the child inherits every tool from the parent.
+pub fn requires_exclusive_workspace(spec: &SubagentSpec) -> bool {
+    spec.metadata.tools.is_empty()
+        || spec
+            .metadata
+            .tools
+            .iter()
+            .any(|tool| WORKSPACE_WRITE_TOOLS.contains(&tool.as_str()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -302,11 +759,93 @@ mod tests {
             None,
             Some("gpt-5".to_string()),
             Duration::from_millis(1_250),
+            None,
+            Some(TokenUsage {
+                input_tokens: 10,
+                output_tokens: 5,
+                total_tokens: 15,
+                ..TokenUsage::default()
+            }),
         );
         let EventMsg::SubAgentCompleted(payload) = event else {
             panic!("expected subagent completed event");
         };
         assert_eq!(payload.duration_ms, Some(1_250));
+        assert_eq!(
+            payload.token_usage.map(|usage| usage.total_tokens),
+            Some(15)
+        );
+    }
+
+    #[test]
+    fn exclusive_workspace_tracks_write_tools() {
+        let unrestricted = make_spec("tester");
+        assert!(requires_exclusive_workspace(&unrestricted));
+
+        let writer = SubagentBuilder::new("writer")
+            .tools(["apply_patch"])
+            .instructions("Write code")
+            .build()
+            .expect("spec");
+        assert!(requires_exclusive_workspace(&writer));
+
+        for tools in [["shell"], ["*"], ["container.exec"], ["exec_command"]] {
+            let spec = SubagentBuilder::new("shell-user")
+                .tools(tools)
+                .instructions("Run commands")
+                .build()
+                .expect("spec");
+            assert!(requires_exclusive_workspace(&spec), "{tools:?}");
+        }
+
+        let reader = SubagentBuilder::new("reviewer")
+            .tools(["view_image", "web_search"])
+            .instructions("Review code")
+            .build()
+            .expect("spec");
+        assert!(!requires_exclusive_workspace(&reader));
+    }
+
+    #[test]
+    fn batch_result_counts_outcomes() {
+        let ok = SubagentRunState {
+            conversation_id: ConversationId::default(),
+            model: None,
+            outcome: SubAgentOutcome::Success,
+            error: None,
+            last_message: None,
+            duration: Duration::from_millis(10),
+            token_usage: TokenUsage {
+                input_tokens: 100,
+                output_tokens: 20,
+                total_tokens: 120,
+                ..TokenUsage::default()
+            },
+        };
+        let failed = SubagentRunState {
+            outcome: SubAgentOutcome::Error,
+            ..ok.clone()
+        };
+        let batch = SubagentBatchResult {
+            runs: vec![
+                SubagentBatchEntry {
+                    agent_name: "security".to_string(),
+                    result: Ok(ok),
+                },
+                SubagentBatchEntry {
+                    agent_name: "perf".to_string(),
+                    result: Ok(failed),
+                },
+                SubagentBatchEntry {
+                    agent_name: "style".to_string(),
+                    result: Err("spawn failed".to_string()),
+                },
+            ],
+        };
+        assert_eq!(batch.succeeded(), 1);
+        assert_eq!(batch.failed(), 2);
+        assert!(!batch.all_succeeded());
+        assert_eq!(batch.token_usage().total_tokens, 240);
     }
 
     #[test]
@@ -319,10 +858,41 @@ mod tests {
             None,
             None,
             Duration::from_secs(u64::MAX),
+            None,
+            None,
         );
         let EventMsg::SubAgentCompleted(payload) = event else {
             panic!("expected subagent completed event");
         };
         assert_eq!(payload.duration_ms, Some(u64::MAX));
     }
+
+    #[test]
+    fn handoff_targets_respect_registry_and_chain_limit() {
+        let writer = SubagentBuilder::new("writer")
+            .handoff_to(["tester", "deployer"])
+            .instructions("Write code")
+            .build()
+            .expect("spec");
+        let agents = vec![make_spec("tester"), make_spec("reviewer")];
+
+        assert_eq!(
+            available_handoff_targets(&writer, &agents, 0, 3),
+            vec!["tester".to_string()]
+        );
+        assert!(available_handoff_targets(&writer, &agents, 3, 3).is_empty());
+        assert!(available_handoff_targets(&writer, &[], 0, 3).is_empty());
+    }
+
+    #[test]
+    fn handoff_prompt_includes_note_and_summary() {
+        assert_eq!(
+            handoff_prompt("writer", "Run the parser tests", Some("Fixed the lexer")),
+            "The `writer` agent handed this task to you.\n\nHandoff note:\nRun the parser tests\n\nSummary from `writer`:\nFixed the lexer"
+        );
+        assert_eq!(
+            handoff_prompt("writer", "Run the parser tests", Some("  ")),
+            "The `writer` agent handed this task to you.\n\nHandoff note:\nRun the parser tests"
+        );
+    }
 }
@@ -0,0 +1,29 @@
+use std::path::PathBuf;
+
+use codex_subagents::SubagentRegistry;
+use codex_subagents::builtin_agents;
+
+use crate::config::CONFIG_TOML_FILE;
+use crate::config::Config;
+
+/// Registry over every agent source `config` knows about: builtin agents,
+/// `[subagents.agents]` tables, `$CODEX_HOME/agents`, and `.codex/agents`
+/// under the working directory.
+pub fn subagent_registry(config: &Config) -> SubagentRegistry {
+    SubagentRegistry::new(project_agents_dir(config), user_agents_dir(config))
+        .with_builtin_agents(builtin_agents())
+        .with_config_agents(
+            config.codex_home.join(CONFIG_TOML_FILE),
+            config.subagents.agents.clone(),
+        )
+}
+
+/// `.codex/agents` under the working directory.
+pub fn project_agents_dir(config: &Config) -> PathBuf {
+    config.cwd.join(".codex/agents")
+}
+
+/// `$CODEX_HOME/agents`.
+pub fn user_agents_dir(config: &Config) -> PathBuf {
+    config.codex_home.join("agents")
+}
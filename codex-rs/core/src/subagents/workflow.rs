@@ -0,0 +1,267 @@
+use std::collections::BTreeMap;
+
+use crate::config::Config;
+use crate::protocol::BackgroundEventEvent;
+use crate::protocol::EventMsg;
+use crate::protocol::SubAgentOutcome;
+use crate::subagents::SubagentBatchEntry;
+use crate::subagents::SubagentInvocation;
+use crate::subagents::SubagentOrchestrator;
+use codex_subagents::CheckpointError;
+use codex_subagents::DiagnosticLevel;
+use codex_subagents::StepResult;
+use codex_subagents::StepStatus;
+use codex_subagents::SubagentSpec;
+use codex_subagents::TaskContextError;
+use codex_subagents::WorkflowDefinition;
+use codex_subagents::WorkflowError;
+use codex_subagents::WorkflowReport;
+use codex_subagents::WorkflowRun;
+use codex_subagents::checkpoint::RunCheckpoint;
+use codex_subagents::checkpoint::RunStatus;
+use codex_subagents::workflow::StepRecord;
+use codex_subagents::workflow::reported_failure;
+
+impl SubagentOrchestrator {
+    /// Drive a declarative workflow to completion. Each batch produced by the
+    /// workflow engine runs through [`SubagentOrchestrator::run_batch`], so
+    /// parallel steps share the `subagents.max_concurrency` budget. Skipped
+    /// steps and loop iterations are reported as background events next to the
+    /// usual subagent events.
+    pub async fn run_workflow<F>(
+        &self,
+        parent_config: &Config,
+        workflow: &WorkflowDefinition,
+        inputs: BTreeMap<String, String>,
+        agents: &[SubagentSpec],
+        on_event: F,
+    ) -> Result<WorkflowReport, WorkflowError>
+    where
+        F: Fn(EventMsg) + Send + Sync,
+    {
+        let run = WorkflowRun::new(workflow, inputs)?;
+        self.drive_workflow(parent_config, run, agents, None, on_event)
+            .await
+    }
+
+    /// Like [`Self::run_workflow`], but saves `checkpoint` after every batch
+    /// that does not stop the workflow. Steps already in the checkpoint are
+    /// replayed rather than run again, so a failed or interrupted run picks
+    /// up at the first unfinished step. Each step's outcome is kept in the
+    /// checkpoint's task context under `workflow.steps.<id>`.
+    pub async fn run_workflow_checkpointed<F>(
+        &self,
+        parent_config: &Config,
+        workflow: &WorkflowDefinition,
+        agents: &[SubagentSpec],
+        checkpoint: &mut RunCheckpoint,
+        on_event: F,
+    ) -> Result<WorkflowReport, WorkflowError>
+    where
+        F: Fn(EventMsg) + Send + Sync,
+    {
+        let manifest = checkpoint.manifest();
+        let run = WorkflowRun::resume(workflow, manifest.inputs.clone(), &manifest.steps)?;
+        let replayed = manifest.steps.len();
+        if replayed > 0 {
+            on_event(background(format!(
+                "Workflow {}: resuming after {replayed} recorded step(s)",
+                workflow.name
+            )));
+        }
+        self.drive_workflow(parent_config, run, agents, Some(checkpoint), on_event)
+            .await
+    }
+
+    async fn drive_workflow<F>(
+        &self,
+        parent_config: &Config,
+        mut run: WorkflowRun<'_>,
+        agents: &[SubagentSpec],
+        mut checkpoint: Option<&mut RunCheckpoint>,
+        on_event: F,
+    ) -> Result<WorkflowReport, WorkflowError>
+    where
+        F: Fn(EventMsg) + Send + Sync,
+    {
+        let workflow = run.workflow();
+        while let Some(batch) = run.next_batch() {
+            for step_id in &batch.skipped {
+                on_event(background(format!(
+                    "Workflow {}: skipped step `{step_id}`",
+                    workflow.name
+                )));
+            }
+
+            let mut runs = Vec::new();
+            let mut step_ids = Vec::new();
+            for planned in batch.steps {
+                let Some(spec) = agents
+                    .iter()
+                    .find(|spec| spec.metadata.name.eq_ignore_ascii_case(&planned.agent))
+                else {
+                    run.record(
+                        &planned.step_id,
+                        StepResult::failed(
+                            format!("Subagent '{}' not found.", planned.agent),
+                            None,
+                        ),
+                    )?;
+                    continue;
+                };
+                let attempt = if planned.attempt > 1 {
+                    format!(" (attempt {})", planned.attempt)
+                } else {
+                    String::new()
+                };
+                on_event(background(format!(
+                    "Workflow {}: step `{}` → {}{attempt}",
+                    workflow.name, planned.step_id, spec.metadata.name
+                )));
+                runs.push((
+                    SubagentInvocation {
+                        spec,
+                        parent_submit_id: format!("workflow-{}-{}", workflow.name, planned.step_id),
+                    },
+                    planned.prompt,
+                ));
+                step_ids.push(planned.step_id);
+            }
+            if !runs.is_empty() {
+                let results = self.run_batch(parent_config, runs, &on_event).await;
+                for (step_id, entry) in step_ids.iter().zip(results.runs) {
+                    run.record(step_id, step_result(entry))?;
+                }
+            }
+
+            if let Some(checkpoint) = checkpoint.as_deref_mut()
+                && !run.is_halted()
+            {
+                save_checkpoint(checkpoint, run.history())?;
+            }
+        }
+
+        if let Some(checkpoint) = checkpoint {
+            let status = if run.is_halted() {
+                RunStatus::Failed
+            } else {
+                RunStatus::Succeeded
+            };
+            checkpoint.finish(status)?;
+        }
+        Ok(run.into_report())
+    }
+}
+
+fn save_checkpoint(
+    checkpoint: &mut RunCheckpoint,
+    history: &[StepRecord],
+) -> Result<(), CheckpointError> {
+    let saved = checkpoint.manifest().steps.len();
+    for record in history.iter().skip(saved) {
+        let ctx = checkpoint.context();
+        ctx.set_scratchpad(
+            format!("workflow.steps.{}", record.step_id),
+            serde_json::to_value(&record.result).map_err(TaskContextError::Serialization)?,
+        )?;
+        let level = match record.result.status {
+            StepStatus::Failed => DiagnosticLevel::Warn,
+            StepStatus::Succeeded | StepStatus::Skipped => DiagnosticLevel::Info,
+        };
+        ctx.push_diagnostic(
+            level,
+            format!(
+                "step `{}` {}",
+                record.step_id,
+                record.result.status.as_str()
+            ),
+        )?;
+    }
+    checkpoint.save(history)
+}
+
+fn background(message: String) -> EventMsg {
+    EventMsg::BackgroundEvent(BackgroundEventEvent { message })
+}
+
+fn step_result(entry: SubagentBatchEntry) -> StepResult {
+    match entry.result {
+        Ok(state) => match state.outcome {
+            SubAgentOutcome::Success => {
+                match state.last_message.as_deref().and_then(reported_failure) {
+                    Some(error) => StepResult::failed(error, state.last_message),
+                    None => StepResult::succeeded(state.last_message),
+                }
+            }
+            SubAgentOutcome::Error => StepResult::failed(
+                state.error.unwrap_or_else(|| "subagent failed".to_string()),
+                state.last_message,
+            ),
+        },
+        Err(err) => StepResult::failed(err, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::subagents::SubagentRunState;
+    use codex_protocol::mcp_protocol::ConversationId;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    #[test]
+    fn batch_entries_map_to_step_results() {
+        let state = SubagentRunState {
+            conversation_id: ConversationId::default(),
+            model: None,
+            outcome: SubAgentOutcome::Error,
+            error: Some("tests failed".to_string()),
+            last_message: Some("2 failures".to_string()),
+            duration: Duration::from_millis(5),
+            token_usage: Default::default(),
+        };
+        let failed = step_result(SubagentBatchEntry {
+            agent_name: "tester".to_string(),
+            result: Ok(state.clone()),
+        });
+        assert_eq!(
+            failed,
+            StepResult::failed("tests failed", Some("2 failures".to_string()))
+        );
+
+        let passed = step_result(SubagentBatchEntry {
+            agent_name: "tester".to_string(),
+            result: Ok(SubagentRunState {
+                outcome: SubAgentOutcome::Success,
+                error: None,
+                ..state.clone()
+            }),
+        });
+        assert_eq!(passed.status, StepStatus::Succeeded);
+        assert_eq!(passed.output.as_deref(), Some("2 failures"));
+
+        // A run that finished but reports failing tests fails the step.
+        let reported = step_result(SubagentBatchEntry {
+            agent_name: "tester".to_string(),
+            result: Ok(SubagentRunState {
+                outcome: SubAgentOutcome::Success,
+                last_message: Some("{\"status\": \"failed\"}".to_string()),
+                ..state
+            }),
+        });
+        assert_eq!(
+            reported,
+            StepResult::failed(
+                "agent reported failure",
+                Some("{\"status\": \"failed\"}".to_string())
+            )
+        );
+
+        let spawn_error = step_result(SubagentBatchEntry {
+            agent_name: "tester".to_string(),
+            result: Err("spawn failed".to_string()),
+        });
+        assert_eq!(spawn_error.error.as_deref(), Some("spawn failed"));
+    }
+}
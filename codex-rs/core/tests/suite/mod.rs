@@ -19,4 +19,5 @@ mod rollout_list_find;
 mod seatbelt;
 mod stream_error_allows_next_turn;
 mod stream_no_completed;
+mod subagents_parallel;
 mod user_notification;
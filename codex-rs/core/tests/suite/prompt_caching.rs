@@ -415,6 +415,7 @@ async fn overrides_turn_context_but_keeps_cached_prefix_and_key_constant() {
                 network_access: true,
                 exclude_tmpdir_env_var: true,
                 exclude_slash_tmp: true,
+                exclude_cwd: false,
             }),
             model: Some("o3".to_string()),
             effort: Some(Some(ReasoningEffort::High)),
@@ -548,6 +549,7 @@ async fn per_turn_overrides_keep_cached_prefix_and_key_constant() {
                 network_access: true,
                 exclude_tmpdir_env_var: true,
                 exclude_slash_tmp: true,
+                exclude_cwd: false,
             },
             model: "o3".to_string(),
             effort: Some(ReasoningEffort::High),
@@ -0,0 +1,198 @@
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use codex_core::CodexAuth;
+use codex_core::ConversationManager;
+use codex_core::ModelProviderInfo;
+use codex_core::WireApi;
+use codex_core::built_in_model_providers;
+use codex_core::config::Config;
+use codex_core::protocol::EventMsg;
+use codex_core::subagents::SubagentBatchResult;
+use codex_core::subagents::SubagentInvocation;
+use codex_core::subagents::SubagentOrchestrator;
+use codex_subagents::SubagentBuilder;
+use codex_subagents::SubagentSpec;
+use core_test_support::load_default_config_for_test;
+use core_test_support::responses;
+use core_test_support::skip_if_no_network;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::Request;
+use wiremock::Respond;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+fn mock_config(codex_home: &TempDir, project: &TempDir, server: &MockServer) -> Config {
+    let mut config = load_default_config_for_test(codex_home);
+    config.cwd = project.path().to_path_buf();
+    config.subagents.max_concurrency = 4;
+    config.model_provider = ModelProviderInfo {
+        name: "mock".to_string(),
+        base_url: Some(format!("{}/v1", server.uri())),
+        env_key: None,
+        wire_api: WireApi::Responses,
+        requires_openai_auth: false,
+        request_max_retries: Some(0),
+        stream_max_retries: Some(0),
+        ..built_in_model_providers()["openai"].clone()
+    };
+    config
+}
+
+fn agent(name: &str, tool: &str, instructions: &str) -> SubagentSpec {
+    SubagentBuilder::new(name)
+        .tools([tool])
+        .instructions(instructions)
+        .build()
+        .expect("spec")
+}
+
+fn done_body() -> String {
+    responses::sse(vec![
+        serde_json::json!({"type": "response.created", "response": {"id": "resp1"}}),
+        responses::ev_assistant_message("m1", "Done."),
+        responses::ev_completed("resp1"),
+    ])
+}
+
+/// Runs `agents` through `run_parallel` and returns the started/completed
+/// events in the order they were seen.
+async fn run_and_record(
+    orchestrator: &SubagentOrchestrator,
+    config: &Config,
+    agents: &[SubagentSpec],
+) -> (SubagentBatchResult, Vec<(String, &'static str)>) {
+    let invocations = agents
+        .iter()
+        .map(|spec| SubagentInvocation {
+            spec,
+            parent_submit_id: "parent".to_string(),
+        })
+        .collect();
+    let lifecycle = Mutex::new(Vec::new());
+    let result = orchestrator
+        .run_parallel(config, invocations, Some("Go.".to_string()), |msg| {
+            let entry = match msg {
+                EventMsg::SubAgentStarted(event) => (event.agent_name, "started"),
+                EventMsg::SubAgentCompleted(event) => (event.agent_name, "completed"),
+                _ => return,
+            };
+            lifecycle.lock().unwrap().push(entry);
+        })
+        .await;
+    (result, lifecycle.into_inner().unwrap())
+}
+
+/// Asserts that each of `writers` finishes before the next one starts.
+fn assert_writers_ran_alone(lifecycle: &[(String, &'static str)], writers: &[&str]) {
+    let writer_events: Vec<_> = lifecycle
+        .iter()
+        .filter(|(name, _)| writers.contains(&name.as_str()))
+        .collect();
+    assert_eq!(writer_events.len(), writers.len() * 2, "{lifecycle:?}");
+    for pair in writer_events.chunks(2) {
+        assert_eq!(pair[0].0, pair[1].0, "{lifecycle:?}");
+        assert_eq!((pair[0].1, pair[1].1), ("started", "completed"));
+    }
+}
+
+/// Agents that can run shell commands must not overlap with other writers,
+/// whether they name `shell` or grant every tool with `*`.
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn run_parallel_runs_shell_agents_alone() {
+    skip_if_no_network!();
+
+    let server = responses::start_mock_server().await;
+    // Slow responses so that runs which are allowed to overlap do overlap.
+    Mock::given(method("POST"))
+        .and(path("/v1/responses"))
+        .respond_with(responses::sse_response(done_body()).set_delay(Duration::from_millis(300)))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let codex_home = TempDir::new().unwrap();
+    let project = TempDir::new().unwrap();
+    let config = mock_config(&codex_home, &project, &server);
+    let agents = [
+        agent("patcher", "apply_patch", "Do the task."),
+        agent("shell-user", "shell", "Do the task."),
+        agent("anything", "*", "Do the task."),
+    ];
+    let orchestrator = SubagentOrchestrator::new(Arc::new(ConversationManager::with_auth(
+        CodexAuth::from_api_key("Test API Key"),
+    )));
+
+    let (result, lifecycle) = run_and_record(&orchestrator, &config, &agents).await;
+    assert!(result.all_succeeded(), "{result:?}");
+    assert_writers_ran_alone(&lifecycle, &["patcher", "shell-user", "anything"]);
+}
+
+/// Answers the planner's first request with a handoff to `shell-user` and
+/// keeps `patcher` busy long enough for an unlocked handoff to overlap it.
+struct HandoffResponder;
+
+impl Respond for HandoffResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let body = String::from_utf8_lossy(&request.body);
+        if body.contains("Plan the work.") && !body.contains("function_call_output") {
+            return responses::sse_response(responses::sse(vec![
+                serde_json::json!({"type": "response.created", "response": {"id": "resp1"}}),
+                responses::ev_function_call(
+                    "call-1",
+                    "handoff",
+                    r#"{"agent": "shell-user", "note": "Run the build."}"#,
+                ),
+                responses::ev_completed("resp1"),
+            ]));
+        }
+        let delay = if body.contains("Patch the files.") {
+            Duration::from_millis(1_500)
+        } else {
+            Duration::from_millis(200)
+        };
+        responses::sse_response(done_body()).set_delay(delay)
+    }
+}
+
+/// A read-only agent starts without the write lock; the writer it hands off
+/// to must still wait for the other writers.
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn run_parallel_locks_writers_reached_through_a_handoff() {
+    skip_if_no_network!();
+
+    let server = responses::start_mock_server().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/responses"))
+        .respond_with(HandoffResponder)
+        .expect(4)
+        .mount(&server)
+        .await;
+
+    let codex_home = TempDir::new().unwrap();
+    let project = TempDir::new().unwrap();
+    let config = mock_config(&codex_home, &project, &server);
+    let planner = SubagentBuilder::new("planner")
+        .tools(["view_image"])
+        .handoff_to(["shell-user"])
+        .instructions("Plan the work.")
+        .build()
+        .expect("spec");
+    let patcher = agent("patcher", "apply_patch", "Patch the files.");
+    let shell_user = agent("shell-user", "shell", "Run commands.");
+    let orchestrator = SubagentOrchestrator::new(Arc::new(ConversationManager::with_auth(
+        CodexAuth::from_api_key("Test API Key"),
+    )))
+    .with_handoff_agents(vec![planner.clone(), patcher.clone(), shell_user]);
+
+    let (result, lifecycle) = run_and_record(&orchestrator, &config, &[planner, patcher]).await;
+    assert!(result.all_succeeded(), "{result:?}");
+    assert_writers_ran_alone(&lifecycle, &["patcher", "shell-user"]);
+}
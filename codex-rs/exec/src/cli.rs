@@ -1,6 +1,7 @@
 use clap::Parser;
 use clap::ValueEnum;
 use codex_common::CliConfigOverrides;
+use codex_subagents::ContextAttachment;
 use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
@@ -82,6 +83,25 @@ pub struct Cli {
     #[arg(long = "include-plan-tool", default_value_t = false)]
     pub include_plan_tool: bool,
 
+    /// Run the prompt through the named subagent from `.codex/agents` or
+    /// `$CODEX_HOME/agents` instead of the default agent.
+    #[arg(long = "agent", value_name = "NAME")]
+    pub agent: Option<String>,
+
+    /// Attach input to the subagent's first message (`git_diff`,
+    /// `git_diff=<base>`, `files=<glob>`, `parent_last_messages=<n>`, `plan`).
+    /// Repeatable; requires `--agent`.
+    #[arg(long = "context", value_name = "ATTACHMENT", requires = "agent")]
+    pub context: Vec<ContextAttachment>,
+
+    /// Run the prompt as a code review, like `/review` in the TUI.
+    #[arg(long = "review", default_value_t = false, conflicts_with = "agent")]
+    pub review: bool,
+
+    /// Write the review findings to FILE as SARIF 2.1.0.
+    #[arg(long = "sarif", value_name = "FILE", requires = "review")]
+    pub sarif: Option<PathBuf>,
+
     /// Specifies file where the last message from the agent should be written.
     #[arg(long = "output-last-message")]
     pub last_message_file: Option<PathBuf>,
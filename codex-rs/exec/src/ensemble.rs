@@ -0,0 +1,457 @@
+//! `codex subagents run <agent> --samples N`: run several samples of an agent,
+//! each in its own git worktree, let a judge agent pick the best result and
+//! apply only the winning diff.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::Arc;
+
+use codex_core::AuthManager;
+use codex_core::ConversationManager;
+use codex_core::config::Config;
+use codex_core::git_info::get_git_repo_root;
+use codex_core::protocol::AskForApproval;
+use codex_core::protocol::SandboxPolicy;
+use codex_core::protocol::SubAgentOutcome;
+use codex_core::subagents::SubagentInvocation;
+use codex_core::subagents::SubagentOrchestrator;
+use codex_git_tooling::CreateGhostCommitOptions;
+use codex_git_tooling::create_ghost_commit;
+use codex_subagents::ContextAttachment;
+use codex_subagents::SubagentSpec;
+use codex_subagents::ensemble::Candidate;
+use codex_subagents::ensemble::JudgeVerdict;
+use codex_subagents::ensemble::TestOutcome;
+use codex_subagents::ensemble::diff_stat;
+use codex_subagents::ensemble::judge_prompt;
+use futures::StreamExt;
+use owo_colors::OwoColorize;
+use tempfile::TempDir;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use crate::resolve_agent;
+
+#[derive(Debug, Clone)]
+pub struct EnsembleOptions {
+    pub agent: String,
+    pub prompt: Option<String>,
+    pub samples: usize,
+    /// Models handed to the samples in turn; empty keeps the agent's own.
+    pub models: Vec<String>,
+    /// Sampling temperatures handed to the samples in turn; empty keeps the
+    /// agent's own.
+    pub temperatures: Vec<f64>,
+    /// Agent that ranks the samples.
+    pub judge: String,
+    /// Shell command run in each sample's worktree once the agent is done.
+    pub test_command: Option<String>,
+    pub context: Vec<ContextAttachment>,
+}
+
+/// Everything the samples share.
+struct SampleRun<'a> {
+    orchestrator: &'a SubagentOrchestrator,
+    config: &'a Config,
+    spec: &'a SubagentSpec,
+    prompt: &'a str,
+    /// The working directory relative to the repository root.
+    relative_cwd: &'a Path,
+    /// Ghost commit of the working tree the samples start from.
+    baseline: &'a str,
+    test_command: Option<&'a str>,
+}
+
+pub async fn run_ensemble(mut config: Config, options: EnsembleOptions) -> anyhow::Result<()> {
+    if options.samples < 2 {
+        anyhow::bail!("An ensemble needs at least two samples.");
+    }
+    let Some(repo_root) = get_git_repo_root(&config.cwd) else {
+        anyhow::bail!("Ensemble runs need a git repository to create worktrees in.");
+    };
+    let (spec, agents) = resolve_agent(&config, &options.agent, "ensemble runs")?;
+    let judge = agents
+        .iter()
+        .find(|agent| agent.metadata.name.eq_ignore_ascii_case(&options.judge))
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("Judge agent '{}' not found.", options.judge))?;
+    let prompt = options
+        .prompt
+        .clone()
+        .unwrap_or_else(|| "Please execute your standard workflow.".to_string());
+
+    let head = git(&repo_root, &["rev-parse", "--verify", "HEAD"])
+        .await
+        .map_err(|_| anyhow::anyhow!("Ensemble runs need at least one commit."))?
+        .trim()
+        .to_string();
+    let baseline = create_ghost_commit(&CreateGhostCommitOptions::new(&repo_root))?;
+    let worktrees = Worktrees::create(&repo_root, &head, baseline.id(), options.samples).await?;
+
+    config.sandbox_policy = SandboxPolicy::new_workspace_write_policy();
+    config.approval_policy = AskForApproval::Never;
+    let orchestrator = SubagentOrchestrator::new(Arc::new(ConversationManager::new(
+        AuthManager::shared(config.codex_home.clone()),
+    )))
+    .with_handoff_agents(agents)
+    .with_context_attachments(options.context.clone());
+    let relative_cwd = config
+        .cwd
+        .strip_prefix(&repo_root)
+        .unwrap_or(Path::new(""))
+        .to_path_buf();
+    let run = SampleRun {
+        orchestrator: &orchestrator,
+        config: &config,
+        spec: &spec,
+        prompt: &prompt,
+        relative_cwd: &relative_cwd,
+        baseline: baseline.id(),
+        test_command: options.test_command.as_deref(),
+    };
+
+    println!(
+        "{} Running {} samples of {} in separate worktrees",
+        "→".cyan(),
+        options.samples,
+        spec.metadata.name.cyan().bold()
+    );
+    let samples = worktrees
+        .paths
+        .iter()
+        .enumerate()
+        .map(|(offset, worktree)| {
+            let model = (!options.models.is_empty())
+                .then(|| options.models[offset % options.models.len()].clone());
+            let temperature = (!options.temperatures.is_empty())
+                .then(|| options.temperatures[offset % options.temperatures.len()]);
+            run_sample(&run, offset + 1, model, temperature, worktree)
+        });
+    let candidates: Vec<Candidate> = futures::stream::iter(samples)
+        .buffered(config.subagents.max_concurrency.max(1))
+        .collect()
+        .await;
+    drop(worktrees);
+
+    if !candidates.iter().any(Candidate::is_eligible) {
+        anyhow::bail!("No sample finished with changes; nothing to apply.");
+    }
+
+    println!(
+        "{} Asking {} to pick a winner",
+        "→".cyan(),
+        judge.metadata.name.cyan().bold()
+    );
+    let mut judge_config = config.clone();
+    judge_config.sandbox_policy = SandboxPolicy::new_read_only_policy();
+    let judge_message = orchestrator
+        .run_subagent(
+            &judge_config,
+            SubagentInvocation {
+                spec: &judge,
+                parent_submit_id: format!("ensemble-judge-{}", judge.metadata.name),
+            },
+            Some(judge_prompt(&prompt, &candidates)),
+            |_| {},
+        )
+        .await?
+        .last_message
+        .unwrap_or_default();
+    let Some(verdict) = JudgeVerdict::from_message(&judge_message, &candidates) else {
+        let dir = save_patches(&config, &candidates)?;
+        anyhow::bail!(
+            "{} did not pick an eligible sample; nothing was applied. Sample diffs are in {}.",
+            judge.metadata.name,
+            dir.display()
+        );
+    };
+
+    print_summary(&candidates, &verdict);
+    let Some(winner) = candidates
+        .iter()
+        .find(|candidate| candidate.index == verdict.winner)
+    else {
+        return Ok(());
+    };
+    if let Err(err) = git_apply(&repo_root, &winner.diff).await {
+        let dir = save_patches(&config, &candidates)?;
+        anyhow::bail!(
+            "Failed to apply sample {}: {err}. Sample diffs are in {}.",
+            winner.label(),
+            dir.display()
+        );
+    }
+    println!(
+        "{} Applied sample {}",
+        "✓".green(),
+        winner.label().cyan().bold()
+    );
+    Ok(())
+}
+
+async fn run_sample(
+    run: &SampleRun<'_>,
+    index: usize,
+    model: Option<String>,
+    temperature: Option<f64>,
+    worktree: &Path,
+) -> Candidate {
+    let mut spec = run.spec.clone();
+    if model.is_some() || temperature.is_some() {
+        let mut binding = spec.metadata.model_config.clone().unwrap_or_default();
+        if let Some(model) = &model {
+            binding.model = Some(model.clone());
+        }
+        if let Some(temperature) = temperature {
+            binding
+                .parameters
+                .insert("temperature".to_string(), serde_json::json!(temperature));
+        }
+        spec.metadata.model_config = Some(binding);
+    }
+    let mut config = run.config.clone();
+    config.cwd = worktree.join(run.relative_cwd);
+
+    let mut candidate = Candidate {
+        index,
+        model,
+        temperature,
+        ..Default::default()
+    };
+    let invocation = SubagentInvocation {
+        spec: &spec,
+        parent_submit_id: format!("ensemble-subagent-{}-{index}", spec.metadata.name),
+    };
+    match run
+        .orchestrator
+        .run_subagent(&config, invocation, Some(run.prompt.to_string()), |_| {})
+        .await
+    {
+        Ok(state) => {
+            if state.outcome == SubAgentOutcome::Error {
+                candidate.error = Some(state.error.unwrap_or_else(|| "run failed".to_string()));
+            }
+            candidate.final_message = state.last_message;
+        }
+        Err(err) => candidate.error = Some(err.to_string()),
+    }
+    if let Some(command) = run.test_command {
+        candidate.tests = Some(run_tests(command, &config.cwd).await);
+    }
+    match sample_diff(worktree, run.baseline).await {
+        Ok(diff) => candidate.diff = diff,
+        Err(err) => {
+            candidate
+                .error
+                .get_or_insert_with(|| format!("failed to collect changes: {err}"));
+        }
+    }
+
+    let status = match &candidate.error {
+        Some(error) => format!("{} {}", "✗".red(), error.red()),
+        None => "✓".green().to_string(),
+    };
+    println!(
+        "  {status} sample {} {}",
+        candidate.label().cyan(),
+        describe_changes(&candidate).dimmed()
+    );
+    candidate
+}
+
+async fn run_tests(command: &str, cwd: &Path) -> TestOutcome {
+    match Command::new("sh")
+        .args(["-c", command])
+        .current_dir(cwd)
+        .stdin(Stdio::null())
+        .output()
+        .await
+    {
+        Ok(output) => {
+            let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+            text.push_str(&String::from_utf8_lossy(&output.stderr));
+            TestOutcome::new(command, output.status.code(), &text)
+        }
+        Err(err) => TestOutcome::new(command, None, &format!("failed to run: {err}")),
+    }
+}
+
+/// The sample's changes against the shared baseline, untracked files
+/// included.
+async fn sample_diff(worktree: &Path, baseline: &str) -> anyhow::Result<Vec<u8>> {
+    let result = create_ghost_commit(&CreateGhostCommitOptions::new(worktree))?;
+    git_bytes(
+        worktree,
+        &["diff", "--binary", "--no-ext-diff", baseline, result.id()],
+    )
+    .await
+}
+
+fn describe_changes(candidate: &Candidate) -> String {
+    let (files, added, removed) = diff_stat(&candidate.diff);
+    let mut parts = vec![format!("{files} file(s), +{added} -{removed}")];
+    if let Some(tests) = &candidate.tests {
+        parts.push(if tests.passed {
+            "tests passed".to_string()
+        } else {
+            match tests.exit_code {
+                Some(code) => format!("tests failed (exit {code})"),
+                None => "tests failed".to_string(),
+            }
+        });
+    }
+    parts.join(", ")
+}
+
+fn print_summary(candidates: &[Candidate], verdict: &JudgeVerdict) {
+    println!("\n{}", "Samples".bold());
+    for candidate in candidates {
+        let rank = verdict
+            .ranking
+            .iter()
+            .position(|index| *index == candidate.index)
+            .map(|position| format!("rank {}", position + 1))
+            .unwrap_or_else(|| "unranked".to_string());
+        let marker = if candidate.index == verdict.winner {
+            "★".yellow().to_string()
+        } else {
+            " ".to_string()
+        };
+        println!(
+            "  {marker} {} {} {}",
+            candidate.label().cyan(),
+            rank.dimmed(),
+            describe_changes(candidate)
+        );
+        if let Some(error) = &candidate.error {
+            println!("      {}", error.red());
+        }
+    }
+    if !verdict.rationale.trim().is_empty() {
+        println!("\n{}", "Judge".bold());
+        for line in verdict.rationale.trim().lines() {
+            println!("  {line}");
+        }
+    }
+}
+
+/// Write every sample's diff under `$CODEX_HOME/subagents/ensembles/` so
+/// nothing is lost when no diff could be applied.
+fn save_patches(config: &Config, candidates: &[Candidate]) -> anyhow::Result<PathBuf> {
+    let dir = config
+        .codex_home
+        .join("subagents")
+        .join("ensembles")
+        .join(chrono::Local::now().format("%Y%m%d-%H%M%S").to_string());
+    std::fs::create_dir_all(&dir)?;
+    for candidate in candidates
+        .iter()
+        .filter(|candidate| candidate.is_eligible())
+    {
+        std::fs::write(
+            dir.join(format!("sample-{}.patch", candidate.index)),
+            &candidate.diff,
+        )?;
+    }
+    Ok(dir)
+}
+
+/// Detached worktrees at `HEAD` plus the uncommitted changes of the main
+/// working tree, removed on drop.
+struct Worktrees {
+    repo_root: PathBuf,
+    paths: Vec<PathBuf>,
+    scratch: TempDir,
+}
+
+impl Worktrees {
+    async fn create(
+        repo_root: &Path,
+        head: &str,
+        baseline: &str,
+        count: usize,
+    ) -> anyhow::Result<Self> {
+        let scratch = tempfile::Builder::new()
+            .prefix("codex-ensemble-")
+            .tempdir()?;
+        let mut worktrees = Self {
+            repo_root: repo_root.to_path_buf(),
+            paths: Vec::new(),
+            scratch,
+        };
+        let pending = git_bytes(
+            repo_root,
+            &["diff", "--binary", "--no-ext-diff", head, baseline],
+        )
+        .await?;
+        for index in 1..=count {
+            let path = worktrees.scratch.path().join(format!("sample-{index}"));
+            let path_arg = path.to_string_lossy().into_owned();
+            git(
+                repo_root,
+                &["worktree", "add", "--detach", "--quiet", &path_arg, head],
+            )
+            .await?;
+            worktrees.paths.push(path.clone());
+            if !pending.trim_ascii().is_empty() {
+                git_apply(&path, &pending).await?;
+            }
+        }
+        Ok(worktrees)
+    }
+}
+
+impl Drop for Worktrees {
+    fn drop(&mut self) {
+        for path in &self.paths {
+            let _ = std::process::Command::new("git")
+                .args(["worktree", "remove", "--force"])
+                .arg(path)
+                .current_dir(&self.repo_root)
+                .output();
+        }
+    }
+}
+
+async fn git(cwd: &Path, args: &[&str]) -> anyhow::Result<String> {
+    let stdout = git_bytes(cwd, args).await?;
+    Ok(String::from_utf8_lossy(&stdout).into_owned())
+}
+
+/// Like [`git`], but returns stdout untouched, for diffs that must round-trip
+/// through `git apply`.
+async fn git_bytes(cwd: &Path, args: &[&str]) -> anyhow::Result<Vec<u8>> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(cwd)
+        .stdin(Stdio::null())
+        .output()
+        .await?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "`git {}` failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(output.stdout)
+}
+
+async fn git_apply(cwd: &Path, diff: &[u8]) -> anyhow::Result<()> {
+    let mut child = Command::new("git")
+        .args(["apply", "--binary", "--whitespace=nowarn", "-"])
+        .current_dir(cwd)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(diff).await?;
+    }
+    let output = child.wait_with_output().await?;
+    if !output.status.success() {
+        anyhow::bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(())
+}
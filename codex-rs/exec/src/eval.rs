@@ -0,0 +1,257 @@
+//! `codex subagents eval`: run each agent's eval cases against a scratch copy
+//! of their fixture and report which assertions held.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Instant;
+
+use codex_core::AuthManager;
+use codex_core::ConversationManager;
+use codex_core::config::Config;
+use codex_core::protocol::AskForApproval;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::SandboxPolicy;
+use codex_core::protocol::SubAgentOutcome;
+use codex_core::subagents::SubagentInvocation;
+use codex_core::subagents::SubagentOrchestrator;
+use codex_core::subagents::project_agents_dir;
+use codex_subagents::SubagentSpec;
+use codex_subagents::evals::CaseReport;
+use codex_subagents::evals::EVALS_DIR;
+use codex_subagents::evals::EvalCase;
+use codex_subagents::evals::EvalObservation;
+use codex_subagents::evals::EvalReport;
+use codex_subagents::evals::FixtureSnapshot;
+use codex_subagents::evals::copy_fixture;
+use codex_subagents::evals::load_cases;
+use owo_colors::OwoColorize;
+
+use crate::resolve_agent;
+
+#[derive(Debug, Clone)]
+pub struct EvalOptions {
+    /// Only run this agent's cases.
+    pub agent: Option<String>,
+    pub report: PathBuf,
+}
+
+/// Run the cases under `.codex/agents/evals/`, print a pass/fail table and
+/// write the JSON report. Each case runs in its own temporary directory with
+/// a workspace-write sandbox and `approval_policy = "never"`, against the
+/// configured model provider.
+pub async fn run_evals(config: Config, options: EvalOptions) -> anyhow::Result<EvalReport> {
+    let evals_dir = project_agents_dir(&config).join(EVALS_DIR);
+    let cases = load_cases(&evals_dir, options.agent.as_deref())?;
+    if cases.is_empty() {
+        anyhow::bail!(
+            "No eval cases found. Add them as {}/<agent>/<case>.yaml.",
+            evals_dir.display()
+        );
+    }
+
+    let mut agents = Vec::new();
+    let mut specs: Vec<SubagentSpec> = Vec::new();
+    for case in &cases {
+        if !specs
+            .iter()
+            .any(|spec| spec.metadata.name.eq_ignore_ascii_case(&case.agent))
+        {
+            let (spec, all) = resolve_agent(&config, &case.agent, "evals")?;
+            specs.push(spec);
+            agents = all;
+        }
+    }
+    let orchestrator = SubagentOrchestrator::new(Arc::new(ConversationManager::new(
+        AuthManager::shared(config.codex_home.clone()),
+    )))
+    .with_handoff_agents(agents);
+
+    println!(
+        "{} Running {} eval case(s) with {}",
+        "→".cyan(),
+        cases.len(),
+        config.model_provider.name.bold()
+    );
+    let mut reports = Vec::new();
+    for case in &cases {
+        println!("{}", format!("  {}/{}", case.agent, case.name).dimmed());
+        let Some(spec) = specs
+            .iter()
+            .find(|spec| spec.metadata.name.eq_ignore_ascii_case(&case.agent))
+        else {
+            continue;
+        };
+        reports.push(run_case(&orchestrator, &config, spec, case).await);
+    }
+
+    let report = EvalReport::new(reports);
+    print_table(&report);
+    report.write(&options.report)?;
+    println!(
+        "{}",
+        format!("Report written to {}", options.report.display()).dimmed()
+    );
+    Ok(report)
+}
+
+async fn run_case(
+    orchestrator: &SubagentOrchestrator,
+    config: &Config,
+    spec: &SubagentSpec,
+    case: &EvalCase,
+) -> CaseReport {
+    let started = Instant::now();
+    let mut observed = EvalObservation::default();
+    let error = match run_in_scratch_dir(orchestrator, config, spec, case, &mut observed).await {
+        Ok(()) => None,
+        Err(err) => Some(err.to_string()),
+    };
+    CaseReport::new(case, observed, error, started.elapsed())
+}
+
+async fn run_in_scratch_dir(
+    orchestrator: &SubagentOrchestrator,
+    config: &Config,
+    spec: &SubagentSpec,
+    case: &EvalCase,
+    observed: &mut EvalObservation,
+) -> anyhow::Result<()> {
+    let scratch = tempfile::Builder::new().prefix("codex-eval-").tempdir()?;
+    let root = scratch.path();
+    if let Some(fixture) = case.fixture.as_deref() {
+        copy_fixture(fixture, root)?;
+    }
+    let before = FixtureSnapshot::capture(root)?;
+
+    let mut config = config.clone();
+    config.cwd = root.to_path_buf();
+    config.sandbox_policy = SandboxPolicy::new_workspace_write_policy();
+    config.approval_policy = AskForApproval::Never;
+    let invocation = SubagentInvocation {
+        spec,
+        parent_submit_id: format!("eval-subagent-{}-{}", case.agent, case.name),
+    };
+    let commands = &mut observed.commands;
+    let state = orchestrator
+        .run_subagent(&config, invocation, Some(case.prompt.clone()), |msg| {
+            if let EventMsg::SubAgentActivity(activity) = msg
+                && let EventMsg::ExecCommandBegin(begin) = *activity.msg
+            {
+                commands.push(command_text(&begin.command));
+            }
+        })
+        .await?;
+
+    observed.changed_files = before.changed_files(&FixtureSnapshot::capture(root)?);
+    observed.final_message = state.last_message;
+    if state.outcome == SubAgentOutcome::Error {
+        anyhow::bail!(
+            "{}",
+            state
+                .error
+                .unwrap_or_else(|| format!("{} failed", case.agent))
+        );
+    }
+    Ok(())
+}
+
+/// The script of `bash -lc <script>`, or the words joined by spaces.
+fn command_text(command: &[String]) -> String {
+    match command {
+        [shell, flag, script] if is_shell(shell) && matches!(flag.as_str(), "-c" | "-lc") => {
+            script.clone()
+        }
+        _ => command.join(" "),
+    }
+}
+
+fn is_shell(program: &str) -> bool {
+    matches!(
+        Path::new(program)
+            .file_name()
+            .and_then(|name| name.to_str()),
+        Some("bash" | "sh" | "zsh")
+    )
+}
+
+fn print_table(report: &EvalReport) {
+    let rows: Vec<[String; 4]> = report
+        .cases
+        .iter()
+        .map(|case| {
+            [
+                case.agent.clone(),
+                case.case.clone(),
+                if case.passed { "pass" } else { "FAIL" }.to_string(),
+                codex_common::elapsed::format_duration(std::time::Duration::from_millis(
+                    case.duration_ms,
+                )),
+            ]
+        })
+        .collect();
+    let header = ["AGENT", "CASE", "RESULT", "TIME"];
+    let mut widths = header.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    println!();
+    println!(
+        "{}",
+        format!(
+            "{:<w0$}  {:<w1$}  {:<w2$}  {:<w3$}  DETAIL",
+            header[0],
+            header[1],
+            header[2],
+            header[3],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3],
+        )
+        .bold()
+    );
+    for (row, case) in rows.iter().zip(&report.cases) {
+        let result = format!("{:<w$}", row[2], w = widths[2]);
+        let result = if case.passed {
+            result.green().to_string()
+        } else {
+            result.red().bold().to_string()
+        };
+        println!(
+            "{:<w0$}  {:<w1$}  {result}  {:<w3$}  {}",
+            row[0],
+            row[1],
+            row[3],
+            case.failure().unwrap_or_default(),
+            w0 = widths[0],
+            w1 = widths[1],
+            w3 = widths[3],
+        );
+    }
+    let summary = format!("{} passed, {} failed", report.passed, report.failed);
+    if report.failed == 0 {
+        println!("\n{}", summary.green().bold());
+    } else {
+        println!("\n{}", summary.red().bold());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn command_text_unwraps_shell_scripts() {
+        let words = |words: &[&str]| words.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(
+            command_text(&words(&["/bin/bash", "-lc", "cargo test -p demo"])),
+            "cargo test -p demo"
+        );
+        assert_eq!(command_text(&words(&["rg", "TODO", "src"])), "rg TODO src");
+    }
+}
@@ -13,6 +13,7 @@ use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::ExecCommandBeginEvent;
 use codex_core::protocol::ExecCommandEndEvent;
+use codex_core::protocol::ExitedReviewModeEvent;
 use codex_core::protocol::FileChange;
 use codex_core::protocol::McpInvocation;
 use codex_core::protocol::McpToolCallBeginEvent;
@@ -21,11 +22,17 @@ use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
 use codex_core::protocol::SessionConfiguredEvent;
 use codex_core::protocol::StreamErrorEvent;
+use codex_core::protocol::SubAgentCompletedEvent;
+use codex_core::protocol::SubAgentHandoffEvent;
+use codex_core::protocol::SubAgentMessageEvent;
+use codex_core::protocol::SubAgentOutcome;
+use codex_core::protocol::SubAgentStartedEvent;
 use codex_core::protocol::TaskCompleteEvent;
 use codex_core::protocol::TurnAbortReason;
 use codex_core::protocol::TurnDiffEvent;
 use codex_core::protocol::WebSearchBeginEvent;
 use codex_core::protocol::WebSearchEndEvent;
+use codex_core::review_format::format_review_findings_block;
 use codex_protocol::num_format::format_with_separators;
 use owo_colors::OwoColorize;
 use owo_colors::Style;
@@ -33,6 +40,7 @@ use shlex::try_join;
 use std::collections::HashMap;
 use std::io::Write;
 use std::path::PathBuf;
+use std::time::Duration;
 use std::time::Instant;
 
 use crate::event_processor::CodexStatus;
@@ -43,6 +51,9 @@ use codex_common::create_config_summary_entries;
 /// This should be configurable. When used in CI, users may not want to impose
 /// a limit so they can see the full transcript.
 const MAX_OUTPUT_LINES_FOR_EXEC_TOOL_CALL: usize = 20;
+/// Subagent transcripts are indented under the run header and trimmed to this
+/// many lines per message to keep headless logs compact.
+const MAX_LINES_PER_SUBAGENT_MESSAGE: usize = 20;
 pub(crate) struct EventProcessorWithHumanOutput {
     call_id_to_command: HashMap<String, ExecCommandBegin>,
     call_id_to_patch: HashMap<String, PatchApplyBegin>,
@@ -596,10 +607,91 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             EventMsg::ConversationPath(_) => {}
             EventMsg::UserMessage(_) => {}
             EventMsg::EnteredReviewMode(_) => {}
-            EventMsg::ExitedReviewMode(_) => {}
-            EventMsg::SubAgentStarted(_) => {}
-            EventMsg::SubAgentMessage(_) => {}
-            EventMsg::SubAgentCompleted(_) => {}
+            EventMsg::SubAgentActivity(_) => {}
+            EventMsg::ExitedReviewMode(ExitedReviewModeEvent { review_output }) => {
+                let Some(output) = review_output else {
+                    return CodexStatus::Running;
+                };
+                let text = if output.findings.is_empty() {
+                    output.overall_explanation.trim().to_string()
+                } else {
+                    format_review_findings_block(&output.findings, None)
+                };
+                ts_println!(
+                    self,
+                    "{}\n{}",
+                    "review".style(self.italic).style(self.magenta),
+                    text.trim_start()
+                );
+            }
+            EventMsg::SubAgentStarted(SubAgentStartedEvent {
+                agent_name, model, ..
+            }) => {
+                let model = model.map(|model| format!(" ({model})")).unwrap_or_default();
+                ts_println!(
+                    self,
+                    "{} {}{}",
+                    "subagent".style(self.magenta),
+                    agent_name.style(self.bold),
+                    model.style(self.dimmed)
+                );
+            }
+            EventMsg::SubAgentMessage(SubAgentMessageEvent { message, .. }) => {
+                let total_lines = message.lines().count();
+                for line in message.lines().take(MAX_LINES_PER_SUBAGENT_MESSAGE) {
+                    println!("  {}", line.style(self.dimmed));
+                }
+                if total_lines > MAX_LINES_PER_SUBAGENT_MESSAGE {
+                    let hidden = total_lines - MAX_LINES_PER_SUBAGENT_MESSAGE;
+                    println!("  {}", format!("… +{hidden} lines").style(self.dimmed));
+                }
+            }
+            EventMsg::SubAgentCompleted(SubAgentCompletedEvent {
+                agent_name,
+                outcome,
+                error,
+                duration_ms,
+                token_usage,
+                ..
+            }) => {
+                let mut summary = duration_ms
+                    .map(|ms| format!(" in {}", format_duration(Duration::from_millis(ms))))
+                    .unwrap_or_default();
+                if let Some(usage) = token_usage.filter(|usage| !usage.is_zero()) {
+                    summary.push_str(&format!(
+                        " ({} tokens)",
+                        format_with_separators(usage.blended_total())
+                    ));
+                }
+                match outcome {
+                    SubAgentOutcome::Success => {
+                        let title = format!("subagent {agent_name} completed{summary}");
+                        ts_println!(self, "{}", title.style(self.green));
+                    }
+                    SubAgentOutcome::Error => {
+                        let mut title = format!("subagent {agent_name} failed{summary}");
+                        if let Some(error) = error {
+                            title.push_str(&format!(": {error}"));
+                        }
+                        ts_println!(self, "{}", title.style(self.red));
+                    }
+                }
+            }
+            EventMsg::SubAgentHandoff(SubAgentHandoffEvent {
+                agent_name,
+                target_agent,
+                note,
+                ..
+            }) => {
+                ts_println!(
+                    self,
+                    "{} {} → {}",
+                    "handoff".style(self.magenta),
+                    agent_name.style(self.bold),
+                    target_agent.style(self.bold)
+                );
+                println!("  {}", note.style(self.dimmed));
+            }
         }
         CodexStatus::Running
     }
@@ -35,12 +35,24 @@ pub struct TurnCompletedEvent {
     pub usage: Usage,
 }
 
-/// Minimal usage summary for a turn.
+/// Minimal usage summary for a turn. The counts include tokens used by
+/// subagents, which are also broken out in `subagents`.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, TS, Default)]
 pub struct Usage {
     pub input_tokens: u64,
     pub cached_input_tokens: u64,
     pub output_tokens: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subagents: Option<SubagentUsage>,
+}
+
+/// Tokens used by delegated subagent runs.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, TS, Default)]
+pub struct SubagentUsage {
+    pub input_tokens: u64,
+    pub cached_input_tokens: u64,
+    pub output_tokens: u64,
+    pub reasoning_output_tokens: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, TS)]
@@ -83,6 +95,7 @@ pub enum ConversationItemDetails {
     McpToolCall(McpToolCallItem),
     WebSearch(WebSearchItem),
     TodoList(TodoListItem),
+    SubagentRun(SubagentRunItem),
     Error(ErrorItem),
 }
 
@@ -189,3 +202,31 @@ pub struct TodoItem {
 pub struct TodoListItem {
     pub items: Vec<TodoItem>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum SubagentRunStatus {
+    #[default]
+    InProgress,
+    Completed,
+    Failed,
+}
+
+/// Delegated subagent run, updated as the child reports progress.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, TS)]
+pub struct SubagentRunItem {
+    pub agent: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    pub status: SubagentRunStatus,
+    pub messages: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Agent that took over from this run through a handoff.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub handoff_to: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<SubagentUsage>,
+}
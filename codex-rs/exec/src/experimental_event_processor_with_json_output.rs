@@ -21,6 +21,9 @@ use crate::exec_events::PatchApplyStatus;
 use crate::exec_events::PatchChangeKind;
 use crate::exec_events::ReasoningItem;
 use crate::exec_events::SessionCreatedEvent;
+use crate::exec_events::SubagentRunItem;
+use crate::exec_events::SubagentRunStatus;
+use crate::exec_events::SubagentUsage;
 use crate::exec_events::TodoItem;
 use crate::exec_events::TodoListItem;
 use crate::exec_events::TurnCompletedEvent;
@@ -39,6 +42,10 @@ use codex_core::protocol::FileChange;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
 use codex_core::protocol::SessionConfiguredEvent;
+use codex_core::protocol::SubAgentCompletedEvent;
+use codex_core::protocol::SubAgentMessageEvent;
+use codex_core::protocol::SubAgentOutcome;
+use codex_core::protocol::SubAgentStartedEvent;
 use codex_core::protocol::TaskCompleteEvent;
 use codex_core::protocol::TaskStartedEvent;
 use tracing::error;
@@ -52,7 +59,11 @@ pub struct ExperimentalEventProcessorWithJsonOutput {
     running_patch_applies: HashMap<String, PatchApplyBeginEvent>,
     // Tracks the todo list for the current turn (at most one per turn).
     running_todo_list: Option<RunningTodoList>,
+    // Tracks subagent runs by sub_conversation_id.
+    running_subagents: HashMap<String, RunningSubagent>,
     last_total_token_usage: Option<codex_core::protocol::TokenUsage>,
+    // Tokens used by finished subagent runs, added to `turn.completed` usage.
+    subagent_token_usage: Option<codex_core::protocol::TokenUsage>,
 }
 
 #[derive(Debug, Clone)]
@@ -61,6 +72,12 @@ struct RunningCommand {
     item_id: String,
 }
 
+#[derive(Debug, Clone)]
+struct RunningSubagent {
+    item_id: String,
+    item: SubagentRunItem,
+}
+
 #[derive(Debug, Clone)]
 struct RunningTodoList {
     item_id: String,
@@ -75,7 +92,9 @@ impl ExperimentalEventProcessorWithJsonOutput {
             running_commands: HashMap::new(),
             running_patch_applies: HashMap::new(),
             running_todo_list: None,
+            running_subagents: HashMap::new(),
             last_total_token_usage: None,
+            subagent_token_usage: None,
         }
     }
 
@@ -103,6 +122,9 @@ impl ExperimentalEventProcessorWithJsonOutput {
                 message: ev.message.clone(),
             })],
             EventMsg::PlanUpdate(ev) => self.handle_plan_update(ev),
+            EventMsg::SubAgentStarted(ev) => self.handle_subagent_started(ev),
+            EventMsg::SubAgentMessage(ev) => self.handle_subagent_message(ev),
+            EventMsg::SubAgentCompleted(ev) => self.handle_subagent_completed(ev),
             _ => Vec::new(),
         }
     }
@@ -296,20 +318,111 @@ impl ExperimentalEventProcessorWithJsonOutput {
         vec![ConversationEvent::ItemStarted(ItemStartedEvent { item })]
     }
 
+    fn handle_subagent_started(&mut self, ev: &SubAgentStartedEvent) -> Vec<ConversationEvent> {
+        let item_id = self.get_next_item_id();
+        let item = SubagentRunItem {
+            agent: ev.agent_name.clone(),
+            model: ev.model.clone(),
+            status: SubagentRunStatus::InProgress,
+            messages: Vec::new(),
+            duration_ms: None,
+            error: None,
+            handoff_to: None,
+            usage: None,
+        };
+        self.running_subagents.insert(
+            ev.sub_conversation_id.to_string(),
+            RunningSubagent {
+                item_id: item_id.clone(),
+                item: item.clone(),
+            },
+        );
+        let item = ConversationItem {
+            id: item_id,
+            details: ConversationItemDetails::SubagentRun(item),
+        };
+        vec![ConversationEvent::ItemStarted(ItemStartedEvent { item })]
+    }
+
+    fn handle_subagent_message(&mut self, ev: &SubAgentMessageEvent) -> Vec<ConversationEvent> {
+        let Some(running) = self
+            .running_subagents
+            .get_mut(&ev.sub_conversation_id.to_string())
+        else {
+            warn!(
+                agent = ev.agent_name,
+                "SubAgentMessage without matching SubAgentStarted; skipping item.updated"
+            );
+            return Vec::new();
+        };
+        running.item.messages.push(ev.message.clone());
+        let item = ConversationItem {
+            id: running.item_id.clone(),
+            details: ConversationItemDetails::SubagentRun(running.item.clone()),
+        };
+        vec![ConversationEvent::ItemUpdated(ItemUpdatedEvent { item })]
+    }
+
+    fn handle_subagent_completed(&mut self, ev: &SubAgentCompletedEvent) -> Vec<ConversationEvent> {
+        let Some(RunningSubagent {
+            item_id,
+            item: mut run,
+        }) = self
+            .running_subagents
+            .remove(&ev.sub_conversation_id.to_string())
+        else {
+            warn!(
+                agent = ev.agent_name,
+                "SubAgentCompleted without matching SubAgentStarted; skipping item.completed"
+            );
+            return Vec::new();
+        };
+        run.status = match ev.outcome {
+            SubAgentOutcome::Success => SubagentRunStatus::Completed,
+            SubAgentOutcome::Error => SubagentRunStatus::Failed,
+        };
+        if ev.model.is_some() {
+            run.model = ev.model.clone();
+        }
+        run.duration_ms = ev.duration_ms;
+        run.error = ev.error.clone();
+        run.handoff_to = ev.handoff_to.clone();
+        if let Some(usage) = &ev.token_usage {
+            run.usage = Some(subagent_usage(usage));
+            self.subagent_token_usage
+                .get_or_insert_with(Default::default)
+                .add_assign(usage);
+        }
+        let item = ConversationItem {
+            id: item_id,
+            details: ConversationItemDetails::SubagentRun(run),
+        };
+        vec![ConversationEvent::ItemCompleted(ItemCompletedEvent {
+            item,
+        })]
+    }
+
     fn handle_task_started(&self, _: &TaskStartedEvent) -> Vec<ConversationEvent> {
         vec![ConversationEvent::TurnStarted(TurnStartedEvent {})]
     }
 
     fn handle_task_complete(&mut self) -> Vec<ConversationEvent> {
-        let usage = if let Some(u) = &self.last_total_token_usage {
+        let mut usage = if let Some(u) = &self.last_total_token_usage {
             Usage {
                 input_tokens: u.input_tokens,
                 cached_input_tokens: u.cached_input_tokens,
                 output_tokens: u.output_tokens,
+                subagents: None,
             }
         } else {
             Usage::default()
         };
+        if let Some(sub) = &self.subagent_token_usage {
+            usage.input_tokens += sub.input_tokens;
+            usage.cached_input_tokens += sub.cached_input_tokens;
+            usage.output_tokens += sub.output_tokens;
+            usage.subagents = Some(subagent_usage(sub));
+        }
 
         let mut items = Vec::new();
 
@@ -333,6 +446,15 @@ impl ExperimentalEventProcessorWithJsonOutput {
     }
 }
 
+fn subagent_usage(usage: &codex_core::protocol::TokenUsage) -> SubagentUsage {
+    SubagentUsage {
+        input_tokens: usage.input_tokens,
+        cached_input_tokens: usage.cached_input_tokens,
+        output_tokens: usage.output_tokens,
+        reasoning_output_tokens: usage.reasoning_output_tokens,
+    }
+}
+
 impl EventProcessor for ExperimentalEventProcessorWithJsonOutput {
     fn print_config_summary(&mut self, _: &Config, _: &str, ev: &SessionConfiguredEvent) {
         self.process_event(Event {
@@ -0,0 +1,221 @@
+//! `codex subagents hook run`: the command installed git hooks call. Runs an
+//! agent in a read-only sandbox on the staged diff (pre-commit) or on the
+//! commits being pushed (pre-push), and blocks on findings at or above a
+//! severity threshold, or when the agent's reply holds no findings to check.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use codex_core::AuthManager;
+use codex_core::ConversationManager;
+use codex_core::config::Config;
+use codex_core::git_info::get_git_repo_root;
+use codex_core::git_info::git_diff_commits;
+use codex_core::git_info::git_staged_diff;
+use codex_core::protocol::AskForApproval;
+use codex_core::protocol::SandboxPolicy;
+use codex_core::protocol::SubAgentOutcome;
+use codex_core::subagents::SubagentInvocation;
+use codex_core::subagents::SubagentOrchestrator;
+use codex_subagents::hooks::HookFinding;
+use codex_subagents::hooks::HookStage;
+use codex_subagents::hooks::PushUpdate;
+use codex_subagents::hooks::findings_from_message;
+use codex_subagents::pipeline::Severity;
+use owo_colors::OwoColorize;
+
+use crate::resolve_agent;
+use crate::watch::capped_diff;
+
+#[derive(Debug, Clone)]
+pub struct HookRunOptions {
+    pub stage: HookStage,
+    pub agent: String,
+    /// Findings at or above this severity block the commit or push.
+    pub severity: Severity,
+    /// What git passed a `pre-push` hook on stdin; unused for `pre-commit`.
+    pub push_updates: Vec<PushUpdate>,
+}
+
+/// Run the hook's agent and print a summary to stderr. Returns whether the
+/// commit or push may go ahead.
+pub async fn run_hook(mut config: Config, options: HookRunOptions) -> anyhow::Result<bool> {
+    let stage = options.stage;
+    let Some(repo_root) = get_git_repo_root(&config.cwd) else {
+        anyhow::bail!("`codex subagents hook run` must run inside a git repository.");
+    };
+    let diff = stage_diff(&config, &options).await?;
+    if diff.trim().is_empty() {
+        eprintln!("{}", format!("codex {stage}: nothing to review.").dimmed());
+        return Ok(true);
+    }
+
+    let (spec, agents) = resolve_agent(&config, &options.agent, "git hooks")?;
+    config.sandbox_policy = SandboxPolicy::new_read_only_policy();
+    config.approval_policy = AskForApproval::Never;
+    let files = diff
+        .lines()
+        .filter(|line| line.starts_with("diff --git "))
+        .count();
+    eprintln!(
+        "{} codex {stage}: {} is reviewing {files} file(s)…",
+        "→".cyan(),
+        spec.metadata.name.cyan().bold(),
+    );
+
+    let orchestrator = SubagentOrchestrator::new(Arc::new(ConversationManager::new(
+        AuthManager::shared(config.codex_home.clone()),
+    )))
+    .with_handoff_agents(agents);
+    let invocation = SubagentInvocation {
+        spec: &spec,
+        parent_submit_id: format!("hook-subagent-{}", spec.metadata.name),
+    };
+    let result = orchestrator
+        .run_subagent(&config, invocation, Some(hook_prompt(stage, &diff)), |_| {})
+        .await;
+    let message = match result {
+        Ok(state) if state.outcome == SubAgentOutcome::Success => state.last_message,
+        Ok(state) => {
+            let error = state.error.unwrap_or_else(|| "run failed".to_string());
+            return Ok(blocked(
+                stage,
+                &format!("{} failed: {error}", spec.metadata.name),
+            ));
+        }
+        Err(err) => {
+            return Ok(blocked(
+                stage,
+                &format!("{} failed: {err}", spec.metadata.name),
+            ));
+        }
+    };
+    let Some(findings) = message
+        .as_deref()
+        .and_then(|message| findings_from_message(message, &repo_root))
+    else {
+        return Ok(blocked(
+            stage,
+            &format!(
+                "{} did not report findings as JSON, so the changes were not checked.",
+                spec.metadata.name
+            ),
+        ));
+    };
+
+    eprintln!("codex {stage}: {}", summarize(&findings));
+    let blocking: Vec<&HookFinding> = findings
+        .iter()
+        .filter(|finding| finding.severity >= options.severity)
+        .collect();
+    for finding in &blocking {
+        let location = finding
+            .location
+            .as_deref()
+            .map(|location| format!(" {location}"))
+            .unwrap_or_default();
+        let summary = finding.message.lines().next().unwrap_or_default();
+        eprintln!("  {}{} {summary}", finding.severity.red(), location.cyan());
+    }
+    if blocking.is_empty() {
+        return Ok(true);
+    }
+    Ok(blocked(
+        stage,
+        &format!(
+            "{} finding(s) at or above {}.",
+            blocking.len(),
+            options.severity
+        ),
+    ))
+}
+
+async fn stage_diff(config: &Config, options: &HookRunOptions) -> anyhow::Result<String> {
+    match options.stage {
+        HookStage::PreCommit => git_staged_diff(&config.cwd)
+            .await
+            .ok_or_else(|| anyhow::anyhow!("failed to read the staged diff")),
+        HookStage::PrePush => {
+            let mut diff = String::new();
+            for update in options.push_updates.iter().filter(|u| !u.is_delete()) {
+                let base = (!update.is_new_ref()).then_some(update.remote_sha.as_str());
+                let commits = git_diff_commits(&config.cwd, base, &update.local_sha)
+                    .await
+                    .ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "failed to diff the commits pushed to {}",
+                            update.remote_ref
+                        )
+                    })?;
+                diff.push_str(&commits);
+            }
+            Ok(diff)
+        }
+    }
+}
+
+fn hook_prompt(stage: HookStage, diff: &str) -> String {
+    let (tag, subject) = match stage {
+        HookStage::PreCommit => ("staged_diff", "staged changes"),
+        HookStage::PrePush => ("pushed_diff", "commits being pushed"),
+    };
+    format!(
+        "<{tag}>\n{}\n</{tag}>\n\n\
+         Review the {subject} above. Report problems in the changed code only; do not modify any files. \
+         Reply with JSON of the form {{\"summary\": \"...\", \"findings\": [{{\"severity\": \"info|low|medium|high|critical\", \"message\": \"...\"}}]}}.",
+        capped_diff(diff)
+    )
+}
+
+/// `3 finding(s): 1 high, 2 low`, most severe first.
+fn summarize(findings: &[HookFinding]) -> String {
+    if findings.is_empty() {
+        return "no findings.".to_string();
+    }
+    let mut counts = BTreeMap::new();
+    for finding in findings {
+        *counts.entry(finding.severity).or_insert(0) += 1;
+    }
+    let counts: Vec<String> = counts
+        .iter()
+        .rev()
+        .map(|(severity, count)| format!("{count} {severity}"))
+        .collect();
+    format!("{} finding(s): {}", findings.len(), counts.join(", "))
+}
+
+fn blocked(stage: HookStage, reason: &str) -> bool {
+    eprintln!(
+        "{} {reason}",
+        format!("codex {stage} blocked:").red().bold()
+    );
+    eprintln!(
+        "{}",
+        format!("Bypass once with `{}`.", stage.bypass_hint()).dimmed()
+    );
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn summarizes_most_severe_first() {
+        let finding = |severity| HookFinding {
+            severity,
+            message: String::new(),
+            location: None,
+        };
+        assert_eq!(summarize(&[]), "no findings.");
+        assert_eq!(
+            summarize(&[
+                finding(Severity::Low),
+                finding(Severity::Critical),
+                finding(Severity::Low)
+            ]),
+            "3 finding(s): 1 critical, 2 low"
+        );
+    }
+}
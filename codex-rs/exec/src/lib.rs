@@ -1,13 +1,18 @@
 mod cli;
+pub mod ensemble;
+pub mod eval;
 mod event_processor;
 mod event_processor_with_human_output;
 pub mod event_processor_with_json_output;
 pub mod exec_events;
 pub mod experimental_event_processor_with_json_output;
+pub mod hook;
+pub mod watch;
 
 use std::io::IsTerminal;
 use std::io::Read;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 pub use cli::Cli;
 use codex_core::AuthManager;
@@ -20,11 +25,23 @@ use codex_core::git_info::get_git_repo_root;
 use codex_core::protocol::AskForApproval;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
+use codex_core::protocol::ExitedReviewModeEvent;
 use codex_core::protocol::InputItem;
 use codex_core::protocol::Op;
+use codex_core::protocol::ReviewRequest;
+use codex_core::protocol::SubAgentOutcome;
 use codex_core::protocol::TaskCompleteEvent;
+use codex_core::protocol::TaskStartedEvent;
+use codex_core::subagents::SubagentInvocation;
+use codex_core::subagents::SubagentOrchestrator;
+use codex_core::subagents::SubagentRunState;
+use codex_core::subagents::subagent_registry;
 use codex_ollama::DEFAULT_OSS_MODEL;
 use codex_protocol::config_types::SandboxMode;
+use codex_subagents::ContextAttachment;
+use codex_subagents::SubagentSpec;
+use codex_subagents::sarif::SarifLog;
+use codex_subagents::sarif::SarifRun;
 use event_processor_with_human_output::EventProcessorWithHumanOutput;
 use experimental_event_processor_with_json_output::ExperimentalEventProcessorWithJsonOutput;
 use serde_json::Value;
@@ -58,6 +75,10 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         prompt,
         output_schema: output_schema_path,
         include_plan_tool,
+        agent,
+        context,
+        review,
+        sarif: sarif_path,
         config_overrides,
     } = cli;
 
@@ -221,6 +242,29 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     let conversation_manager =
         ConversationManager::new(AuthManager::shared(config.codex_home.clone()));
 
+    if review && (command.is_some() || !images.is_empty() || output_schema.is_some()) {
+        anyhow::bail!("--review cannot be combined with resume, --image, or --output-schema");
+    }
+
+    if let Some(agent_name) = agent {
+        if command.is_some() || !images.is_empty() || output_schema.is_some() {
+            anyhow::bail!("--agent cannot be combined with resume, --image, or --output-schema");
+        }
+        let succeeded = run_agent(
+            &config,
+            conversation_manager,
+            &agent_name,
+            prompt,
+            context,
+            event_processor.as_mut(),
+        )
+        .await?;
+        if !succeeded {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     // Handle resume subcommand by resolving a rollout path and using explicit resume API.
     let NewConversation {
         conversation_id: _,
@@ -315,29 +359,47 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     }
 
     // Send the prompt.
-    let items: Vec<InputItem> = vec![InputItem::Text { text: prompt }];
-    let initial_prompt_task_id = conversation
-        .submit(Op::UserTurn {
-            items,
-            cwd: default_cwd,
-            approval_policy: default_approval_policy,
-            sandbox_policy: default_sandbox_policy,
-            model: default_model,
-            effort: default_effort,
-            summary: default_summary,
-            final_output_json_schema: output_schema,
-        })
-        .await?;
+    let initial_prompt_task_id = if review {
+        conversation
+            .submit(Op::Review {
+                review_request: ReviewRequest {
+                    user_facing_hint: prompt.clone(),
+                    prompt,
+                },
+            })
+            .await?
+    } else {
+        let items: Vec<InputItem> = vec![InputItem::Text { text: prompt }];
+        conversation
+            .submit(Op::UserTurn {
+                items,
+                cwd: default_cwd.clone(),
+                approval_policy: default_approval_policy,
+                sandbox_policy: default_sandbox_policy,
+                model: default_model,
+                effort: default_effort,
+                summary: default_summary,
+                final_output_json_schema: output_schema,
+            })
+            .await?
+    };
     info!("Sent prompt with event ID: {initial_prompt_task_id}");
 
     // Run the loop until the task is complete.
     // Track whether a fatal error was reported by the server so we can
     // exit with a non-zero status for automation-friendly signaling.
     let mut error_seen = false;
+    let mut review_output = None;
     while let Some(event) = rx.recv().await {
         if matches!(event.msg, EventMsg::Error(_)) {
             error_seen = true;
         }
+        if let EventMsg::ExitedReviewMode(ExitedReviewModeEvent {
+            review_output: Some(output),
+        }) = &event.msg
+        {
+            review_output = Some(output.clone());
+        }
         let shutdown: CodexStatus = event_processor.process_event(event);
         match shutdown {
             CodexStatus::Running => continue,
@@ -349,6 +411,17 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
             }
         }
     }
+    if let Some(path) = sarif_path {
+        match review_output {
+            Some(output) => SarifLog::default()
+                .with_run(SarifRun::from_review_output(&output, &default_cwd))
+                .write_to(&path)?,
+            None => eprintln!(
+                "No review output was produced; {} not written.",
+                path.display()
+            ),
+        }
+    }
     if error_seen {
         std::process::exit(1);
     }
@@ -356,6 +429,117 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     Ok(())
 }
 
+/// Run `prompt` through a single subagent and feed its events to
+/// `event_processor`. Returns whether the subagent finished successfully.
+async fn run_agent(
+    config: &Config,
+    conversation_manager: ConversationManager,
+    agent_name: &str,
+    prompt: String,
+    context: Vec<ContextAttachment>,
+    event_processor: &mut dyn EventProcessor,
+) -> anyhow::Result<bool> {
+    let (spec, agents) = resolve_agent(config, agent_name, "--agent")?;
+    let orchestrator = SubagentOrchestrator::new(Arc::new(conversation_manager))
+        .with_handoff_agents(agents)
+        .with_context_attachments(context);
+    let parent_submit_id = format!("exec-subagent-{}", spec.metadata.name);
+    let invocation = SubagentInvocation {
+        spec: &spec,
+        parent_submit_id,
+    };
+    let child = orchestrator.spawn_child(config, &invocation).await?;
+    event_processor.print_config_summary(config, &prompt, &child.session_configured);
+    let state = forward_run(
+        &orchestrator,
+        config,
+        invocation,
+        child,
+        prompt,
+        event_processor,
+    )
+    .await?;
+    Ok(state.outcome == SubAgentOutcome::Success)
+}
+
+/// Look up `agent_name` in the registry, returning its spec and every
+/// registered agent (for handoffs). `feature` names the caller in the error
+/// shown when subagents are disabled.
+fn resolve_agent(
+    config: &Config,
+    agent_name: &str,
+    feature: &str,
+) -> anyhow::Result<(SubagentSpec, Vec<SubagentSpec>)> {
+    if !config.subagents.enabled {
+        anyhow::bail!(
+            "Subagents feature is disabled in this configuration. Enable `subagents.enabled` to use {feature}."
+        );
+    }
+
+    let mut registry = subagent_registry(config);
+    let agents: Vec<SubagentSpec> = registry
+        .reload()?
+        .agents
+        .iter()
+        .map(|handle| handle.spec.clone())
+        .collect();
+    let spec = agents
+        .iter()
+        .find(|spec| spec.metadata.name.eq_ignore_ascii_case(agent_name))
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("Subagent '{agent_name}' not found."))?;
+    Ok((spec, agents))
+}
+
+/// Run a spawned child to completion, feeding its events to
+/// `event_processor` framed as one task.
+async fn forward_run(
+    orchestrator: &SubagentOrchestrator,
+    config: &Config,
+    invocation: SubagentInvocation<'_>,
+    child: NewConversation,
+    prompt: String,
+    event_processor: &mut dyn EventProcessor,
+) -> anyhow::Result<SubagentRunState> {
+    let parent_submit_id = invocation.parent_submit_id.clone();
+    event_processor.process_event(Event {
+        id: parent_submit_id.clone(),
+        msg: EventMsg::TaskStarted(TaskStartedEvent {
+            model_context_window: None,
+        }),
+    });
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<EventMsg>();
+    let run = orchestrator.run_spawned(config, invocation, child, Some(prompt), move |msg| {
+        if let Err(e) = tx.send(msg) {
+            error!("Error forwarding subagent event: {e:?}");
+        }
+    });
+    tokio::pin!(run);
+    let state = loop {
+        tokio::select! {
+            result = &mut run => break result?,
+            Some(msg) = rx.recv() => {
+                event_processor.process_event(Event { id: parent_submit_id.clone(), msg });
+            }
+        }
+    };
+    while let Ok(msg) = rx.try_recv() {
+        event_processor.process_event(Event {
+            id: parent_submit_id.clone(),
+            msg,
+        });
+    }
+
+    event_processor.process_event(Event {
+        id: parent_submit_id,
+        msg: EventMsg::TaskComplete(TaskCompleteEvent {
+            last_agent_message: state.last_message.clone(),
+        }),
+    });
+    Ok(state)
+}
+
 async fn resolve_resume_path(
     config: &Config,
     args: &crate::cli::ResumeArgs,
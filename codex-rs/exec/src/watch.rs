@@ -0,0 +1,515 @@
+//! `codex subagents watch`: rerun an agent in a read-only sandbox whenever
+//! watched files change, once the changes have settled.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::hash::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::io::IsTerminal;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
+
+use codex_core::AuthManager;
+use codex_core::ConversationManager;
+use codex_core::config::Config;
+use codex_core::git_info::get_git_repo_root;
+use codex_core::git_info::git_diff_working_tree;
+use codex_core::protocol::AskForApproval;
+use codex_core::protocol::SandboxPolicy;
+use codex_core::protocol::SubAgentOutcome;
+use codex_core::subagents::SubagentInvocation;
+use codex_core::subagents::SubagentOrchestrator;
+use codex_subagents::sarif::SarifLevel;
+use codex_subagents::sarif::SarifResult;
+use codex_subagents::sarif::SarifRun;
+use owo_colors::OwoColorize;
+use tokio::process::Command;
+use wildmatch::WildMatch;
+
+use crate::event_processor_with_human_output::EventProcessorWithHumanOutput;
+use crate::forward_run;
+use crate::resolve_agent;
+
+/// How often the watched files are listed and stat'ed.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+const MAX_DIFF_BYTES: usize = 64 * 1024;
+const DEFAULT_PROMPT: &str =
+    "Review the changes above. Report problems in the changed code only; do not modify any files.";
+
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+    pub agent: String,
+    /// Globs relative to the working directory; empty watches every file git
+    /// does not ignore.
+    pub paths: Vec<String>,
+    pub debounce: Duration,
+    pub prompt: Option<String>,
+}
+
+/// Watch until interrupted with Ctrl-C. Each run gets the changed files and
+/// their diff against `HEAD`; runs whose diff matches the previous run's are
+/// skipped.
+pub async fn run_watch(mut config: Config, options: WatchOptions) -> anyhow::Result<()> {
+    let (spec, agents) = resolve_agent(&config, &options.agent, "watch")?;
+    let Some(repo_root) = get_git_repo_root(&config.cwd) else {
+        anyhow::bail!("`codex subagents watch` needs a git repository to diff against.");
+    };
+    config.sandbox_policy = SandboxPolicy::new_read_only_policy();
+    config.approval_policy = AskForApproval::Never;
+
+    let cwd = config.cwd.clone();
+    let watched = WatchedPaths::new(&options.paths, &repo_root, &cwd);
+    let orchestrator = SubagentOrchestrator::new(Arc::new(ConversationManager::new(
+        AuthManager::shared(config.codex_home.clone()),
+    )))
+    .with_handoff_agents(agents);
+    let mut processor = EventProcessorWithHumanOutput::create_with_ansi(
+        std::io::stdout().is_terminal(),
+        &config,
+        None,
+    );
+
+    println!(
+        "{} Watching {} for {} {}",
+        "→".cyan(),
+        watched.describe().bold(),
+        spec.metadata.name.cyan().bold(),
+        format!(
+            "(debounce {}, read-only). Press Ctrl-C to stop.",
+            codex_common::elapsed::format_duration(options.debounce)
+        )
+        .dimmed()
+    );
+
+    let mut tracker = ChangeTracker::new(snapshot(&cwd, &watched).await, options.debounce);
+    let mut gate = DiffGate::default();
+    let mut findings = FindingsLog::default();
+    let mut runs = 0;
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => break,
+            _ = tokio::time::sleep(POLL_INTERVAL) => {}
+        }
+        let Some(changed) = tracker.observe(snapshot(&cwd, &watched).await, Instant::now()) else {
+            continue;
+        };
+        let diff =
+            watched.filter_diff(&git_diff_working_tree(&cwd, None).await.unwrap_or_default());
+        if !gate.should_run(&diff) {
+            println!(
+                "{}",
+                format!(
+                    "{} file(s) changed but the diff matches the last successful run; skipping.",
+                    changed.len()
+                )
+                .dimmed()
+            );
+            continue;
+        }
+
+        runs += 1;
+        println!(
+            "\n{} {} {}",
+            format!("Run #{runs}").bold(),
+            format!("{} changed file(s):", changed.len()).dimmed(),
+            changed.join(", ")
+        );
+        let invocation = SubagentInvocation {
+            spec: &spec,
+            parent_submit_id: format!("watch-subagent-{}-{runs}", spec.metadata.name),
+        };
+        let prompt = watch_prompt(&changed, &diff, options.prompt.as_deref());
+        let run = async {
+            let child = orchestrator.spawn_child(&config, &invocation).await?;
+            forward_run(
+                &orchestrator,
+                &config,
+                invocation,
+                child,
+                prompt,
+                &mut processor,
+            )
+            .await
+        };
+        let result = tokio::select! {
+            _ = tokio::signal::ctrl_c() => break,
+            result = run => result,
+        };
+        match result {
+            Ok(state) if state.outcome == SubAgentOutcome::Success => {
+                gate.record_success(&diff);
+                let results = state
+                    .last_message
+                    .as_deref()
+                    .and_then(|message| {
+                        SarifRun::from_agent_message(&spec.metadata.name, message, &cwd)
+                    })
+                    .map(|run| run.results);
+                if let Some(results) = results {
+                    print_findings(&mut findings, &results);
+                }
+            }
+            Ok(_) => {}
+            Err(err) => eprintln!("{} {err}", "error:".red()),
+        }
+    }
+    println!("{}", format!("Stopped after {runs} run(s).").dimmed());
+    Ok(())
+}
+
+/// Print the findings that were not reported by the previous run, and how
+/// many of its findings are gone.
+fn print_findings(log: &mut FindingsLog, results: &[SarifResult]) {
+    let (new, resolved) = log.update(results);
+    println!(
+        "{}",
+        format!(
+            "findings: {} ({} new, {resolved} resolved)",
+            results.len(),
+            new.len()
+        )
+        .bold()
+    );
+    for result in new {
+        let level = match result.level {
+            SarifLevel::Error => "error".red().to_string(),
+            SarifLevel::Warning => "warning".yellow().to_string(),
+            SarifLevel::Note => "note".dimmed().to_string(),
+        };
+        let location = result_location(result)
+            .map(|location| format!(" {location}"))
+            .unwrap_or_default();
+        let summary = result.message.text.lines().next().unwrap_or_default();
+        println!("  + {level}{} {summary}", location.cyan());
+    }
+}
+
+fn result_location(result: &SarifResult) -> Option<String> {
+    let location = &result.locations.first()?.physical_location;
+    let uri = &location.artifact_location.uri;
+    Some(match &location.region {
+        Some(region) => format!("{uri}:{}", region.start_line),
+        None => uri.clone(),
+    })
+}
+
+fn watch_prompt(changed: &[String], diff: &str, prompt: Option<&str>) -> String {
+    format!(
+        "<changed_files>\n{}\n</changed_files>\n\n<git_diff base=\"HEAD\">\n{}\n</git_diff>\n\n{}",
+        changed.join("\n"),
+        capped_diff(diff),
+        prompt.unwrap_or(DEFAULT_PROMPT)
+    )
+}
+
+/// `diff` cut to [`MAX_DIFF_BYTES`] for a prompt, or a placeholder when empty.
+pub(crate) fn capped_diff(diff: &str) -> String {
+    if diff.trim().is_empty() {
+        "(no changes)".to_string()
+    } else if diff.len() > MAX_DIFF_BYTES {
+        let mut end = MAX_DIFF_BYTES;
+        while !diff.is_char_boundary(end) {
+            end -= 1;
+        }
+        format!(
+            "{}\n[… {} more bytes of diff omitted]",
+            &diff[..end],
+            diff.len() - end
+        )
+    } else {
+        diff.trim_end().to_string()
+    }
+}
+
+/// The `--paths` globs, matched against paths relative to the working
+/// directory.
+struct WatchedPaths {
+    globs: Vec<String>,
+    matchers: Vec<WildMatch>,
+    /// Working directory relative to the repository root, `/`-terminated, as
+    /// `git diff` reports paths from the root.
+    prefix: String,
+}
+
+impl WatchedPaths {
+    fn new(globs: &[String], repo_root: &Path, cwd: &Path) -> Self {
+        let prefix = cwd
+            .strip_prefix(repo_root)
+            .ok()
+            .map(|relative| {
+                relative
+                    .components()
+                    .map(|component| format!("{}/", component.as_os_str().to_string_lossy()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            globs: globs.to_vec(),
+            matchers: globs.iter().map(|glob| WildMatch::new(glob)).collect(),
+            prefix,
+        }
+    }
+
+    fn describe(&self) -> String {
+        if self.globs.is_empty() {
+            "all files".to_string()
+        } else {
+            self.globs.join(", ")
+        }
+    }
+
+    fn matches(&self, path: &str) -> bool {
+        self.matchers.is_empty() || self.matchers.iter().any(|matcher| matcher.matches(path))
+    }
+
+    /// Keep the sections of a `git diff` whose file is watched.
+    fn filter_diff(&self, diff: &str) -> String {
+        let mut kept = String::new();
+        let mut keep = false;
+        for line in diff.split_inclusive('\n') {
+            if let Some(header) = line.strip_prefix("diff --git ") {
+                keep = header
+                    .trim_end()
+                    .rsplit_once(" b/")
+                    .and_then(|(_, path)| path.strip_prefix(self.prefix.as_str()))
+                    .is_some_and(|path| self.matches(path));
+            }
+            if keep {
+                kept.push_str(line);
+            }
+        }
+        kept
+    }
+}
+
+/// Modification time and size of each watched file; `None` for tracked
+/// files that were deleted.
+type Snapshot = BTreeMap<String, Option<(SystemTime, u64)>>;
+
+async fn snapshot(cwd: &Path, watched: &WatchedPaths) -> Snapshot {
+    let output = Command::new("git")
+        .args(["ls-files", "--cached", "--others", "--exclude-standard"])
+        .current_dir(cwd)
+        .output()
+        .await;
+    let Ok(output) = output else {
+        return Snapshot::new();
+    };
+    let mut snapshot = Snapshot::new();
+    for path in String::from_utf8_lossy(&output.stdout).lines() {
+        if path.is_empty() || !watched.matches(path) {
+            continue;
+        }
+        let stat = std::fs::metadata(cwd.join(path))
+            .ok()
+            .and_then(|meta| Some((meta.modified().ok()?, meta.len())));
+        snapshot.insert(path.to_string(), stat);
+    }
+    snapshot
+}
+
+/// Debounces snapshots: reports the files that differ from the last report
+/// once no snapshot has changed for `debounce`.
+struct ChangeTracker {
+    debounce: Duration,
+    reported: Snapshot,
+    latest: Snapshot,
+    last_change: Option<Instant>,
+}
+
+impl ChangeTracker {
+    fn new(initial: Snapshot, debounce: Duration) -> Self {
+        Self {
+            debounce,
+            reported: initial.clone(),
+            latest: initial,
+            last_change: None,
+        }
+    }
+
+    fn observe(&mut self, snapshot: Snapshot, now: Instant) -> Option<Vec<String>> {
+        if snapshot != self.latest {
+            self.latest = snapshot;
+            self.last_change = Some(now);
+            return None;
+        }
+        let changed_at = self.last_change?;
+        if now.duration_since(changed_at) < self.debounce {
+            return None;
+        }
+        self.last_change = None;
+        let changed: Vec<String> = self
+            .reported
+            .keys()
+            .chain(self.latest.keys())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .filter(|path| self.reported.get(*path) != self.latest.get(*path))
+            .cloned()
+            .collect();
+        self.reported = self.latest.clone();
+        (!changed.is_empty()).then_some(changed)
+    }
+}
+
+/// Skips a run when the watched diff hashes the same as the last successful
+/// run's, so a failed or cancelled run can be retried by saving again.
+#[derive(Default)]
+struct DiffGate {
+    last_hash: Option<u64>,
+}
+
+impl DiffGate {
+    fn should_run(&self, diff: &str) -> bool {
+        self.last_hash != Some(Self::hash(diff))
+    }
+
+    /// Remember `diff` once a run over it has succeeded.
+    fn record_success(&mut self, diff: &str) {
+        self.last_hash = Some(Self::hash(diff));
+    }
+
+    fn hash(diff: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        diff.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// Findings reported by the previous run, keyed by rule, location and
+/// message.
+#[derive(Default)]
+struct FindingsLog {
+    known: BTreeSet<String>,
+}
+
+impl FindingsLog {
+    /// Returns the findings that are new since the last update and how many
+    /// earlier findings disappeared.
+    fn update<'a>(&mut self, results: &'a [SarifResult]) -> (Vec<&'a SarifResult>, usize) {
+        let keyed: Vec<(String, &SarifResult)> = results
+            .iter()
+            .map(|result| {
+                let key = format!(
+                    "{}|{}|{}",
+                    result.rule_id,
+                    result_location(result).unwrap_or_default(),
+                    result.message.text
+                );
+                (key, result)
+            })
+            .collect();
+        let current: BTreeSet<String> = keyed.iter().map(|(key, _)| key.clone()).collect();
+        let resolved = self.known.difference(&current).count();
+        let new = keyed
+            .iter()
+            .filter(|(key, _)| !self.known.contains(key))
+            .map(|(_, result)| *result)
+            .collect();
+        self.known = current;
+        (new, resolved)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_subagents::sarif::SarifMessage;
+    use pretty_assertions::assert_eq;
+
+    fn snapshot_of(entries: &[(&str, u64)]) -> Snapshot {
+        entries
+            .iter()
+            .map(|(path, len)| (path.to_string(), Some((SystemTime::UNIX_EPOCH, *len))))
+            .collect()
+    }
+
+    #[test]
+    fn reports_changes_once_they_settle() {
+        let debounce = Duration::from_secs(2);
+        let start = Instant::now();
+        let mut tracker = ChangeTracker::new(snapshot_of(&[("src/a.rs", 1)]), debounce);
+
+        assert_eq!(
+            tracker.observe(snapshot_of(&[("src/a.rs", 1)]), start),
+            None
+        );
+        let edited = snapshot_of(&[("src/a.rs", 2), ("src/b.rs", 1)]);
+        assert_eq!(tracker.observe(edited.clone(), start), None);
+        assert_eq!(
+            tracker.observe(edited.clone(), start + Duration::from_secs(1)),
+            None
+        );
+        assert_eq!(
+            tracker.observe(edited.clone(), start + Duration::from_secs(2)),
+            Some(vec!["src/a.rs".to_string(), "src/b.rs".to_string()])
+        );
+        assert_eq!(
+            tracker.observe(edited, start + Duration::from_secs(5)),
+            None
+        );
+
+        let reverted = snapshot_of(&[("src/a.rs", 2)]);
+        assert_eq!(
+            tracker.observe(reverted.clone(), start + Duration::from_secs(6)),
+            None
+        );
+        assert_eq!(
+            tracker.observe(reverted, start + Duration::from_secs(9)),
+            Some(vec!["src/b.rs".to_string()])
+        );
+    }
+
+    #[test]
+    fn filters_diff_to_watched_paths_under_cwd() {
+        let watched = WatchedPaths::new(
+            &["src/**".to_string()],
+            Path::new("/repo"),
+            Path::new("/repo/crate"),
+        );
+        let diff = "diff --git a/crate/src/lib.rs b/crate/src/lib.rs\n+fn a() {}\ndiff --git a/crate/README.md b/crate/README.md\n+docs\ndiff --git a/src/other.rs b/src/other.rs\n+outside\n";
+        assert_eq!(
+            watched.filter_diff(diff),
+            "diff --git a/crate/src/lib.rs b/crate/src/lib.rs\n+fn a() {}\n"
+        );
+    }
+
+    #[test]
+    fn skips_runs_with_an_unchanged_diff() {
+        let mut gate = DiffGate::default();
+        assert!(gate.should_run("+a"));
+        // A failed run records nothing, so the same diff runs again.
+        assert!(gate.should_run("+a"));
+        gate.record_success("+a");
+        assert!(!gate.should_run("+a"));
+        assert!(gate.should_run("+b"));
+        gate.record_success("+b");
+        assert!(gate.should_run("+a"));
+    }
+
+    #[test]
+    fn reports_new_and_resolved_findings() {
+        let finding = |text: &str| SarifResult {
+            rule_id: "codex-review/P1".to_string(),
+            level: SarifLevel::Warning,
+            message: SarifMessage {
+                text: text.to_string(),
+            },
+            locations: Vec::new(),
+            properties: None,
+        };
+        let mut log = FindingsLog::default();
+        let first = vec![finding("unwrap on None"), finding("missing test")];
+        let (new, resolved) = log.update(&first);
+        assert_eq!((new.len(), resolved), (2, 0));
+
+        let second = vec![finding("missing test"), finding("off by one")];
+        let (new, resolved) = log.update(&second);
+        assert_eq!(new, vec![&second[1]]);
+        assert_eq!(resolved, 1);
+    }
+}
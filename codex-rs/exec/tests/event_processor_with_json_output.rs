@@ -8,6 +8,10 @@ use codex_core::protocol::FileChange;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
 use codex_core::protocol::SessionConfiguredEvent;
+use codex_core::protocol::SubAgentCompletedEvent;
+use codex_core::protocol::SubAgentMessageEvent;
+use codex_core::protocol::SubAgentOutcome;
+use codex_core::protocol::SubAgentStartedEvent;
 use codex_exec::exec_events::AssistantMessageItem;
 use codex_exec::exec_events::CommandExecutionItem;
 use codex_exec::exec_events::CommandExecutionStatus;
@@ -22,6 +26,9 @@ use codex_exec::exec_events::PatchApplyStatus;
 use codex_exec::exec_events::PatchChangeKind;
 use codex_exec::exec_events::ReasoningItem;
 use codex_exec::exec_events::SessionCreatedEvent;
+use codex_exec::exec_events::SubagentRunItem;
+use codex_exec::exec_events::SubagentRunStatus;
+use codex_exec::exec_events::SubagentUsage;
 use codex_exec::exec_events::TodoItem as ExecTodoItem;
 use codex_exec::exec_events::TodoListItem as ExecTodoListItem;
 use codex_exec::exec_events::TurnCompletedEvent;
@@ -662,6 +669,190 @@ fn task_complete_produces_turn_completed_with_usage() {
                 input_tokens: 1200,
                 cached_input_tokens: 200,
                 output_tokens: 345,
+                subagents: None,
+            },
+        })]
+    );
+}
+
+#[test]
+fn subagent_events_produce_subagent_run_item_lifecycle() {
+    let mut ep = ExperimentalEventProcessorWithJsonOutput::new(None);
+    let sub_conversation_id = codex_protocol::mcp_protocol::ConversationId::default();
+
+    let started = ep.collect_conversation_events(&event(
+        "s1",
+        EventMsg::SubAgentStarted(SubAgentStartedEvent {
+            agent_name: "tester".to_string(),
+            parent_submit_id: "p1".to_string(),
+            sub_conversation_id,
+            model: Some("gpt-5".to_string()),
+            handoff_from: None,
+        }),
+    ));
+    let mut expected = SubagentRunItem {
+        agent: "tester".to_string(),
+        model: Some("gpt-5".to_string()),
+        status: SubagentRunStatus::InProgress,
+        messages: Vec::new(),
+        duration_ms: None,
+        error: None,
+        handoff_to: None,
+        usage: None,
+    };
+    assert_eq!(
+        started,
+        vec![ConversationEvent::ItemStarted(ItemStartedEvent {
+            item: ConversationItem {
+                id: "item_0".to_string(),
+                details: ConversationItemDetails::SubagentRun(expected.clone()),
+            },
+        })]
+    );
+
+    let updated = ep.collect_conversation_events(&event(
+        "s2",
+        EventMsg::SubAgentMessage(SubAgentMessageEvent {
+            agent_name: "tester".to_string(),
+            sub_conversation_id,
+            message: "running cargo test".to_string(),
+        }),
+    ));
+    expected.messages.push("running cargo test".to_string());
+    assert_eq!(
+        updated,
+        vec![ConversationEvent::ItemUpdated(ItemUpdatedEvent {
+            item: ConversationItem {
+                id: "item_0".to_string(),
+                details: ConversationItemDetails::SubagentRun(expected.clone()),
+            },
+        })]
+    );
+
+    let completed = ep.collect_conversation_events(&event(
+        "s3",
+        EventMsg::SubAgentCompleted(SubAgentCompletedEvent {
+            agent_name: "tester".to_string(),
+            sub_conversation_id,
+            outcome: SubAgentOutcome::Error,
+            error: Some("2 tests failed".to_string()),
+            model: Some("gpt-5".to_string()),
+            duration_ms: Some(1_500),
+            handoff_to: None,
+            token_usage: None,
+        }),
+    ));
+    expected.status = SubagentRunStatus::Failed;
+    expected.duration_ms = Some(1_500);
+    expected.error = Some("2 tests failed".to_string());
+    assert_eq!(
+        completed,
+        vec![ConversationEvent::ItemCompleted(ItemCompletedEvent {
+            item: ConversationItem {
+                id: "item_0".to_string(),
+                details: ConversationItemDetails::SubagentRun(expected),
+            },
+        })]
+    );
+
+    // A second completion for the same child is ignored.
+    let duplicate = ep.collect_conversation_events(&event(
+        "s4",
+        EventMsg::SubAgentCompleted(SubAgentCompletedEvent {
+            agent_name: "tester".to_string(),
+            sub_conversation_id,
+            outcome: SubAgentOutcome::Success,
+            error: None,
+            model: None,
+            duration_ms: None,
+            handoff_to: None,
+            token_usage: None,
+        }),
+    ));
+    assert!(duplicate.is_empty());
+}
+
+#[test]
+fn subagent_token_usage_rolls_into_turn_completed() {
+    let mut ep = ExperimentalEventProcessorWithJsonOutput::new(None);
+    let sub_conversation_id = codex_protocol::mcp_protocol::ConversationId::default();
+
+    let parent = codex_core::protocol::TokenUsage {
+        input_tokens: 1_000,
+        cached_input_tokens: 100,
+        output_tokens: 200,
+        reasoning_output_tokens: 0,
+        total_tokens: 1_200,
+    };
+    ep.collect_conversation_events(&event(
+        "t1",
+        EventMsg::TokenCount(codex_core::protocol::TokenCountEvent {
+            info: Some(codex_core::protocol::TokenUsageInfo {
+                total_token_usage: parent.clone(),
+                last_token_usage: parent,
+                model_context_window: None,
+            }),
+            rate_limits: None,
+        }),
+    ));
+    ep.collect_conversation_events(&event(
+        "s1",
+        EventMsg::SubAgentStarted(SubAgentStartedEvent {
+            agent_name: "reviewer".to_string(),
+            parent_submit_id: "p1".to_string(),
+            sub_conversation_id,
+            model: None,
+            handoff_from: None,
+        }),
+    ));
+    let completed = ep.collect_conversation_events(&event(
+        "s2",
+        EventMsg::SubAgentCompleted(SubAgentCompletedEvent {
+            agent_name: "reviewer".to_string(),
+            sub_conversation_id,
+            outcome: SubAgentOutcome::Success,
+            error: None,
+            model: None,
+            duration_ms: Some(10),
+            handoff_to: None,
+            token_usage: Some(codex_core::protocol::TokenUsage {
+                input_tokens: 500,
+                cached_input_tokens: 50,
+                output_tokens: 80,
+                reasoning_output_tokens: 30,
+                total_tokens: 580,
+            }),
+        }),
+    ));
+    let subagents = SubagentUsage {
+        input_tokens: 500,
+        cached_input_tokens: 50,
+        output_tokens: 80,
+        reasoning_output_tokens: 30,
+    };
+    let [ConversationEvent::ItemCompleted(ItemCompletedEvent { item })] = completed.as_slice()
+    else {
+        panic!("unexpected events: {completed:?}");
+    };
+    let ConversationItemDetails::SubagentRun(run) = &item.details else {
+        panic!("unexpected details: {item:?}");
+    };
+    assert_eq!(run.usage, Some(subagents.clone()));
+
+    let out = ep.collect_conversation_events(&event(
+        "t2",
+        EventMsg::TaskComplete(codex_core::protocol::TaskCompleteEvent {
+            last_agent_message: None,
+        }),
+    ));
+    assert_eq!(
+        out,
+        vec![ConversationEvent::TurnCompleted(TurnCompletedEvent {
+            usage: Usage {
+                input_tokens: 1_500,
+                cached_input_tokens: 150,
+                output_tokens: 280,
+                subagents: Some(subagents),
             },
         })]
     );
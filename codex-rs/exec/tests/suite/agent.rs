@@ -0,0 +1,82 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use core_test_support::responses;
+use core_test_support::test_codex_exec::test_codex_exec;
+use serde_json::Value;
+use wiremock::matchers::any;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn exec_agent_emits_subagent_run_item() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    let agents_dir = test.cwd_path().join(".codex/agents");
+    std::fs::create_dir_all(&agents_dir)?;
+    std::fs::write(
+        agents_dir.join("reviewer.md"),
+        "---\nname: reviewer\ndescription: Reviews diffs\n---\nReview the change and list risks.\n",
+    )?;
+
+    let server = responses::start_mock_server().await;
+    let body = responses::sse(vec![
+        serde_json::json!({
+            "type": "response.created",
+            "response": {"id": "resp1"}
+        }),
+        responses::ev_assistant_message("m1", "no risks found"),
+        responses::ev_completed("resp1"),
+    ]);
+    responses::mount_sse_once(&server, any(), body).await;
+
+    let output = test
+        .cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("-C")
+        .arg(test.cwd_path())
+        .arg("--experimental-json")
+        .arg("--agent")
+        .arg("reviewer")
+        .arg("review the latest change")
+        .output()?;
+    assert!(output.status.success(), "{output:?}");
+
+    let events: Vec<Value> = String::from_utf8(output.stdout)?
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()?;
+    let completed = events
+        .iter()
+        .find(|event| {
+            event["type"] == "item.completed" && event["item"]["item_type"] == "subagent_run"
+        })
+        .expect("subagent_run item.completed");
+    assert_eq!(completed["item"]["agent"], "reviewer");
+    assert_eq!(completed["item"]["status"], "completed");
+    let messages = completed["item"]["messages"].as_array().expect("messages");
+    assert!(messages.iter().any(|message| message == "no risks found"));
+    assert!(events.iter().any(|event| event["type"] == "turn.completed"));
+
+    let requests = server
+        .received_requests()
+        .await
+        .expect("failed to capture requests");
+    assert_eq!(requests.len(), 1, "expected exactly one request");
+    let request_body = String::from_utf8(requests[0].body.clone())?;
+    assert!(request_body.contains("Review the change and list risks."));
+
+    Ok(())
+}
+
+#[test]
+fn exec_agent_rejects_unknown_agent() {
+    let test = test_codex_exec();
+    test.cmd()
+        .arg("--skip-git-repo-check")
+        .arg("-C")
+        .arg(test.cwd_path())
+        .arg("--agent")
+        .arg("missing")
+        .arg("hello")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Subagent 'missing' not found."));
+}
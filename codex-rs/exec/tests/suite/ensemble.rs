@@ -0,0 +1,114 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use std::path::Path;
+use std::process::Command;
+
+use codex_core::ModelProviderInfo;
+use codex_core::WireApi;
+use codex_core::built_in_model_providers;
+use codex_exec::ensemble::EnsembleOptions;
+use codex_exec::ensemble::run_ensemble;
+use core_test_support::load_default_config_for_test;
+use core_test_support::responses;
+use tempfile::TempDir;
+
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("git");
+    assert!(output.status.success(), "git {args:?}: {output:?}");
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn ensemble_samples_start_from_the_working_tree_and_clean_up() -> anyhow::Result<()> {
+    let codex_home = TempDir::new()?;
+    let repo = TempDir::new()?;
+    let repo_path = repo.path();
+    git(repo_path, &["init", "--quiet"]);
+    std::fs::write(repo_path.join("notes.txt"), "committed\n")?;
+    git(repo_path, &["add", "."]);
+    git(repo_path, &["commit", "--quiet", "-m", "init"]);
+    // Uncommitted work the samples should see.
+    std::fs::write(repo_path.join("notes.txt"), "draft in progress\n")?;
+    let agents_dir = repo_path.join(".codex/agents");
+    std::fs::create_dir_all(&agents_dir)?;
+    std::fs::write(
+        agents_dir.join("refactorer.md"),
+        "---\nname: refactorer\ndescription: Refactors code\n---\nRefactor as asked.\n",
+    )?;
+
+    let server = responses::start_mock_server().await;
+    let body = || {
+        responses::sse(vec![
+            serde_json::json!({"type": "response.created", "response": {"id": "resp1"}}),
+            responses::ev_assistant_message("m1", "Nothing needed changing."),
+            responses::ev_completed("resp1"),
+        ])
+    };
+    responses::mount_sse_sequence(&server, vec![body(), body()]).await;
+
+    let mut config = load_default_config_for_test(&codex_home);
+    config.cwd = repo_path.to_path_buf();
+    config.model_provider = ModelProviderInfo {
+        name: "mock".to_string(),
+        base_url: Some(format!("{}/v1", server.uri())),
+        env_key: None,
+        wire_api: WireApi::Responses,
+        requires_openai_auth: false,
+        request_max_retries: Some(0),
+        stream_max_retries: Some(0),
+        ..built_in_model_providers()["openai"].clone()
+    };
+    let err = run_ensemble(
+        config,
+        EnsembleOptions {
+            agent: "refactorer".to_string(),
+            prompt: Some("Tidy notes.txt.".to_string()),
+            samples: 2,
+            models: vec!["model-a".to_string(), "model-b".to_string()],
+            temperatures: vec![0.2, 0.9],
+            judge: "reviewer".to_string(),
+            test_command: Some("cat notes.txt".to_string()),
+            context: vec!["files=notes.txt".parse()?],
+        },
+    )
+    .await
+    .expect_err("no sample changed anything");
+    assert!(err.to_string().contains("No sample finished with changes"));
+
+    let requests = server.received_requests().await.expect("requests");
+    assert_eq!(
+        requests.len(),
+        2,
+        "the judge only runs when a sample has changes"
+    );
+    let mut models = Vec::new();
+    for request in &requests {
+        let body: serde_json::Value = serde_json::from_slice(&request.body)?;
+        models.push((
+            body["model"].as_str().unwrap_or_default().to_string(),
+            body["temperature"].as_f64(),
+        ));
+        assert!(body.to_string().contains("draft in progress"));
+    }
+    models.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(
+        models,
+        vec![
+            ("model-a".to_string(), Some(0.2)),
+            ("model-b".to_string(), Some(0.9)),
+        ]
+    );
+
+    assert_eq!(git(repo_path, &["worktree", "list"]).lines().count(), 1);
+    assert_eq!(
+        std::fs::read_to_string(repo_path.join("notes.txt"))?,
+        "draft in progress\n"
+    );
+    Ok(())
+}
@@ -0,0 +1,85 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use codex_core::ModelProviderInfo;
+use codex_core::WireApi;
+use codex_core::built_in_model_providers;
+use codex_exec::eval::EvalOptions;
+use codex_exec::eval::run_evals;
+use core_test_support::load_default_config_for_test;
+use core_test_support::responses;
+use tempfile::TempDir;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn eval_runs_cases_on_fixture_copies_and_writes_report() -> anyhow::Result<()> {
+    let codex_home = TempDir::new()?;
+    let project = TempDir::new()?;
+    let agents_dir = project.path().join(".codex/agents");
+    let cases_dir = agents_dir.join("evals/reviewer");
+    std::fs::create_dir_all(cases_dir.join("fixtures/app"))?;
+    std::fs::write(
+        agents_dir.join("reviewer.md"),
+        "---\nname: reviewer\ndescription: Reviews diffs\n---\nReview the change and list risks.\n",
+    )?;
+    std::fs::write(cases_dir.join("fixtures/app/main.py"), "print('hi')\n")?;
+    std::fs::write(
+        cases_dir.join("a-clean.yaml"),
+        "fixture: fixtures/app\nprompt: Review main.py.\nassert:\n  final_message: no risks\n  json_schema:\n    type: object\n    required: [summary, findings]\n",
+    )?;
+    std::fs::write(
+        cases_dir.join("b-docs.yaml"),
+        "fixture: fixtures/app\nprompt: Document main.py.\nassert:\n  files_changed: [README.md]\n",
+    )?;
+
+    let server = responses::start_mock_server().await;
+    let body = || {
+        responses::sse(vec![
+            serde_json::json!({"type": "response.created", "response": {"id": "resp1"}}),
+            responses::ev_assistant_message(
+                "m1",
+                r#"{"summary": "no risks found", "findings": []}"#,
+            ),
+            responses::ev_completed("resp1"),
+        ])
+    };
+    responses::mount_sse_sequence(&server, vec![body(), body()]).await;
+
+    let mut config = load_default_config_for_test(&codex_home);
+    config.cwd = project.path().to_path_buf();
+    config.model_provider = ModelProviderInfo {
+        name: "mock".to_string(),
+        base_url: Some(format!("{}/v1", server.uri())),
+        env_key: None,
+        wire_api: WireApi::Responses,
+        requires_openai_auth: false,
+        request_max_retries: Some(0),
+        stream_max_retries: Some(0),
+        ..built_in_model_providers()["openai"].clone()
+    };
+    let report_path = project.path().join("report.json");
+    let report = run_evals(
+        config,
+        EvalOptions {
+            agent: None,
+            report: report_path.clone(),
+        },
+    )
+    .await?;
+
+    assert_eq!((report.passed, report.failed), (1, 1));
+    let failure = report.cases[1].failure().expect("b-docs fails");
+    assert_eq!(failure, "files_changed: README.md: no files changed");
+    let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(report_path)?)?;
+    assert_eq!(json["cases"][0]["case"], "a-clean");
+    assert_eq!(json["cases"][0]["passed"], true);
+    assert_eq!(json["cases"][1]["assertions"][0]["passed"], false);
+
+    let requests = server.received_requests().await.expect("requests");
+    assert_eq!(requests.len(), 2);
+    // The fixture itself is untouched; each run got a copy.
+    assert_eq!(
+        std::fs::read_to_string(cases_dir.join("fixtures/app/main.py"))?,
+        "print('hi')\n"
+    );
+    Ok(())
+}
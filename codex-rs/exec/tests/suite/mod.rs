@@ -1,6 +1,10 @@
 // Aggregates all former standalone integration tests as modules.
+mod agent;
 mod apply_patch;
+mod ensemble;
+mod eval;
 mod output_schema;
 mod resume;
+mod review;
 mod sandbox;
 mod server_error_exit;
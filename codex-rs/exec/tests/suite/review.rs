@@ -0,0 +1,65 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use core_test_support::responses;
+use core_test_support::test_codex_exec::test_codex_exec;
+use serde_json::Value;
+use wiremock::matchers::any;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn exec_review_writes_sarif() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    let cwd = test.cwd_path().canonicalize()?;
+    let review_json = serde_json::json!({
+        "findings": [{
+            "title": "[P1] Unchecked index",
+            "body": "`items[0]` panics on empty input.",
+            "confidence_score": 0.9,
+            "priority": 1,
+            "code_location": {
+                "absolute_file_path": cwd.join("src/lib.rs"),
+                "line_range": {"start": 4, "end": 6}
+            }
+        }],
+        "overall_correctness": "patch is incorrect",
+        "overall_explanation": "One panic.",
+        "overall_confidence_score": 0.8
+    });
+
+    let server = responses::start_mock_server().await;
+    let body = responses::sse(vec![
+        serde_json::json!({
+            "type": "response.created",
+            "response": {"id": "resp1"}
+        }),
+        responses::ev_assistant_message("m1", &review_json.to_string()),
+        responses::ev_completed("resp1"),
+    ]);
+    responses::mount_sse_once(&server, any(), body).await;
+
+    let sarif_path = cwd.join("out.sarif");
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("-C")
+        .arg(&cwd)
+        .arg("--review")
+        .arg("--sarif")
+        .arg(&sarif_path)
+        .arg("review my changes")
+        .assert()
+        .success();
+
+    let sarif: Value = serde_json::from_str(&std::fs::read_to_string(&sarif_path)?)?;
+    assert_eq!(sarif["version"], "2.1.0");
+    let result = &sarif["runs"][0]["results"][0];
+    assert_eq!(result["ruleId"], "codex-review/P1");
+    assert_eq!(result["level"], "error");
+    assert_eq!(
+        result["locations"][0]["physicalLocation"],
+        serde_json::json!({
+            "artifactLocation": {"uri": "src/lib.rs"},
+            "region": {"startLine": 4, "endLine": 6}
+        })
+    );
+    Ok(())
+}
@@ -72,6 +72,7 @@ async fn python_multiprocessing_lock_works_under_sandbox() {
         network_access: false,
         exclude_tmpdir_env_var: false,
         exclude_slash_tmp: false,
+        exclude_cwd: false,
     };
 
     let python_code = r#"import multiprocessing
@@ -132,6 +133,7 @@ async fn sandbox_distinguishes_command_and_policy_cwds() {
         network_access: false,
         exclude_tmpdir_env_var: true,
         exclude_slash_tmp: true,
+        exclude_cwd: false,
     };
 
     // Attempt to write inside the command cwd, which is outside of the sandbox policy cwd.
@@ -34,8 +34,11 @@ use codex_core::protocol::ExecApprovalRequestEvent;
 use codex_core::protocol::InputItem as CoreInputItem;
 use codex_core::protocol::Op;
 use codex_core::protocol::ReviewDecision;
+use codex_core::protocol::SubAgentOutcome;
+use codex_core::subagents::ParentContext;
 use codex_core::subagents::SubagentInvocation;
 use codex_core::subagents::SubagentOrchestrator;
+use codex_core::subagents::subagent_registry;
 use codex_login::ServerOptions as LoginServerOptions;
 use codex_login::ShutdownHandle;
 use codex_login::run_login_server;
@@ -81,7 +84,10 @@ use codex_protocol::mcp_protocol::SetDefaultModelParams;
 use codex_protocol::mcp_protocol::SetDefaultModelResponse;
 use codex_protocol::mcp_protocol::SubagentListAgent;
 use codex_protocol::mcp_protocol::SubagentParseError;
+use codex_protocol::mcp_protocol::SubagentRunSummary;
 use codex_protocol::mcp_protocol::SubagentsListResponse;
+use codex_protocol::mcp_protocol::SubagentsRunManyParams;
+use codex_protocol::mcp_protocol::SubagentsRunManyResponse;
 use codex_protocol::mcp_protocol::SubagentsRunParams;
 use codex_protocol::mcp_protocol::SubagentsRunResponse;
 use codex_protocol::mcp_protocol::UserInfoResponse;
@@ -90,7 +96,10 @@ use codex_protocol::models::ContentItem;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::protocol::InputMessageKind;
 use codex_protocol::protocol::USER_MESSAGE_BEGIN;
-use codex_subagents::SubagentRegistry;
+use codex_subagents::AgentHandle;
+use codex_subagents::ContextAttachment;
+use codex_subagents::RegistrySnapshot;
+use codex_subagents::SubagentSpec;
 use mcp_types::JSONRPCErrorError;
 use mcp_types::RequestId;
 use std::collections::HashMap;
@@ -132,6 +141,9 @@ pub(crate) struct CodexMessageProcessor {
     // Queue of pending interrupt requests per conversation. We reply when TurnAborted arrives.
     pending_interrupts: Arc<Mutex<HashMap<ConversationId, Vec<RequestId>>>>,
     conversation_configs: Arc<Mutex<HashMap<ConversationId, Config>>>,
+    // Recent messages and plan of each listened-to conversation, for subagent
+    // `parent_last_messages` and `plan` context attachments.
+    parent_contexts: Arc<Mutex<HashMap<ConversationId, ParentContext>>>,
 }
 
 impl CodexMessageProcessor {
@@ -152,6 +164,7 @@ impl CodexMessageProcessor {
             active_login: Arc::new(Mutex::new(None)),
             pending_interrupts: Arc::new(Mutex::new(HashMap::new())),
             conversation_configs: Arc::new(Mutex::new(HashMap::new())),
+            parent_contexts: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -223,6 +236,9 @@ impl CodexMessageProcessor {
             ClientRequest::SubagentsRun { request_id, params } => {
                 self.handle_subagents_run(request_id, params).await;
             }
+            ClientRequest::SubagentsRunMany { request_id, params } => {
+                self.handle_subagents_run_many(request_id, params).await;
+            }
             ClientRequest::ExecOneOffCommand { request_id, params } => {
                 self.exec_one_off_command(request_id, params).await;
             }
@@ -534,17 +550,9 @@ impl CodexMessageProcessor {
     }
 
     async fn handle_subagents_list(&self, request_id: RequestId) {
-        let project_dir = self.config.cwd.join(".codex/agents");
-        let user_dir = self.config.codex_home.join("agents");
-        let mut registry = SubagentRegistry::new(project_dir, user_dir);
-        let snapshot = match registry.reload() {
-            Ok(snapshot) => snapshot.clone(),
-            Err(err) => {
-                let error = JSONRPCErrorError {
-                    code: INTERNAL_ERROR_CODE,
-                    message: format!("failed to load subagent registry: {err}"),
-                    data: None,
-                };
+        let snapshot = match load_subagent_snapshot(&self.config) {
+            Ok(snapshot) => snapshot,
+            Err(error) => {
                 self.outgoing.send_error(request_id, error).await;
                 return;
             }
@@ -594,83 +602,47 @@ impl CodexMessageProcessor {
             conversation_id,
             agent_name,
             prompt,
+            context,
         } = params;
 
-        if self
-            .conversation_manager
-            .get_conversation(conversation_id)
-            .await
-            .is_err()
-        {
-            let error = JSONRPCErrorError {
-                code: INVALID_REQUEST_ERROR_CODE,
-                message: format!("conversation {conversation_id} not found"),
-                data: None,
-            };
-            self.outgoing.send_error(request_id, error).await;
-            return;
-        }
-
-        let config = {
-            let configs = self.conversation_configs.lock().await;
-            configs.get(&conversation_id).cloned()
+        let config = match self.subagent_run_config(conversation_id).await {
+            Ok(config) => config,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
         };
-        let config = if let Some(cfg) = config {
-            cfg
-        } else {
-            let error = JSONRPCErrorError {
-                code: INTERNAL_ERROR_CODE,
-                message: format!("configuration for conversation {conversation_id} is unavailable"),
-                data: None,
-            };
-            self.outgoing.send_error(request_id, error).await;
-            return;
+        let context = match parse_context_attachments(&context) {
+            Ok(context) => context,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
         };
-
-        if !config.subagents.enabled {
-            let error = JSONRPCErrorError {
-                code: INVALID_REQUEST_ERROR_CODE,
-                message: "subagents feature is disabled for this conversation".to_string(),
-                data: None,
-            };
-            self.outgoing.send_error(request_id, error).await;
-            return;
-        }
-
-        let project_dir = config.cwd.join(".codex/agents");
-        let user_dir = config.codex_home.join("agents");
-        let mut registry = SubagentRegistry::new(project_dir, user_dir);
-        let snapshot = match registry.reload() {
-            Ok(snapshot) => snapshot.clone(),
-            Err(err) => {
-                let error = JSONRPCErrorError {
-                    code: INTERNAL_ERROR_CODE,
-                    message: format!("failed to load subagent registry: {err}"),
-                    data: None,
-                };
+        let parent_context = self
+            .parent_contexts
+            .lock()
+            .await
+            .get(&conversation_id)
+            .cloned()
+            .unwrap_or_default();
+        let snapshot = match load_subagent_snapshot(&config) {
+            Ok(snapshot) => snapshot,
+            Err(error) => {
                 self.outgoing.send_error(request_id, error).await;
                 return;
             }
         };
-
-        let handle = match snapshot
-            .agents
-            .iter()
-            .find(|agent| agent.spec.metadata.name.eq_ignore_ascii_case(&agent_name))
-        {
-            Some(handle) => handle.clone(),
-            None => {
-                let error = JSONRPCErrorError {
-                    code: INVALID_REQUEST_ERROR_CODE,
-                    message: format!("subagent '{agent_name}' not found"),
-                    data: None,
-                };
+        let handle = match find_subagent(&snapshot, &agent_name) {
+            Ok(handle) => handle,
+            Err(error) => {
                 self.outgoing.send_error(request_id, error).await;
                 return;
             }
         };
 
         let spec = handle.spec;
+        let agents = handoff_agents(snapshot);
         let prompt_for_run = prompt.clone();
         let outgoing = self.outgoing.clone();
         let conversation_manager = self.conversation_manager.clone();
@@ -679,7 +651,10 @@ impl CodexMessageProcessor {
         let agent_display = spec.metadata.name.clone();
         let response_request_id = request_id.clone();
         tokio::spawn(async move {
-            let orchestrator = SubagentOrchestrator::new(conversation_manager);
+            let orchestrator = SubagentOrchestrator::new(conversation_manager)
+                .with_handoff_agents(agents)
+                .with_context_attachments(context)
+                .with_parent_context(parent_context);
             let run_result = orchestrator
                 .run_subagent(
                     &config,
@@ -724,6 +699,172 @@ impl CodexMessageProcessor {
         });
     }
 
+    async fn handle_subagents_run_many(
+        &self,
+        request_id: RequestId,
+        params: SubagentsRunManyParams,
+    ) {
+        let SubagentsRunManyParams {
+            conversation_id,
+            agent_names,
+            prompt,
+            context,
+        } = params;
+
+        if agent_names.is_empty() {
+            let error = JSONRPCErrorError {
+                code: INVALID_REQUEST_ERROR_CODE,
+                message: "agentNames must not be empty".to_string(),
+                data: None,
+            };
+            self.outgoing.send_error(request_id, error).await;
+            return;
+        }
+
+        let config = match self.subagent_run_config(conversation_id).await {
+            Ok(config) => config,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+        let context = match parse_context_attachments(&context) {
+            Ok(context) => context,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+        let parent_context = self
+            .parent_contexts
+            .lock()
+            .await
+            .get(&conversation_id)
+            .cloned()
+            .unwrap_or_default();
+        let snapshot = match load_subagent_snapshot(&config) {
+            Ok(snapshot) => snapshot,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+        let mut specs = Vec::with_capacity(agent_names.len());
+        for agent_name in &agent_names {
+            match find_subagent(&snapshot, agent_name) {
+                Ok(handle) => specs.push(handle.spec),
+                Err(error) => {
+                    self.outgoing.send_error(request_id, error).await;
+                    return;
+                }
+            }
+        }
+
+        let agents = handoff_agents(snapshot);
+        let outgoing = self.outgoing.clone();
+        let conversation_manager = self.conversation_manager.clone();
+        let meta = OutgoingNotificationMeta::new(Some(request_id.clone()));
+        let parent_submit_id = format!("mcp-subagent-{}", Uuid::now_v7());
+        tokio::spawn(async move {
+            let orchestrator = SubagentOrchestrator::new(conversation_manager)
+                .with_handoff_agents(agents)
+                .with_context_attachments(context)
+                .with_parent_context(parent_context);
+            let invocations = specs
+                .iter()
+                .map(|spec| SubagentInvocation {
+                    spec,
+                    parent_submit_id: parent_submit_id.clone(),
+                })
+                .collect();
+            let batch = orchestrator
+                .run_parallel(&config, invocations, prompt, |msg| {
+                    let outgoing = outgoing.clone();
+                    let meta = meta.clone();
+                    let event = Event {
+                        id: parent_submit_id.clone(),
+                        msg,
+                    };
+                    tokio::spawn(async move {
+                        outgoing
+                            .send_event_as_notification(&event, Some(meta))
+                            .await;
+                    });
+                })
+                .await;
+
+            let runs = batch
+                .runs
+                .into_iter()
+                .map(|entry| match entry.result {
+                    Ok(state) => SubagentRunSummary {
+                        agent_name: entry.agent_name,
+                        sub_conversation_id: Some(state.conversation_id),
+                        outcome: state.outcome,
+                        error: state.error,
+                        duration_ms: Some(
+                            state.duration.as_millis().min(u128::from(u64::MAX)) as u64
+                        ),
+                        token_usage: Some(state.token_usage),
+                    },
+                    Err(err) => SubagentRunSummary {
+                        agent_name: entry.agent_name,
+                        sub_conversation_id: None,
+                        outcome: SubAgentOutcome::Error,
+                        error: Some(err),
+                        duration_ms: None,
+                        token_usage: None,
+                    },
+                })
+                .collect();
+            outgoing
+                .send_response(request_id, SubagentsRunManyResponse { runs })
+                .await;
+        });
+    }
+
+    /// Resolve the configuration used to launch subagents on behalf of an
+    /// existing conversation, rejecting the request when the feature is off.
+    async fn subagent_run_config(
+        &self,
+        conversation_id: ConversationId,
+    ) -> Result<Config, JSONRPCErrorError> {
+        if self
+            .conversation_manager
+            .get_conversation(conversation_id)
+            .await
+            .is_err()
+        {
+            return Err(JSONRPCErrorError {
+                code: INVALID_REQUEST_ERROR_CODE,
+                message: format!("conversation {conversation_id} not found"),
+                data: None,
+            });
+        }
+
+        let config = {
+            let configs = self.conversation_configs.lock().await;
+            configs.get(&conversation_id).cloned()
+        };
+        let Some(config) = config else {
+            return Err(JSONRPCErrorError {
+                code: INTERNAL_ERROR_CODE,
+                message: format!("configuration for conversation {conversation_id} is unavailable"),
+                data: None,
+            });
+        };
+
+        if !config.subagents.enabled {
+            return Err(JSONRPCErrorError {
+                code: INVALID_REQUEST_ERROR_CODE,
+                message: "subagents feature is disabled for this conversation".to_string(),
+                data: None,
+            });
+        }
+
+        Ok(config)
+    }
+
     async fn set_default_model(&self, request_id: RequestId, params: SetDefaultModelParams) {
         let SetDefaultModelParams {
             model,
@@ -1072,6 +1213,7 @@ impl CodexMessageProcessor {
             let mut configs = self.conversation_configs.lock().await;
             configs.remove(&conversation_id);
         }
+        self.parent_contexts.lock().await.remove(&conversation_id);
         if let Some(conversation) = removed_conversation {
             info!("conversation {conversation_id} was active; shutting down");
             let conversation_clone = conversation.clone();
@@ -1296,6 +1438,7 @@ impl CodexMessageProcessor {
             .insert(subscription_id, cancel_tx);
         let outgoing_for_task = self.outgoing.clone();
         let pending_interrupts = self.pending_interrupts.clone();
+        let parent_contexts = self.parent_contexts.clone();
         tokio::spawn(async move {
             loop {
                 tokio::select! {
@@ -1312,6 +1455,13 @@ impl CodexMessageProcessor {
                             }
                         };
 
+                        parent_contexts
+                            .lock()
+                            .await
+                            .entry(conversation_id)
+                            .or_default()
+                            .observe(&event.msg);
+
                         // For now, we send a notification for every event,
                         // JSON-serializing the `Event` as-is, but these should
                         // be migrated to be variants of `ServerNotification`
@@ -1632,6 +1782,54 @@ fn extract_conversation_summary(
     })
 }
 
+fn load_subagent_snapshot(config: &Config) -> Result<RegistrySnapshot, JSONRPCErrorError> {
+    let mut registry = subagent_registry(config);
+    registry.reload().cloned().map_err(|err| JSONRPCErrorError {
+        code: INTERNAL_ERROR_CODE,
+        message: format!("failed to load subagent registry: {err}"),
+        data: None,
+    })
+}
+
+fn handoff_agents(snapshot: RegistrySnapshot) -> Vec<SubagentSpec> {
+    snapshot
+        .agents
+        .into_iter()
+        .map(|handle| handle.spec)
+        .collect()
+}
+
+fn parse_context_attachments(
+    context: &[String],
+) -> Result<Vec<ContextAttachment>, JSONRPCErrorError> {
+    context
+        .iter()
+        .map(|raw| {
+            raw.parse().map_err(|err| JSONRPCErrorError {
+                code: INVALID_REQUEST_ERROR_CODE,
+                message: format!("{err}"),
+                data: None,
+            })
+        })
+        .collect()
+}
+
+fn find_subagent(
+    snapshot: &RegistrySnapshot,
+    agent_name: &str,
+) -> Result<AgentHandle, JSONRPCErrorError> {
+    snapshot
+        .agents
+        .iter()
+        .find(|agent| agent.spec.metadata.name.eq_ignore_ascii_case(agent_name))
+        .cloned()
+        .ok_or_else(|| JSONRPCErrorError {
+            code: INVALID_REQUEST_ERROR_CODE,
+            message: format!("subagent '{agent_name}' not found"),
+            data: None,
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
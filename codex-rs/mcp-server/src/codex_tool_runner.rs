@@ -286,7 +286,9 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::ExitedReviewMode(_)
                     | EventMsg::SubAgentStarted(_)
                     | EventMsg::SubAgentMessage(_)
-                    | EventMsg::SubAgentCompleted(_) => {
+                    | EventMsg::SubAgentCompleted(_)
+                    | EventMsg::SubAgentHandoff(_)
+                    | EventMsg::SubAgentActivity(_) => {
                         // For now, we do not do anything extra for these
                         // events. Note that
                         // send(codex_event_to_notification(&event)) above has
@@ -8,6 +8,12 @@ use crate::codex_tool_config::create_tool_for_codex_tool_call_param;
 use crate::codex_tool_config::create_tool_for_codex_tool_call_reply_param;
 use crate::error_code::INVALID_REQUEST_ERROR_CODE;
 use crate::outgoing_message::OutgoingMessageSender;
+use crate::subagent_tools::SubagentToolCallParam;
+use crate::subagent_tools::agent_name_for_tool;
+use crate::subagent_tools::create_tools_for_subagents;
+use crate::subagent_tools::load_agent_snapshot;
+use crate::subagent_tools::spawn_tool_list_watcher;
+use crate::subagent_tools::subagent_tools_enabled;
 use codex_protocol::mcp_protocol::ClientRequest;
 use codex_protocol::mcp_protocol::ConversationId;
 
@@ -44,6 +50,7 @@ pub(crate) struct MessageProcessor {
     codex_linux_sandbox_exe: Option<PathBuf>,
     conversation_manager: Arc<ConversationManager>,
     running_requests_id_to_codex_uuid: Arc<Mutex<HashMap<RequestId, ConversationId>>>,
+    config: Arc<Config>,
 }
 
 impl MessageProcessor {
@@ -62,7 +69,7 @@ impl MessageProcessor {
             conversation_manager.clone(),
             outgoing.clone(),
             codex_linux_sandbox_exe.clone(),
-            config,
+            config.clone(),
         );
         Self {
             codex_message_processor,
@@ -71,6 +78,7 @@ impl MessageProcessor {
             codex_linux_sandbox_exe,
             conversation_manager,
             running_requests_id_to_codex_uuid: Arc::new(Mutex::new(HashMap::new())),
+            config,
         }
     }
 
@@ -217,6 +225,9 @@ impl MessageProcessor {
         }
 
         self.initialized = true;
+        if subagent_tools_enabled(&self.config) {
+            spawn_tool_list_watcher(self.config.clone(), self.outgoing.clone());
+        }
 
         // Build a minimal InitializeResult. Fill with placeholders.
         let result = mcp_types::InitializeResult {
@@ -318,11 +329,18 @@ impl MessageProcessor {
         params: <mcp_types::ListToolsRequest as mcp_types::ModelContextProtocolRequest>::Params,
     ) {
         tracing::trace!("tools/list -> {params:?}");
+        let mut tools = vec![
+            create_tool_for_codex_tool_call_param(),
+            create_tool_for_codex_tool_call_reply_param(),
+        ];
+        if subagent_tools_enabled(&self.config) {
+            match load_agent_snapshot(&self.config) {
+                Ok(snapshot) => tools.extend(create_tools_for_subagents(&snapshot.agents)),
+                Err(err) => tracing::warn!("failed to load subagent registry: {err}"),
+            }
+        }
         let result = ListToolsResult {
-            tools: vec![
-                create_tool_for_codex_tool_call_param(),
-                create_tool_for_codex_tool_call_reply_param(),
-            ],
+            tools,
             next_cursor: None,
         };
 
@@ -344,6 +362,13 @@ impl MessageProcessor {
                 self.handle_tool_call_codex_session_reply(id, arguments)
                     .await
             }
+            tool_name
+                if subagent_tools_enabled(&self.config)
+                    && agent_name_for_tool(tool_name).is_some() =>
+            {
+                self.handle_tool_call_subagent(id, tool_name, arguments)
+                    .await
+            }
             _ => {
                 let result = CallToolResult {
                     content: vec![ContentBlock::TextContent(TextContent {
@@ -548,6 +573,79 @@ impl MessageProcessor {
         });
     }
 
+    async fn handle_tool_call_subagent(
+        &self,
+        id: RequestId,
+        tool_name: &str,
+        arguments: Option<serde_json::Value>,
+    ) {
+        let agent_name = agent_name_for_tool(tool_name).unwrap_or_default();
+        let prompt = match arguments
+            .map(serde_json::from_value::<SubagentToolCallParam>)
+            .transpose()
+        {
+            Ok(Some(SubagentToolCallParam { prompt })) => prompt,
+            Ok(None) => {
+                self.send_tool_error(
+                    id,
+                    format!("Missing arguments for {tool_name} tool-call; the `prompt` field is required."),
+                )
+                .await;
+                return;
+            }
+            Err(e) => {
+                self.send_tool_error(
+                    id,
+                    format!("Failed to parse arguments for {tool_name}: {e}"),
+                )
+                .await;
+                return;
+            }
+        };
+
+        let spec = match load_agent_snapshot(&self.config) {
+            Ok(snapshot) => snapshot
+                .agents
+                .into_iter()
+                .map(|handle| handle.spec)
+                .find(|spec| spec.metadata.name.eq_ignore_ascii_case(agent_name)),
+            Err(e) => {
+                self.send_tool_error(id, format!("Failed to load subagent registry: {e}"))
+                    .await;
+                return;
+            }
+        };
+        let Some(spec) = spec else {
+            self.send_tool_error(id, format!("Unknown tool '{tool_name}'"))
+                .await;
+            return;
+        };
+
+        task::spawn(crate::subagent_tools::run_subagent_tool(
+            id,
+            self.config.clone(),
+            spec,
+            prompt,
+            self.outgoing.clone(),
+            self.conversation_manager.clone(),
+            self.running_requests_id_to_codex_uuid.clone(),
+        ));
+    }
+
+    async fn send_tool_error(&self, id: RequestId, text: String) {
+        let result = CallToolResult {
+            content: vec![ContentBlock::TextContent(TextContent {
+                r#type: "text".to_string(),
+                text,
+                annotations: None,
+            })],
+            is_error: Some(true),
+            structured_content: None,
+        };
+        self.send_response::<mcp_types::CallToolRequest>(id, result)
+            .await;
+    }
+
     fn handle_set_level(
         &self,
         params: <mcp_types::SetLevelRequest as mcp_types::ModelContextProtocolRequest>::Params,
@@ -0,0 +1,302 @@
+//! Exposes every registered subagent as its own MCP tool (`agent_<name>`)
+//! when `subagents.mcp_tools` is enabled, so other MCP hosts can call the
+//! agent library directly.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use codex_core::ConversationManager;
+use codex_core::config::Config;
+use codex_core::protocol::Event;
+use codex_core::protocol::SubAgentOutcome;
+use codex_core::subagents::SubagentInvocation;
+use codex_core::subagents::SubagentOrchestrator;
+use codex_core::subagents::subagent_registry;
+use codex_protocol::mcp_protocol::ConversationId;
+use codex_subagents::AgentHandle;
+use codex_subagents::RegistryError;
+use codex_subagents::RegistrySnapshot;
+use codex_subagents::SubagentRegistry;
+use codex_subagents::SubagentSpec;
+use mcp_types::CallToolResult;
+use mcp_types::ContentBlock;
+use mcp_types::RequestId;
+use mcp_types::TextContent;
+use mcp_types::Tool;
+use mcp_types::ToolInputSchema;
+use schemars::JsonSchema;
+use schemars::r#gen::SchemaSettings;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::json;
+use tokio::sync::Mutex;
+
+use crate::outgoing_message::OutgoingMessageSender;
+use crate::outgoing_message::OutgoingNotification;
+use crate::outgoing_message::OutgoingNotificationMeta;
+
+/// Prefix shared by all subagent tool names.
+pub(crate) const SUBAGENT_TOOL_PREFIX: &str = "agent_";
+
+/// How often the agent directories are rescanned for changes.
+const REGISTRY_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Arguments accepted by an `agent_<name>` tool-call.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SubagentToolCallParam {
+    /// The task to hand to the subagent.
+    pub prompt: String,
+}
+
+/// Whether `config` opts into advertising subagents as MCP tools.
+pub(crate) fn subagent_tools_enabled(config: &Config) -> bool {
+    config.subagents.enabled && config.subagents.mcp_tools
+}
+
+/// Map an MCP tool name back to the agent it runs, if it is a subagent tool.
+pub(crate) fn agent_name_for_tool(tool_name: &str) -> Option<&str> {
+    tool_name
+        .strip_prefix(SUBAGENT_TOOL_PREFIX)
+        .filter(|name| !name.is_empty())
+}
+
+pub(crate) fn load_agent_registry(config: &Config) -> SubagentRegistry {
+    subagent_registry(config)
+}
+
+pub(crate) fn load_agent_snapshot(config: &Config) -> Result<RegistrySnapshot, RegistryError> {
+    load_agent_registry(config).reload().cloned()
+}
+
+pub(crate) fn create_tools_for_subagents(agents: &[AgentHandle]) -> Vec<Tool> {
+    agents
+        .iter()
+        .map(|handle| create_tool_for_subagent(&handle.spec))
+        .collect()
+}
+
+fn create_tool_for_subagent(spec: &SubagentSpec) -> Tool {
+    let schema = SchemaSettings::draft2019_09()
+        .with(|s| {
+            s.inline_subschemas = true;
+            s.option_add_null_type = false;
+        })
+        .into_generator()
+        .into_root_schema_for::<SubagentToolCallParam>();
+
+    #[expect(clippy::expect_used)]
+    let schema_value =
+        serde_json::to_value(&schema).expect("Subagent tool schema should serialise to JSON");
+
+    let tool_input_schema =
+        serde_json::from_value::<ToolInputSchema>(schema_value).unwrap_or_else(|e| {
+            panic!("failed to create Tool from schema: {e}");
+        });
+
+    let name = &spec.metadata.name;
+    Tool {
+        name: format!("{SUBAGENT_TOOL_PREFIX}{name}"),
+        title: Some(name.clone()),
+        input_schema: tool_input_schema,
+        output_schema: None,
+        description: Some(
+            spec.metadata
+                .description
+                .clone()
+                .unwrap_or_else(|| format!("Run the `{name}` subagent.")),
+        ),
+        annotations: None,
+    }
+}
+
+/// Poll the agent directories and send `notifications/tools/list_changed`
+/// whenever the advertised set of subagent tools changes.
+pub(crate) fn spawn_tool_list_watcher(config: Arc<Config>, outgoing: Arc<OutgoingMessageSender>) {
+    tokio::spawn(async move {
+        let mut registry = load_agent_registry(&config);
+        let mut current = match registry.reload() {
+            Ok(snapshot) => create_tools_for_subagents(&snapshot.agents),
+            Err(err) => {
+                tracing::warn!("failed to load subagent registry: {err}");
+                Vec::new()
+            }
+        };
+        loop {
+            tokio::time::sleep(REGISTRY_POLL_INTERVAL).await;
+            let tools = match registry.reload() {
+                Ok(snapshot) => create_tools_for_subagents(&snapshot.agents),
+                Err(err) => {
+                    tracing::warn!("failed to reload subagent registry: {err}");
+                    continue;
+                }
+            };
+            if tools != current {
+                current = tools;
+                outgoing
+                    .send_notification(OutgoingNotification {
+                        method: "notifications/tools/list_changed".to_string(),
+                        params: None,
+                    })
+                    .await;
+            }
+        }
+    });
+}
+
+/// Run `spec` for an `agent_<name>` tool-call, streaming subagent events as
+/// `codex/event` notifications and answering the `tools/call` request with the
+/// agent's final message.
+pub(crate) async fn run_subagent_tool(
+    id: RequestId,
+    config: Arc<Config>,
+    spec: SubagentSpec,
+    prompt: String,
+    outgoing: Arc<OutgoingMessageSender>,
+    conversation_manager: Arc<ConversationManager>,
+    running_requests_id_to_codex_uuid: Arc<Mutex<HashMap<RequestId, ConversationId>>>,
+) {
+    let agents = match load_agent_snapshot(&config) {
+        Ok(snapshot) => snapshot
+            .agents
+            .into_iter()
+            .map(|handle| handle.spec)
+            .collect(),
+        Err(err) => {
+            tracing::warn!("failed to load subagent registry: {err}");
+            Vec::new()
+        }
+    };
+    let orchestrator = SubagentOrchestrator::new(conversation_manager).with_handoff_agents(agents);
+    let sub_id = match &id {
+        RequestId::String(s) => s.clone(),
+        RequestId::Integer(n) => n.to_string(),
+    };
+    let invocation = SubagentInvocation {
+        spec: &spec,
+        parent_submit_id: sub_id.clone(),
+    };
+
+    let child = match orchestrator.spawn_child(&config, &invocation).await {
+        Ok(child) => child,
+        Err(err) => {
+            let text = format!("Failed to start subagent '{}': {err}", spec.metadata.name);
+            outgoing.send_response(id, error_result(text)).await;
+            return;
+        }
+    };
+    let conversation_id = child.conversation_id;
+    // Register the child so `notifications/cancelled` can interrupt it.
+    running_requests_id_to_codex_uuid
+        .lock()
+        .await
+        .insert(id.clone(), conversation_id);
+
+    let meta = OutgoingNotificationMeta::new(Some(id.clone()));
+    let result = orchestrator
+        .run_spawned(&config, invocation, child, Some(prompt), |msg| {
+            let outgoing = outgoing.clone();
+            let meta = meta.clone();
+            let event = Event {
+                id: sub_id.clone(),
+                msg,
+            };
+            tokio::spawn(async move {
+                outgoing
+                    .send_event_as_notification(&event, Some(meta))
+                    .await;
+            });
+        })
+        .await;
+
+    running_requests_id_to_codex_uuid.lock().await.remove(&id);
+
+    let response = match result {
+        Ok(state) => {
+            let is_error = state.outcome == SubAgentOutcome::Error;
+            let text = if is_error {
+                state
+                    .error
+                    .clone()
+                    .or_else(|| state.last_message.clone())
+                    .unwrap_or_else(|| "subagent failed".to_string())
+            } else {
+                state.last_message.clone().unwrap_or_default()
+            };
+            CallToolResult {
+                content: vec![ContentBlock::TextContent(TextContent {
+                    r#type: "text".to_string(),
+                    text,
+                    annotations: None,
+                })],
+                is_error: Some(is_error),
+                structured_content: Some(json!({
+                    "agent": spec.metadata.name,
+                    "sub_conversation_id": state.conversation_id,
+                    "model": state.model,
+                })),
+            }
+        }
+        Err(err) => error_result(format!("Subagent '{}' failed: {err}", spec.metadata.name)),
+    };
+    outgoing.send_response(id, response).await;
+}
+
+fn error_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::TextContent(TextContent {
+            r#type: "text".to_string(),
+            text,
+            annotations: None,
+        })],
+        is_error: Some(true),
+        structured_content: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_subagents::SubagentBuilder;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn agent_tools_use_prefixed_names_and_descriptions() {
+        let reviewer = SubagentBuilder::new("reviewer")
+            .description(Some("Reviews diffs".to_string()))
+            .instructions("Review the diff.")
+            .build()
+            .expect("valid spec");
+        let tester = SubagentBuilder::new("tester")
+            .instructions("Run the tests.")
+            .build()
+            .expect("valid spec");
+        let agents = vec![AgentHandle::new(reviewer), AgentHandle::new(tester)];
+
+        let tools = create_tools_for_subagents(&agents);
+        let summary: Vec<(String, Option<String>)> = tools
+            .iter()
+            .map(|tool| (tool.name.clone(), tool.description.clone()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "agent_reviewer".to_string(),
+                    Some("Reviews diffs".to_string())
+                ),
+                (
+                    "agent_tester".to_string(),
+                    Some("Run the `tester` subagent.".to_string())
+                ),
+            ]
+        );
+        assert_eq!(
+            tools[0].input_schema.required,
+            Some(vec!["prompt".to_string()])
+        );
+        assert_eq!(agent_name_for_tool("agent_reviewer"), Some("reviewer"));
+        assert_eq!(agent_name_for_tool("agent_"), None);
+        assert_eq!(agent_name_for_tool("codex"), None);
+    }
+}
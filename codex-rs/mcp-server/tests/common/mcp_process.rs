@@ -213,6 +213,29 @@ impl McpProcess {
         .await
     }
 
+    /// Send a `tools/list` JSON-RPC request.
+    pub async fn send_list_tools_request(&mut self) -> anyhow::Result<i64> {
+        self.send_request(mcp_types::ListToolsRequest::METHOD, Some(json!({})))
+            .await
+    }
+
+    /// Send a `tools/call` JSON-RPC request for an arbitrary tool.
+    pub async fn send_tool_call(
+        &mut self,
+        name: &str,
+        arguments: serde_json::Value,
+    ) -> anyhow::Result<i64> {
+        let params = CallToolRequestParams {
+            name: name.to_string(),
+            arguments: Some(arguments),
+        };
+        self.send_request(
+            mcp_types::CallToolRequest::METHOD,
+            Some(serde_json::to_value(params)?),
+        )
+        .await
+    }
+
     /// Send a `newConversation` JSON-RPC request.
     pub async fn send_new_conversation_request(
         &mut self,
@@ -10,5 +10,6 @@ mod list_resume;
 mod login;
 mod send_message;
 mod set_default_model;
+mod subagent_tools;
 mod user_agent;
 mod user_info;
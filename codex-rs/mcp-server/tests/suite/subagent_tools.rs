@@ -0,0 +1,157 @@
+use std::path::Path;
+
+use mcp_test_support::McpProcess;
+use mcp_test_support::create_final_assistant_message_sse_response;
+use mcp_test_support::create_mock_chat_completions_server;
+use mcp_types::JSONRPCResponse;
+use mcp_types::ListToolsResult;
+use mcp_types::RequestId;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(20);
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn subagent_tools_are_listed_and_refreshed() -> anyhow::Result<()> {
+    let server = create_mock_chat_completions_server(Vec::new()).await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri(), true)?;
+    write_agent(codex_home.path(), "reviewer", "Reviews diffs")?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    // The user's reviewer replaces the builtin one; the other builtin
+    // agents are advertised alongside it.
+    let names = list_tool_names(&mut mcp).await?;
+    assert_eq!(
+        names,
+        vec![
+            "codex",
+            "codex-reply",
+            "agent_code-writer",
+            "agent_reviewer",
+            "agent_spec-parser",
+            "agent_tester"
+        ]
+    );
+
+    write_agent(codex_home.path(), "planner", "Plans the work")?;
+    timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_notification_message("notifications/tools/list_changed"),
+    )
+    .await??;
+
+    let names = list_tool_names(&mut mcp).await?;
+    assert_eq!(
+        names,
+        vec![
+            "codex",
+            "codex-reply",
+            "agent_code-writer",
+            "agent_planner",
+            "agent_reviewer",
+            "agent_spec-parser",
+            "agent_tester"
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn subagent_tools_are_opt_in() -> anyhow::Result<()> {
+    let server = create_mock_chat_completions_server(Vec::new()).await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri(), false)?;
+    write_agent(codex_home.path(), "reviewer", "Reviews diffs")?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let names = list_tool_names(&mut mcp).await?;
+    assert_eq!(names, vec!["codex", "codex-reply"]);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn subagent_tool_call_returns_final_message() -> anyhow::Result<()> {
+    let server =
+        create_mock_chat_completions_server(vec![create_final_assistant_message_sse_response(
+            "LGTM",
+        )?])
+        .await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri(), true)?;
+    write_agent(codex_home.path(), "reviewer", "Reviews diffs")?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_tool_call("agent_reviewer", json!({ "prompt": "Review the diff" }))
+        .await?;
+    let JSONRPCResponse { result, .. } = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+
+    assert_eq!(
+        result.get("content"),
+        Some(&json!([{ "type": "text", "text": "LGTM" }]))
+    );
+    assert_eq!(result.get("isError"), Some(&json!(false)));
+    assert_eq!(
+        result.pointer("/structuredContent/agent"),
+        Some(&json!("reviewer"))
+    );
+    Ok(())
+}
+
+async fn list_tool_names(mcp: &mut McpProcess) -> anyhow::Result<Vec<String>> {
+    let request_id = mcp.send_list_tools_request().await?;
+    let response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let result: ListToolsResult = serde_json::from_value(response.result)?;
+    Ok(result.tools.into_iter().map(|tool| tool.name).collect())
+}
+
+fn write_agent(codex_home: &Path, name: &str, description: &str) -> std::io::Result<()> {
+    let agents_dir = codex_home.join("agents");
+    std::fs::create_dir_all(&agents_dir)?;
+    std::fs::write(
+        agents_dir.join(format!("{name}.md")),
+        format!("---\nname: {name}\ndescription: {description}\n---\n\nYou are the {name}.\n"),
+    )
+}
+
+fn create_config_toml(codex_home: &Path, server_uri: &str, mcp_tools: bool) -> std::io::Result<()> {
+    std::fs::write(
+        codex_home.join("config.toml"),
+        format!(
+            r#"
+model = "mock-model"
+approval_policy = "never"
+sandbox_mode = "read-only"
+
+model_provider = "mock_provider"
+
+[subagents]
+mcp_tools = {mcp_tools}
+
+[model_providers.mock_provider]
+name = "Mock provider for test"
+base_url = "{server_uri}/v1"
+wire_api = "chat"
+request_max_retries = 0
+stream_max_retries = 0
+"#
+        ),
+    )
+}
@@ -11,6 +11,8 @@ use crate::protocol::EventMsg;
 use crate::protocol::FileChange;
 use crate::protocol::ReviewDecision;
 use crate::protocol::SandboxPolicy;
+use crate::protocol::SubAgentOutcome;
+use crate::protocol::TokenUsage;
 use crate::protocol::TurnAbortReason;
 use mcp_types::RequestId;
 use serde::Deserialize;
@@ -195,6 +197,12 @@ pub enum ClientRequest {
         request_id: RequestId,
         params: SubagentsRunParams,
     },
+    #[serde(rename = "subagents/runMany")]
+    SubagentsRunMany {
+        #[serde(rename = "id")]
+        request_id: RequestId,
+        params: SubagentsRunManyParams,
+    },
     /// Execute a command (argv vector) under the server's sandbox.
     ExecOneOffCommand {
         #[serde(rename = "id")]
@@ -442,6 +450,10 @@ pub struct SubagentsRunParams {
     pub agent_name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub prompt: Option<String>,
+    /// Inputs attached to the first message, in the CLI's `--context`
+    /// syntax (e.g. `git_diff=main`, `files=src/**/*.rs`, `plan`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub context: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, TS)]
@@ -450,6 +462,40 @@ pub struct SubagentsRunResponse {
     pub sub_conversation_id: ConversationId,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct SubagentsRunManyParams {
+    pub conversation_id: ConversationId,
+    pub agent_names: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+    /// Inputs attached to the first message, in the CLI's `--context`
+    /// syntax (e.g. `git_diff=main`, `files=src/**/*.rs`, `plan`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub context: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct SubagentsRunManyResponse {
+    pub runs: Vec<SubagentRunSummary>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct SubagentRunSummary {
+    pub agent_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_conversation_id: Option<ConversationId>,
+    pub outcome: SubAgentOutcome,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_usage: Option<TokenUsage>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, TS)]
 #[serde(rename_all = "camelCase")]
 pub struct SubagentListAgent {
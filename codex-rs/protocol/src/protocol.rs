@@ -243,6 +243,12 @@ pub enum SandboxPolicy {
         /// writable roots on UNIX. Defaults to `false`.
         #[serde(default)]
         exclude_slash_tmp: bool,
+
+        /// When set to `true`, will NOT include the current working directory
+        /// among the default writable roots, so only `writable_roots` (and
+        /// the temp dirs) are writable. Defaults to `false`.
+        #[serde(default)]
+        exclude_cwd: bool,
     },
 }
 
@@ -300,6 +306,7 @@ impl SandboxPolicy {
             network_access: false,
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
+            exclude_cwd: false,
         }
     }
 
@@ -335,14 +342,17 @@ impl SandboxPolicy {
                 writable_roots,
                 exclude_tmpdir_env_var,
                 exclude_slash_tmp,
+                exclude_cwd,
                 network_access: _,
             } => {
                 // Start from explicitly configured writable roots.
                 let mut roots: Vec<PathBuf> = writable_roots.clone();
 
-                // Always include defaults: cwd, /tmp (if present on Unix), and
-                // on macOS, the per-user TMPDIR unless explicitly excluded.
-                roots.push(cwd.to_path_buf());
+                // Include defaults: cwd, /tmp (if present on Unix), and on
+                // macOS, the per-user TMPDIR unless explicitly excluded.
+                if !exclude_cwd {
+                    roots.push(cwd.to_path_buf());
+                }
 
                 // Include /tmp on Unix unless explicitly excluded.
                 if cfg!(unix) && !exclude_slash_tmp {
@@ -462,6 +472,11 @@ pub enum EventMsg {
     SubAgentStarted(SubAgentStartedEvent),
     SubAgentMessage(SubAgentMessageEvent),
     SubAgentCompleted(SubAgentCompletedEvent),
+    /// A subagent asked to pass control to another agent via the `handoff` tool.
+    SubAgentHandoff(SubAgentHandoffEvent),
+    /// A command, patch, approval request, or tool call from inside a
+    /// subagent's conversation, forwarded so clients can show its transcript.
+    SubAgentActivity(SubAgentActivityEvent),
 
     /// Ack the client's configure message.
     SessionConfigured(SessionConfiguredEvent),
@@ -537,6 +552,10 @@ pub struct SubAgentStartedEvent {
     pub parent_submit_id: String,
     pub sub_conversation_id: ConversationId,
     pub model: Option<String>,
+    /// Set when this run continues a handoff; the previous hop's
+    /// `sub_conversation_id`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub handoff_from: Option<ConversationId>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
@@ -546,7 +565,7 @@ pub struct SubAgentMessageEvent {
     pub message: String,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, TS)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, TS)]
 #[serde(rename_all = "lowercase")]
 pub enum SubAgentOutcome {
     Success,
@@ -564,6 +583,28 @@ pub struct SubAgentCompletedEvent {
     pub model: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub duration_ms: Option<u64>,
+    /// Agent that takes over from this run, if it handed off.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub handoff_to: Option<String>,
+    /// Tokens used by this run's child conversation across all its turns.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_usage: Option<TokenUsage>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, TS)]
+pub struct SubAgentHandoffEvent {
+    pub agent_name: String,
+    pub sub_conversation_id: ConversationId,
+    pub target_agent: String,
+    pub note: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, TS)]
+pub struct SubAgentActivityEvent {
+    pub agent_name: String,
+    pub sub_conversation_id: ConversationId,
+    /// The event as the subagent's conversation emitted it.
+    pub msg: Box<EventMsg>,
 }
 
 // Individual event payload types matching each `EventMsg` variant.
@@ -583,7 +624,7 @@ pub struct TaskStartedEvent {
     pub model_context_window: Option<u64>,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, Default, TS)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, Default, TS)]
 pub struct TokenUsage {
     pub input_tokens: u64,
     pub cached_input_tokens: u64,
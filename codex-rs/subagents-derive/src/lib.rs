@@ -7,6 +7,7 @@ use syn::Ident;
 use syn::LitStr;
 use syn::Result;
 use syn::Token;
+use syn::Type;
 use syn::parse::Parse;
 use syn::parse_macro_input;
 
@@ -18,6 +19,9 @@ struct SubagentArgs {
     tools: Vec<Expr>,
     keywords: Vec<Expr>,
     instructions: Option<Expr>,
+    input: Option<Type>,
+    output: Option<Type>,
+    scratchpad: Option<LitStr>,
 }
 
 impl SubagentArgs {
@@ -45,6 +49,18 @@ impl SubagentArgs {
                             let expr: Expr = input.parse()?;
                             args.instructions = Some(expr);
                         }
+                        "input" => {
+                            let ty: Type = input.parse()?;
+                            args.input = Some(ty);
+                        }
+                        "output" => {
+                            let ty: Type = input.parse()?;
+                            args.output = Some(ty);
+                        }
+                        "scratchpad" => {
+                            let lit: LitStr = input.parse()?;
+                            args.scratchpad = Some(lit);
+                        }
                         "tools" => {
                             let content;
                             syn::bracketed!(content in input);
@@ -132,6 +148,17 @@ pub fn derive_subagent(input: TokenStream) -> TokenStream {
 
     let ident = &input.ident;
 
+    let typed_tokens = match typed_subagent_tokens(
+        ident,
+        &name,
+        args.input.as_ref(),
+        args.output.as_ref(),
+        args.scratchpad.as_ref(),
+    ) {
+        Ok(tokens) => tokens,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
     let builder_tokens = quote! {
         codex_subagents::SubagentBuilder::new(#name)
             #description_tokens
@@ -156,7 +183,104 @@ pub fn derive_subagent(input: TokenStream) -> TokenStream {
                 std::borrow::Cow::Owned(Self::subagent_spec())
             }
         }
+
+        #typed_tokens
     };
 
     expanded.into()
 }
+
+/// `TypedSubagent` and `SubagentSchemas` impls for agents that declare
+/// `input` and `output`. `prepare` reads the input from its typed slot;
+/// `finalize` stores the output in its typed slot and, when `scratchpad` is
+/// set, as JSON under that key. `execute` is left to a `SubagentExecute` impl.
+fn typed_subagent_tokens(
+    ident: &Ident,
+    name: &LitStr,
+    input: Option<&Type>,
+    output: Option<&Type>,
+    scratchpad: Option<&LitStr>,
+) -> Result<proc_macro2::TokenStream> {
+    let (input, output) = match (input, output) {
+        (Some(input), Some(output)) => (input, output),
+        (None, None) => {
+            if let Some(scratchpad) = scratchpad {
+                return Err(syn::Error::new_spanned(
+                    scratchpad,
+                    "`scratchpad` requires `input` and `output` in #[subagent(...)]",
+                ));
+            }
+            return Ok(quote! {});
+        }
+        (Some(ty), None) | (None, Some(ty)) => {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "`input` and `output` must be set together in #[subagent(...)]",
+            ));
+        }
+    };
+
+    let scratchpad_tokens = scratchpad.map(|key| {
+        quote! {
+            ctx.set_scratchpad(
+                #key,
+                codex_subagents::__derive::serde_json::to_value(&output)
+                    .map_err(codex_subagents::TaskContextError::Serialization)?,
+            )?;
+        }
+    });
+
+    Ok(quote! {
+        impl codex_subagents::TypedSubagent for #ident {
+            type Input = #input;
+            type Output = #output;
+
+            fn prepare(
+                &self,
+                ctx: &codex_subagents::TaskContext,
+            ) -> codex_subagents::__derive::anyhow::Result<Self::Input> {
+                ctx.get_typed::<#input>()?.ok_or_else(|| {
+                    codex_subagents::__derive::anyhow::anyhow!(
+                        "{} needs `{}` in the task context; run an agent that produces it first",
+                        #name,
+                        stringify!(#input)
+                    )
+                })
+            }
+
+            fn execute(
+                &self,
+                ctx: &mut codex_subagents::TaskContext,
+                input: Self::Input,
+            ) -> codex_subagents::__derive::anyhow::Result<Self::Output> {
+                <Self as codex_subagents::SubagentExecute<#input, #output>>::run(self, ctx, input)
+            }
+
+            fn finalize(
+                &self,
+                ctx: &mut codex_subagents::TaskContext,
+                output: Self::Output,
+            ) -> codex_subagents::__derive::anyhow::Result<()> {
+                #scratchpad_tokens
+                ctx.insert_typed(output)?;
+                Ok(())
+            }
+        }
+
+        impl codex_subagents::SubagentSchemas for #ident {
+            fn input_schema() -> codex_subagents::__derive::serde_json::Value {
+                codex_subagents::__derive::serde_json::to_value(
+                    codex_subagents::__derive::schemars::schema_for!(#input),
+                )
+                .expect("JSON Schema of #[derive(Subagent)] input serializes")
+            }
+
+            fn output_schema() -> codex_subagents::__derive::serde_json::Value {
+                codex_subagents::__derive::serde_json::to_value(
+                    codex_subagents::__derive::schemars::schema_for!(#output),
+                )
+                .expect("JSON Schema of #[derive(Subagent)] output serializes")
+            }
+        }
+    })
+}
@@ -2,4 +2,6 @@
 fn derive_subagent_compiles() {
     let t = trybuild::TestCases::new();
     t.pass("tests/trybuild/basic.rs");
+    t.pass("tests/trybuild/typed.rs");
+    t.compile_fail("tests/trybuild/fail/*.rs");
 }
@@ -0,0 +1,19 @@
+use codex_subagents_derive::Subagent;
+
+#[derive(Subagent)]
+#[subagent(
+    name = "planner",
+    instructions = "Plan changes.",
+    output = codex_subagents::pipeline::ProposedChanges
+)]
+struct Planner;
+
+#[derive(Subagent)]
+#[subagent(
+    name = "recorder",
+    instructions = "Record notes.",
+    scratchpad = "subagents.recorder.output"
+)]
+struct Recorder;
+
+fn main() {}
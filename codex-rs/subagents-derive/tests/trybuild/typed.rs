@@ -0,0 +1,75 @@
+use codex_subagents::SubagentExecute;
+use codex_subagents::SubagentSchemas;
+use codex_subagents::TaskContext;
+use codex_subagents::TypedSubagent;
+use codex_subagents::pipeline::AcceptanceCriterion;
+use codex_subagents::pipeline::ProposedChange;
+use codex_subagents::pipeline::ProposedChanges;
+use codex_subagents::pipeline::Requirement;
+use codex_subagents::pipeline::RequirementsSpec;
+use codex_subagents_derive::Subagent;
+
+#[derive(Subagent)]
+#[subagent(
+    name = "planner",
+    instructions = "Plan one change per requirement.",
+    input = RequirementsSpec,
+    output = ProposedChanges,
+    scratchpad = "subagents.planner.output"
+)]
+struct Planner;
+
+impl SubagentExecute<RequirementsSpec, ProposedChanges> for Planner {
+    fn run(
+        &self,
+        _ctx: &mut TaskContext,
+        input: RequirementsSpec,
+    ) -> anyhow::Result<ProposedChanges> {
+        let changes = input
+            .requirements
+            .iter()
+            .map(|requirement| {
+                ProposedChange::new(&requirement.id, &requirement.summary, vec![], vec![])
+            })
+            .collect();
+        Ok(ProposedChanges::new(input.title, changes))
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    let agent = Planner;
+    let mut ctx = TaskContext::new();
+
+    let err = agent.prepare(&ctx).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "planner needs `RequirementsSpec` in the task context; run an agent that produces it first"
+    );
+
+    ctx.insert_typed(RequirementsSpec::new(
+        "Export",
+        "overview",
+        vec![Requirement::new(
+            "REQ-001",
+            "Export as CSV",
+            vec![AcceptanceCriterion::new(None, "CSV is written")],
+            vec![],
+        )],
+    ))?;
+    let input = agent.prepare(&ctx)?;
+    let output = agent.execute(&mut ctx, input)?;
+    agent.finalize(&mut ctx, output)?;
+
+    let stored = ctx.get_typed::<ProposedChanges>()?.expect("typed output");
+    assert_eq!(stored.changes[0].requirement_id, "REQ-001");
+    let scratchpad = ctx
+        .get_scratchpad("subagents.planner.output")?
+        .expect("scratchpad");
+    assert_eq!(scratchpad["changes"][0]["summary"], "Export as CSV");
+
+    let input_schema = Planner::input_schema();
+    assert_eq!(input_schema["title"], "RequirementsSpec");
+    assert!(input_schema["properties"]["requirements"].is_object());
+    assert_eq!(Planner::output_schema()["title"], "ProposedChanges");
+    Ok(())
+}
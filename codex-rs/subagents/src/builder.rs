@@ -1,3 +1,4 @@
+use crate::context::ContextAttachment;
 use crate::error::SubagentValidationError;
 use crate::parser::validate_agent_name;
 use crate::spec::AgentSource;
@@ -7,6 +8,7 @@ use crate::spec::SubagentSpec;
 use sha1::Digest;
 use sha1::Sha1;
 use std::collections::BTreeMap;
+use std::path::Component;
 use std::path::PathBuf;
 
 #[derive(Debug)]
@@ -17,6 +19,12 @@ pub struct SubagentBuilder {
     model_config: Option<ModelBinding>,
     tools: Vec<String>,
     keywords: Vec<String>,
+    handoff_to: Vec<String>,
+    memory: bool,
+    context: Vec<ContextAttachment>,
+    cwd: Option<PathBuf>,
+    writable_paths: Vec<PathBuf>,
+    env: BTreeMap<String, String>,
     instructions: Option<String>,
     source: AgentSource,
     source_path: Option<PathBuf>,
@@ -31,6 +39,12 @@ impl Default for SubagentBuilder {
             model_config: None,
             tools: Vec::new(),
             keywords: Vec::new(),
+            handoff_to: Vec::new(),
+            memory: false,
+            context: Vec::new(),
+            cwd: None,
+            writable_paths: Vec::new(),
+            env: BTreeMap::new(),
             instructions: None,
             source: AgentSource::Inline,
             source_path: None,
@@ -47,6 +61,12 @@ impl SubagentBuilder {
             model_config: None,
             tools: Vec::new(),
             keywords: Vec::new(),
+            handoff_to: Vec::new(),
+            memory: false,
+            context: Vec::new(),
+            cwd: None,
+            writable_paths: Vec::new(),
+            env: BTreeMap::new(),
             instructions: None,
             source: AgentSource::Inline,
             source_path: None,
@@ -86,6 +106,45 @@ impl SubagentBuilder {
         self
     }
 
+    pub fn handoff_to<I, S>(mut self, agents: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.handoff_to = agents.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn memory(mut self, memory: bool) -> Self {
+        self.memory = memory;
+        self
+    }
+
+    pub fn context(mut self, context: Vec<ContextAttachment>) -> Self {
+        self.context = context;
+        self
+    }
+
+    /// Working directory relative to the project root.
+    pub fn cwd(mut self, cwd: impl Into<Option<PathBuf>>) -> Self {
+        self.cwd = cwd.into();
+        self
+    }
+
+    pub fn writable_paths<I, P>(mut self, paths: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        self.writable_paths = paths.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn env(mut self, env: BTreeMap<String, String>) -> Self {
+        self.env = env;
+        self
+    }
+
     pub fn instructions(mut self, instructions: impl Into<String>) -> Self {
         self.instructions = Some(instructions.into());
         self
@@ -113,6 +172,17 @@ impl SubagentBuilder {
 
         let tools = normalize_unique(self.tools, false)?;
         let keywords = normalize_unique(self.keywords, true)?;
+        let handoff_to = normalize_handoff_targets(&name, self.handoff_to)?;
+        let cwd = self
+            .cwd
+            .map(|path| normalize_scoped_path("cwd", path))
+            .transpose()?;
+        let writable_paths = self
+            .writable_paths
+            .into_iter()
+            .map(|path| normalize_scoped_path("writable_paths", path))
+            .collect::<Result<Vec<_>, _>>()?;
+        validate_env(&self.env)?;
 
         let mut model_config = self.model_config;
         if let Some(binding) = model_config.as_mut() {
@@ -139,7 +209,13 @@ impl SubagentBuilder {
             .model(display_model)
             .model_config(model_config.clone())
             .tools(tools)
-            .keywords(keywords);
+            .keywords(keywords)
+            .handoff_to(handoff_to)
+            .memory(self.memory)
+            .context(self.context)
+            .cwd(cwd)
+            .writable_paths(writable_paths)
+            .env(self.env);
 
         let mut hasher = Sha1::new();
         hasher.update(name.as_bytes());
@@ -170,6 +246,25 @@ impl SubagentBuilder {
         for keyword in &metadata.keywords {
             hasher.update(keyword.as_bytes());
         }
+        for target in &metadata.handoff_to {
+            hasher.update(target.as_bytes());
+        }
+        if metadata.memory {
+            hasher.update(b"memory");
+        }
+        for attachment in &metadata.context {
+            hasher.update(attachment.to_string().as_bytes());
+        }
+        if let Some(cwd) = metadata.cwd.as_ref() {
+            hasher.update(cwd.to_string_lossy().as_bytes());
+        }
+        for path in &metadata.writable_paths {
+            hasher.update(path.to_string_lossy().as_bytes());
+        }
+        for (key, value) in &metadata.env {
+            hasher.update(key.as_bytes());
+            hasher.update(value.as_bytes());
+        }
         let hash = format!("{:x}", hasher.finalize());
 
         Ok(SubagentSpec {
@@ -208,3 +303,60 @@ fn normalize_unique(
     }
     Ok(output)
 }
+
+fn normalize_handoff_targets(
+    name: &str,
+    targets: Vec<String>,
+) -> Result<Vec<String>, SubagentValidationError> {
+    let mut output: Vec<String> = Vec::new();
+    for raw in targets {
+        let target = raw.trim().to_string();
+        validate_agent_name(&target)?;
+        if target == name {
+            return Err(SubagentValidationError::SelfHandoff(target));
+        }
+        if output.contains(&target) {
+            return Err(SubagentValidationError::DuplicateHandoffTarget(target));
+        }
+        output.push(target);
+    }
+    Ok(output)
+}
+
+/// Keep `path` relative to the project root: no absolute paths and no `..`.
+/// `.` components are dropped, so `./web/` becomes `web` and `.` the root.
+fn normalize_scoped_path(
+    field: &'static str,
+    path: PathBuf,
+) -> Result<PathBuf, SubagentValidationError> {
+    let invalid = |reason| SubagentValidationError::InvalidScopedPath {
+        field,
+        path: path.clone(),
+        reason,
+    };
+    if path.as_os_str().is_empty() {
+        return Err(invalid("must not be empty"));
+    }
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => normalized.push(part),
+            Component::CurDir => {}
+            Component::ParentDir => return Err(invalid("must not contain `..`")),
+            Component::RootDir | Component::Prefix(_) => {
+                return Err(invalid("must be relative to the project root"));
+            }
+        }
+    }
+    Ok(normalized)
+}
+
+fn validate_env(env: &BTreeMap<String, String>) -> Result<(), SubagentValidationError> {
+    match env
+        .keys()
+        .find(|key| key.is_empty() || key.contains(['=', '\0']) || key.trim() != key.as_str())
+    {
+        Some(key) => Err(SubagentValidationError::InvalidEnvName(key.clone())),
+        None => Ok(()),
+    }
+}
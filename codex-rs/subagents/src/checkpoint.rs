@@ -0,0 +1,457 @@
+//! Checkpoints that let a multi-step run survive a process restart.
+//!
+//! Typed slots hold arbitrary `Any` values, so only slots whose type is
+//! registered in [`SlotCodecs`] are persisted; scratchpads and diagnostics are
+//! plain data already. A [`RunStore`] keeps one directory per run holding
+//! `run.json` (the steps recorded so far) and `context.json` (the task context
+//! as of the last completed step).
+
+use crate::SubagentSpec;
+use crate::error::CheckpointError;
+use crate::error::TaskContextError;
+use crate::pipeline::ProposedChanges;
+use crate::pipeline::RequirementsSpec;
+use crate::pipeline::ReviewFindings;
+use crate::pipeline::TestResults;
+use crate::project::ProjectRoot;
+use crate::spec_parser::SpecParserSeed;
+use crate::task_context::DiagnosticEntry;
+use crate::task_context::TaskContext;
+use crate::workflow::StepRecord;
+use serde::Deserialize;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::any::Any;
+use std::any::type_name;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use time::OffsetDateTime;
+
+const MANIFEST_FILE: &str = "run.json";
+const CONTEXT_FILE: &str = "context.json";
+
+type EncodeFn = fn(&TaskContext) -> Result<Option<serde_json::Value>, TaskContextError>;
+type DecodeFn = fn(&TaskContext, serde_json::Value) -> Result<(), TaskContextError>;
+
+struct SlotCodec {
+    name: String,
+    type_name: &'static str,
+    encode: EncodeFn,
+    decode: DecodeFn,
+}
+
+/// Serde codecs for typed slots, keyed by a stable name that is written to
+/// the checkpoint instead of the Rust type name.
+#[derive(Default)]
+pub struct SlotCodecs {
+    codecs: Vec<SlotCodec>,
+}
+
+impl std::fmt::Debug for SlotCodecs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.codecs.iter().map(|codec| &codec.name))
+            .finish()
+    }
+}
+
+impl SlotCodecs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Codecs for the slots the built-in pipeline agents read and write.
+    pub fn pipeline() -> Self {
+        Self::new()
+            .register::<SubagentSpec>("subagent_spec")
+            .register::<ProjectRoot>("project_root")
+            .register::<SpecParserSeed>("spec_parser_seed")
+            .register::<RequirementsSpec>("requirements_spec")
+            .register::<ProposedChanges>("proposed_changes")
+            .register::<TestResults>("test_results")
+            .register::<ReviewFindings>("review_findings")
+    }
+
+    /// Persist slots of type `T` under `name`. Registering a name again
+    /// replaces the earlier codec.
+    pub fn register<T>(mut self, name: impl Into<String>) -> Self
+    where
+        T: Serialize + DeserializeOwned + Any + Send + Sync + 'static,
+    {
+        let name = name.into();
+        self.codecs.retain(|codec| codec.name != name);
+        self.codecs.push(SlotCodec {
+            name,
+            type_name: type_name::<T>(),
+            encode: encode_slot::<T>,
+            decode: decode_slot::<T>,
+        });
+        self
+    }
+
+    pub(crate) fn encode(
+        &self,
+        ctx: &TaskContext,
+    ) -> Result<BTreeMap<String, serde_json::Value>, TaskContextError> {
+        let mut slots = BTreeMap::new();
+        for codec in &self.codecs {
+            if let Some(value) = (codec.encode)(ctx)? {
+                slots.insert(codec.name.clone(), value);
+            }
+        }
+        Ok(slots)
+    }
+
+    pub(crate) fn decode(
+        &self,
+        ctx: &TaskContext,
+        name: &str,
+        value: serde_json::Value,
+    ) -> Result<(), TaskContextError> {
+        let codec = self
+            .codecs
+            .iter()
+            .find(|codec| codec.name == name)
+            .ok_or_else(|| TaskContextError::UnknownSlotCodec(name.to_string()))?;
+        (codec.decode)(ctx, value)
+    }
+
+    pub(crate) fn handles(&self, type_name: &str) -> bool {
+        self.codecs.iter().any(|codec| codec.type_name == type_name)
+    }
+}
+
+fn encode_slot<T>(ctx: &TaskContext) -> Result<Option<serde_json::Value>, TaskContextError>
+where
+    T: Serialize + Any + Send + Sync + 'static,
+{
+    ctx.with_typed(|value: &T| serde_json::to_value(value))?
+        .transpose()
+        .map_err(TaskContextError::Serialization)
+}
+
+fn decode_slot<T>(ctx: &TaskContext, value: serde_json::Value) -> Result<(), TaskContextError>
+where
+    T: DeserializeOwned + Any + Send + Sync + 'static,
+{
+    let value: T = serde_json::from_value(value).map_err(TaskContextError::Serialization)?;
+    ctx.insert_typed(value)
+}
+
+/// Serialized [`TaskContext`], produced by [`TaskContext::checkpoint`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TaskContextCheckpoint {
+    /// Slots with a registered codec, keyed by codec name.
+    pub typed_slots: BTreeMap<String, serde_json::Value>,
+    /// Type names of slots without a codec. They are not restored.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dropped_slots: Vec<String>,
+    pub scratchpads: BTreeMap<String, serde_json::Value>,
+    pub diagnostics: Vec<DiagnosticEntry>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+/// What a saved run executed, stored as `run.json`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RunManifest {
+    pub id: String,
+    pub workflow: String,
+    pub inputs: BTreeMap<String, String>,
+    pub status: RunStatus,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+    /// Steps recorded up to the last checkpoint, in the order the workflow
+    /// engine produced them.
+    pub steps: Vec<StepRecord>,
+}
+
+/// Saved runs under a directory, usually `$CODEX_HOME/subagents/runs`.
+#[derive(Debug, Clone)]
+pub struct RunStore {
+    root: PathBuf,
+}
+
+impl RunStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn for_codex_home(codex_home: &Path) -> Self {
+        Self::new(codex_home.join("subagents").join("runs"))
+    }
+
+    pub fn run_dir(&self, id: &str) -> PathBuf {
+        self.root.join(id)
+    }
+
+    pub fn load(&self, id: &str) -> Result<(RunManifest, TaskContextCheckpoint), CheckpointError> {
+        let dir = self.run_dir(id);
+        let is_plain_name =
+            !id.is_empty() && Path::new(id).file_name().and_then(|name| name.to_str()) == Some(id);
+        if !is_plain_name || !dir.join(MANIFEST_FILE).is_file() {
+            return Err(CheckpointError::UnknownRun(id.to_string()));
+        }
+        let manifest = read_json(&dir.join(MANIFEST_FILE))?;
+        let context = read_json(&dir.join(CONTEXT_FILE))?;
+        Ok((manifest, context))
+    }
+
+    /// Writes the context first: if the process dies in between, the old step
+    /// list is kept and the interrupted step runs again on resume.
+    pub fn save(
+        &self,
+        manifest: &RunManifest,
+        context: &TaskContextCheckpoint,
+    ) -> Result<(), CheckpointError> {
+        let dir = self.run_dir(&manifest.id);
+        fs::create_dir_all(&dir).map_err(|source| CheckpointError::Io {
+            path: dir.clone(),
+            source,
+        })?;
+        write_json(&dir.join(CONTEXT_FILE), context)?;
+        write_json(&dir.join(MANIFEST_FILE), manifest)
+    }
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T, CheckpointError> {
+    let text = fs::read_to_string(path).map_err(|source| CheckpointError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    serde_json::from_str(&text).map_err(|source| CheckpointError::Parse {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), CheckpointError> {
+    let io_error = |source: io::Error| CheckpointError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    let text = serde_json::to_string_pretty(value).map_err(TaskContextError::Serialization)?;
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, text).map_err(io_error)?;
+    fs::rename(&tmp, path).map_err(io_error)
+}
+
+/// A run being checkpointed: its manifest, its live task context, and where
+/// both are saved.
+#[derive(Debug)]
+pub struct RunCheckpoint {
+    store: RunStore,
+    codecs: SlotCodecs,
+    manifest: RunManifest,
+    context: TaskContext,
+}
+
+impl RunCheckpoint {
+    /// Start a new run of `workflow` and save its empty checkpoint.
+    pub fn start(
+        store: RunStore,
+        codecs: SlotCodecs,
+        workflow: impl Into<String>,
+        inputs: BTreeMap<String, String>,
+    ) -> Result<Self, CheckpointError> {
+        let now = OffsetDateTime::now_utc();
+        let mut checkpoint = Self {
+            store,
+            codecs,
+            manifest: RunManifest {
+                id: uuid::Uuid::now_v7().to_string(),
+                workflow: workflow.into(),
+                inputs,
+                status: RunStatus::Running,
+                created_at: now,
+                updated_at: now,
+                steps: Vec::new(),
+            },
+            context: TaskContext::new(),
+        };
+        checkpoint.write()?;
+        Ok(checkpoint)
+    }
+
+    /// Load run `id` with its task context restored.
+    pub fn resume(store: RunStore, codecs: SlotCodecs, id: &str) -> Result<Self, CheckpointError> {
+        let (manifest, context) = store.load(id)?;
+        let context = TaskContext::from_checkpoint(context, &codecs)?;
+        Ok(Self {
+            store,
+            codecs,
+            manifest,
+            context,
+        })
+    }
+
+    pub fn id(&self) -> &str {
+        &self.manifest.id
+    }
+
+    pub fn manifest(&self) -> &RunManifest {
+        &self.manifest
+    }
+
+    pub fn context(&self) -> &TaskContext {
+        &self.context
+    }
+
+    pub fn dir(&self) -> PathBuf {
+        self.store.run_dir(&self.manifest.id)
+    }
+
+    /// Record `steps` as completed and save the current task context.
+    pub fn save(&mut self, steps: &[StepRecord]) -> Result<(), CheckpointError> {
+        self.manifest.steps = steps.to_vec();
+        self.manifest.status = RunStatus::Running;
+        self.write()
+    }
+
+    /// Mark the run finished without touching the saved steps or context, so
+    /// a failed run resumes at the step that failed.
+    pub fn finish(&mut self, status: RunStatus) -> Result<(), CheckpointError> {
+        self.manifest.status = status;
+        self.manifest.updated_at = OffsetDateTime::now_utc();
+        let dir = self.dir();
+        write_json(&dir.join(MANIFEST_FILE), &self.manifest)
+    }
+
+    fn write(&mut self) -> Result<(), CheckpointError> {
+        self.manifest.updated_at = OffsetDateTime::now_utc();
+        let context = self.context.checkpoint(&self.codecs)?;
+        self.store.save(&self.manifest, &context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DiagnosticLevel;
+    use crate::StepResult;
+    use crate::pipeline::ChangeFile;
+    use crate::pipeline::ChangeType;
+    use crate::pipeline::ProposedChange;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Unregistered(u8);
+
+    #[test]
+    fn task_context_round_trips_registered_slots() {
+        let ctx = TaskContext::new();
+        let changes = ProposedChanges::new(
+            "add export",
+            vec![ProposedChange::new(
+                "REQ-1",
+                "Export CSV",
+                vec![ChangeFile::new(
+                    "src/export.rs",
+                    ChangeType::Create,
+                    "new module",
+                )],
+                Vec::new(),
+            )],
+        );
+        ctx.insert_typed(changes.clone()).expect("insert");
+        ctx.insert_typed(Unregistered(7)).expect("insert");
+        ctx.set_scratchpad(
+            "subagents.code_writer.output",
+            serde_json::json!({"ok": true}),
+        )
+        .expect("scratchpad");
+        ctx.push_diagnostic(DiagnosticLevel::Warn, "slow formatter")
+            .expect("diagnostic");
+
+        let checkpoint = ctx.checkpoint(&SlotCodecs::pipeline()).expect("checkpoint");
+        assert_eq!(
+            checkpoint.typed_slots.keys().collect::<Vec<_>>(),
+            vec!["proposed_changes"]
+        );
+        assert_eq!(
+            checkpoint.dropped_slots,
+            vec![type_name::<Unregistered>().to_string()]
+        );
+
+        let text = serde_json::to_string(&checkpoint).expect("serialize");
+        let restored = TaskContext::from_checkpoint(
+            serde_json::from_str(&text).expect("deserialize"),
+            &SlotCodecs::pipeline(),
+        )
+        .expect("restore");
+        assert_eq!(
+            restored.get_typed::<ProposedChanges>().expect("slot"),
+            Some(changes)
+        );
+        assert_eq!(restored.get_typed::<Unregistered>().expect("slot"), None);
+        assert_eq!(
+            restored
+                .get_scratchpad("subagents.code_writer.output")
+                .expect("scratchpad"),
+            Some(serde_json::json!({"ok": true}))
+        );
+        assert_eq!(restored.diagnostics().expect("diagnostics").len(), 1);
+
+        let err = TaskContext::from_checkpoint(checkpoint, &SlotCodecs::new())
+            .expect_err("missing codec");
+        assert!(
+            matches!(err, TaskContextError::UnknownSlotCodec(name) if name == "proposed_changes")
+        );
+    }
+
+    #[test]
+    fn run_checkpoints_resume_from_saved_steps() {
+        let home = TempDir::new().expect("tempdir");
+        let store = RunStore::for_codex_home(home.path());
+        let inputs = BTreeMap::from([("issue".to_string(), "crash".to_string())]);
+        let mut run =
+            RunCheckpoint::start(store.clone(), SlotCodecs::pipeline(), "fix-loop", inputs)
+                .expect("start");
+        assert!(
+            run.dir()
+                .starts_with(home.path().join("subagents").join("runs"))
+        );
+
+        run.context()
+            .set_scratchpad("workflow.steps.triage", serde_json::json!("found it"))
+            .expect("scratchpad");
+        let steps = vec![StepRecord {
+            step_id: "triage".to_string(),
+            agent: Some("triage".to_string()),
+            attempt: 1,
+            result: StepResult::succeeded(Some("found it".to_string())),
+        }];
+        run.save(&steps).expect("save");
+        run.finish(RunStatus::Failed).expect("finish");
+
+        let resumed =
+            RunCheckpoint::resume(store.clone(), SlotCodecs::pipeline(), run.id()).expect("resume");
+        assert_eq!(resumed.manifest().workflow, "fix-loop");
+        assert_eq!(resumed.manifest().status, RunStatus::Failed);
+        assert_eq!(resumed.manifest().steps, steps);
+        assert_eq!(
+            resumed
+                .context()
+                .get_scratchpad("workflow.steps.triage")
+                .expect("scratchpad"),
+            Some(serde_json::json!("found it"))
+        );
+
+        for id in ["missing", "../runs", ""] {
+            assert!(matches!(
+                RunCheckpoint::resume(store.clone(), SlotCodecs::pipeline(), id),
+                Err(CheckpointError::UnknownRun(_))
+            ));
+        }
+    }
+}
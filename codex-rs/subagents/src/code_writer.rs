@@ -10,6 +10,7 @@ use crate::pipeline::FormatterRun;
 use crate::pipeline::ProposedChanges;
 use crate::pipeline::RequirementsSpec;
 use crate::pipeline::derive_changes_from_spec;
+use crate::project::ProjectRoot;
 use anyhow::Result;
 use anyhow::anyhow;
 use serde::Serialize;
@@ -92,7 +93,7 @@ impl TypedSubagent for CodeWriterSubagent {
     }
 
     fn execute(&self, ctx: &mut TaskContext, input: Self::Input) -> Result<Self::Output> {
-        let changes = derive_changes_from_spec(&input);
+        let changes = derive_changes_from_spec(&ProjectRoot::resolve(ctx)?, &input);
         ctx.push_diagnostic(
             DiagnosticLevel::Info,
             format!("Drafted {} planned changes", changes.changes.len()),
@@ -119,6 +120,7 @@ mod tests {
     use super::*;
     use crate::TaskContext;
     use crate::pipeline::AcceptanceCriterion;
+    use crate::pipeline::ChangeType;
     use crate::pipeline::Requirement;
     use crate::pipeline::RequirementsSpec;
     use pretty_assertions::assert_eq;
@@ -137,12 +139,21 @@ mod tests {
             )],
         );
         ctx.insert_typed(spec.clone()).unwrap();
+        let project = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(project.path().join("src")).unwrap();
+        std::fs::write(project.path().join("src/lib.rs"), "").unwrap();
+        ctx.insert_typed(ProjectRoot(project.path().to_path_buf()))
+            .unwrap();
 
         let agent = CodeWriterSubagent;
         let input = agent.prepare(&ctx).unwrap();
         assert_eq!(input.requirements.len(), 1);
         let output = agent.execute(&mut ctx, input).unwrap();
         assert_eq!(output.changes.changes.len(), 1);
+        assert_eq!(
+            output.changes.changes[0].files[0].change_type,
+            ChangeType::Modify
+        );
         agent.finalize(&mut ctx, output).unwrap();
     }
 }
@@ -0,0 +1,261 @@
+use crate::error::SubagentValidationError;
+use serde::Deserialize;
+use serde::Serialize;
+use std::fmt;
+use std::str::FromStr;
+
+/// Input attached to a child's first user message when it is spawned.
+///
+/// In frontmatter each entry is either a bare flag (`git_diff`, `plan`) or a
+/// single-key map (`git_diff: main`, `files: ["src/**/*.rs"]`,
+/// `parent_last_messages: 3`). The CLI and MCP flags use the `key=value`
+/// form parsed by [`FromStr`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "RawAttachment", into = "RawAttachment")]
+pub enum ContextAttachment {
+    /// Working tree diff against `HEAD`, or against `base` when set.
+    GitDiff { base: Option<String> },
+    /// Files matching any of the globs, relative to the working directory.
+    Files(Vec<String>),
+    /// The parent conversation's last `n` user and agent messages.
+    ParentLastMessages(usize),
+    /// The parent's current plan, as last set through `update_plan`.
+    Plan,
+}
+
+impl ContextAttachment {
+    fn validate(self) -> Result<Self, SubagentValidationError> {
+        let invalid = |reason: &str| Err(SubagentValidationError::InvalidContext(reason.into()));
+        match &self {
+            ContextAttachment::GitDiff { base: Some(base) } if base.trim().is_empty() => {
+                invalid("git_diff base must be a non-empty ref")
+            }
+            ContextAttachment::Files(globs) if globs.is_empty() => {
+                invalid("files needs at least one glob")
+            }
+            ContextAttachment::Files(globs) if globs.iter().any(|glob| glob.trim().is_empty()) => {
+                invalid("files globs must be non-empty strings")
+            }
+            ContextAttachment::ParentLastMessages(0) => {
+                invalid("parent_last_messages must be at least 1")
+            }
+            _ => Ok(self),
+        }
+    }
+}
+
+impl fmt::Display for ContextAttachment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContextAttachment::GitDiff { base: None } => f.write_str("git_diff"),
+            ContextAttachment::GitDiff { base: Some(base) } => write!(f, "git_diff={base}"),
+            ContextAttachment::Files(globs) => write!(f, "files={}", globs.join(",")),
+            ContextAttachment::ParentLastMessages(n) => write!(f, "parent_last_messages={n}"),
+            ContextAttachment::Plan => f.write_str("plan"),
+        }
+    }
+}
+
+/// Parses the flag syntax: `git_diff`, `git_diff=<base>`,
+/// `files=<glob>[,<glob>...]`, `parent_last_messages=<n>`, or `plan`.
+impl FromStr for ContextAttachment {
+    type Err = SubagentValidationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (key, value) = match s.split_once('=') {
+            Some((key, value)) => (key.trim(), Some(value.trim())),
+            None => (s, None),
+        };
+        let attachment = match (key, value) {
+            ("git_diff", None) => ContextAttachment::GitDiff { base: None },
+            ("git_diff", Some(base)) => ContextAttachment::GitDiff {
+                base: Some(base.to_string()),
+            },
+            ("files", Some(globs)) => {
+                ContextAttachment::Files(globs.split(',').map(|g| g.trim().to_string()).collect())
+            }
+            ("parent_last_messages", Some(n)) => {
+                let n = n.parse().map_err(|_| {
+                    SubagentValidationError::InvalidContext(format!(
+                        "parent_last_messages expects a number, got `{n}`"
+                    ))
+                })?;
+                ContextAttachment::ParentLastMessages(n)
+            }
+            ("plan", None) => ContextAttachment::Plan,
+            ("files" | "parent_last_messages", None) => {
+                return Err(SubagentValidationError::InvalidContext(format!(
+                    "`{key}` needs a value, e.g. `{key}=...`"
+                )));
+            }
+            ("plan", Some(_)) => {
+                return Err(SubagentValidationError::InvalidContext(
+                    "`plan` does not take a value".to_string(),
+                ));
+            }
+            _ => {
+                return Err(SubagentValidationError::InvalidContext(format!(
+                    "unknown context attachment `{key}`; expected git_diff, files, parent_last_messages, or plan"
+                )));
+            }
+        };
+        attachment.validate()
+    }
+}
+
+/// Wire shape shared by YAML frontmatter, TOML and JSON agent definitions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum RawAttachment {
+    Flag(String),
+    Table(RawTable),
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawTable {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    git_diff: Option<GitDiffValue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    files: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    parent_last_messages: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    plan: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum GitDiffValue {
+    Enabled(bool),
+    Base(String),
+}
+
+impl TryFrom<RawAttachment> for ContextAttachment {
+    type Error = SubagentValidationError;
+
+    fn try_from(raw: RawAttachment) -> Result<Self, Self::Error> {
+        let table = match raw {
+            RawAttachment::Flag(flag) => return flag.parse(),
+            RawAttachment::Table(table) => table,
+        };
+        let mut entries = Vec::new();
+        match table.git_diff {
+            Some(GitDiffValue::Enabled(true)) => {
+                entries.push(ContextAttachment::GitDiff { base: None })
+            }
+            Some(GitDiffValue::Base(base)) => {
+                entries.push(ContextAttachment::GitDiff { base: Some(base) })
+            }
+            Some(GitDiffValue::Enabled(false)) | None => {}
+        }
+        if let Some(globs) = table.files {
+            entries.push(ContextAttachment::Files(globs));
+        }
+        if let Some(n) = table.parent_last_messages {
+            entries.push(ContextAttachment::ParentLastMessages(n));
+        }
+        if table.plan == Some(true) {
+            entries.push(ContextAttachment::Plan);
+        }
+        match (entries.pop(), entries.is_empty()) {
+            (Some(attachment), true) => attachment.validate(),
+            _ => Err(SubagentValidationError::InvalidContext(
+                "each context entry must set exactly one attachment".to_string(),
+            )),
+        }
+    }
+}
+
+impl From<ContextAttachment> for RawAttachment {
+    fn from(attachment: ContextAttachment) -> Self {
+        match attachment {
+            ContextAttachment::GitDiff { base: None } | ContextAttachment::Plan => {
+                RawAttachment::Flag(attachment.to_string())
+            }
+            ContextAttachment::GitDiff { base: Some(base) } => RawAttachment::Table(RawTable {
+                git_diff: Some(GitDiffValue::Base(base)),
+                ..RawTable::default()
+            }),
+            ContextAttachment::Files(globs) => RawAttachment::Table(RawTable {
+                files: Some(globs),
+                ..RawTable::default()
+            }),
+            ContextAttachment::ParentLastMessages(n) => RawAttachment::Table(RawTable {
+                parent_last_messages: Some(n),
+                ..RawTable::default()
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_flag_syntax() {
+        assert_eq!(
+            "git_diff".parse::<ContextAttachment>().unwrap(),
+            ContextAttachment::GitDiff { base: None }
+        );
+        assert_eq!(
+            "git_diff=main".parse::<ContextAttachment>().unwrap(),
+            ContextAttachment::GitDiff {
+                base: Some("main".to_string())
+            }
+        );
+        assert_eq!(
+            "files=src/**/*.rs, Cargo.toml"
+                .parse::<ContextAttachment>()
+                .unwrap(),
+            ContextAttachment::Files(vec!["src/**/*.rs".to_string(), "Cargo.toml".to_string()])
+        );
+        assert_eq!(
+            "parent_last_messages=3"
+                .parse::<ContextAttachment>()
+                .unwrap(),
+            ContextAttachment::ParentLastMessages(3)
+        );
+        assert_eq!(
+            "plan".parse::<ContextAttachment>().unwrap(),
+            ContextAttachment::Plan
+        );
+
+        for bad in [
+            "files",
+            "parent_last_messages=0",
+            "plan=yes",
+            "diff",
+            "files=",
+        ] {
+            assert!(bad.parse::<ContextAttachment>().is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn yaml_round_trips_flags_and_tables() {
+        let yaml = "- git_diff\n- git_diff: main\n- files: [\"src/**\"]\n- parent_last_messages: 2\n- plan\n";
+        let parsed: Vec<ContextAttachment> = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(
+            parsed,
+            vec![
+                ContextAttachment::GitDiff { base: None },
+                ContextAttachment::GitDiff {
+                    base: Some("main".to_string())
+                },
+                ContextAttachment::Files(vec!["src/**".to_string()]),
+                ContextAttachment::ParentLastMessages(2),
+                ContextAttachment::Plan,
+            ]
+        );
+
+        let json = serde_json::to_string(&parsed).unwrap();
+        let reparsed: Vec<ContextAttachment> = serde_json::from_str(&json).unwrap();
+        assert_eq!(reparsed, parsed);
+
+        assert!(serde_yaml::from_str::<ContextAttachment>("{plan: true, git_diff: true}").is_err());
+    }
+}
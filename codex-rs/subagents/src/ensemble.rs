@@ -0,0 +1,249 @@
+//! Ensemble runs: several samples of one agent work on the same task in
+//! isolation, and a judge agent picks the diff to keep.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// How much of each candidate's diff the judge sees.
+const MAX_JUDGE_DIFF_BYTES: usize = 32 * 1024;
+/// Lines of test output kept per candidate.
+const TEST_OUTPUT_LINES: usize = 20;
+
+/// Result of the `--test` command in one candidate's worktree.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TestOutcome {
+    pub command: String,
+    pub passed: bool,
+    pub exit_code: Option<i32>,
+    /// The last lines of combined stdout and stderr.
+    pub output_tail: String,
+}
+
+impl TestOutcome {
+    pub fn new(command: impl Into<String>, exit_code: Option<i32>, output: &str) -> Self {
+        let lines: Vec<&str> = output.lines().collect();
+        let tail = &lines[lines.len().saturating_sub(TEST_OUTPUT_LINES)..];
+        Self {
+            command: command.into(),
+            passed: exit_code == Some(0),
+            exit_code,
+            output_tail: tail.join("\n"),
+        }
+    }
+}
+
+/// One sample, as the judge and the summary see it.
+#[derive(Debug, Clone, Default)]
+pub struct Candidate {
+    /// 1-based, as shown to the judge.
+    pub index: usize,
+    /// The model the sample ran with, when it was overridden.
+    pub model: Option<String>,
+    /// The sampling temperature the sample ran with, when it was set.
+    pub temperature: Option<f64>,
+    /// Why the run failed, if it did.
+    pub error: Option<String>,
+    pub final_message: Option<String>,
+    /// Changes against the shared starting point, as a binary git diff. Kept
+    /// as bytes so patches to files that are not UTF-8 apply unchanged.
+    pub diff: Vec<u8>,
+    pub tests: Option<TestOutcome>,
+}
+
+impl Candidate {
+    /// A candidate the judge may pick: it finished and changed something.
+    pub fn is_eligible(&self) -> bool {
+        self.error.is_none() && !self.diff.trim_ascii().is_empty()
+    }
+
+    pub fn label(&self) -> String {
+        let details: Vec<String> = self
+            .model
+            .iter()
+            .cloned()
+            .chain(self.temperature.map(|t| format!("temperature {t}")))
+            .collect();
+        if details.is_empty() {
+            format!("#{}", self.index)
+        } else {
+            format!("#{} ({})", self.index, details.join(", "))
+        }
+    }
+}
+
+/// Files touched, lines added and lines removed by a unified diff.
+pub fn diff_stat(diff: &[u8]) -> (usize, usize, usize) {
+    let mut files = 0;
+    let mut added = 0;
+    let mut removed = 0;
+    for line in diff.split(|byte| *byte == b'\n') {
+        if line.starts_with(b"diff --git ") {
+            files += 1;
+        } else if line.starts_with(b"+") && !line.starts_with(b"+++") {
+            added += 1;
+        } else if line.starts_with(b"-") && !line.starts_with(b"---") {
+            removed += 1;
+        }
+    }
+    (files, added, removed)
+}
+
+/// The judge's answer: 1-based candidate indices, best first.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct JudgeVerdict {
+    pub winner: usize,
+    #[serde(default)]
+    pub ranking: Vec<usize>,
+    #[serde(default)]
+    pub rationale: String,
+}
+
+impl JudgeVerdict {
+    /// Read the verdict from the judge's final message, which may wrap the
+    /// JSON in prose or a code fence. Returns `None` unless the winner is
+    /// one of `candidates` and eligible.
+    pub fn from_message(message: &str, candidates: &[Candidate]) -> Option<Self> {
+        let start = message.find('{')?;
+        let end = message.rfind('}')?;
+        if start > end {
+            return None;
+        }
+        let verdict: JudgeVerdict = serde_json::from_str(&message[start..=end]).ok()?;
+        candidates
+            .iter()
+            .any(|candidate| candidate.index == verdict.winner && candidate.is_eligible())
+            .then_some(verdict)
+    }
+}
+
+pub fn judge_prompt(task: &str, candidates: &[Candidate]) -> String {
+    let mut prompt = format!(
+        "Several attempts were made at the task below, each in its own copy of the repository. \
+         Compare them and pick the one that best completes the task. Weigh correctness and test \
+         results first, then scope and code quality. Do not modify any files.\n\n<task>\n{}\n</task>\n",
+        task.trim()
+    );
+    for candidate in candidates {
+        prompt.push_str(&format!("\n<candidate index=\"{}\"", candidate.index));
+        if let Some(model) = &candidate.model {
+            prompt.push_str(&format!(" model=\"{model}\""));
+        }
+        if let Some(temperature) = candidate.temperature {
+            prompt.push_str(&format!(" temperature=\"{temperature}\""));
+        }
+        prompt.push_str(">\n");
+        if let Some(error) = &candidate.error {
+            prompt.push_str(&format!("The run failed: {error}\n"));
+        }
+        match &candidate.tests {
+            Some(tests) => prompt.push_str(&format!(
+                "<tests command=\"{}\" passed=\"{}\">\n{}\n</tests>\n",
+                tests.command, tests.passed, tests.output_tail
+            )),
+            None => prompt.push_str("Tests were not run.\n"),
+        }
+        if let Some(message) = &candidate.final_message {
+            prompt.push_str(&format!(
+                "<final_message>\n{}\n</final_message>\n",
+                message.trim()
+            ));
+        }
+        prompt.push_str(&format!(
+            "<diff>\n{}\n</diff>\n",
+            judge_diff(&candidate.diff)
+        ));
+        prompt.push_str("</candidate>\n");
+    }
+    prompt.push_str(
+        "\nOnly candidates that finished and changed files can win. Reply with only JSON of the form \
+         {\"winner\": <index>, \"ranking\": [<index>, ...], \"rationale\": \"<why the winner is best>\"}.",
+    );
+    prompt
+}
+
+fn judge_diff(diff: &[u8]) -> String {
+    let diff = String::from_utf8_lossy(diff);
+    if diff.trim().is_empty() {
+        return "(no changes)".to_string();
+    }
+    if diff.len() <= MAX_JUDGE_DIFF_BYTES {
+        return diff.trim_end().to_string();
+    }
+    let mut end = MAX_JUDGE_DIFF_BYTES;
+    while !diff.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!(
+        "{}\n[… {} more bytes of diff omitted]",
+        &diff[..end],
+        diff.len() - end
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn candidate(index: usize, diff: &str) -> Candidate {
+        Candidate {
+            index,
+            diff: diff.as_bytes().to_vec(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn verdict_must_name_an_eligible_candidate() {
+        let candidates = vec![candidate(1, "diff --git a/a b/a\n+x\n"), candidate(2, "")];
+        let verdict = JudgeVerdict::from_message(
+            "```json\n{\"winner\": 1, \"ranking\": [1, 2], \"rationale\": \"Only #1 changed code.\"}\n```",
+            &candidates,
+        )
+        .unwrap();
+        assert_eq!(verdict.ranking, vec![1, 2]);
+        assert_eq!(verdict.rationale, "Only #1 changed code.");
+        assert_eq!(
+            JudgeVerdict::from_message("{\"winner\": 2}", &candidates),
+            None
+        );
+        assert_eq!(
+            JudgeVerdict::from_message("{\"winner\": 3}", &candidates),
+            None
+        );
+        assert_eq!(JudgeVerdict::from_message("I pick #1.", &candidates), None);
+    }
+
+    #[test]
+    fn prompt_includes_each_candidate_with_tests() {
+        let mut first = candidate(1, "diff --git a/src/lib.rs b/src/lib.rs\n-old\n+new\n");
+        first.model = Some("gpt-5-codex".to_string());
+        first.temperature = Some(0.8);
+        first.tests = Some(TestOutcome::new(
+            "cargo test",
+            Some(101),
+            "test a ... FAILED\n",
+        ));
+        let mut second = candidate(2, "");
+        second.error = Some("stream error".to_string());
+        assert_eq!(first.label(), "#1 (gpt-5-codex, temperature 0.8)");
+        assert_eq!(second.label(), "#2");
+
+        let prompt = judge_prompt("Rename the flag.", &[first, second]);
+        assert!(prompt.contains("<task>\nRename the flag.\n</task>"));
+        assert!(
+            prompt.contains("<candidate index=\"1\" model=\"gpt-5-codex\" temperature=\"0.8\">")
+        );
+        assert!(prompt.contains(
+            "<tests command=\"cargo test\" passed=\"false\">\ntest a ... FAILED\n</tests>"
+        ));
+        assert!(prompt.contains("The run failed: stream error\nTests were not run.\n"));
+        assert!(prompt.contains("<diff>\n(no changes)\n</diff>"));
+    }
+
+    #[test]
+    fn diff_stat_counts_files_and_lines() {
+        let diff = b"diff --git a/a b/a\n--- a/a\n+++ b/a\n-one\n+two\n+three\ndiff --git a/b b/b\n+++ b/b\n+x\n+caf\xe9\n";
+        assert_eq!(diff_stat(diff), (2, 4, 1));
+    }
+}
@@ -24,6 +24,22 @@ pub enum SubagentValidationError {
     InvalidModelParameterKey,
     #[error("model `{model}` conflicts with model_config.model `{model_config}`")]
     ConflictingModelDefinitions { model: String, model_config: String },
+    #[error("duplicate handoff_to entry `{0}`")]
+    DuplicateHandoffTarget(String),
+    #[error("agent `{0}` cannot hand off to itself")]
+    SelfHandoff(String),
+    #[error("set either instructions or instructions_file, not both")]
+    ConflictingInstructions,
+    #[error("invalid context attachment: {0}")]
+    InvalidContext(String),
+    #[error("{field} entry `{path}` {reason}")]
+    InvalidScopedPath {
+        field: &'static str,
+        path: PathBuf,
+        reason: &'static str,
+    },
+    #[error("invalid env variable name `{0}`")]
+    InvalidEnvName(String),
 }
 
 #[derive(Debug, Error)]
@@ -32,6 +48,14 @@ pub enum ParserError {
     MissingFrontmatter,
     #[error("failed to parse YAML frontmatter: {0}")]
     InvalidFrontmatter(serde_yaml::Error),
+    #[error("failed to parse TOML agent definition: {0}")]
+    InvalidToml(toml::de::Error),
+    #[error("failed to parse JSON agent definition: {0}")]
+    InvalidJson(serde_json::Error),
+    #[error("failed to read instructions file {path}: {source}")]
+    InstructionsFile { path: PathBuf, source: io::Error },
+    #[error("instructions file {path} is outside {root}")]
+    InstructionsOutsideRoot { path: PathBuf, root: PathBuf },
     #[error(transparent)]
     Io(#[from] io::Error),
     #[error(transparent)]
@@ -52,8 +76,81 @@ pub enum TaskContextError {
     SlotDowncast { expected: &'static str },
     #[error("internal state poisoned")]
     Poisoned,
-    #[error("failed to serialize debug snapshot: {0}")]
+    #[error("failed to serialize task context: {0}")]
     Serialization(serde_json::Error),
+    #[error("no codec registered for typed slot `{0}`")]
+    UnknownSlotCodec(String),
+}
+
+#[derive(Debug, Error)]
+pub enum CheckpointError {
+    #[error("no saved run `{0}`")]
+    UnknownRun(String),
+    #[error("failed to access {path}: {source}")]
+    Io { path: PathBuf, source: io::Error },
+    #[error("failed to parse {path}: {source}")]
+    Parse {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    #[error(transparent)]
+    Context(#[from] TaskContextError),
+}
+
+#[derive(Debug, Error)]
+pub enum PackError {
+    #[error("`{0}` is not a pack directory, a tarball (.tar, .tar.gz, .tgz), or a git URL")]
+    UnknownSource(String),
+    #[error("{0} has no pack.toml manifest")]
+    MissingManifest(PathBuf),
+    #[error("invalid pack manifest {path}: {reason}")]
+    InvalidManifest { path: PathBuf, reason: String },
+    #[error("pack `{pack}` has an invalid agent {path}: {source}")]
+    InvalidAgent {
+        pack: String,
+        path: PathBuf,
+        source: Box<ParserError>,
+    },
+    #[error("pack `{0}` does not contain any agents")]
+    NoAgents(String),
+    #[error("pack `{0}` is already installed")]
+    AlreadyInstalled(String),
+    #[error("pack `{0}` is not installed")]
+    NotInstalled(String),
+    #[error("source now provides pack `{found}` instead of `{expected}`")]
+    Renamed { expected: String, found: String },
+    #[error("pack `{0}` was not installed from git, so it has no revision to change")]
+    RevisionNotSupported(String),
+    #[error("`{command}` failed: {stderr}")]
+    Command { command: String, stderr: String },
+    #[error("invalid lockfile {path}: {reason}")]
+    InvalidLock { path: PathBuf, reason: String },
+    #[error("{0} is a symlink; packs may only contain regular files and directories")]
+    Symlink(PathBuf),
+    #[error("failed to access {path}: {source}")]
+    Io { path: PathBuf, source: io::Error },
+}
+
+#[derive(Debug, Error)]
+pub enum EvalError {
+    #[error("no eval cases for agent `{agent}` in {dir}")]
+    NoCases { agent: String, dir: PathBuf },
+    #[error("invalid eval case {path}: {reason}")]
+    InvalidCase { path: PathBuf, reason: String },
+    #[error("failed to access {path}: {source}")]
+    Io { path: PathBuf, source: io::Error },
+}
+
+#[derive(Debug, Error)]
+pub enum HookError {
+    #[error("{0} was not installed by codex")]
+    ForeignHook(PathBuf),
+    #[error("no codex {0} hook is installed")]
+    NotInstalled(String),
+    #[error(transparent)]
+    Validation(#[from] SubagentValidationError),
+    #[error("failed to access {path}: {source}")]
+    Io { path: PathBuf, source: io::Error },
 }
 
 #[derive(Debug, Clone)]
@@ -70,3 +167,38 @@ impl AgentParseError {
         }
     }
 }
+
+#[derive(Debug, Error)]
+pub enum WorkflowError {
+    #[error("failed to read {path}: {source}")]
+    Io { path: PathBuf, source: io::Error },
+    #[error("failed to parse workflow YAML: {0}")]
+    InvalidYaml(serde_yaml::Error),
+    #[error("workflow `{workflow}` is invalid: {reason}")]
+    Invalid { workflow: String, reason: String },
+    #[error("missing required input `{0}`")]
+    MissingInput(String),
+    #[error("unknown input `{0}`")]
+    UnknownInput(String),
+    #[error("step `{0}` is not awaiting a result")]
+    UnexpectedResult(String),
+    #[error("saved run does not match the workflow at step `{0}`")]
+    ResumeMismatch(String),
+    #[error(transparent)]
+    Checkpoint(#[from] CheckpointError),
+}
+
+#[derive(Debug, Clone)]
+pub struct WorkflowParseError {
+    pub path: PathBuf,
+    pub message: String,
+}
+
+impl WorkflowParseError {
+    pub fn new(path: PathBuf, message: impl Into<String>) -> Self {
+        Self {
+            path,
+            message: message.into(),
+        }
+    }
+}
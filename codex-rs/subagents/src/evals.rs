@@ -0,0 +1,751 @@
+//! Offline evaluation cases for agents, read from
+//! `.codex/agents/evals/<agent>/*.yaml`.
+//!
+//! A case names a fixture directory, a prompt and assertions about the run:
+//!
+//! ```yaml
+//! fixture: fixtures/todo-app   # relative to this file
+//! prompt: Add a `--verbose` flag and document it.
+//! assert:
+//!   files_changed: ["src/cli.rs", "README.md"]
+//!   commands_run: ["cargo test"]
+//!   final_message: "(?i)verbose"
+//!   json_schema: { type: object, required: [summary] }
+//! ```
+//!
+//! The runner copies the fixture to a scratch directory, runs the agent there
+//! and checks what it observed with [`EvalAssertions::check`].
+
+use crate::error::EvalError;
+use regex_lite::Regex;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde_json::Value;
+use sha2::Digest;
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use wildmatch::WildMatch;
+
+/// Directory under an agents directory that holds one folder of cases per
+/// agent.
+pub const EVALS_DIR: &str = "evals";
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CaseFile {
+    name: Option<String>,
+    fixture: Option<PathBuf>,
+    prompt: String,
+    #[serde(default, rename = "assert")]
+    assertions: EvalAssertions,
+}
+
+#[derive(Debug, Clone)]
+pub struct EvalCase {
+    pub agent: String,
+    /// `name:` from the file, or the file stem.
+    pub name: String,
+    pub path: PathBuf,
+    /// Absolute fixture directory; `None` starts from an empty directory.
+    pub fixture: Option<PathBuf>,
+    pub prompt: String,
+    pub assertions: EvalAssertions,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EvalAssertions {
+    /// Globs, relative to the fixture root, that must each match a file the
+    /// run added, modified or deleted.
+    #[serde(default)]
+    pub files_changed: Vec<String>,
+    /// Substrings that must each appear in a command the agent ran.
+    #[serde(default)]
+    pub commands_run: Vec<String>,
+    /// Regex the final message must match.
+    #[serde(default, deserialize_with = "deserialize_regex")]
+    pub final_message: Option<Regex>,
+    /// JSON Schema the JSON in the final message must satisfy. Supports
+    /// `type`, `enum`, `const`, `properties`, `required`,
+    /// `additionalProperties`, `items`, `minItems`, `maxItems`, `pattern`,
+    /// `minimum` and `maximum`.
+    #[serde(default)]
+    pub json_schema: Option<Value>,
+}
+
+fn deserialize_regex<'de, D>(deserializer: D) -> Result<Option<Regex>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|pattern| Regex::new(&pattern).map_err(serde::de::Error::custom))
+        .transpose()
+}
+
+/// Load the cases under `evals_dir`, for one agent or for every agent
+/// directory, sorted by agent and file name.
+pub fn load_cases(evals_dir: &Path, agent: Option<&str>) -> Result<Vec<EvalCase>, EvalError> {
+    let agents = match agent {
+        Some(agent) => vec![agent.to_string()],
+        None => sorted_entries(evals_dir)?
+            .into_iter()
+            .filter(|path| path.is_dir())
+            .filter_map(|path| Some(path.file_name()?.to_str()?.to_string()))
+            .collect(),
+    };
+
+    let mut cases = Vec::new();
+    for agent in agents {
+        let dir = evals_dir.join(&agent);
+        let files: Vec<PathBuf> = sorted_entries(&dir)?
+            .into_iter()
+            .filter(|path| {
+                path.is_file()
+                    && matches!(
+                        path.extension().and_then(|ext| ext.to_str()),
+                        Some("yaml" | "yml")
+                    )
+            })
+            .collect();
+        if files.is_empty() {
+            return Err(EvalError::NoCases { agent, dir });
+        }
+        for path in files {
+            cases.push(load_case(&agent, path)?);
+        }
+    }
+    Ok(cases)
+}
+
+fn load_case(agent: &str, path: PathBuf) -> Result<EvalCase, EvalError> {
+    let text = fs::read_to_string(&path).map_err(|source| EvalError::Io {
+        path: path.clone(),
+        source,
+    })?;
+    let file: CaseFile = serde_yaml::from_str(&text).map_err(|err| EvalError::InvalidCase {
+        path: path.clone(),
+        reason: err.to_string(),
+    })?;
+    if let Some(schema) = &file.assertions.json_schema
+        && let Err(reason) = validate_schema(schema, "$")
+    {
+        return Err(EvalError::InvalidCase {
+            path,
+            reason: format!("json_schema: {reason}"),
+        });
+    }
+    let base = path.parent().unwrap_or(Path::new("."));
+    let fixture = file.fixture.map(|fixture| base.join(fixture));
+    if let Some(fixture) = fixture.as_deref()
+        && !fixture.is_dir()
+    {
+        return Err(EvalError::InvalidCase {
+            path,
+            reason: format!("fixture {} is not a directory", fixture.display()),
+        });
+    }
+    let name = match file.name {
+        Some(name) => name,
+        None => path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+    };
+    Ok(EvalCase {
+        agent: agent.to_string(),
+        name,
+        path,
+        fixture,
+        prompt: file.prompt,
+        assertions: file.assertions,
+    })
+}
+
+fn sorted_entries(dir: &Path) -> Result<Vec<PathBuf>, EvalError> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(source) => {
+            return Err(EvalError::Io {
+                path: dir.to_path_buf(),
+                source,
+            });
+        }
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+/// Copy a fixture directory, skipping `.git`.
+pub fn copy_fixture(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        if entry.file_name() == ".git" {
+            continue;
+        }
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_fixture(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Content hashes of every file under a directory, keyed by `/`-separated
+/// relative path.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FixtureSnapshot(BTreeMap<String, [u8; 32]>);
+
+impl FixtureSnapshot {
+    pub fn capture(root: &Path) -> io::Result<Self> {
+        let mut files = BTreeMap::new();
+        capture_dir(root, "", &mut files)?;
+        Ok(Self(files))
+    }
+
+    /// Files added, modified or deleted since `self`, sorted.
+    pub fn changed_files(&self, after: &FixtureSnapshot) -> Vec<String> {
+        let mut changed: Vec<String> = after
+            .0
+            .iter()
+            .filter(|(path, hash)| self.0.get(*path) != Some(*hash))
+            .map(|(path, _)| path.clone())
+            .collect();
+        changed.extend(
+            self.0
+                .keys()
+                .filter(|path| !after.0.contains_key(*path))
+                .cloned(),
+        );
+        changed.sort();
+        changed
+    }
+}
+
+fn capture_dir(dir: &Path, prefix: &str, files: &mut BTreeMap<String, [u8; 32]>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name == ".git" {
+            continue;
+        }
+        let relative = format!("{prefix}{name}");
+        if entry.file_type()?.is_dir() {
+            capture_dir(&entry.path(), &format!("{relative}/"), files)?;
+        } else {
+            files.insert(relative, Sha256::digest(fs::read(entry.path())?).into());
+        }
+    }
+    Ok(())
+}
+
+/// What a run did, as far as the assertions can tell.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EvalObservation {
+    pub changed_files: Vec<String>,
+    pub commands: Vec<String>,
+    pub final_message: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AssertionResult {
+    /// `files_changed: src/*.rs`, `final_message`, and so on.
+    pub assertion: String,
+    pub passed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl AssertionResult {
+    fn new(assertion: String, failure: Option<String>) -> Self {
+        Self {
+            assertion,
+            passed: failure.is_none(),
+            detail: failure,
+        }
+    }
+}
+
+impl EvalAssertions {
+    pub fn check(&self, observed: &EvalObservation) -> Vec<AssertionResult> {
+        let mut results = Vec::new();
+        for glob in &self.files_changed {
+            let matcher = WildMatch::new(glob);
+            let failure = (!observed
+                .changed_files
+                .iter()
+                .any(|path| matcher.matches(path)))
+            .then(|| {
+                if observed.changed_files.is_empty() {
+                    "no files changed".to_string()
+                } else {
+                    format!("changed: {}", observed.changed_files.join(", "))
+                }
+            });
+            results.push(AssertionResult::new(
+                format!("files_changed: {glob}"),
+                failure,
+            ));
+        }
+        for expected in &self.commands_run {
+            let failure = (!observed
+                .commands
+                .iter()
+                .any(|command| command.contains(expected.as_str())))
+            .then(|| format!("{} command(s) ran, none matched", observed.commands.len()));
+            results.push(AssertionResult::new(
+                format!("commands_run: {expected}"),
+                failure,
+            ));
+        }
+        let message = observed.final_message.as_deref().unwrap_or_default();
+        if let Some(regex) = &self.final_message {
+            let failure = (!regex.is_match(message))
+                .then(|| format!("final message did not match `{}`", regex.as_str()));
+            results.push(AssertionResult::new("final_message".to_string(), failure));
+        }
+        if let Some(schema) = &self.json_schema {
+            let failure = match extract_json(message) {
+                None => Some("final message has no JSON".to_string()),
+                Some(value) => {
+                    let mut errors = Vec::new();
+                    schema_errors(schema, &value, "$", &mut errors);
+                    (!errors.is_empty()).then(|| errors.join("; "))
+                }
+            };
+            results.push(AssertionResult::new("json_schema".to_string(), failure));
+        }
+        results
+    }
+}
+
+/// The final message as JSON, or the outermost object or array in it when
+/// it is wrapped in prose or a code fence.
+fn extract_json(message: &str) -> Option<Value> {
+    if let Ok(value) = serde_json::from_str(message.trim()) {
+        return Some(value);
+    }
+    ['{', '[']
+        .into_iter()
+        .zip(['}', ']'])
+        .find_map(|(open, close)| {
+            let start = message.find(open)?;
+            let end = message.rfind(close)?;
+            (start < end)
+                .then(|| serde_json::from_str(&message[start..=end]).ok())
+                .flatten()
+        })
+}
+
+/// Keywords `schema_errors` checks. Annotations that do not constrain the
+/// value are accepted too; anything else is rejected when the case loads
+/// rather than silently passing.
+const SCHEMA_KEYWORDS: &[&str] = &[
+    "type",
+    "const",
+    "enum",
+    "required",
+    "properties",
+    "additionalProperties",
+    "items",
+    "minItems",
+    "maxItems",
+    "pattern",
+    "minimum",
+    "maximum",
+];
+const SCHEMA_ANNOTATIONS: &[&str] = &["$schema", "title", "description", "default", "examples"];
+const SCHEMA_TYPES: &[&str] = &[
+    "object", "array", "string", "number", "integer", "boolean", "null",
+];
+
+/// Check that `schema` only uses what `schema_errors` understands.
+fn validate_schema(schema: &Value, at: &str) -> Result<(), String> {
+    let Some(object) = schema.as_object() else {
+        return Err(format!("{at}: a schema must be an object"));
+    };
+    for (keyword, value) in object {
+        let at = format!("{at}.{keyword}");
+        match keyword.as_str() {
+            "type" => {
+                let names: Vec<Option<&str>> = match value {
+                    Value::String(name) => vec![Some(name.as_str())],
+                    Value::Array(names) if !names.is_empty() => {
+                        names.iter().map(Value::as_str).collect()
+                    }
+                    _ => vec![None],
+                };
+                for name in names {
+                    match name {
+                        Some(name) if SCHEMA_TYPES.contains(&name) => {}
+                        Some(name) => return Err(format!("{at}: unknown type `{name}`")),
+                        None => {
+                            return Err(format!("{at}: expected a type name or a list of them"));
+                        }
+                    }
+                }
+            }
+            "enum" if !value.is_array() => return Err(format!("{at}: expected an array")),
+            "required"
+                if !value
+                    .as_array()
+                    .is_some_and(|keys| keys.iter().all(Value::is_string)) =>
+            {
+                return Err(format!("{at}: expected an array of property names"));
+            }
+            "properties" => {
+                let Some(properties) = value.as_object() else {
+                    return Err(format!("{at}: expected an object of schemas"));
+                };
+                for (key, child) in properties {
+                    validate_schema(child, &format!("{at}.{key}"))?;
+                }
+            }
+            "additionalProperties" if !value.is_boolean() => validate_schema(value, &at)?,
+            "items" => validate_schema(value, &at)?,
+            "minItems" | "maxItems" if !value.is_u64() => {
+                return Err(format!("{at}: expected a non-negative integer"));
+            }
+            "minimum" | "maximum" if !value.is_number() => {
+                return Err(format!("{at}: expected a number"));
+            }
+            "pattern" => {
+                let Some(pattern) = value.as_str() else {
+                    return Err(format!("{at}: expected a string"));
+                };
+                Regex::new(pattern).map_err(|err| format!("{at}: invalid pattern: {err}"))?;
+            }
+            keyword
+                if SCHEMA_KEYWORDS.contains(&keyword) || SCHEMA_ANNOTATIONS.contains(&keyword) => {}
+            keyword => return Err(format!("{at}: unsupported keyword `{keyword}`")),
+        }
+    }
+    Ok(())
+}
+
+fn schema_errors(schema: &Value, value: &Value, at: &str, errors: &mut Vec<String>) {
+    let Some(schema) = schema.as_object() else {
+        return;
+    };
+    if let Some(expected) = schema.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::String(name) => vec![name.as_str()],
+            Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !types.is_empty() && !types.iter().any(|name| has_type(value, name)) {
+            errors.push(format!("{at}: expected {}", types.join(" or ")));
+            return;
+        }
+    }
+    if let Some(constant) = schema.get("const")
+        && constant != value
+    {
+        errors.push(format!("{at}: expected {constant}"));
+    }
+    if let Some(Value::Array(allowed)) = schema.get("enum")
+        && !allowed.contains(value)
+    {
+        errors.push(format!("{at}: {value} is not one of the allowed values"));
+    }
+    match value {
+        Value::Object(object) => {
+            if let Some(Value::Array(required)) = schema.get("required") {
+                for key in required.iter().filter_map(Value::as_str) {
+                    if !object.contains_key(key) {
+                        errors.push(format!("{at}: missing `{key}`"));
+                    }
+                }
+            }
+            let properties = schema.get("properties").and_then(Value::as_object);
+            for (key, child) in object {
+                let path = format!("{at}.{key}");
+                match (
+                    properties.and_then(|props| props.get(key)),
+                    schema.get("additionalProperties"),
+                ) {
+                    (Some(child_schema), _) => schema_errors(child_schema, child, &path, errors),
+                    (None, Some(Value::Bool(false))) => {
+                        errors.push(format!("{path}: unexpected property"));
+                    }
+                    (None, Some(extra)) => schema_errors(extra, child, &path, errors),
+                    (None, None) => {}
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(min) = schema.get("minItems").and_then(Value::as_u64)
+                && (items.len() as u64) < min
+            {
+                errors.push(format!("{at}: expected at least {min} item(s)"));
+            }
+            if let Some(max) = schema.get("maxItems").and_then(Value::as_u64)
+                && (items.len() as u64) > max
+            {
+                errors.push(format!("{at}: expected at most {max} item(s)"));
+            }
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    schema_errors(item_schema, item, &format!("{at}[{index}]"), errors);
+                }
+            }
+        }
+        Value::String(text) => {
+            if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+                match Regex::new(pattern) {
+                    Ok(regex) if regex.is_match(text) => {}
+                    Ok(_) => errors.push(format!("{at}: does not match `{pattern}`")),
+                    Err(err) => errors.push(format!("{at}: invalid pattern `{pattern}`: {err}")),
+                }
+            }
+        }
+        Value::Number(number) => {
+            let number = number.as_f64().unwrap_or_default();
+            if let Some(min) = schema.get("minimum").and_then(Value::as_f64)
+                && number < min
+            {
+                errors.push(format!("{at}: below the minimum {min}"));
+            }
+            if let Some(max) = schema.get("maximum").and_then(Value::as_f64)
+                && number > max
+            {
+                errors.push(format!("{at}: above the maximum {max}"));
+            }
+        }
+        Value::Null | Value::Bool(_) => {}
+    }
+}
+
+fn has_type(value: &Value, name: &str) -> bool {
+    match name {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        _ => false,
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CaseReport {
+    pub agent: String,
+    pub case: String,
+    pub file: PathBuf,
+    pub passed: bool,
+    pub duration_ms: u64,
+    /// Why the run itself failed; assertions are not checked then.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub assertions: Vec<AssertionResult>,
+    #[serde(flatten)]
+    pub observed: EvalObservation,
+}
+
+impl CaseReport {
+    pub fn new(
+        case: &EvalCase,
+        observed: EvalObservation,
+        error: Option<String>,
+        duration: Duration,
+    ) -> Self {
+        let assertions = if error.is_none() {
+            case.assertions.check(&observed)
+        } else {
+            Vec::new()
+        };
+        Self {
+            agent: case.agent.clone(),
+            case: case.name.clone(),
+            file: case.path.clone(),
+            passed: error.is_none() && assertions.iter().all(|result| result.passed),
+            duration_ms: duration.as_millis().min(u128::from(u64::MAX)) as u64,
+            error,
+            assertions,
+            observed,
+        }
+    }
+
+    /// The run error or the first failed assertion.
+    pub fn failure(&self) -> Option<String> {
+        if let Some(error) = &self.error {
+            return Some(error.clone());
+        }
+        let failed = self.assertions.iter().find(|result| !result.passed)?;
+        Some(match &failed.detail {
+            Some(detail) => format!("{}: {detail}", failed.assertion),
+            None => failed.assertion.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EvalReport {
+    pub passed: usize,
+    pub failed: usize,
+    pub cases: Vec<CaseReport>,
+}
+
+impl EvalReport {
+    pub fn new(cases: Vec<CaseReport>) -> Self {
+        let passed = cases.iter().filter(|case| case.passed).count();
+        Self {
+            passed,
+            failed: cases.len() - passed,
+            cases,
+        }
+    }
+
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(path, json + "\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn loads_cases_with_fixtures_relative_to_the_file() {
+        let temp = tempfile::tempdir().unwrap();
+        let agent_dir = temp.path().join("writer");
+        fs::create_dir_all(agent_dir.join("fixtures/basic")).unwrap();
+        fs::write(
+            agent_dir.join("b-docs.yaml"),
+            "fixture: fixtures/basic\nprompt: Document it.\nassert:\n  files_changed: [README.md]\n  final_message: '(?i)done'\n",
+        )
+        .unwrap();
+        fs::write(
+            agent_dir.join("a-empty.yml"),
+            "name: from-scratch\nprompt: Create a file.\n",
+        )
+        .unwrap();
+
+        let cases = load_cases(temp.path(), None).unwrap();
+        let names: Vec<&str> = cases.iter().map(|case| case.name.as_str()).collect();
+        assert_eq!(names, vec!["from-scratch", "b-docs"]);
+        assert_eq!(cases[0].fixture, None);
+        assert_eq!(cases[1].fixture, Some(agent_dir.join("fixtures/basic")));
+        assert_eq!(cases[1].assertions.files_changed, vec!["README.md"]);
+
+        fs::write(
+            agent_dir.join("c-bad.yaml"),
+            "prompt: x\nassert:\n  final_message: '('\n",
+        )
+        .unwrap();
+        assert!(matches!(
+            load_cases(temp.path(), Some("writer")),
+            Err(EvalError::InvalidCase { .. })
+        ));
+        assert!(matches!(
+            load_cases(temp.path(), Some("missing")),
+            Err(EvalError::NoCases { .. })
+        ));
+        fs::remove_file(agent_dir.join("c-bad.yaml")).unwrap();
+
+        for (schema, reason) in [
+            (
+                "{ oneOf: [{ type: string }] }",
+                "json_schema: $.oneOf: unsupported keyword `oneOf`",
+            ),
+            ("{ type: obj }", "json_schema: $.type: unknown type `obj`"),
+            (
+                "{ properties: { id: { pattern: '(' } } }",
+                "json_schema: $.properties.id.pattern: invalid pattern",
+            ),
+        ] {
+            fs::write(
+                agent_dir.join("d-schema.yaml"),
+                format!("prompt: x\nassert:\n  json_schema: {schema}\n"),
+            )
+            .unwrap();
+            let err = load_cases(temp.path(), Some("writer")).unwrap_err();
+            assert!(err.to_string().contains(reason), "{err}");
+        }
+    }
+
+    #[test]
+    fn snapshot_reports_added_modified_and_deleted_files() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/lib.rs"), "fn a() {}").unwrap();
+        fs::write(root.join("old.txt"), "old").unwrap();
+        fs::write(root.join("same.txt"), "same").unwrap();
+        let before = FixtureSnapshot::capture(root).unwrap();
+
+        fs::write(root.join("src/lib.rs"), "fn b() {}").unwrap();
+        fs::remove_file(root.join("old.txt")).unwrap();
+        fs::write(root.join("new.txt"), "new").unwrap();
+        let after = FixtureSnapshot::capture(root).unwrap();
+
+        assert_eq!(
+            before.changed_files(&after),
+            vec!["new.txt", "old.txt", "src/lib.rs"]
+        );
+    }
+
+    #[test]
+    fn checks_each_assertion_kind() {
+        let assertions = EvalAssertions {
+            files_changed: vec!["src/*.rs".to_string(), "README.md".to_string()],
+            commands_run: vec!["cargo test".to_string()],
+            final_message: Some(Regex::new("(?i)summary").unwrap()),
+            json_schema: Some(json!({
+                "type": "object",
+                "required": ["summary", "findings"],
+                "properties": {
+                    "findings": {"type": "array", "items": {"enum": ["low", "high"]}}
+                }
+            })),
+        };
+        let observed = EvalObservation {
+            changed_files: vec!["src/lib.rs".to_string()],
+            commands: vec!["cargo test -p demo".to_string()],
+            final_message: Some(
+                "```json\n{\"summary\": \"ok\", \"findings\": [\"medium\"]}\n```".to_string(),
+            ),
+        };
+
+        let failed: Vec<(String, Option<String>)> = assertions
+            .check(&observed)
+            .into_iter()
+            .filter(|result| !result.passed)
+            .map(|result| (result.assertion, result.detail))
+            .collect();
+        assert_eq!(
+            failed,
+            vec![
+                (
+                    "files_changed: README.md".to_string(),
+                    Some("changed: src/lib.rs".to_string())
+                ),
+                (
+                    "json_schema".to_string(),
+                    Some("$.findings[0]: \"medium\" is not one of the allowed values".to_string())
+                ),
+            ]
+        );
+    }
+}
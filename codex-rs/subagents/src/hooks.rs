@@ -0,0 +1,452 @@
+//! Git hooks that run a review agent before a commit or a push.
+//!
+//! `codex subagents hook install` writes a short shell script into the
+//! repository's hooks directory that calls `codex subagents hook run`. A
+//! marker line records the stage, agent and severity threshold, so `status`
+//! and `uninstall` can tell the script apart from hooks written by other
+//! tools.
+
+use crate::error::HookError;
+use crate::json_text::json_objects;
+use crate::parser::validate_agent_name;
+use crate::pipeline::ReviewFindings;
+use crate::pipeline::Severity;
+use codex_protocol::protocol::ReviewOutputEvent;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+const MARKER: &str = "# codex-subagents-hook:";
+/// Suffix of a replaced hook, restored on uninstall.
+const BACKUP_SUFFIX: &str = ".pre-codex";
+const ZERO_SHA: &str = "0000000000000000000000000000000000000000";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookStage {
+    PreCommit,
+    PrePush,
+}
+
+impl HookStage {
+    pub const ALL: [HookStage; 2] = [HookStage::PreCommit, HookStage::PrePush];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HookStage::PreCommit => "pre-commit",
+            HookStage::PrePush => "pre-push",
+        }
+    }
+
+    /// The git command whose `--no-verify` skips this hook.
+    pub fn bypass_hint(self) -> &'static str {
+        match self {
+            HookStage::PreCommit => "git commit --no-verify",
+            HookStage::PrePush => "git push --no-verify",
+        }
+    }
+}
+
+impl fmt::Display for HookStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for HookStage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pre-commit" => Ok(HookStage::PreCommit),
+            "pre-push" => Ok(HookStage::PrePush),
+            other => Err(format!(
+                "unknown hook stage `{other}`; expected pre-commit or pre-push"
+            )),
+        }
+    }
+}
+
+/// What an installed hook runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookConfig {
+    pub stage: HookStage,
+    pub agent: String,
+    /// Findings at or above this severity fail the hook.
+    pub severity: Severity,
+}
+
+impl HookConfig {
+    pub fn script(&self) -> String {
+        let HookConfig {
+            stage,
+            agent,
+            severity,
+        } = self;
+        format!(
+            "#!/bin/sh\n\
+             {MARKER} stage={stage} agent={agent} severity={severity}\n\
+             # Installed by `codex subagents hook install`. Skip it once with `{}`.\n\
+             exec \"${{CODEX_BIN:-codex}}\" subagents hook run --stage {stage} --agent {agent} --severity {severity} \"$@\"\n",
+            stage.bypass_hint()
+        )
+    }
+
+    /// Read the marker line of a script written by [`Self::script`].
+    pub fn from_script(script: &str) -> Option<Self> {
+        let fields = script.lines().find_map(|line| line.strip_prefix(MARKER))?;
+        let mut stage = None;
+        let mut agent = None;
+        let mut severity = None;
+        for field in fields.split_whitespace() {
+            match field.split_once('=')? {
+                ("stage", value) => stage = value.parse().ok(),
+                ("agent", value) => agent = Some(value.to_string()),
+                ("severity", value) => severity = value.parse().ok(),
+                _ => {}
+            }
+        }
+        Some(Self {
+            stage: stage?,
+            agent: agent?,
+            severity: severity?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HookState {
+    NotInstalled,
+    Installed(HookConfig),
+    /// A hook that codex did not write.
+    Foreign,
+}
+
+pub fn hook_path(hooks_dir: &Path, stage: HookStage) -> PathBuf {
+    hooks_dir.join(stage.as_str())
+}
+
+pub fn hook_state(hooks_dir: &Path, stage: HookStage) -> Result<HookState, HookError> {
+    let path = hook_path(hooks_dir, stage);
+    match fs::read_to_string(&path) {
+        Ok(script) => Ok(match HookConfig::from_script(&script) {
+            Some(config) => HookState::Installed(config),
+            None => HookState::Foreign,
+        }),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(HookState::NotInstalled),
+        // Binary hooks cannot be ours.
+        Err(err) if err.kind() == std::io::ErrorKind::InvalidData => Ok(HookState::Foreign),
+        Err(source) => Err(HookError::Io { path, source }),
+    }
+}
+
+/// Write the hook for `config.stage`. A foreign hook is only replaced with
+/// `force`, and is then kept next to it for [`uninstall_hook`] to restore;
+/// its new path is returned.
+pub fn install_hook(
+    hooks_dir: &Path,
+    config: &HookConfig,
+    force: bool,
+) -> Result<Option<PathBuf>, HookError> {
+    validate_agent_name(&config.agent)?;
+    let path = hook_path(hooks_dir, config.stage);
+    let backup = match hook_state(hooks_dir, config.stage)? {
+        HookState::Foreign if !force => return Err(HookError::ForeignHook(path)),
+        HookState::Foreign => {
+            let backup = backup_path(&path);
+            fs::rename(&path, &backup).map_err(|source| HookError::Io {
+                path: backup.clone(),
+                source,
+            })?;
+            Some(backup)
+        }
+        HookState::NotInstalled | HookState::Installed(_) => None,
+    };
+
+    let io_err = |source| HookError::Io {
+        path: path.clone(),
+        source,
+    };
+    fs::create_dir_all(hooks_dir).map_err(io_err)?;
+    fs::write(&path, config.script()).map_err(io_err)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).map_err(io_err)?;
+    }
+    Ok(backup)
+}
+
+/// Remove the codex hook for `stage`, restoring the hook it replaced if
+/// there was one; returns the restored path.
+pub fn uninstall_hook(hooks_dir: &Path, stage: HookStage) -> Result<Option<PathBuf>, HookError> {
+    let path = hook_path(hooks_dir, stage);
+    match hook_state(hooks_dir, stage)? {
+        HookState::NotInstalled => return Err(HookError::NotInstalled(stage.to_string())),
+        HookState::Foreign => return Err(HookError::ForeignHook(path)),
+        HookState::Installed(_) => {}
+    }
+    fs::remove_file(&path).map_err(|source| HookError::Io {
+        path: path.clone(),
+        source,
+    })?;
+    let backup = backup_path(&path);
+    if !backup.exists() {
+        return Ok(None);
+    }
+    fs::rename(&backup, &path).map_err(|source| HookError::Io {
+        path: path.clone(),
+        source,
+    })?;
+    Ok(Some(path))
+}
+
+fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(BACKUP_SUFFIX);
+    PathBuf::from(name)
+}
+
+/// One review finding, reduced to what the hook summary shows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookFinding {
+    pub severity: Severity,
+    pub message: String,
+    /// `path[:line]` relative to the repository root, when known.
+    pub location: Option<String>,
+}
+
+/// Findings in an agent's final message, which may hold [`ReviewFindings`]
+/// or `/review`-style JSON, possibly wrapped in prose. Each JSON object in
+/// the message is tried, last first. `/review` priorities P0 to P3 map to
+/// critical, high, medium and low. Returns `None` when no object has either
+/// shape.
+pub fn findings_from_message(message: &str, root: &Path) -> Option<Vec<HookFinding>> {
+    json_objects(message)
+        .into_iter()
+        .rev()
+        .find_map(|json| findings_from_json(json, root))
+}
+
+fn findings_from_json(json: &str, root: &Path) -> Option<Vec<HookFinding>> {
+    if let Ok(review) = serde_json::from_str::<ReviewFindings>(json) {
+        return Some(
+            review
+                .findings
+                .into_iter()
+                .map(|finding| HookFinding {
+                    severity: finding.severity,
+                    message: finding.message,
+                    location: None,
+                })
+                .collect(),
+        );
+    }
+    let output = serde_json::from_str::<ReviewOutputEvent>(json).ok()?;
+    Some(
+        output
+            .findings
+            .into_iter()
+            .map(|finding| {
+                let location = &finding.code_location;
+                let path = location.absolute_file_path.as_path();
+                let path = path.strip_prefix(root).unwrap_or(path);
+                let line = location.line_range.start;
+                HookFinding {
+                    severity: match finding.priority {
+                        i32::MIN..=0 => Severity::Critical,
+                        1 => Severity::High,
+                        2 => Severity::Medium,
+                        _ => Severity::Low,
+                    },
+                    message: finding.title,
+                    location: Some(if line > 0 {
+                        format!("{}:{line}", path.display())
+                    } else {
+                        path.display().to_string()
+                    }),
+                }
+            })
+            .collect(),
+    )
+}
+
+/// One line of what git feeds a `pre-push` hook on stdin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PushUpdate {
+    pub local_ref: String,
+    pub local_sha: String,
+    pub remote_ref: String,
+    pub remote_sha: String,
+}
+
+impl PushUpdate {
+    /// The push deletes `remote_ref`; there is nothing to review.
+    pub fn is_delete(&self) -> bool {
+        self.local_sha == ZERO_SHA
+    }
+
+    /// `remote_ref` does not exist on the remote yet.
+    pub fn is_new_ref(&self) -> bool {
+        self.remote_sha == ZERO_SHA
+    }
+}
+
+pub fn parse_push_updates(stdin: &str) -> Vec<PushUpdate> {
+    stdin
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            Some(PushUpdate {
+                local_ref: parts.next()?.to_string(),
+                local_sha: parts.next()?.to_string(),
+                remote_ref: parts.next()?.to_string(),
+                remote_sha: parts.next()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn reviewer_hook(stage: HookStage) -> HookConfig {
+        HookConfig {
+            stage,
+            agent: "reviewer".to_string(),
+            severity: Severity::High,
+        }
+    }
+
+    #[test]
+    fn installs_reports_and_uninstalls_hooks() {
+        let temp = tempfile::tempdir().unwrap();
+        let hooks_dir = temp.path().join("hooks");
+        let config = reviewer_hook(HookStage::PreCommit);
+
+        assert_eq!(
+            hook_state(&hooks_dir, HookStage::PreCommit).unwrap(),
+            HookState::NotInstalled
+        );
+        assert_eq!(install_hook(&hooks_dir, &config, false).unwrap(), None);
+        let script = fs::read_to_string(hooks_dir.join("pre-commit")).unwrap();
+        assert!(script.contains(
+            "subagents hook run --stage pre-commit --agent reviewer --severity high \"$@\""
+        ));
+        assert!(script.contains("git commit --no-verify"));
+        assert_eq!(
+            hook_state(&hooks_dir, HookStage::PreCommit).unwrap(),
+            HookState::Installed(config.clone())
+        );
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(hooks_dir.join("pre-commit"))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o111, 0o111);
+        }
+
+        assert_eq!(
+            uninstall_hook(&hooks_dir, HookStage::PreCommit).unwrap(),
+            None
+        );
+        assert!(matches!(
+            uninstall_hook(&hooks_dir, HookStage::PreCommit),
+            Err(HookError::NotInstalled(_))
+        ));
+    }
+
+    #[test]
+    fn replaces_foreign_hooks_only_with_force() {
+        let temp = tempfile::tempdir().unwrap();
+        let hooks_dir = temp.path();
+        let path = hooks_dir.join("pre-push");
+        fs::write(&path, "#!/bin/sh\nmake lint\n").unwrap();
+        let config = reviewer_hook(HookStage::PrePush);
+
+        assert!(matches!(
+            install_hook(hooks_dir, &config, false),
+            Err(HookError::ForeignHook(_))
+        ));
+        let backup = install_hook(hooks_dir, &config, true).unwrap().unwrap();
+        assert_eq!(
+            fs::read_to_string(&backup).unwrap(),
+            "#!/bin/sh\nmake lint\n"
+        );
+        assert_eq!(
+            uninstall_hook(hooks_dir, HookStage::PrePush).unwrap(),
+            Some(path.clone())
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "#!/bin/sh\nmake lint\n");
+        assert_eq!(
+            hook_state(hooks_dir, HookStage::PrePush).unwrap(),
+            HookState::Foreign
+        );
+    }
+
+    #[test]
+    fn reads_findings_from_either_review_format() {
+        let root = Path::new("/repo");
+        let findings = findings_from_message(
+            "Done.\n{\"summary\": \"ok\", \"findings\": [{\"severity\": \"high\", \"message\": \"SQL built from input\"}]}",
+            root,
+        )
+        .unwrap();
+        assert_eq!(
+            findings,
+            vec![HookFinding {
+                severity: Severity::High,
+                message: "SQL built from input".to_string(),
+                location: None,
+            }]
+        );
+
+        let review = r#"{"findings": [{"title": "Off by one", "body": "", "confidence_score": 0.9, "priority": 2,
+            "code_location": {"absolute_file_path": "/repo/src/lib.rs", "line_range": {"start": 7, "end": 9}}}],
+            "overall_correctness": "patch is incorrect", "overall_explanation": "", "overall_confidence_score": 0.8}"#;
+        assert_eq!(
+            findings_from_message(review, root).unwrap(),
+            vec![HookFinding {
+                severity: Severity::Medium,
+                message: "Off by one".to_string(),
+                location: Some("src/lib.rs:7".to_string()),
+            }]
+        );
+        assert_eq!(findings_from_message("Looks good to me.", root), None);
+        assert_eq!(
+            findings_from_message("Checked `{path}` handling. {\"note\": 1}", root),
+            None
+        );
+    }
+
+    #[test]
+    fn reads_findings_between_other_braces() {
+        let root = Path::new("/repo");
+        let message = "Guarded the `{}` placeholder.\n\
+            {\"summary\": \"one issue\", \"findings\": [{\"severity\": \"low\", \"message\": \"Use `{}` sparingly\"}]}\n\
+            Scratch: {\"plan\": \"done\"}";
+        assert_eq!(
+            findings_from_message(message, root).unwrap(),
+            vec![HookFinding {
+                severity: Severity::Low,
+                message: "Use `{}` sparingly".to_string(),
+                location: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn parses_pre_push_stdin() {
+        let updates = parse_push_updates(&format!(
+            "refs/heads/main abc refs/heads/main def\nrefs/heads/gone {ZERO_SHA} refs/heads/gone abc\n\n"
+        ));
+        assert_eq!(updates.len(), 2);
+        assert!(!updates[0].is_delete() && !updates[0].is_new_ref());
+        assert!(updates[1].is_delete());
+    }
+}
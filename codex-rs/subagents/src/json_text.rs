@@ -0,0 +1,57 @@
+//! Finding JSON objects inside an agent's free-form final message.
+
+/// Every balanced top-level `{...}` span in `text`, in order. Braces inside
+/// JSON strings are ignored, so prose around the objects (or stray braces in
+/// it) does not merge them into one span. The spans are not validated as
+/// JSON.
+pub fn json_objects(text: &str) -> Vec<&str> {
+    let mut objects = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (index, c) in text.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' if depth > 0 => in_string = true,
+            '{' => {
+                if depth == 0 {
+                    start = index;
+                }
+                depth += 1;
+            }
+            '}' if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    objects.push(&text[start..=index]);
+                }
+            }
+            _ => {}
+        }
+    }
+    objects
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn finds_each_balanced_object() {
+        let text = "Use `{}` sparingly. Result:\n```json\n{\"a\": {\"b\": \"}\"}}\n```\nthen {\"c\": 1} and a stray }";
+        assert_eq!(
+            json_objects(text),
+            vec!["{}", "{\"a\": {\"b\": \"}\"}}", "{\"c\": 1}"]
+        );
+        assert_eq!(json_objects("no objects { here"), Vec::<&str>::new());
+    }
+}
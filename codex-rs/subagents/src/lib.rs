@@ -1,26 +1,54 @@
 mod builder;
+pub mod checkpoint;
 mod code_writer;
+mod context;
+pub mod ensemble;
 mod error;
+pub mod evals;
+pub mod hooks;
+pub mod json_text;
+pub mod memory;
+pub mod packs;
 mod parser;
 pub mod pipeline;
+pub mod project;
 mod registry;
 mod reviewer;
+pub mod sarif;
+pub mod scope;
 mod spec;
 mod spec_parser;
+pub mod spec_sources;
+mod targets;
 mod task_context;
+pub mod test_reports;
+#[cfg(test)]
+mod test_support;
 mod tester;
+pub mod workflow;
 
 pub use builder::SubagentBuilder;
 pub use code_writer::CodeWriterOutput;
 pub use code_writer::CodeWriterSubagent;
+pub use context::ContextAttachment;
 pub use error::AgentParseError;
+pub use error::CheckpointError;
+pub use error::EvalError;
+pub use error::HookError;
+pub use error::PackError;
 pub use error::ParserError;
 pub use error::RegistryError;
 pub use error::SubagentValidationError;
 pub use error::TaskContextError;
+pub use error::WorkflowError;
+pub use error::WorkflowParseError;
+pub use parser::AgentDefinition;
+pub use parser::AgentModelConfig;
 pub use parser::ParsedAgent;
+pub use parser::parse_agent_definition;
 pub use parser::parse_agent_file;
 pub use parser::parse_agent_str;
+pub use parser::parse_confined_agent_file;
 pub use parser::validate_agent_name;
 pub use registry::AgentHandle;
 pub use registry::RegistrySnapshot;
@@ -40,6 +68,14 @@ pub use task_context::TaskContext;
 pub use task_context::TaskContextSnapshot;
 pub use tester::TesterOutput;
 pub use tester::TesterSubagent;
+pub use workflow::PlannedStep;
+pub use workflow::StepResult;
+pub use workflow::StepStatus;
+pub use workflow::WorkflowBatch;
+pub use workflow::WorkflowCatalog;
+pub use workflow::WorkflowDefinition;
+pub use workflow::WorkflowReport;
+pub use workflow::WorkflowRun;
 
 use std::borrow::Cow;
 
@@ -58,11 +94,44 @@ pub trait TypedSubagent: Subagent {
     fn finalize(&self, ctx: &mut TaskContext, output: Self::Output) -> anyhow::Result<()>;
 }
 
+/// Execution step of an agent whose `prepare` and `finalize` come from
+/// `#[derive(Subagent)]` with `input = ...` and `output = ...`; the derived
+/// `TypedSubagent::execute` calls `run`.
+pub trait SubagentExecute<Input, Output> {
+    fn run(&self, ctx: &mut TaskContext, input: Input) -> anyhow::Result<Output>;
+}
+
+/// JSON Schemas of a typed subagent's input and output, for describing the
+/// agent as a tool.
+pub trait SubagentSchemas: TypedSubagent {
+    fn input_schema() -> serde_json::Value;
+    fn output_schema() -> serde_json::Value;
+}
+
 /// Trait for subagents that require additional context seeding prior to execution.
 pub trait ContextualSubagent: Subagent {
     fn seed_context(&self, ctx: &mut TaskContext) -> anyhow::Result<()>;
 }
 
+/// Re-exports used by code generated by `#[derive(Subagent)]`.
+#[doc(hidden)]
+pub mod __derive {
+    pub use anyhow;
+    pub use schemars;
+    pub use serde_json;
+}
+
+/// Specs of the pipeline agents that ship with Codex, lowest in registry
+/// precedence.
+pub fn builtin_agents() -> Vec<SubagentSpec> {
+    vec![
+        SpecParserSubagent::subagent_spec(),
+        CodeWriterSubagent::subagent_spec(),
+        TesterSubagent::subagent_spec(),
+        ReviewerSubagent::subagent_spec(),
+    ]
+}
+
 /// Helper function to interpret sandbox metadata values.
 pub fn seatbelt_active(value: Option<&str>) -> bool {
     matches!(value, Some("seatbelt"))
@@ -0,0 +1,289 @@
+//! Long-term notes for agents that opt in with `memory: true`.
+//!
+//! Each agent gets one Markdown file per project at
+//! `.codex/agents/.memory/<name>.md`, holding one `- note` bullet per line so
+//! the file stays easy to edit by hand. Notes are capped in size; when the
+//! file would grow past [`MAX_MEMORY_BYTES`] the oldest notes are dropped.
+
+use crate::parser::validate_agent_name;
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// Longest single note, in bytes.
+pub const MAX_NOTE_BYTES: usize = 500;
+/// Largest memory file, in bytes.
+pub const MAX_MEMORY_BYTES: usize = 16 * 1024;
+/// Share of the memory prepended to an agent's instructions, in bytes.
+pub const MAX_PROMPT_BYTES: usize = 4 * 1024;
+
+const HEADER: &str = "<!-- One note per `- ` line. Edit freely; the oldest notes are dropped first when the file grows too large. -->";
+
+/// Memory directory of the project rooted at `project_root`.
+pub fn memory_dir(project_root: &Path) -> PathBuf {
+    project_root.join(".codex").join("agents").join(".memory")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RememberOutcome {
+    Saved { dropped: usize },
+    AlreadyKnown,
+}
+
+#[derive(Debug, Clone)]
+pub struct AgentMemory {
+    path: PathBuf,
+}
+
+impl AgentMemory {
+    pub fn for_agent(project_root: &Path, agent_name: &str) -> io::Result<Self> {
+        validate_agent_name(agent_name)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err.to_string()))?;
+        Ok(Self::at(
+            memory_dir(project_root).join(format!("{agent_name}.md")),
+        ))
+    }
+
+    pub fn at(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Notes from oldest to newest; empty when the file does not exist.
+    pub fn notes(&self) -> io::Result<Vec<String>> {
+        match fs::read_to_string(&self.path) {
+            Ok(text) => Ok(parse_notes(&text)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Append `note`, dropping the oldest notes if the file would exceed
+    /// [`MAX_MEMORY_BYTES`].
+    pub fn remember(&self, note: &str) -> io::Result<RememberOutcome> {
+        let note = normalize_note(note)?;
+        let mut notes = self.notes()?;
+        let key = note.to_lowercase();
+        if notes.iter().any(|existing| existing.to_lowercase() == key) {
+            return Ok(RememberOutcome::AlreadyKnown);
+        }
+        notes.push(note);
+        let mut dropped = 0;
+        while render_notes(&notes).len() > MAX_MEMORY_BYTES && notes.len() > 1 {
+            notes.remove(0);
+            dropped += 1;
+        }
+        self.write(&notes)?;
+        Ok(RememberOutcome::Saved { dropped })
+    }
+
+    /// Up to `limit` notes sharing the most words with `query`, best match
+    /// first. An empty query returns the newest notes.
+    pub fn recall(&self, query: &str, limit: usize) -> io::Result<Vec<String>> {
+        let notes = self.notes()?;
+        let terms = words(query);
+        if terms.is_empty() {
+            return Ok(notes.into_iter().rev().take(limit).collect());
+        }
+        let mut scored: Vec<(usize, usize, String)> = notes
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, note)| {
+                let score = words(&note).intersection(&terms).count();
+                (score > 0).then_some((score, index, note))
+            })
+            .collect();
+        // Higher score first; newer notes win ties.
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then(b.1.cmp(&a.1)));
+        Ok(scored
+            .into_iter()
+            .take(limit)
+            .map(|(_, _, note)| note)
+            .collect())
+    }
+
+    /// The newest notes that fit in [`MAX_PROMPT_BYTES`], oldest first, as a
+    /// section to prepend to the agent's instructions.
+    pub fn prompt_section(&self) -> io::Result<Option<String>> {
+        let notes = self.notes()?;
+        let mut kept = Vec::new();
+        let mut used = 0;
+        for note in notes.iter().rev() {
+            used += note.len() + 3;
+            if used > MAX_PROMPT_BYTES {
+                break;
+            }
+            kept.push(note.as_str());
+        }
+        if kept.is_empty() {
+            return Ok(None);
+        }
+        kept.reverse();
+        let mut section = String::from(
+            "## Memory\nNotes you saved in earlier runs on this project. Use `recall` to search for more and `remember` to add to them.\n",
+        );
+        for note in kept {
+            section.push_str("- ");
+            section.push_str(note);
+            section.push('\n');
+        }
+        Ok(Some(section))
+    }
+
+    /// Delete the memory file. Returns false when there was nothing to clear.
+    pub fn clear(&self) -> io::Result<bool> {
+        match fs::remove_file(&self.path) {
+            Ok(()) => Ok(true),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Create the file with its header if it does not exist, e.g. before
+    /// opening it in an editor.
+    pub fn ensure_file(&self) -> io::Result<()> {
+        if self.path.exists() {
+            return Ok(());
+        }
+        self.write(&[])
+    }
+
+    fn write(&self, notes: &[String]) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, render_notes(notes))
+    }
+}
+
+fn normalize_note(note: &str) -> io::Result<String> {
+    let note = note.split_whitespace().collect::<Vec<_>>().join(" ");
+    let note = note.trim_start_matches("- ").trim().to_string();
+    if note.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "note must not be empty",
+        ));
+    }
+    if note.len() > MAX_NOTE_BYTES {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "note is {} bytes; keep it under {MAX_NOTE_BYTES}",
+                note.len()
+            ),
+        ));
+    }
+    Ok(note)
+}
+
+fn parse_notes(text: &str) -> Vec<String> {
+    text.lines()
+        .filter_map(|line| line.trim().strip_prefix("- "))
+        .map(str::trim)
+        .filter(|note| !note.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn render_notes(notes: &[String]) -> String {
+    let mut text = format!("{HEADER}\n");
+    for note in notes {
+        text.push_str("- ");
+        text.push_str(note);
+        text.push('\n');
+    }
+    text
+}
+
+fn words(text: &str) -> BTreeSet<String> {
+    text.split(|ch: char| !ch.is_alphanumeric() && ch != '_')
+        .filter(|word| word.len() > 2)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn remembers_and_recalls_notes() {
+        let project = TempDir::new().expect("tempdir");
+        let memory = AgentMemory::for_agent(project.path(), "reviewer").expect("memory");
+        assert_eq!(
+            memory.path(),
+            project.path().join(".codex/agents/.memory/reviewer.md")
+        );
+        assert_eq!(memory.prompt_section().expect("section"), None);
+
+        memory
+            .remember("Errors use thiserror enums in each crate")
+            .expect("remember");
+        memory
+            .remember("  Tests use pretty_assertions\n and insta snapshots ")
+            .expect("remember");
+        assert_eq!(
+            memory
+                .remember("tests use PRETTY_ASSERTIONS and insta snapshots")
+                .expect("duplicate"),
+            RememberOutcome::AlreadyKnown
+        );
+        assert!(memory.remember(" ").is_err());
+        assert!(memory.remember(&"x".repeat(MAX_NOTE_BYTES + 1)).is_err());
+
+        assert_eq!(
+            memory.recall("which snapshot tests?", 5).expect("recall"),
+            vec!["Tests use pretty_assertions and insta snapshots".to_string()]
+        );
+        assert_eq!(
+            memory.recall("", 1).expect("recall"),
+            vec!["Tests use pretty_assertions and insta snapshots".to_string()]
+        );
+        assert_eq!(
+            memory.prompt_section().expect("section").as_deref(),
+            Some(
+                "## Memory\nNotes you saved in earlier runs on this project. Use `recall` to search for more and `remember` to add to them.\n- Errors use thiserror enums in each crate\n- Tests use pretty_assertions and insta snapshots\n"
+            )
+        );
+
+        assert!(memory.clear().expect("clear"));
+        assert!(!memory.clear().expect("clear again"));
+        assert!(memory.notes().expect("notes").is_empty());
+    }
+
+    #[test]
+    fn drops_oldest_notes_past_the_size_limit() {
+        let project = TempDir::new().expect("tempdir");
+        let memory = AgentMemory::for_agent(project.path(), "reviewer").expect("memory");
+        let note = |index: usize| format!("note {index:03} {}", "x".repeat(400));
+        let mut dropped = 0;
+        for index in 0..50 {
+            if let RememberOutcome::Saved { dropped: count } =
+                memory.remember(&note(index)).expect("remember")
+            {
+                dropped += count;
+            }
+        }
+        let notes = memory.notes().expect("notes");
+        assert!(dropped > 0);
+        assert_eq!(notes.len() + dropped, 50);
+        assert_eq!(notes.last(), Some(&note(49)));
+        assert!(fs::metadata(memory.path()).expect("file").len() as usize <= MAX_MEMORY_BYTES);
+        assert!(
+            memory
+                .prompt_section()
+                .expect("section")
+                .is_some_and(|section| section.len() <= MAX_PROMPT_BYTES + 200)
+        );
+
+        assert!(AgentMemory::for_agent(project.path(), "../escape").is_err());
+    }
+}
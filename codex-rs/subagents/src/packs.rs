@@ -0,0 +1,1027 @@
+//! Installable agent packs.
+//!
+//! A pack is a directory with a `pack.toml` manifest next to its agent files.
+//! Packs are installed from a local directory, a tarball, or a git repository
+//! into `<agents dir>/packs/<pack>/`, where `<agents dir>` is the project's
+//! `.codex/agents` or `$CODEX_HOME/agents`. Each installed pack is recorded in
+//! `<agents dir>/agents.lock` with its source, resolved revision and a hash of
+//! its files; the registry only loads packs whose files still match the lock.
+
+use crate::error::PackError;
+use crate::parser::parse_confined_agent_file;
+use crate::parser::validate_agent_name;
+use crate::spec::AgentSource;
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Manifest file at the root of every pack.
+pub const PACK_MANIFEST: &str = "pack.toml";
+/// Lockfile recording the packs installed in an agents directory.
+pub const LOCK_FILE: &str = "agents.lock";
+/// Subdirectory of an agents directory that holds installed packs.
+pub const PACKS_DIR: &str = "packs";
+
+const LOCK_VERSION: u32 = 1;
+const LOCK_HEADER: &str =
+    "# Written by `codex subagents install`; edit with `codex subagents update` and `remove`.\n";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PackManifest {
+    pub name: String,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Where a pack is installed from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PackSource {
+    Path(PathBuf),
+    Tarball(PathBuf),
+    /// A repository URL or local repository path, optionally pinned to a
+    /// branch, tag or commit.
+    Git {
+        url: String,
+        rev: Option<String>,
+    },
+}
+
+impl PackSource {
+    /// Interpret `spec` as given on the command line: `<git-url>[@rev]`, a
+    /// `.tar`, `.tar.gz` or `.tgz` file, or a pack directory. Relative paths
+    /// are resolved against `cwd`.
+    pub fn parse(spec: &str, cwd: &Path) -> Result<Self, PackError> {
+        let spec = spec.trim();
+        let unknown = || PackError::UnknownSource(spec.to_string());
+        if spec.is_empty() {
+            return Err(unknown());
+        }
+
+        let (location, rev) = split_rev(spec);
+        if location.starts_with('-') || rev.as_deref().is_some_and(|rev| rev.starts_with('-')) {
+            return Err(unknown());
+        }
+        if is_remote_url(location) {
+            return Ok(PackSource::Git {
+                url: location.to_string(),
+                rev,
+            });
+        }
+        let repo = cwd.join(location);
+        if location.ends_with(".git") || is_bare_repo(&repo) {
+            return Ok(PackSource::Git {
+                url: repo.to_string_lossy().into_owned(),
+                rev,
+            });
+        }
+
+        let path = cwd.join(spec);
+        if path.is_file() && is_tarball(&path) {
+            Ok(PackSource::Tarball(path))
+        } else if path.is_dir() {
+            Ok(PackSource::Path(path))
+        } else {
+            Err(unknown())
+        }
+    }
+
+    pub fn kind(&self) -> SourceKind {
+        match self {
+            PackSource::Path(_) => SourceKind::Path,
+            PackSource::Tarball(_) => SourceKind::Tarball,
+            PackSource::Git { .. } => SourceKind::Git,
+        }
+    }
+
+    fn location(&self) -> String {
+        match self {
+            PackSource::Path(path) | PackSource::Tarball(path) => {
+                path.to_string_lossy().into_owned()
+            }
+            PackSource::Git { url, .. } => url.clone(),
+        }
+    }
+}
+
+impl fmt::Display for PackSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PackSource::Git {
+                url,
+                rev: Some(rev),
+            } => write!(f, "{url}@{rev}"),
+            _ => f.write_str(&self.location()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceKind {
+    Path,
+    Tarball,
+    Git,
+}
+
+/// One `[[pack]]` entry of `agents.lock`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedPack {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    pub kind: SourceKind,
+    pub source: String,
+    /// Branch, tag or commit requested at install time; `update` follows it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rev: Option<String>,
+    /// Commit the pack was installed from, for git sources.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revision: Option<String>,
+    /// `sha256:<hex>` over the installed files; see [`content_hash`].
+    pub hash: String,
+    #[serde(default)]
+    pub agents: Vec<String>,
+}
+
+impl LockedPack {
+    pub fn pack_source(&self) -> PackSource {
+        match self.kind {
+            SourceKind::Path => PackSource::Path(PathBuf::from(&self.source)),
+            SourceKind::Tarball => PackSource::Tarball(PathBuf::from(&self.source)),
+            SourceKind::Git => PackSource::Git {
+                url: self.source.clone(),
+                rev: self.rev.clone(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentsLock {
+    pub version: u32,
+    #[serde(default, rename = "pack")]
+    pub packs: Vec<LockedPack>,
+}
+
+impl Default for AgentsLock {
+    fn default() -> Self {
+        Self {
+            version: LOCK_VERSION,
+            packs: Vec::new(),
+        }
+    }
+}
+
+impl AgentsLock {
+    pub fn path(agents_dir: &Path) -> PathBuf {
+        agents_dir.join(LOCK_FILE)
+    }
+
+    /// Lock of `agents_dir`; empty when it has no `agents.lock`.
+    pub fn load(agents_dir: &Path) -> Result<Self, PackError> {
+        let path = Self::path(agents_dir);
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(source) => return Err(PackError::Io { path, source }),
+        };
+        let lock: Self = toml::from_str(&text).map_err(|err| PackError::InvalidLock {
+            path: path.clone(),
+            reason: err.to_string(),
+        })?;
+        if lock.version != LOCK_VERSION {
+            return Err(PackError::InvalidLock {
+                path,
+                reason: format!("unsupported lockfile version {}", lock.version),
+            });
+        }
+        for pack in &lock.packs {
+            let invalid = |reason: String| PackError::InvalidLock {
+                path: path.clone(),
+                reason,
+            };
+            validate_agent_name(&pack.name)
+                .map_err(|err| invalid(format!("pack `{}`: {err}", pack.name)))?;
+            if pack.source.starts_with('-')
+                || pack.rev.as_deref().is_some_and(|rev| rev.starts_with('-'))
+            {
+                return Err(invalid(format!(
+                    "pack `{}` has a source or rev starting with `-`",
+                    pack.name
+                )));
+            }
+        }
+        Ok(lock)
+    }
+
+    pub fn save(&self, agents_dir: &Path) -> Result<(), PackError> {
+        let path = Self::path(agents_dir);
+        let body = toml::to_string(self).map_err(|err| PackError::InvalidLock {
+            path: path.clone(),
+            reason: err.to_string(),
+        })?;
+        fs::create_dir_all(agents_dir).map_err(|source| PackError::Io {
+            path: agents_dir.to_path_buf(),
+            source,
+        })?;
+        fs::write(&path, format!("{LOCK_HEADER}{body}"))
+            .map_err(|source| PackError::Io { path, source })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&LockedPack> {
+        self.packs.iter().find(|pack| pack.name == name)
+    }
+
+    fn upsert(&mut self, pack: LockedPack) {
+        self.packs.retain(|existing| existing.name != pack.name);
+        self.packs.push(pack);
+        self.packs.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+}
+
+/// State of one pack directory compared with `agents.lock`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PackStatus {
+    Verified,
+    Modified {
+        expected: String,
+        actual: String,
+    },
+    /// Locked, but `packs/<name>/` is gone.
+    Missing,
+    /// `packs/<name>/` exists without a lock entry.
+    Unlocked,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackCheck {
+    pub name: String,
+    pub dir: PathBuf,
+    pub status: PackStatus,
+}
+
+impl PackCheck {
+    /// Why the pack's agents are not loaded, or `None` when it verified.
+    pub fn problem(&self) -> Option<String> {
+        let name = &self.name;
+        match &self.status {
+            PackStatus::Verified => None,
+            PackStatus::Modified { expected, actual } => Some(format!(
+                "pack `{name}` does not match {LOCK_FILE} (expected {expected}, found {actual}); restore it with `codex subagents update {name}`"
+            )),
+            PackStatus::Missing => Some(format!(
+                "pack `{name}` is recorded in {LOCK_FILE} but not installed; restore it with `codex subagents update {name}`"
+            )),
+            PackStatus::Unlocked => Some(format!(
+                "pack `{name}` is not recorded in {LOCK_FILE}; install packs with `codex subagents install`"
+            )),
+        }
+    }
+}
+
+/// Compare every pack under `agents_dir` with its lock entry.
+pub fn verify_packs(agents_dir: &Path) -> Result<Vec<PackCheck>, PackError> {
+    let lock = AgentsLock::load(agents_dir)?;
+    let packs_dir = agents_dir.join(PACKS_DIR);
+    let mut installed = installed_pack_dirs(&packs_dir)?;
+
+    let mut checks = Vec::new();
+    for locked in &lock.packs {
+        let dir = packs_dir.join(&locked.name);
+        let status = if installed.remove(&locked.name) {
+            let actual = content_hash(&dir)?;
+            if actual == locked.hash {
+                PackStatus::Verified
+            } else {
+                PackStatus::Modified {
+                    expected: locked.hash.clone(),
+                    actual,
+                }
+            }
+        } else {
+            PackStatus::Missing
+        };
+        checks.push(PackCheck {
+            name: locked.name.clone(),
+            dir,
+            status,
+        });
+    }
+    for name in installed {
+        checks.push(PackCheck {
+            dir: packs_dir.join(&name),
+            name,
+            status: PackStatus::Unlocked,
+        });
+    }
+    Ok(checks)
+}
+
+/// `sha256:<hex>` over every file under `dir` except `.git`, keyed by its
+/// `/`-separated relative path, so the hash does not depend on where the
+/// pack is installed.
+pub fn content_hash(dir: &Path) -> Result<String, PackError> {
+    let mut files = Vec::new();
+    collect_files(dir, dir, &mut files)?;
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    for relative in files {
+        let path = dir.join(&relative);
+        let contents = fs::read(&path).map_err(|source| PackError::Io { path, source })?;
+        hasher.update(relative.as_bytes());
+        hasher.update([0]);
+        hasher.update((contents.len() as u64).to_le_bytes());
+        hasher.update(&contents);
+    }
+    Ok(format!("sha256:{:x}", hasher.finalize()))
+}
+
+/// Installs, updates and removes the packs of one agents directory.
+#[derive(Debug, Clone)]
+pub struct PackStore {
+    agents_dir: PathBuf,
+}
+
+impl PackStore {
+    pub fn new(agents_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            agents_dir: agents_dir.into(),
+        }
+    }
+
+    pub fn agents_dir(&self) -> &Path {
+        &self.agents_dir
+    }
+
+    pub fn lock(&self) -> Result<AgentsLock, PackError> {
+        AgentsLock::load(&self.agents_dir)
+    }
+
+    /// Fetch `source`, validate its manifest and agents, and record it in the
+    /// lock. Fails if a pack with the same name is already installed.
+    pub fn install(&self, source: &PackSource) -> Result<LockedPack, PackError> {
+        self.install_inner(source, None)
+    }
+
+    /// Re-fetch an installed pack from its recorded source, following `rev`
+    /// instead of the recorded one when given. Returns the previous and new
+    /// lock entries.
+    pub fn update(
+        &self,
+        name: &str,
+        rev: Option<String>,
+    ) -> Result<(LockedPack, LockedPack), PackError> {
+        let previous = self
+            .lock()?
+            .get(name)
+            .cloned()
+            .ok_or_else(|| PackError::NotInstalled(name.to_string()))?;
+        let source = match (previous.pack_source(), rev) {
+            (PackSource::Git { url, .. }, Some(rev)) => PackSource::Git {
+                url,
+                rev: Some(rev),
+            },
+            (_, Some(_)) => return Err(PackError::RevisionNotSupported(name.to_string())),
+            (source, None) => source,
+        };
+        let current = self.install_inner(&source, Some(name))?;
+        Ok((previous, current))
+    }
+
+    /// Delete an installed pack and its lock entry.
+    pub fn remove(&self, name: &str) -> Result<LockedPack, PackError> {
+        let mut lock = self.lock()?;
+        let removed = lock
+            .get(name)
+            .cloned()
+            .ok_or_else(|| PackError::NotInstalled(name.to_string()))?;
+        let dir = self.packs_dir().join(name);
+        match fs::remove_dir_all(&dir) {
+            Ok(()) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(source) => return Err(PackError::Io { path: dir, source }),
+        }
+        lock.packs.retain(|pack| pack.name != name);
+        lock.save(&self.agents_dir)?;
+        Ok(removed)
+    }
+
+    fn packs_dir(&self) -> PathBuf {
+        self.agents_dir.join(PACKS_DIR)
+    }
+
+    /// Installs `source`; `replacing` names the pack an update expects to
+    /// overwrite.
+    fn install_inner(
+        &self,
+        source: &PackSource,
+        replacing: Option<&str>,
+    ) -> Result<LockedPack, PackError> {
+        let packs_dir = self.packs_dir();
+        let staging = Staging::create(&packs_dir)?;
+        let (root, revision) = fetch(source, staging.path())?;
+
+        let manifest = read_manifest(&root)?;
+        validate_agent_name(&manifest.name).map_err(|err| PackError::InvalidManifest {
+            path: root.join(PACK_MANIFEST),
+            reason: err.to_string(),
+        })?;
+        let mut lock = self.lock()?;
+        match replacing {
+            Some(expected) if expected != manifest.name => {
+                return Err(PackError::Renamed {
+                    expected: expected.to_string(),
+                    found: manifest.name,
+                });
+            }
+            Some(_) => {}
+            None if lock.get(&manifest.name).is_some()
+                || packs_dir.join(&manifest.name).exists() =>
+            {
+                return Err(PackError::AlreadyInstalled(manifest.name));
+            }
+            None => {}
+        }
+
+        let unpacked = staging.path().join("pack");
+        copy_dir(&root, &unpacked)?;
+        let agents = validate_agents(&manifest.name, &unpacked)?;
+        let hash = content_hash(&unpacked)?;
+
+        let target = packs_dir.join(&manifest.name);
+        if target.exists() {
+            fs::remove_dir_all(&target).map_err(|source| PackError::Io {
+                path: target.clone(),
+                source,
+            })?;
+        }
+        fs::rename(&unpacked, &target).map_err(|source| PackError::Io {
+            path: target.clone(),
+            source,
+        })?;
+
+        let (rev, revision) = match source {
+            PackSource::Git { rev, .. } => (rev.clone(), revision),
+            _ => (None, None),
+        };
+        let locked = LockedPack {
+            name: manifest.name,
+            version: manifest.version,
+            kind: source.kind(),
+            source: source.location(),
+            rev,
+            revision,
+            hash,
+            agents,
+        };
+        lock.upsert(locked.clone());
+        lock.save(&self.agents_dir)?;
+        Ok(locked)
+    }
+}
+
+/// Scratch directory under `packs/` that is removed when dropped. Living
+/// next to the final location lets the pack be moved into place with a
+/// rename; the leading dot keeps it out of [`verify_packs`].
+struct Staging(PathBuf);
+
+impl Staging {
+    fn create(packs_dir: &Path) -> Result<Self, PackError> {
+        let path = packs_dir.join(format!(".staging-{}", uuid::Uuid::now_v7()));
+        fs::create_dir_all(&path).map_err(|source| PackError::Io {
+            path: path.clone(),
+            source,
+        })?;
+        Ok(Self(path))
+    }
+
+    fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for Staging {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Bring `source` onto disk and return the pack root plus, for git, the
+/// checked-out commit.
+fn fetch(source: &PackSource, staging: &Path) -> Result<(PathBuf, Option<String>), PackError> {
+    match source {
+        PackSource::Path(path) => Ok((path.clone(), None)),
+        PackSource::Tarball(path) => {
+            let extracted = staging.join("extract");
+            fs::create_dir_all(&extracted).map_err(|source| PackError::Io {
+                path: extracted.clone(),
+                source,
+            })?;
+            let mut tar = Command::new("tar");
+            tar.arg("-xf").arg(path).arg("-C").arg(&extracted);
+            run(tar)?;
+            Ok((find_pack_root(&extracted), None))
+        }
+        PackSource::Git { url, rev } => {
+            let checkout = staging.join("checkout");
+            let mut clone = git();
+            clone.args(["clone", "--quiet", "--", url]).arg(&checkout);
+            run(clone)?;
+            if let Some(rev) = rev {
+                let mut switch = git();
+                switch
+                    .arg("-C")
+                    .arg(&checkout)
+                    .args(["checkout", "--quiet", "--detach", rev]);
+                run(switch)?;
+            }
+            let mut head = git();
+            head.arg("-C").arg(&checkout).args(["rev-parse", "HEAD"]);
+            let commit = run(head)?;
+            Ok((checkout, Some(commit)))
+        }
+    }
+}
+
+fn git() -> Command {
+    let mut command = Command::new("git");
+    command
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .args(["-c", "advice.detachedHead=false"]);
+    command
+}
+
+/// Run `command`, returning its trimmed stdout.
+fn run(mut command: Command) -> Result<String, PackError> {
+    let display = format!(
+        "{} {}",
+        command.get_program().to_string_lossy(),
+        command
+            .get_args()
+            .map(|arg| arg.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join(" ")
+    );
+    let output = command.output().map_err(|err| PackError::Command {
+        command: display.clone(),
+        stderr: err.to_string(),
+    })?;
+    if !output.status.success() {
+        return Err(PackError::Command {
+            command: display,
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Tarballs usually wrap the pack in one top-level directory.
+fn find_pack_root(extracted: &Path) -> PathBuf {
+    if extracted.join(PACK_MANIFEST).is_file() {
+        return extracted.to_path_buf();
+    }
+    let dirs: Vec<PathBuf> = fs::read_dir(extracted)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    match dirs.as_slice() {
+        [only] => only.clone(),
+        _ => extracted.to_path_buf(),
+    }
+}
+
+fn read_manifest(root: &Path) -> Result<PackManifest, PackError> {
+    let path = root.join(PACK_MANIFEST);
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return Err(PackError::MissingManifest(root.to_path_buf()));
+        }
+        Err(source) => return Err(PackError::Io { path, source }),
+    };
+    toml::from_str(&text).map_err(|err| PackError::InvalidManifest {
+        path,
+        reason: err.to_string(),
+    })
+}
+
+/// Parse every agent file at the top of `dir`, returning their names.
+/// Instructions files must stay inside `dir`.
+fn validate_agents(pack: &str, dir: &Path) -> Result<Vec<String>, PackError> {
+    let mut names = Vec::new();
+    for path in pack_agent_files(dir) {
+        let parsed =
+            parse_confined_agent_file(&path, dir, AgentSource::Project).map_err(|source| {
+                PackError::InvalidAgent {
+                    pack: pack.to_string(),
+                    path: path.clone(),
+                    source: Box::new(source),
+                }
+            })?;
+        names.push(parsed.spec.metadata.name);
+    }
+    if names.is_empty() {
+        return Err(PackError::NoAgents(pack.to_string()));
+    }
+    names.sort();
+    Ok(names)
+}
+
+/// Agent files at the top level of an installed pack, sorted.
+pub fn pack_agent_files(dir: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| matches!(ext, "md" | "markdown" | "toml" | "json"))
+                && path.file_name().is_some_and(|name| name != PACK_MANIFEST)
+        })
+        .collect();
+    paths.sort();
+    paths
+}
+
+fn installed_pack_dirs(packs_dir: &Path) -> Result<BTreeSet<String>, PackError> {
+    let entries = match fs::read_dir(packs_dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(BTreeSet::new()),
+        Err(source) => {
+            return Err(PackError::Io {
+                path: packs_dir.to_path_buf(),
+                source,
+            });
+        }
+    };
+    Ok(entries
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .filter(|name| !name.starts_with('.'))
+        .collect())
+}
+
+/// Relative paths of the files under `dir`, skipping `.git`. Symlinks are
+/// rejected so a pack cannot pull in files from outside it.
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<String>) -> Result<(), PackError> {
+    let entries = fs::read_dir(dir).map_err(|source| PackError::Io {
+        path: dir.to_path_buf(),
+        source,
+    })?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if entry.file_name() == ".git" {
+            continue;
+        }
+        let file_type = entry.file_type().map_err(|source| PackError::Io {
+            path: path.clone(),
+            source,
+        })?;
+        if file_type.is_symlink() {
+            return Err(PackError::Symlink(path));
+        }
+        if file_type.is_dir() {
+            collect_files(root, &path, files)?;
+        } else if let Ok(relative) = path.strip_prefix(root) {
+            files.push(relative.to_string_lossy().replace('\\', "/"));
+        }
+    }
+    Ok(())
+}
+
+fn copy_dir(from: &Path, to: &Path) -> Result<(), PackError> {
+    let mut files = Vec::new();
+    collect_files(from, from, &mut files)?;
+    for relative in files {
+        let target = to.join(&relative);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|source| PackError::Io {
+                path: parent.to_path_buf(),
+                source,
+            })?;
+        }
+        fs::copy(from.join(&relative), &target).map_err(|source| PackError::Io {
+            path: target.clone(),
+            source,
+        })?;
+    }
+    Ok(())
+}
+
+/// Split a trailing `@rev` off the path part of a git location, leaving the
+/// `user@` of `git@host:org/repo` alone.
+fn split_rev(spec: &str) -> (&str, Option<String>) {
+    let path_start = if let Some(scheme_end) = spec.find("://") {
+        let host_start = scheme_end + 3;
+        spec[host_start..]
+            .find('/')
+            .map_or(spec.len(), |idx| host_start + idx)
+    } else {
+        scp_colon(spec).unwrap_or_default()
+    };
+    match spec[path_start..].rfind('@') {
+        Some(idx) if !spec[path_start + idx + 1..].is_empty() => {
+            let at = path_start + idx;
+            (&spec[..at], Some(spec[at + 1..].to_string()))
+        }
+        _ => (spec, None),
+    }
+}
+
+/// Position of the `:` in scp-style `[user@]host:path`, if `spec` is one.
+fn scp_colon(spec: &str) -> Option<usize> {
+    let colon = spec.find(':')?;
+    let host = &spec[..colon];
+    // Skip Windows drive letters such as `C:\packs`.
+    (host.len() > 1 && !host.contains('/') && !host.contains('\\')).then_some(colon)
+}
+
+fn is_remote_url(location: &str) -> bool {
+    location.contains("://") || scp_colon(location).is_some()
+}
+
+fn is_bare_repo(path: &Path) -> bool {
+    path.join("HEAD").is_file() && path.join("objects").is_dir()
+}
+
+fn is_tarball(path: &Path) -> bool {
+    let name = path.to_string_lossy();
+    [".tar", ".tar.gz", ".tgz"]
+        .iter()
+        .any(|suffix| name.ends_with(suffix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn write_pack(dir: &Path, name: &str, agents: &[&str]) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(
+            dir.join(PACK_MANIFEST),
+            format!("name = \"{name}\"\nversion = \"1.0.0\"\n"),
+        )
+        .unwrap();
+        for agent in agents {
+            fs::write(
+                dir.join(format!("{agent}.md")),
+                format!("---\nname: {agent}\n---\nDo {agent} things.\n"),
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn parses_sources() {
+        let temp = tempfile::tempdir().unwrap();
+        let cwd = temp.path();
+        write_pack(&cwd.join("local"), "local-pack", &["helper"]);
+        fs::write(cwd.join("pack.tgz"), "").unwrap();
+
+        assert_eq!(
+            PackSource::parse("https://example.com/org/agents.git@v1.2", cwd).unwrap(),
+            PackSource::Git {
+                url: "https://example.com/org/agents.git".to_string(),
+                rev: Some("v1.2".to_string()),
+            }
+        );
+        assert_eq!(
+            PackSource::parse("git@example.com:org/agents.git", cwd).unwrap(),
+            PackSource::Git {
+                url: "git@example.com:org/agents.git".to_string(),
+                rev: None,
+            }
+        );
+        assert_eq!(
+            PackSource::parse("git@example.com:org/agents@feature/x", cwd).unwrap(),
+            PackSource::Git {
+                url: "git@example.com:org/agents".to_string(),
+                rev: Some("feature/x".to_string()),
+            }
+        );
+        assert_eq!(
+            PackSource::parse("packs.git@main", cwd).unwrap(),
+            PackSource::Git {
+                url: cwd.join("packs.git").to_string_lossy().into_owned(),
+                rev: Some("main".to_string()),
+            }
+        );
+        assert_eq!(
+            PackSource::parse("pack.tgz", cwd).unwrap(),
+            PackSource::Tarball(cwd.join("pack.tgz"))
+        );
+        assert_eq!(
+            PackSource::parse("local", cwd).unwrap(),
+            PackSource::Path(cwd.join("local"))
+        );
+        assert!(matches!(
+            PackSource::parse("missing", cwd),
+            Err(PackError::UnknownSource(_))
+        ));
+        for spec in [
+            "--upload-pack=touch pwned",
+            "https://example.com/agents.git@--force",
+        ] {
+            assert!(matches!(
+                PackSource::parse(spec, cwd),
+                Err(PackError::UnknownSource(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn lock_rejects_unsafe_entries() {
+        let temp = tempfile::tempdir().unwrap();
+        let agents_dir = temp.path();
+        for (entry, reason) in [
+            (
+                "name = \"../escape\"\nkind = \"path\"\nsource = \"pack\"",
+                "pack `../escape`: invalid agent name",
+            ),
+            (
+                "name = \"team-pack\"\nkind = \"git\"\nsource = \"--upload-pack=touch pwned\"",
+                "pack `team-pack` has a source or rev starting with `-`",
+            ),
+        ] {
+            fs::write(
+                AgentsLock::path(agents_dir),
+                format!("version = {LOCK_VERSION}\n\n[[pack]]\n{entry}\nhash = \"sha256:0\"\n"),
+            )
+            .unwrap();
+            let err = AgentsLock::load(agents_dir).unwrap_err();
+            assert!(err.to_string().contains(reason), "{err}");
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejects_packs_with_symlinks() {
+        let temp = tempfile::tempdir().unwrap();
+        let source_dir = temp.path().join("source");
+        write_pack(&source_dir, "team-pack", &["linter"]);
+        std::os::unix::fs::symlink(temp.path(), source_dir.join("keys")).unwrap();
+        let store = PackStore::new(temp.path().join("agents"));
+
+        assert!(matches!(
+            store.install(&PackSource::Path(source_dir.clone())),
+            Err(PackError::Symlink(path)) if path == source_dir.join("keys")
+        ));
+        assert!(store.lock().unwrap().packs.is_empty());
+    }
+
+    #[test]
+    fn rejects_pack_agents_reading_instructions_outside_the_pack() {
+        let temp = tempfile::tempdir().unwrap();
+        let secret = temp.path().join("secret.md");
+        fs::write(&secret, "Leaked.\n").unwrap();
+        let source_dir = temp.path().join("source");
+        let agents_dir = temp.path().join("agents");
+        let store = PackStore::new(&agents_dir);
+        for instructions_file in ["../secret.md", secret.to_str().unwrap()] {
+            write_pack(&source_dir, "team-pack", &[]);
+            fs::write(
+                source_dir.join("leaker.toml"),
+                format!("name = \"leaker\"\ninstructions_file = {instructions_file:?}\n"),
+            )
+            .unwrap();
+
+            assert!(matches!(
+                store.install(&PackSource::Path(source_dir.clone())),
+                Err(PackError::InvalidAgent { source, .. })
+                    if matches!(*source, crate::error::ParserError::InstructionsOutsideRoot { .. })
+            ));
+            assert!(store.lock().unwrap().packs.is_empty());
+        }
+
+        // A pack recorded in the lockfile by hand is checked again at load time.
+        let installed = agents_dir.join(PACKS_DIR).join("team-pack");
+        copy_dir(&source_dir, &installed).unwrap();
+        AgentsLock {
+            version: LOCK_VERSION,
+            packs: vec![LockedPack {
+                name: "team-pack".to_string(),
+                version: None,
+                kind: SourceKind::Path,
+                source: source_dir.display().to_string(),
+                rev: None,
+                revision: None,
+                hash: content_hash(&installed).unwrap(),
+                agents: vec!["leaker".to_string()],
+            }],
+        }
+        .save(&agents_dir)
+        .unwrap();
+        let mut registry =
+            crate::registry::SubagentRegistry::new(&agents_dir, temp.path().join("user"));
+        let snapshot = registry.reload().unwrap();
+        assert!(snapshot.agents.is_empty());
+        assert_eq!(snapshot.parse_errors.len(), 1);
+        assert!(
+            snapshot.parse_errors[0].message.contains("is outside"),
+            "{:?}",
+            snapshot.parse_errors
+        );
+    }
+
+    #[test]
+    fn installs_updates_and_removes_path_packs() {
+        let temp = tempfile::tempdir().unwrap();
+        let source_dir = temp.path().join("source");
+        write_pack(&source_dir, "team-pack", &["linter", "documenter"]);
+        let agents_dir = temp.path().join("agents");
+        let store = PackStore::new(&agents_dir);
+        let source = PackSource::Path(source_dir.clone());
+
+        let locked = store.install(&source).unwrap();
+        assert_eq!(locked.agents, vec!["documenter", "linter"]);
+        assert_eq!(locked.version.as_deref(), Some("1.0.0"));
+        assert_eq!(store.lock().unwrap().packs, vec![locked.clone()]);
+        let installed = agents_dir.join(PACKS_DIR).join("team-pack");
+        assert_eq!(content_hash(&installed).unwrap(), locked.hash);
+        assert_eq!(content_hash(&source_dir).unwrap(), locked.hash);
+        assert!(matches!(
+            store.install(&source),
+            Err(PackError::AlreadyInstalled(name)) if name == "team-pack"
+        ));
+
+        fs::write(
+            installed.join("linter.md"),
+            "---\nname: linter\n---\nEdited.\n",
+        )
+        .unwrap();
+        let checks = verify_packs(&agents_dir).unwrap();
+        assert!(matches!(checks[0].status, PackStatus::Modified { .. }));
+
+        fs::remove_file(source_dir.join("documenter.md")).unwrap();
+        let (previous, current) = store.update("team-pack", None).unwrap();
+        assert_eq!(previous, locked);
+        assert_eq!(current.agents, vec!["linter"]);
+        assert_eq!(
+            verify_packs(&agents_dir).unwrap()[0].status,
+            PackStatus::Verified
+        );
+        assert!(matches!(
+            store.update("team-pack", Some("main".to_string())),
+            Err(PackError::RevisionNotSupported(_))
+        ));
+
+        store.remove("team-pack").unwrap();
+        assert!(!installed.exists());
+        assert_eq!(store.lock().unwrap().packs, Vec::new());
+        assert!(verify_packs(&agents_dir).unwrap().is_empty());
+        let leftovers: Vec<_> = fs::read_dir(agents_dir.join(PACKS_DIR))
+            .unwrap()
+            .flatten()
+            .collect();
+        assert!(leftovers.is_empty());
+    }
+
+    #[test]
+    fn rejects_packs_without_valid_agents() {
+        let temp = tempfile::tempdir().unwrap();
+        let store = PackStore::new(temp.path().join("agents"));
+
+        let empty = temp.path().join("empty");
+        write_pack(&empty, "empty-pack", &[]);
+        assert!(matches!(
+            store.install(&PackSource::Path(empty)),
+            Err(PackError::NoAgents(_))
+        ));
+
+        let broken = temp.path().join("broken");
+        write_pack(&broken, "broken-pack", &[]);
+        fs::write(broken.join("bad.md"), "no frontmatter").unwrap();
+        assert!(matches!(
+            store.install(&PackSource::Path(broken)),
+            Err(PackError::InvalidAgent { .. })
+        ));
+
+        let unnamed = temp.path().join("unnamed");
+        fs::create_dir_all(&unnamed).unwrap();
+        assert!(matches!(
+            store.install(&PackSource::Path(unnamed)),
+            Err(PackError::MissingManifest(_))
+        ));
+        assert!(store.lock().unwrap().packs.is_empty());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::builder::SubagentBuilder;
+use crate::context::ContextAttachment;
 use crate::error::ParserError;
 use crate::error::SubagentValidationError;
 use crate::spec::AgentSource;
@@ -11,6 +12,7 @@ use serde_json::Value as JsonValue;
 use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
+use std::path::PathBuf;
 
 #[derive(Debug, Clone)]
 pub struct ParsedAgent {
@@ -24,25 +26,106 @@ struct Frontmatter {
     description: Option<String>,
     model: Option<String>,
     #[serde(default)]
-    model_config: Option<FrontmatterModelConfig>,
+    model_config: Option<AgentModelConfig>,
     tools: Option<Vec<String>>,
     keywords: Option<Vec<String>>,
+    handoff_to: Option<Vec<String>>,
+    #[serde(default)]
+    memory: bool,
+    #[serde(default)]
+    context: Vec<ContextAttachment>,
+    cwd: Option<PathBuf>,
+    #[serde(default)]
+    writable_paths: Vec<PathBuf>,
+    #[serde(default)]
+    env: BTreeMap<String, String>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
-struct FrontmatterModelConfig {
-    provider: Option<String>,
-    model: Option<String>,
-    endpoint: Option<String>,
+/// An agent defined as data rather than Markdown: a `.toml`/`.json` file in an
+/// agents directory, or a `[subagents.agents.<name>]` table in `config.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AgentDefinition {
+    /// Required in agent files; optional for config tables, where it must
+    /// match the table key when set.
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub model: Option<String>,
+    pub model_config: Option<AgentModelConfig>,
+    pub tools: Option<Vec<String>>,
+    pub keywords: Option<Vec<String>>,
+    pub handoff_to: Option<Vec<String>>,
+    pub memory: bool,
+    pub context: Vec<ContextAttachment>,
+    pub cwd: Option<PathBuf>,
+    pub writable_paths: Vec<PathBuf>,
+    pub env: BTreeMap<String, String>,
+    pub instructions: Option<String>,
+    /// Read relative to the directory of the file defining the agent.
+    pub instructions_file: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct AgentModelConfig {
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    pub endpoint: Option<String>,
     #[serde(default)]
-    parameters: BTreeMap<String, JsonValue>,
+    pub parameters: BTreeMap<String, JsonValue>,
+}
+
+/// Fields shared by every way of defining an agent, once the name and
+/// instructions have been resolved.
+struct AgentFields {
+    name: String,
+    description: Option<String>,
+    model: Option<String>,
+    model_config: Option<AgentModelConfig>,
+    tools: Option<Vec<String>>,
+    keywords: Option<Vec<String>>,
+    handoff_to: Option<Vec<String>>,
+    memory: bool,
+    context: Vec<ContextAttachment>,
+    cwd: Option<PathBuf>,
+    writable_paths: Vec<PathBuf>,
+    env: BTreeMap<String, String>,
 }
 
 const FRONTMATTER_DELIM: &str = "---";
 
+/// Parse a Markdown (`.md`), TOML (`.toml`), or JSON (`.json`) agent file.
 pub fn parse_agent_file(path: &Path, source: AgentSource) -> Result<ParsedAgent, ParserError> {
+    parse_agent_file_within(path, None, source)
+}
+
+/// Like [`parse_agent_file`], but rejects an `instructions_file` that
+/// resolves outside `root`. Agents installed from a pack are confined to the
+/// pack directory this way.
+pub fn parse_confined_agent_file(
+    path: &Path,
+    root: &Path,
+    source: AgentSource,
+) -> Result<ParsedAgent, ParserError> {
+    parse_agent_file_within(path, Some(root), source)
+}
+
+fn parse_agent_file_within(
+    path: &Path,
+    root: Option<&Path>,
+    source: AgentSource,
+) -> Result<ParsedAgent, ParserError> {
     let contents = fs::read_to_string(path)?;
-    parse_agent_str(&contents, path, source)
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("toml") => {
+            let definition = toml::from_str(&contents).map_err(ParserError::InvalidToml)?;
+            parse_definition(definition, None, path, root, source)
+        }
+        Some("json") => {
+            let definition = serde_json::from_str(&contents).map_err(ParserError::InvalidJson)?;
+            parse_definition(definition, None, path, root, source)
+        }
+        _ => parse_agent_str(&contents, path, source),
+    }
 }
 
 pub fn parse_agent_str(
@@ -57,15 +140,118 @@ pub fn parse_agent_str(
     let name = frontmatter
         .name
         .ok_or(SubagentValidationError::MissingField("name"))?;
-    validate_agent_name(&name)?;
+    let fields = AgentFields {
+        name,
+        description: frontmatter.description,
+        model: frontmatter.model,
+        model_config: frontmatter.model_config,
+        tools: frontmatter.tools,
+        keywords: frontmatter.keywords,
+        handoff_to: frontmatter.handoff_to,
+        memory: frontmatter.memory,
+        context: frontmatter.context,
+        cwd: frontmatter.cwd,
+        writable_paths: frontmatter.writable_paths,
+        env: frontmatter.env,
+    };
+    build_agent(fields, body, path, source)
+}
+
+/// Build an agent from a data definition found at `path`. `key` is the table
+/// key for agents defined in `config.toml`; agent files name themselves.
+pub fn parse_agent_definition(
+    definition: AgentDefinition,
+    key: Option<&str>,
+    path: &Path,
+    source: AgentSource,
+) -> Result<ParsedAgent, ParserError> {
+    parse_definition(definition, key, path, None, source)
+}
+
+fn parse_definition(
+    definition: AgentDefinition,
+    key: Option<&str>,
+    path: &Path,
+    root: Option<&Path>,
+    source: AgentSource,
+) -> Result<ParsedAgent, ParserError> {
+    let name = match (key, definition.name) {
+        (Some(key), Some(name)) if name != key => {
+            return Err(SubagentValidationError::InvalidName {
+                name,
+                reason: format!("name must match the table key `{key}`"),
+            }
+            .into());
+        }
+        (_, Some(name)) => name,
+        (Some(key), None) => key.to_string(),
+        (None, None) => return Err(SubagentValidationError::MissingField("name").into()),
+    };
+
+    let instructions = match (definition.instructions, definition.instructions_file) {
+        (Some(_), Some(_)) => return Err(SubagentValidationError::ConflictingInstructions.into()),
+        (Some(instructions), None) => instructions,
+        (None, Some(file)) => read_instructions_file(path, file, root)?,
+        (None, None) => String::new(),
+    };
+
+    let fields = AgentFields {
+        name,
+        description: definition.description,
+        model: definition.model,
+        model_config: definition.model_config,
+        tools: definition.tools,
+        keywords: definition.keywords,
+        handoff_to: definition.handoff_to,
+        memory: definition.memory,
+        context: definition.context,
+        cwd: definition.cwd,
+        writable_paths: definition.writable_paths,
+        env: definition.env,
+    };
+    build_agent(fields, &instructions, path, source)
+}
+
+/// Read `file` relative to the directory of the agent file at `path`,
+/// refusing to leave `root` when one is given.
+fn read_instructions_file(
+    path: &Path,
+    file: PathBuf,
+    root: Option<&Path>,
+) -> Result<String, ParserError> {
+    let file = match path.parent() {
+        Some(dir) => dir.join(file),
+        None => file,
+    };
+    let instructions_error = |source| ParserError::InstructionsFile {
+        path: file.clone(),
+        source,
+    };
+    if let Some(root) = root {
+        let resolved = file.canonicalize().map_err(instructions_error)?;
+        let root = root.canonicalize().map_err(instructions_error)?;
+        if !resolved.starts_with(&root) {
+            return Err(ParserError::InstructionsOutsideRoot { path: file, root });
+        }
+    }
+    fs::read_to_string(&file).map_err(instructions_error)
+}
 
-    let instructions = body.trim().to_string();
+fn build_agent(
+    fields: AgentFields,
+    instructions: &str,
+    path: &Path,
+    source: AgentSource,
+) -> Result<ParsedAgent, ParserError> {
+    validate_agent_name(&fields.name)?;
+
+    let instructions = instructions.trim().to_string();
     if instructions.is_empty() {
         return Err(SubagentValidationError::MissingField("instructions").into());
     }
 
-    let simple_model = normalize_optional_string(frontmatter.model);
-    let model_binding = match frontmatter.model_config {
+    let simple_model = normalize_optional_string(fields.model);
+    let model_binding = match fields.model_config {
         Some(cfg) => Some(parse_model_config(cfg, &simple_model)?),
         None => simple_model.clone().map(|model| ModelBinding {
             provider_id: None,
@@ -75,20 +261,28 @@ pub fn parse_agent_str(
         }),
     };
 
-    let mut builder = SubagentBuilder::new(name)
-        .description(frontmatter.description)
+    let mut builder = SubagentBuilder::new(fields.name)
+        .description(fields.description)
         .model(simple_model.clone())
         .model_config(model_binding)
+        .memory(fields.memory)
+        .context(fields.context)
+        .cwd(fields.cwd)
+        .writable_paths(fields.writable_paths)
+        .env(fields.env)
         .source(source)
         .source_path(path.to_path_buf())
         .instructions(instructions);
 
-    if let Some(tools) = frontmatter.tools {
+    if let Some(tools) = fields.tools {
         builder = builder.tools(tools);
     }
-    if let Some(keywords) = frontmatter.keywords {
+    if let Some(keywords) = fields.keywords {
         builder = builder.keywords(keywords);
     }
+    if let Some(handoff_to) = fields.handoff_to {
+        builder = builder.handoff_to(handoff_to);
+    }
 
     let spec = builder.build()?;
     let warnings = Vec::new();
@@ -97,7 +291,7 @@ pub fn parse_agent_str(
 }
 
 fn parse_model_config(
-    raw: FrontmatterModelConfig,
+    raw: AgentModelConfig,
     simple_model: &Option<String>,
 ) -> Result<ModelBinding, ParserError> {
     let provider_id = raw
@@ -221,13 +415,14 @@ mod tests {
 
     #[test]
     fn parses_frontmatter_and_body() {
-        let doc = "---\nname: reviewer\ndescription: Review diffs\ntools: [apply_patch]\n---\nBody text here.";
+        let doc = "---\nname: reviewer\ndescription: Review diffs\ntools: [apply_patch]\nmemory: true\n---\nBody text here.";
         let parsed = parse_agent_str(doc, Path::new("reviewer.md"), AgentSource::Project).unwrap();
         assert_eq!(parsed.spec.metadata.name, "reviewer");
         assert_eq!(parsed.spec.instructions, "Body text here.");
         assert_eq!(parsed.spec.metadata.tools, vec!["apply_patch"]);
         assert_eq!(parsed.spec.metadata.model.as_deref(), None);
         assert!(parsed.spec.metadata.model_config.is_none());
+        assert!(parsed.spec.metadata.memory);
     }
 
     #[test]
@@ -257,6 +452,45 @@ Follow the usual review checklist.";
         assert_eq!(binding.parameters.get("temperature"), Some(&json!(0.1)));
     }
 
+    #[test]
+    fn parses_scope_and_rejects_paths_outside_the_project() {
+        let doc = r#"---
+name: frontend-dev
+cwd: ./web/
+writable_paths: [web/src, shared/types]
+env:
+  NODE_ENV: development
+  PORT: "3000"
+---
+Work on the web app."#;
+        let parsed =
+            parse_agent_str(doc, Path::new("frontend-dev.md"), AgentSource::Project).unwrap();
+        let metadata = &parsed.spec.metadata;
+        assert_eq!(metadata.cwd.as_deref(), Some(Path::new("web")));
+        assert_eq!(
+            metadata.writable_paths,
+            vec![PathBuf::from("web/src"), PathBuf::from("shared/types")]
+        );
+        assert_eq!(metadata.env.get("PORT").map(String::as_str), Some("3000"));
+
+        for (frontmatter, expected) in [
+            (
+                "cwd: ../other",
+                "cwd entry `../other` must not contain `..`",
+            ),
+            (
+                "writable_paths: [/etc]",
+                "writable_paths entry `/etc` must be relative to the project root",
+            ),
+            ("env: {\"A=B\": x}", "invalid env variable name `A=B`"),
+        ] {
+            let doc = format!("---\nname: frontend-dev\n{frontmatter}\n---\nbody");
+            let err = parse_agent_str(&doc, Path::new("frontend-dev.md"), AgentSource::Project)
+                .unwrap_err();
+            assert_eq!(err.to_string(), expected);
+        }
+    }
+
     #[test]
     fn rejects_conflicting_models() {
         let doc = r"---
@@ -287,4 +521,115 @@ text"#;
             ParserError::Validation(SubagentValidationError::InvalidModelProvider)
         ));
     }
+
+    #[test]
+    fn parses_handoff_targets() {
+        let doc = "---\nname: code-writer\nhandoff_to: [tester, reviewer]\n---\nWrite code.";
+        let parsed =
+            parse_agent_str(doc, Path::new("code-writer.md"), AgentSource::Project).unwrap();
+        assert_eq!(parsed.spec.metadata.handoff_to, vec!["tester", "reviewer"]);
+
+        let doc = "---\nname: tester\nhandoff_to: [tester]\n---\nRun tests.";
+        let err = parse_agent_str(doc, Path::new("tester.md"), AgentSource::Project).unwrap_err();
+        assert!(matches!(
+            err,
+            ParserError::Validation(SubagentValidationError::SelfHandoff(_))
+        ));
+
+        let doc = "---\nname: tester\nhandoff_to: [Not Valid]\n---\nRun tests.";
+        let err = parse_agent_str(doc, Path::new("tester.md"), AgentSource::Project).unwrap_err();
+        assert!(matches!(
+            err,
+            ParserError::Validation(SubagentValidationError::InvalidName { .. })
+        ));
+    }
+
+    #[test]
+    fn parses_context_attachments() {
+        let doc = "---\nname: reviewer\ncontext:\n  - git_diff: main\n  - files: [\"src/**/*.rs\"]\n  - parent_last_messages: 4\n  - plan\n---\nReview.";
+        let parsed = parse_agent_str(doc, Path::new("reviewer.md"), AgentSource::Project).unwrap();
+        assert_eq!(
+            parsed.spec.metadata.context,
+            vec![
+                ContextAttachment::GitDiff {
+                    base: Some("main".to_string())
+                },
+                ContextAttachment::Files(vec!["src/**/*.rs".to_string()]),
+                ContextAttachment::ParentLastMessages(4),
+                ContextAttachment::Plan,
+            ]
+        );
+
+        let definition: AgentDefinition = toml::from_str(
+            "name = \"reviewer\"\ninstructions = \"Review.\"\ncontext = [\"git_diff\", { parent_last_messages = 2 }]\n",
+        )
+        .unwrap();
+        let parsed =
+            parse_agent_definition(definition, None, Path::new("a.toml"), AgentSource::User)
+                .unwrap();
+        assert_eq!(
+            parsed.spec.metadata.context,
+            vec![
+                ContextAttachment::GitDiff { base: None },
+                ContextAttachment::ParentLastMessages(2),
+            ]
+        );
+
+        let doc = "---\nname: reviewer\ncontext: [parent_last_messages=0]\n---\nReview.";
+        assert!(parse_agent_str(doc, Path::new("reviewer.md"), AgentSource::Project).is_err());
+    }
+
+    #[test]
+    fn parses_agent_definitions() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("config.toml");
+        fs::write(temp.path().join("triage.md"), "Sort incoming issues.\n").unwrap();
+
+        let definition: AgentDefinition = toml::from_str(
+            r#"
+description = "Triage issues"
+keywords = ["triage"]
+instructions_file = "triage.md"
+model_config = { provider = "openai", model = "gpt-5", parameters = { temperature = 0.2 } }
+"#,
+        )
+        .unwrap();
+        let parsed = parse_agent_definition(
+            definition.clone(),
+            Some("triage"),
+            &path,
+            AgentSource::Config,
+        )
+        .unwrap();
+        assert_eq!(parsed.spec.metadata.name, "triage");
+        assert_eq!(parsed.spec.instructions, "Sort incoming issues.");
+        assert_eq!(parsed.spec.metadata.model.as_deref(), Some("gpt-5"));
+        assert_eq!(parsed.spec.source, AgentSource::Config);
+
+        let renamed = AgentDefinition {
+            name: Some("sorter".to_string()),
+            ..definition
+        };
+        let err = parse_agent_definition(renamed, Some("triage"), &path, AgentSource::Config)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ParserError::Validation(SubagentValidationError::InvalidName { .. })
+        ));
+
+        let both = AgentDefinition {
+            name: Some("triage".to_string()),
+            instructions: Some("Inline.".to_string()),
+            instructions_file: Some(PathBuf::from("triage.md")),
+            ..Default::default()
+        };
+        let err = parse_agent_definition(both, None, &path, AgentSource::Config).unwrap_err();
+        assert!(matches!(
+            err,
+            ParserError::Validation(SubagentValidationError::ConflictingInstructions)
+        ));
+
+        let err = toml::from_str::<AgentDefinition>("instruction = \"typo\"").unwrap_err();
+        assert!(err.to_string().contains("unknown field `instruction`"));
+    }
 }
@@ -1,7 +1,16 @@
+use crate::TaskContextSnapshot;
+use crate::project::NodeProject;
+use crate::project::ProjectLayout;
+use crate::targets::resolve_change_files;
+use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
+use std::fmt;
+use std::path::Path;
+use std::path::PathBuf;
+use std::str::FromStr;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub struct RequirementsSpec {
     pub title: String,
     pub overview: String,
@@ -22,7 +31,7 @@ impl RequirementsSpec {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub struct Requirement {
     pub id: String,
     pub summary: String,
@@ -47,7 +56,7 @@ impl Requirement {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub struct AcceptanceCriterion {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
@@ -63,7 +72,7 @@ impl AcceptanceCriterion {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub struct ProposedChanges {
     pub rationale: String,
     pub changes: Vec<ProposedChange>,
@@ -78,7 +87,7 @@ impl ProposedChanges {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub struct ProposedChange {
     pub requirement_id: String,
     pub summary: String,
@@ -103,12 +112,15 @@ impl ProposedChange {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub struct ChangeFile {
     pub path: String,
     pub change_type: ChangeType,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub rationale: String,
+    /// How sure the planner is that this file is the right target (0-100).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<u8>,
 }
 
 impl ChangeFile {
@@ -121,11 +133,17 @@ impl ChangeFile {
             path: path.into(),
             change_type,
             rationale: rationale.into(),
+            confidence: None,
         }
     }
+
+    pub fn with_confidence(mut self, confidence: u8) -> Self {
+        self.confidence = Some(confidence.min(100));
+        self
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum ChangeType {
     Create,
@@ -133,7 +151,7 @@ pub enum ChangeType {
     Remove,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub struct TestPlan {
     pub summary: String,
     pub tasks: Vec<TestTask>,
@@ -148,7 +166,7 @@ impl TestPlan {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub struct TestTask {
     pub name: String,
     pub command: String,
@@ -170,7 +188,7 @@ impl TestTask {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub struct TestResults {
     pub plan: TestPlan,
     pub outcomes: Vec<TestOutcome>,
@@ -182,12 +200,15 @@ impl TestResults {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub struct TestOutcome {
     pub task_name: String,
     pub status: TestStatus,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub details: Option<String>,
+    /// Individual test cases, when the task's report was imported.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cases: Vec<TestCaseResult>,
 }
 
 impl TestOutcome {
@@ -196,11 +217,95 @@ impl TestOutcome {
             task_name: task_name.into(),
             status,
             details,
+            cases: Vec::new(),
+        }
+    }
+
+    /// Outcome of a task built from its individual test cases. The task fails
+    /// when any case failed and is blocked when no case ran.
+    pub fn from_cases(task_name: impl Into<String>, cases: Vec<TestCaseResult>) -> Self {
+        let failed: Vec<&str> = cases
+            .iter()
+            .filter(|case| case.status == TestCaseStatus::Failed)
+            .map(|case| case.name.as_str())
+            .collect();
+        let ran = cases
+            .iter()
+            .any(|case| case.status != TestCaseStatus::Skipped);
+        let (status, details) = if !failed.is_empty() {
+            (
+                TestStatus::Failed,
+                Some(format!(
+                    "{} of {} tests failed: {}",
+                    failed.len(),
+                    cases.len(),
+                    failed.join(", ")
+                )),
+            )
+        } else if ran {
+            (TestStatus::Passed, None)
+        } else {
+            (TestStatus::Blocked, Some("No test cases ran".to_string()))
+        };
+        Self {
+            task_name: task_name.into(),
+            status,
+            details,
+            cases,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct TestCaseResult {
+    pub name: String,
+    /// Test binary, class or file the case belongs to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suite: Option<String>,
+    pub status: TestCaseStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    /// Failure or skip message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl TestCaseResult {
+    pub fn new(name: impl Into<String>, status: TestCaseStatus) -> Self {
+        Self {
+            name: name.into(),
+            suite: None,
+            status,
+            duration_ms: None,
+            message: None,
         }
     }
+
+    pub fn suite(mut self, suite: impl Into<String>) -> Self {
+        self.suite = Some(suite.into());
+        self
+    }
+
+    pub fn duration_ms(mut self, duration_ms: u64) -> Self {
+        self.duration_ms = Some(duration_ms);
+        self
+    }
+
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TestCaseStatus {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum TestStatus {
     Passed,
@@ -208,7 +313,7 @@ pub enum TestStatus {
     Blocked,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub struct ReviewFindings {
     pub summary: String,
     pub findings: Vec<ReviewFinding>,
@@ -223,7 +328,7 @@ impl ReviewFindings {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub struct ReviewFinding {
     pub severity: Severity,
     pub message: String,
@@ -249,7 +354,9 @@ impl ReviewFinding {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq, PartialOrd, Ord,
+)]
 #[serde(rename_all = "snake_case")]
 pub enum Severity {
     Info,
@@ -259,7 +366,42 @@ pub enum Severity {
     Critical,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Low => "low",
+            Severity::Medium => "medium",
+            Severity::High => "high",
+            Severity::Critical => "critical",
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Severity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "info" => Ok(Severity::Info),
+            "low" => Ok(Severity::Low),
+            "medium" => Ok(Severity::Medium),
+            "high" => Ok(Severity::High),
+            "critical" => Ok(Severity::Critical),
+            other => Err(format!(
+                "unknown severity `{other}`; expected info, low, medium, high, or critical"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub struct FormatterRun {
     pub command: String,
     pub status: FormatterStatus,
@@ -293,7 +435,7 @@ impl FormatterRun {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum FormatterStatus {
     Succeeded,
@@ -301,15 +443,20 @@ pub enum FormatterStatus {
     Skipped,
 }
 
-pub fn derive_changes_from_spec(spec: &RequirementsSpec) -> ProposedChanges {
+/// Propose file-level changes for each requirement, resolving file hints and
+/// inferring targets against the project at `root`.
+pub fn derive_changes_from_spec(root: &Path, spec: &RequirementsSpec) -> ProposedChanges {
     let mut changes = Vec::new();
     for requirement in &spec.requirements {
-        let files = derive_files_for_requirement(requirement);
-        let notes = requirement
+        let files = resolve_change_files(root, requirement);
+        let mut notes: Vec<String> = requirement
             .acceptance_criteria
             .iter()
             .map(|criterion| format!("Acceptance: {}", criterion.text))
             .collect();
+        if files.is_empty() {
+            notes.push("No candidate files found; choose targets manually".to_string());
+        }
         changes.push(ProposedChange::new(
             &requirement.id,
             &requirement.summary,
@@ -323,37 +470,17 @@ pub fn derive_changes_from_spec(spec: &RequirementsSpec) -> ProposedChanges {
     )
 }
 
-fn derive_files_for_requirement(requirement: &Requirement) -> Vec<ChangeFile> {
-    if requirement.file_hints.is_empty() {
-        return vec![ChangeFile::new(
-            "src/lib.rs",
-            ChangeType::Modify,
-            format!("Default target inferred for {}", requirement.id),
-        )];
-    }
-
-    requirement
-        .file_hints
-        .iter()
-        .map(|hint| {
-            ChangeFile::new(
-                hint,
-                ChangeType::Modify,
-                format!("Referenced by {}", requirement.id),
-            )
-        })
-        .collect()
-}
-
-pub fn plan_tests_for_changes(changes: &ProposedChanges) -> TestPlan {
-    let mut tasks = Vec::new();
-    let mut related = Vec::new();
+/// Plan the narrowest test commands covering every file in `changes`, based on
+/// the tooling detected in `project`.
+pub fn plan_tests_for_changes(project: &ProjectLayout, changes: &ProposedChanges) -> TestPlan {
+    let mut tasks: Vec<TestTask> = Vec::new();
     for change in &changes.changes {
-        related.push(change.requirement_id.clone());
-    }
-    if !changes.changes.is_empty() {
-        tasks.push(TestTask::new("cargo test", "cargo test", related.clone()));
-        tasks.push(TestTask::new("fmt check", "cargo fmt -- --check", related));
+        for file in &change.files {
+            let path = project.relative_path(&file.path);
+            for command in test_commands_for_path(project, &path) {
+                add_test_task(&mut tasks, command, &change.requirement_id);
+            }
+        }
     }
 
     let summary = if tasks.is_empty() {
@@ -364,6 +491,124 @@ pub fn plan_tests_for_changes(changes: &ProposedChanges) -> TestPlan {
     TestPlan::new(summary, tasks)
 }
 
+fn add_test_task(tasks: &mut Vec<TestTask>, command: String, requirement_id: &str) {
+    let task = match tasks.iter_mut().find(|task| task.command == command) {
+        Some(task) => task,
+        None => {
+            tasks.push(TestTask::new(command.clone(), command, Vec::new()));
+            let last = tasks.len() - 1;
+            &mut tasks[last]
+        }
+    };
+    if !task
+        .related_requirements
+        .iter()
+        .any(|id| id == requirement_id)
+    {
+        task.related_requirements.push(requirement_id.to_string());
+    }
+}
+
+const DOC_EXTENSIONS: &[&str] = &["md", "mdx", "txt", "rst", "adoc"];
+const NODE_EXTENSIONS: &[&str] = &["js", "jsx", "ts", "tsx", "mjs", "cjs", "mts", "cts"];
+
+fn test_commands_for_path(project: &ProjectLayout, path: &str) -> Vec<String> {
+    let extension = Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or_default();
+    if DOC_EXTENSIONS.contains(&extension) || path.starts_with("docs/") {
+        return Vec::new();
+    }
+
+    if extension == "py" && project.python {
+        return vec![pytest_command(project, path)];
+    }
+    if extension == "go" && project.go {
+        let dir = Path::new(path)
+            .parent()
+            .map(|dir| dir.to_string_lossy().to_string())
+            .unwrap_or_default();
+        return if dir.is_empty() {
+            vec!["go test .".to_string()]
+        } else {
+            vec![format!("go test ./{dir}")]
+        };
+    }
+    if NODE_EXTENSIONS.contains(&extension)
+        && let Some(node) = project
+            .node
+            .as_ref()
+            .filter(|node| node.scripts.iter().any(|script| script == "test"))
+    {
+        return vec![node_test_command(node, path)];
+    }
+    if let Some(package) = project.cargo_package_for(path) {
+        let test = if project.cargo_workspace {
+            format!("cargo test -p {}", package.name)
+        } else {
+            "cargo test".to_string()
+        };
+        let mut commands = vec![test];
+        if extension == "rs" {
+            commands.push("cargo fmt -- --check".to_string());
+        }
+        return commands;
+    }
+    if project.has_just_recipe("test") {
+        return vec!["just test".to_string()];
+    }
+    if project.is_empty() && extension == "rs" {
+        return vec!["cargo test".to_string()];
+    }
+    Vec::new()
+}
+
+fn is_python_test_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with("test_") || name.ends_with("_test.py"))
+}
+
+/// Run a changed test module directly, or the matching `test_<module>.py`
+/// when one exists next to the source or under `tests/`.
+fn pytest_command(project: &ProjectLayout, path: &str) -> String {
+    let source = Path::new(path);
+    if is_python_test_file(source) {
+        return format!("pytest {path}");
+    }
+    let Some(stem) = source.file_stem().and_then(|stem| stem.to_str()) else {
+        return "pytest".to_string();
+    };
+    let dir = source.parent().unwrap_or(Path::new(""));
+    let candidates = [
+        dir.join(format!("test_{stem}.py")),
+        dir.join(format!("{stem}_test.py")),
+        dir.join("tests").join(format!("test_{stem}.py")),
+        PathBuf::from("tests").join(format!("test_{stem}.py")),
+        PathBuf::from("test").join(format!("test_{stem}.py")),
+    ];
+    candidates
+        .iter()
+        .find(|candidate| project.root.join(candidate).is_file())
+        .map_or_else(
+            || "pytest".to_string(),
+            |candidate| format!("pytest {}", candidate.to_string_lossy().replace('\\', "/")),
+        )
+}
+
+fn node_test_command(node: &NodeProject, path: &str) -> String {
+    let is_test_file = [".test.", ".spec."]
+        .iter()
+        .any(|marker| path.contains(marker))
+        || path.contains("__tests__/");
+    match (is_test_file, node.runner.as_str()) {
+        (false, runner) => format!("{runner} test"),
+        (true, "npm") => format!("npm test -- {path}"),
+        (true, runner) => format!("{runner} test {path}"),
+    }
+}
+
 pub fn merge_test_results(plan: &TestPlan, statuses: Vec<TestOutcome>) -> TestResults {
     let mut outcomes = Vec::new();
     for task in &plan.tasks {
@@ -434,32 +679,369 @@ pub fn review_findings_from_results(
     ReviewFindings::new(summary, findings)
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct TraceabilityReport {
+    pub title: String,
+    pub requirements: Vec<RequirementTrace>,
+    /// Share of acceptance criteria that are covered, in percent.
+    pub coverage_percent: u8,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct RequirementTrace {
+    pub id: String,
+    pub summary: String,
+    pub files: Vec<String>,
+    pub tests: Vec<TestTrace>,
+    pub findings: Vec<ReviewFinding>,
+    pub criteria: Vec<CriterionTrace>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct CriterionTrace {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub text: String,
+    /// Findings that cite this criterion's ID.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub findings: Vec<ReviewFinding>,
+    pub covered: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct TestTrace {
+    pub name: String,
+    pub command: String,
+    /// `None` when the task was planned but has no recorded outcome.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<TestStatus>,
+}
+
+/// Links requirements to the changes, tests and findings produced for them.
+/// An acceptance criterion counts as covered when its requirement changed at
+/// least one file, every related test passed, and no finding is open against
+/// the requirement or the criterion.
+#[derive(Debug, Clone)]
+pub struct TraceabilityBuilder<'a> {
+    spec: &'a RequirementsSpec,
+    changes: Option<&'a ProposedChanges>,
+    results: Option<&'a TestResults>,
+    findings: Option<&'a ReviewFindings>,
+}
+
+impl<'a> TraceabilityBuilder<'a> {
+    pub fn new(spec: &'a RequirementsSpec) -> Self {
+        Self {
+            spec,
+            changes: None,
+            results: None,
+            findings: None,
+        }
+    }
+
+    pub fn changes(mut self, changes: impl Into<Option<&'a ProposedChanges>>) -> Self {
+        self.changes = changes.into();
+        self
+    }
+
+    pub fn results(mut self, results: impl Into<Option<&'a TestResults>>) -> Self {
+        self.results = results.into();
+        self
+    }
+
+    pub fn findings(mut self, findings: impl Into<Option<&'a ReviewFindings>>) -> Self {
+        self.findings = findings.into();
+        self
+    }
+
+    pub fn build(self) -> TraceabilityReport {
+        let mut requirements = Vec::new();
+        let mut total_criteria = 0;
+        let mut covered_criteria = 0;
+
+        for requirement in &self.spec.requirements {
+            let files = self.files_for(&requirement.id);
+            let tests = self.tests_for(&requirement.id);
+            let findings: Vec<ReviewFinding> = self
+                .all_findings()
+                .filter(|finding| {
+                    finding.related_requirement.as_deref() == Some(requirement.id.as_str())
+                        || (finding.related_requirement.is_none()
+                            && tests
+                                .iter()
+                                .any(|test| finding_mentions_test(finding, &test.name)))
+                })
+                .cloned()
+                .collect();
+            let criteria_findings: Vec<Vec<ReviewFinding>> = requirement
+                .acceptance_criteria
+                .iter()
+                .map(|criterion| match criterion.id.as_deref() {
+                    Some(id) => findings
+                        .iter()
+                        .filter(|finding| mentions_id(&finding.message, id))
+                        .cloned()
+                        .collect(),
+                    None => Vec::new(),
+                })
+                .collect();
+            // Findings that name one of this requirement's criteria only
+            // count against that criterion.
+            let general_findings = findings.iter().filter(|finding| {
+                !requirement
+                    .acceptance_criteria
+                    .iter()
+                    .filter_map(|criterion| criterion.id.as_deref())
+                    .any(|id| mentions_id(&finding.message, id))
+            });
+            let requirement_ok = !files.is_empty()
+                && !tests.is_empty()
+                && tests
+                    .iter()
+                    .all(|test| test.status == Some(TestStatus::Passed))
+                && general_findings.count() == 0;
+
+            let criteria: Vec<CriterionTrace> = requirement
+                .acceptance_criteria
+                .iter()
+                .zip(criteria_findings)
+                .map(|(criterion, criterion_findings)| CriterionTrace {
+                    id: criterion.id.clone(),
+                    text: criterion.text.clone(),
+                    covered: requirement_ok && criterion_findings.is_empty(),
+                    findings: criterion_findings,
+                })
+                .collect();
+            total_criteria += criteria.len();
+            covered_criteria += criteria
+                .iter()
+                .filter(|criterion| criterion.covered)
+                .count();
+
+            requirements.push(RequirementTrace {
+                id: requirement.id.clone(),
+                summary: requirement.summary.clone(),
+                files,
+                tests,
+                findings,
+                criteria,
+            });
+        }
+
+        let coverage_percent = if total_criteria == 0 {
+            0
+        } else {
+            (covered_criteria * 100 / total_criteria) as u8
+        };
+        TraceabilityReport {
+            title: self.spec.title.clone(),
+            requirements,
+            coverage_percent,
+        }
+    }
+
+    fn all_findings(&self) -> impl Iterator<Item = &'a ReviewFinding> + use<'a> {
+        self.findings
+            .into_iter()
+            .flat_map(|findings| findings.findings.iter())
+    }
+
+    fn files_for(&self, requirement_id: &str) -> Vec<String> {
+        let mut files: Vec<String> = Vec::new();
+        let changes = self
+            .changes
+            .iter()
+            .flat_map(|changes| changes.changes.iter());
+        for change in changes.filter(|change| change.requirement_id == requirement_id) {
+            for file in &change.files {
+                if !files.contains(&file.path) {
+                    files.push(file.path.clone());
+                }
+            }
+        }
+        files
+    }
+
+    fn tests_for(&self, requirement_id: &str) -> Vec<TestTrace> {
+        let Some(results) = self.results else {
+            return Vec::new();
+        };
+        results
+            .plan
+            .tasks
+            .iter()
+            .filter(|task| {
+                task.related_requirements
+                    .iter()
+                    .any(|id| id == requirement_id)
+            })
+            .map(|task| TestTrace {
+                name: task.name.clone(),
+                command: task.command.clone(),
+                status: results
+                    .outcomes
+                    .iter()
+                    .find(|outcome| outcome.task_name == task.name)
+                    .map(|outcome| outcome.status.clone()),
+            })
+            .collect()
+    }
+}
+
+/// Whether `text` names `id` as a whole word, so `AC-1` does not match
+/// `AC-10`.
+fn mentions_id(text: &str, id: &str) -> bool {
+    let is_word = |c: char| c.is_alphanumeric() || c == '-' || c == '_';
+    text.match_indices(id).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + id.len()..].chars().next();
+        !before.is_some_and(is_word) && !after.is_some_and(is_word)
+    })
+}
+
+/// Findings from [`review_findings_from_results`] name the test they concern.
+fn finding_mentions_test(finding: &ReviewFinding, test_name: &str) -> bool {
+    finding.message == format!("Test {test_name} failed")
+        || finding.message == format!("Test {test_name} did not run")
+}
+
+impl TraceabilityReport {
+    /// Rebuild the report from a [`TaskContextSnapshot`] by reading the
+    /// outputs the built-in pipeline agents leave in their scratchpads.
+    pub fn from_snapshot(snapshot: &TaskContextSnapshot) -> anyhow::Result<Self> {
+        let spec: RequirementsSpec = snapshot
+            .scratchpad_field("subagents.spec_parser.output", "spec")?
+            .ok_or_else(|| anyhow::anyhow!("snapshot has no spec-parser output"))?;
+        let changes: Option<ProposedChanges> =
+            snapshot.scratchpad_field("subagents.code_writer.output", "changes")?;
+        let results: Option<TestResults> =
+            snapshot.scratchpad_field("subagents.tester.output", "results")?;
+        let findings: Option<ReviewFindings> =
+            snapshot.scratchpad_field("subagents.reviewer.output", "findings")?;
+
+        Ok(TraceabilityBuilder::new(&spec)
+            .changes(changes.as_ref())
+            .results(results.as_ref())
+            .findings(findings.as_ref())
+            .build())
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# Traceability: {}\n\n", self.title);
+        out.push_str(&format!("Coverage: {}%\n", self.coverage_percent));
+        for requirement in &self.requirements {
+            out.push_str(&format!(
+                "\n## {} {}\n\n",
+                requirement.id, requirement.summary
+            ));
+            out.push_str("| Acceptance criterion | Covered |\n| --- | --- |\n");
+            for criterion in &requirement.criteria {
+                let label = match criterion.id.as_deref() {
+                    Some(id) => format!("{id}: {}", criterion.text),
+                    None => criterion.text.clone(),
+                };
+                let covered = if criterion.covered { "yes" } else { "no" };
+                out.push_str(&format!("| {} | {covered} |\n", label.replace('|', "\\|")));
+            }
+
+            out.push_str("\nFiles:\n");
+            if requirement.files.is_empty() {
+                out.push_str("- none\n");
+            }
+            for file in &requirement.files {
+                out.push_str(&format!("- `{file}`\n"));
+            }
+
+            out.push_str("\nTests:\n");
+            if requirement.tests.is_empty() {
+                out.push_str("- none\n");
+            }
+            for test in &requirement.tests {
+                let status = match &test.status {
+                    Some(TestStatus::Passed) => "passed",
+                    Some(TestStatus::Failed) => "failed",
+                    Some(TestStatus::Blocked) => "blocked",
+                    None => "not run",
+                };
+                out.push_str(&format!("- `{}`: {status}\n", test.command));
+            }
+
+            // `findings` already includes the ones matched to a criterion.
+            let mut any_findings = false;
+            for finding in &requirement.findings {
+                if !any_findings {
+                    out.push_str("\nOpen findings:\n");
+                    any_findings = true;
+                }
+                out.push_str(&format!("- [{}] {}\n", finding.severity, finding.message));
+            }
+        }
+        out
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::write_file;
     use pretty_assertions::assert_eq;
 
     #[test]
-    fn derives_changes_with_default_file() {
+    fn derives_changes_from_resolved_files() {
+        let dir = tempfile::TempDir::new().expect("tempdir");
+        write_file(dir.path(), "src/lib.rs", "pub fn feature() {}\n");
         let spec = RequirementsSpec::new(
             "Spec",
             "Overview",
-            vec![Requirement::new(
-                "REQ-001",
-                "Add feature",
-                vec![
-                    AcceptanceCriterion::new(None, "Works"),
-                    AcceptanceCriterion::new(None, "Tested"),
-                ],
-                vec![],
-            )],
+            vec![
+                Requirement::new(
+                    "REQ-001",
+                    "Add feature",
+                    vec![
+                        AcceptanceCriterion::new(None, "Works"),
+                        AcceptanceCriterion::new(None, "Tested"),
+                    ],
+                    vec!["src/lib.rs".to_string(), "src/feature.rs".to_string()],
+                ),
+                Requirement::new("REQ-002", "Zzz", vec![], vec![]),
+            ],
         );
 
-        let changes = derive_changes_from_spec(&spec);
-        assert_eq!(changes.changes.len(), 1);
-        let change = &changes.changes[0];
-        assert_eq!(change.files.len(), 1);
-        assert_eq!(change.files[0].path, "src/lib.rs");
+        let changes = derive_changes_from_spec(dir.path(), &spec);
+        assert_eq!(changes.changes.len(), 2);
+        let files: Vec<(&str, &ChangeType)> = changes.changes[0]
+            .files
+            .iter()
+            .map(|file| (file.path.as_str(), &file.change_type))
+            .collect();
+        assert_eq!(
+            files,
+            vec![
+                ("src/lib.rs", &ChangeType::Modify),
+                ("src/feature.rs", &ChangeType::Create),
+            ]
+        );
+        assert_eq!(changes.changes[1].files, vec![]);
+        assert_eq!(
+            changes.changes[1].notes,
+            vec!["No candidate files found; choose targets manually".to_string()]
+        );
+    }
+
+    fn change(requirement_id: &str, paths: &[&str]) -> ProposedChange {
+        ProposedChange::new(
+            requirement_id,
+            "work",
+            paths
+                .iter()
+                .map(|path| ChangeFile::new(*path, ChangeType::Modify, ""))
+                .collect(),
+            vec![],
+        )
     }
 
     #[test]
@@ -474,11 +1056,92 @@ mod tests {
             )],
         );
 
-        let plan = plan_tests_for_changes(&changes);
-        assert_eq!(plan.tasks.len(), 2);
+        let plan = plan_tests_for_changes(&ProjectLayout::default(), &changes);
+        assert_eq!(plan.tasks.len(), 1);
+        assert_eq!(plan.tasks[0].command, "cargo test");
         assert!(plan.summary.contains("Planned"));
     }
 
+    #[test]
+    fn plans_narrowest_targets_per_ecosystem() {
+        let dir = tempfile::TempDir::new().expect("tempdir");
+        let root = dir.path();
+        write_file(
+            root,
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"crates/*\"]\n",
+        );
+        write_file(
+            root,
+            "crates/core/Cargo.toml",
+            "[package]\nname = \"demo-core\"\n",
+        );
+        write_file(
+            root,
+            "crates/cli/Cargo.toml",
+            "[package]\nname = \"demo-cli\"\n",
+        );
+        write_file(root, "pyproject.toml", "[tool.pytest.ini_options]\n");
+        write_file(root, "tests/test_parser.py", "");
+        write_file(root, "package.json", r#"{"scripts": {"test": "vitest"}}"#);
+        write_file(root, "go.mod", "module example.com/demo\n");
+
+        let changes = ProposedChanges::new(
+            "r",
+            vec![
+                change("REQ-001", &["crates/core/src/lib.rs", "app/parser.py"]),
+                change("REQ-002", &["crates/core/tests/api.rs", "web/app.test.ts"]),
+                change("REQ-003", &["pkg/server/handler.go", "README.md"]),
+            ],
+        );
+
+        let plan = plan_tests_for_changes(&ProjectLayout::detect(root), &changes);
+        let tasks: Vec<(&str, Vec<&str>)> = plan
+            .tasks
+            .iter()
+            .map(|task| {
+                (
+                    task.command.as_str(),
+                    task.related_requirements
+                        .iter()
+                        .map(String::as_str)
+                        .collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            tasks,
+            vec![
+                ("cargo test -p demo-core", vec!["REQ-001", "REQ-002"]),
+                ("cargo fmt -- --check", vec!["REQ-001", "REQ-002"]),
+                ("pytest tests/test_parser.py", vec!["REQ-001"]),
+                ("npm test -- web/app.test.ts", vec!["REQ-002"]),
+                ("go test ./pkg/server", vec!["REQ-003"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn falls_back_to_just_recipes_and_skips_docs() {
+        let dir = tempfile::TempDir::new().expect("tempdir");
+        let root = dir.path();
+        write_file(root, "justfile", "test:\n    ./run-tests.sh\n");
+        let layout = ProjectLayout::detect(root);
+
+        let docs_only = ProposedChanges::new("r", vec![change("REQ-001", &["docs/guide.md"])]);
+        assert_eq!(plan_tests_for_changes(&layout, &docs_only).tasks, vec![]);
+
+        let scripts = ProposedChanges::new("r", vec![change("REQ-002", &["scripts/build.sh"])]);
+        assert_eq!(
+            plan_tests_for_changes(&layout, &scripts).tasks,
+            vec![TestTask::new(
+                "just test",
+                "just test",
+                vec!["REQ-002".to_string()]
+            )]
+        );
+    }
+
     #[test]
     fn review_findings_cover_failed_tests() {
         let changes = ProposedChanges::new(
@@ -490,7 +1153,7 @@ mod tests {
                 vec![],
             )],
         );
-        let plan = plan_tests_for_changes(&changes);
+        let plan = plan_tests_for_changes(&ProjectLayout::default(), &changes);
         let results = merge_test_results(
             &plan,
             vec![TestOutcome::new(
@@ -504,4 +1167,163 @@ mod tests {
         assert!(!findings.findings.is_empty());
         assert!(matches!(findings.findings[0].severity, Severity::Critical));
     }
+
+    fn traced_pipeline() -> (
+        RequirementsSpec,
+        ProposedChanges,
+        TestResults,
+        ReviewFindings,
+    ) {
+        let spec = RequirementsSpec::new(
+            "Export",
+            "overview",
+            vec![
+                Requirement::new(
+                    "REQ-001",
+                    "Export as CSV",
+                    vec![
+                        AcceptanceCriterion::new(Some("AC-001".to_string()), "CSV is written"),
+                        AcceptanceCriterion::new(Some("AC-002".to_string()), "Header row"),
+                    ],
+                    vec![],
+                ),
+                Requirement::new(
+                    "REQ-002",
+                    "Audit exports",
+                    vec![AcceptanceCriterion::new(None, "Audit event recorded")],
+                    vec![],
+                ),
+            ],
+        );
+        let changes = ProposedChanges::new(
+            "r",
+            vec![
+                change("REQ-001", &["src/export.rs"]),
+                change("REQ-002", &["src/audit.rs"]),
+            ],
+        );
+        let plan = TestPlan::new(
+            "plan",
+            vec![
+                TestTask::new("export", "cargo test export", vec!["REQ-001".to_string()]),
+                TestTask::new("audit", "cargo test audit", vec!["REQ-002".to_string()]),
+            ],
+        );
+        let results = merge_test_results(
+            &plan,
+            vec![
+                TestOutcome::new("export", TestStatus::Passed, None),
+                TestOutcome::new("audit", TestStatus::Failed, None),
+            ],
+        );
+        let mut findings = review_findings_from_results(&changes, &results);
+        findings.findings.push(ReviewFinding::new(
+            Severity::Low,
+            "AC-002 header uses the wrong casing",
+            Some("REQ-001".to_string()),
+            None,
+        ));
+        findings.findings.push(ReviewFinding::new(
+            Severity::Low,
+            "AC-001 audit row lacks a timestamp",
+            Some("REQ-002".to_string()),
+            None,
+        ));
+        (spec, changes, results, findings)
+    }
+
+    #[test]
+    fn mentions_id_matches_whole_ids_only() {
+        assert!(mentions_id("AC-1 is not met", "AC-1"));
+        assert!(mentions_id("see (AC-1).", "AC-1"));
+        assert!(!mentions_id("AC-10 and AC-12 fail", "AC-1"));
+        assert!(!mentions_id("XAC-1 fails", "AC-1"));
+    }
+
+    #[test]
+    fn traceability_links_requirements_to_artifacts() {
+        let (spec, changes, results, findings) = traced_pipeline();
+        let report = TraceabilityBuilder::new(&spec)
+            .changes(&changes)
+            .results(&results)
+            .findings(&findings)
+            .build();
+
+        let export = &report.requirements[0];
+        assert_eq!(export.files, vec!["src/export.rs".to_string()]);
+        assert_eq!(
+            export.tests,
+            vec![TestTrace {
+                name: "export".to_string(),
+                command: "cargo test export".to_string(),
+                status: Some(TestStatus::Passed),
+            }]
+        );
+        let covered: Vec<bool> = export.criteria.iter().map(|c| c.covered).collect();
+        assert_eq!(covered, vec![true, false]);
+        assert_eq!(export.criteria[0].findings, vec![]);
+        assert_eq!(export.criteria[1].findings.len(), 1);
+
+        let audit = &report.requirements[1];
+        assert_eq!(audit.findings.len(), 2);
+        assert_eq!(audit.findings[0].message, "Test audit failed");
+        assert!(!audit.criteria[0].covered);
+        assert_eq!(report.coverage_percent, 33);
+
+        let untraced = TraceabilityBuilder::new(&spec).build();
+        assert_eq!(untraced.coverage_percent, 0);
+        assert!(untraced.requirements[0].tests.is_empty());
+    }
+
+    #[test]
+    fn traceability_from_snapshot_renders_markdown() {
+        let (spec, changes, results, findings) = traced_pipeline();
+        let snapshot = TaskContextSnapshot {
+            typed_slots: vec![],
+            scratchpads: [
+                (
+                    "subagents.spec_parser.output".to_string(),
+                    serde_json::json!({ "format": "markdown", "spec": spec }),
+                ),
+                (
+                    "subagents.code_writer.output".to_string(),
+                    serde_json::json!({ "changes": changes, "formatters": [] }),
+                ),
+                (
+                    "subagents.tester.output".to_string(),
+                    serde_json::json!({ "results": results }),
+                ),
+                (
+                    "subagents.reviewer.output".to_string(),
+                    serde_json::json!({ "findings": findings }),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+            diagnostics: vec![],
+        };
+        let report = TraceabilityReport::from_snapshot(&snapshot).expect("report");
+        let markdown = report.to_markdown();
+        assert!(markdown.starts_with("# Traceability: Export\n\nCoverage: 33%\n"));
+        assert!(markdown.contains("| AC-001: CSV is written | yes |"));
+        assert!(markdown.contains("- `cargo test audit`: failed"));
+        assert!(markdown.contains("- [critical] Test audit failed"));
+        assert_eq!(
+            markdown
+                .matches("- [low] AC-002 header uses the wrong casing")
+                .count(),
+            1
+        );
+
+        let json: TraceabilityReport =
+            serde_json::from_str(&report.to_json().expect("json")).expect("round trip");
+        assert_eq!(json, report);
+
+        let empty = TaskContextSnapshot {
+            typed_slots: vec![],
+            scratchpads: Default::default(),
+            diagnostics: vec![],
+        };
+        assert!(TraceabilityReport::from_snapshot(&empty).is_err());
+    }
 }
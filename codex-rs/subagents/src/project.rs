@@ -0,0 +1,358 @@
+//! Detects which build and test tooling a project uses so test plans can
+//! target the narrowest commands for a set of changed files.
+
+use crate::TaskContext;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::path::PathBuf;
+use wildmatch::WildMatch;
+
+/// Root directory of the project the pipeline operates on. Seed it into the
+/// [`crate::TaskContext`] so agents do not fall back to the process cwd.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProjectRoot(pub PathBuf);
+
+impl ProjectRoot {
+    /// Root seeded into `ctx`, or the current directory when none was seeded.
+    pub(crate) fn resolve(ctx: &TaskContext) -> anyhow::Result<PathBuf> {
+        match ctx.get_typed::<ProjectRoot>()? {
+            Some(ProjectRoot(root)) => Ok(root),
+            None => Ok(std::env::current_dir()?),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProjectLayout {
+    pub root: PathBuf,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cargo_packages: Vec<CargoPackage>,
+    /// True when the root `Cargo.toml` declares a `[workspace]`.
+    #[serde(default)]
+    pub cargo_workspace: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node: Option<NodeProject>,
+    #[serde(default)]
+    pub python: bool,
+    #[serde(default)]
+    pub go: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub just_recipes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CargoPackage {
+    pub name: String,
+    /// Package directory relative to the project root; empty for the root package.
+    pub dir: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NodeProject {
+    /// Package manager inferred from the lockfile (`npm`, `pnpm`, `yarn`, `bun`).
+    pub runner: String,
+    pub scripts: Vec<String>,
+}
+
+impl ProjectLayout {
+    pub fn detect(root: &Path) -> Self {
+        let (cargo_workspace, cargo_packages) = detect_cargo(root);
+        Self {
+            root: root.to_path_buf(),
+            cargo_packages,
+            cargo_workspace,
+            node: detect_node(root),
+            python: detect_python(root),
+            go: root.join("go.mod").is_file(),
+            just_recipes: detect_just_recipes(root),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cargo_packages.is_empty()
+            && self.node.is_none()
+            && !self.python
+            && !self.go
+            && self.just_recipes.is_empty()
+    }
+
+    pub fn has_just_recipe(&self, recipe: &str) -> bool {
+        self.just_recipes.iter().any(|name| name == recipe)
+    }
+
+    /// Cargo package owning `path`, preferring the most deeply nested member.
+    pub fn cargo_package_for(&self, path: &str) -> Option<&CargoPackage> {
+        self.cargo_packages
+            .iter()
+            .filter(|package| {
+                package.dir.is_empty()
+                    || path == package.dir
+                    || path.starts_with(&format!("{}/", package.dir))
+            })
+            .max_by_key(|package| package.dir.len())
+    }
+
+    /// Express `path` relative to the project root with `/` separators.
+    pub fn relative_path(&self, path: &str) -> String {
+        let candidate = Path::new(path);
+        let relative = candidate.strip_prefix(&self.root).unwrap_or(candidate);
+        let normalized = relative.to_string_lossy().replace('\\', "/");
+        normalized.trim_start_matches("./").to_string()
+    }
+}
+
+fn read_toml(path: &Path) -> Option<toml::Value> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    match toml::from_str(&contents) {
+        Ok(value) => Some(value),
+        Err(err) => {
+            tracing::warn!("failed to parse {}: {err}", path.display());
+            None
+        }
+    }
+}
+
+fn package_name(manifest: &toml::Value) -> Option<String> {
+    manifest
+        .get("package")?
+        .get("name")?
+        .as_str()
+        .map(str::to_string)
+}
+
+fn detect_cargo(root: &Path) -> (bool, Vec<CargoPackage>) {
+    let Some(manifest) = read_toml(&root.join("Cargo.toml")) else {
+        return (false, Vec::new());
+    };
+
+    let mut packages = Vec::new();
+    if let Some(name) = package_name(&manifest) {
+        packages.push(CargoPackage {
+            name,
+            dir: String::new(),
+        });
+    }
+
+    let Some(workspace) = manifest.get("workspace") else {
+        return (false, packages);
+    };
+    let members = workspace
+        .get("members")
+        .and_then(toml::Value::as_array)
+        .map(|members| {
+            members
+                .iter()
+                .filter_map(toml::Value::as_str)
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let excluded: BTreeSet<String> = workspace
+        .get("exclude")
+        .and_then(toml::Value::as_array)
+        .map(|entries| {
+            entries
+                .iter()
+                .filter_map(toml::Value::as_str)
+                .map(|entry| entry.trim_end_matches('/').to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    for member in members {
+        for dir in expand_member(root, member) {
+            if excluded.contains(&dir) || packages.iter().any(|package| package.dir == dir) {
+                continue;
+            }
+            if let Some(name) = read_toml(&root.join(&dir).join("Cargo.toml"))
+                .as_ref()
+                .and_then(package_name)
+            {
+                packages.push(CargoPackage { name, dir });
+            }
+        }
+    }
+    (true, packages)
+}
+
+/// Expand a workspace member entry; only the final path component may contain
+/// glob characters, which covers the common `crates/*` layout.
+fn expand_member(root: &Path, member: &str) -> Vec<String> {
+    let member = member.trim_end_matches('/');
+    if !member.contains(['*', '?', '[']) {
+        return vec![member.to_string()];
+    }
+    let (parent, pattern) = match member.rsplit_once('/') {
+        Some((parent, pattern)) => (parent, pattern),
+        None => ("", member),
+    };
+    let matcher = WildMatch::new(pattern);
+    let Ok(entries) = std::fs::read_dir(root.join(parent)) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<String> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .filter(|name| matcher.matches(name))
+        .map(|name| {
+            if parent.is_empty() {
+                name
+            } else {
+                format!("{parent}/{name}")
+            }
+        })
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+fn detect_node(root: &Path) -> Option<NodeProject> {
+    let contents = std::fs::read_to_string(root.join("package.json")).ok()?;
+    let manifest: serde_json::Value = match serde_json::from_str(&contents) {
+        Ok(value) => value,
+        Err(err) => {
+            tracing::warn!("failed to parse package.json: {err}");
+            return None;
+        }
+    };
+    let mut scripts: Vec<String> = manifest
+        .get("scripts")
+        .and_then(serde_json::Value::as_object)
+        .map(|scripts| scripts.keys().cloned().collect())
+        .unwrap_or_default();
+    // Key order depends on whether serde_json's `preserve_order` is enabled.
+    scripts.sort();
+    let runner = [
+        ("pnpm-lock.yaml", "pnpm"),
+        ("yarn.lock", "yarn"),
+        ("bun.lockb", "bun"),
+        ("bun.lock", "bun"),
+    ]
+    .into_iter()
+    .find(|(lockfile, _)| root.join(lockfile).is_file())
+    .map_or("npm", |(_, runner)| runner);
+    Some(NodeProject {
+        runner: runner.to_string(),
+        scripts,
+    })
+}
+
+fn detect_python(root: &Path) -> bool {
+    ["pytest.ini", "conftest.py", "tox.ini", "setup.py"]
+        .iter()
+        .any(|marker| root.join(marker).is_file())
+        || std::fs::read_to_string(root.join("pyproject.toml"))
+            .is_ok_and(|contents| contents.contains("pytest"))
+        || std::fs::read_to_string(root.join("setup.cfg"))
+            .is_ok_and(|contents| contents.contains("[tool:pytest]"))
+}
+
+fn detect_just_recipes(root: &Path) -> Vec<String> {
+    let Some(contents) = ["justfile", "Justfile", ".justfile"]
+        .iter()
+        .find_map(|name| std::fs::read_to_string(root.join(name)).ok())
+    else {
+        return Vec::new();
+    };
+    let mut recipes = Vec::new();
+    for line in contents.lines() {
+        if line.starts_with([' ', '\t', '#', '[']) || line.contains(":=") {
+            continue;
+        }
+        let Some((head, _)) = line.split_once(':') else {
+            continue;
+        };
+        let Some(name) = head.split_whitespace().next() else {
+            continue;
+        };
+        let name = name.trim_start_matches('@');
+        if ["set", "alias", "export", "import", "mod"].contains(&name) {
+            continue;
+        }
+        if !name.is_empty() && !recipes.iter().any(|recipe| recipe == name) {
+            recipes.push(name.to_string());
+        }
+    }
+    recipes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::write_file;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn detects_workspace_members_and_tooling() {
+        let dir = TempDir::new().expect("tempdir");
+        let root = dir.path();
+        write_file(
+            root,
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"cli\", \"crates/*\"]\nexclude = [\"crates/scratch\"]\n",
+        );
+        write_file(root, "cli/Cargo.toml", "[package]\nname = \"demo-cli\"\n");
+        write_file(
+            root,
+            "crates/core/Cargo.toml",
+            "[package]\nname = \"demo-core\"\n",
+        );
+        write_file(
+            root,
+            "crates/scratch/Cargo.toml",
+            "[package]\nname = \"scratch\"\n",
+        );
+        write_file(
+            root,
+            "package.json",
+            r#"{"scripts": {"test": "jest", "lint": "eslint ."}}"#,
+        );
+        write_file(root, "pnpm-lock.yaml", "");
+        write_file(root, "go.mod", "module example.com/demo\n");
+        write_file(
+            root,
+            "justfile",
+            "set shell := [\"bash\", \"-c\"]\n\n# Run tests\ntest *args:\n    cargo test {{args}}\n\n@fmt:\n    cargo fmt\n",
+        );
+
+        let layout = ProjectLayout::detect(root);
+        assert!(layout.cargo_workspace);
+        assert_eq!(
+            layout.cargo_packages,
+            vec![
+                CargoPackage {
+                    name: "demo-cli".to_string(),
+                    dir: "cli".to_string(),
+                },
+                CargoPackage {
+                    name: "demo-core".to_string(),
+                    dir: "crates/core".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            layout.node,
+            Some(NodeProject {
+                runner: "pnpm".to_string(),
+                scripts: vec!["lint".to_string(), "test".to_string()],
+            })
+        );
+        assert!(layout.go);
+        assert!(!layout.python);
+        assert_eq!(layout.just_recipes, vec!["test", "fmt"]);
+        assert_eq!(
+            layout
+                .cargo_package_for("crates/core/src/lib.rs")
+                .map(|package| package.name.as_str()),
+            Some("demo-core")
+        );
+        assert_eq!(layout.cargo_package_for("README.md"), None);
+        assert_eq!(
+            layout.relative_path(&root.join("cli/src/main.rs").to_string_lossy()),
+            "cli/src/main.rs"
+        );
+    }
+}
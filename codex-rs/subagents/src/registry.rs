@@ -1,8 +1,14 @@
 use crate::error::AgentParseError;
 use crate::error::ParserError;
 use crate::error::RegistryError;
+use crate::packs::AgentsLock;
+use crate::packs::pack_agent_files;
+use crate::packs::verify_packs;
+use crate::parser::AgentDefinition;
 use crate::parser::ParsedAgent;
+use crate::parser::parse_agent_definition;
 use crate::parser::parse_agent_file;
+use crate::parser::parse_confined_agent_file;
 use crate::spec::AgentSource;
 use crate::spec::SubagentSpec;
 use std::collections::BTreeMap;
@@ -17,6 +23,18 @@ use time::OffsetDateTime;
 pub struct AgentHandle {
     pub spec: SubagentSpec,
     pub warnings: Vec<String>,
+    /// Lower-precedence sources that also define this agent, nearest first.
+    pub overrides: Vec<AgentSource>,
+}
+
+impl AgentHandle {
+    pub fn new(spec: SubagentSpec) -> Self {
+        Self {
+            spec,
+            warnings: Vec::new(),
+            overrides: Vec::new(),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -33,9 +51,20 @@ struct CachedEntry {
     warnings: Vec<String>,
 }
 
+#[derive(Debug, Clone)]
+struct ConfigAgents {
+    path: PathBuf,
+    definitions: BTreeMap<String, AgentDefinition>,
+}
+
+/// Agents merged from every source. Later sources replace earlier ones with
+/// the same name: builtin, then config, then the user directory, then the
+/// project directory.
 pub struct SubagentRegistry {
     project_dir: PathBuf,
     user_dir: PathBuf,
+    config_agents: Option<ConfigAgents>,
+    builtin_agents: Vec<SubagentSpec>,
     cache: HashMap<PathBuf, CachedEntry>,
     agents: BTreeMap<String, AgentHandle>,
     parse_errors: Vec<AgentParseError>,
@@ -47,6 +76,8 @@ impl SubagentRegistry {
         Self {
             project_dir: project_dir.into(),
             user_dir: user_dir.into(),
+            config_agents: None,
+            builtin_agents: Vec::new(),
             cache: HashMap::new(),
             agents: BTreeMap::new(),
             parse_errors: Vec::new(),
@@ -54,11 +85,57 @@ impl SubagentRegistry {
         }
     }
 
+    pub fn with_builtin_agents(mut self, agents: Vec<SubagentSpec>) -> Self {
+        self.builtin_agents = agents;
+        self
+    }
+
+    /// Agents from `[subagents.agents]` tables, keyed by name. `config_path`
+    /// is reported in parse errors and anchors relative `instructions_file`s.
+    pub fn with_config_agents(
+        mut self,
+        config_path: impl Into<PathBuf>,
+        definitions: BTreeMap<String, AgentDefinition>,
+    ) -> Self {
+        self.config_agents = Some(ConfigAgents {
+            path: config_path.into(),
+            definitions,
+        });
+        self
+    }
+
     pub fn reload(&mut self) -> Result<&RegistrySnapshot, RegistryError> {
         let mut agents = BTreeMap::new();
         let mut cache = HashMap::new();
         let mut parse_errors = Vec::new();
 
+        for spec in &self.builtin_agents {
+            insert_agent(&mut agents, AgentHandle::new(spec.clone()));
+        }
+        if let Some(config) = &self.config_agents {
+            for (key, definition) in &config.definitions {
+                match parse_agent_definition(
+                    definition.clone(),
+                    Some(key),
+                    &config.path,
+                    AgentSource::Config,
+                ) {
+                    Ok(parsed) => insert_agent(
+                        &mut agents,
+                        AgentHandle {
+                            spec: parsed.spec,
+                            warnings: parsed.warnings,
+                            overrides: Vec::new(),
+                        },
+                    ),
+                    Err(err) => parse_errors.push(AgentParseError::new(
+                        config.path.clone(),
+                        format!("[subagents.agents.{key}]: {err}"),
+                    )),
+                }
+            }
+        }
+
         let user_dir = self.user_dir.clone();
         let project_dir = self.project_dir.clone();
 
@@ -109,24 +186,39 @@ impl SubagentRegistry {
             }
         };
 
+        // Each file comes with the pack directory it is confined to, if any.
+        let mut paths = Vec::new();
         for entry in entries {
-            let entry = match entry {
-                Ok(entry) => entry,
+            match entry {
+                Ok(entry) => paths.push((entry.path(), None)),
                 Err(err) => {
                     parse_errors.push(AgentParseError::new(
                         dir.to_path_buf(),
                         format!("failed to read directory entry: {err}"),
                     ));
-                    continue;
                 }
-            };
-            let path = entry.path();
+            }
+        }
+        paths.sort();
+        paths.extend(verified_pack_files(dir, parse_errors));
+
+        let mut seen: HashMap<String, PathBuf> = HashMap::new();
+        for (path, pack_dir) in paths {
             if !is_agent_file(&path) {
                 continue;
             }
 
-            match self.load_agent(&path, source) {
+            match self.load_agent(&path, pack_dir.as_deref(), source) {
                 Ok(Some(parsed)) => {
+                    let name = parsed.spec.metadata.name.clone();
+                    if let Some(first) = seen.get(&name) {
+                        parse_errors.push(AgentParseError::new(
+                            path.clone(),
+                            format!("agent `{name}` is already defined in {}", first.display()),
+                        ));
+                        continue;
+                    }
+                    seen.insert(name, path.clone());
                     let modified = get_modified_time(&path);
                     cache.insert(
                         path.clone(),
@@ -136,11 +228,12 @@ impl SubagentRegistry {
                             warnings: parsed.warnings.clone(),
                         },
                     );
-                    agents.insert(
-                        parsed.spec.metadata.name.clone(),
+                    insert_agent(
+                        agents,
                         AgentHandle {
                             spec: parsed.spec,
                             warnings: parsed.warnings,
+                            overrides: Vec::new(),
                         },
                     );
                 }
@@ -161,10 +254,14 @@ impl SubagentRegistry {
     fn load_agent(
         &mut self,
         path: &Path,
+        pack_dir: Option<&Path>,
         source: AgentSource,
     ) -> Result<Option<ParsedAgent>, RegistryError> {
+        // TOML and JSON agents may read an `instructions_file` whose changes
+        // the file's own mtime does not reflect, so only Markdown is cached.
         let modified = get_modified_time(path);
-        if let Some(entry) = self.cache.get(path)
+        if is_markdown(path)
+            && let Some(entry) = self.cache.get(path)
             && entry.modified == modified
         {
             return Ok(Some(ParsedAgent {
@@ -173,7 +270,11 @@ impl SubagentRegistry {
             }));
         }
 
-        match parse_agent_file(path, source) {
+        let parsed = match pack_dir {
+            Some(pack_dir) => parse_confined_agent_file(path, pack_dir, source),
+            None => parse_agent_file(path, source),
+        };
+        match parsed {
             Ok(parsed) => Ok(Some(parsed)),
             Err(ParserError::Io(err)) => Err(RegistryError::Io {
                 path: path.to_path_buf(),
@@ -203,7 +304,53 @@ impl SubagentRegistry {
     }
 }
 
+/// Agent files of the packs under `dir/packs` that match `agents.lock`,
+/// each with its pack directory. Packs that are modified, missing or
+/// unrecorded are reported instead.
+fn verified_pack_files(
+    dir: &Path,
+    parse_errors: &mut Vec<AgentParseError>,
+) -> Vec<(PathBuf, Option<PathBuf>)> {
+    let checks = match verify_packs(dir) {
+        Ok(checks) => checks,
+        Err(err) => {
+            parse_errors.push(AgentParseError::new(AgentsLock::path(dir), err.to_string()));
+            return Vec::new();
+        }
+    };
+    let mut paths = Vec::new();
+    for check in checks {
+        match check.problem() {
+            Some(problem) => parse_errors.push(AgentParseError::new(check.dir, problem)),
+            None => paths.extend(
+                pack_agent_files(&check.dir)
+                    .into_iter()
+                    .map(|path| (path, Some(check.dir.clone()))),
+            ),
+        }
+    }
+    paths
+}
+
+fn insert_agent(agents: &mut BTreeMap<String, AgentHandle>, mut handle: AgentHandle) {
+    if let Some(previous) = agents.remove(&handle.spec.metadata.name) {
+        handle.overrides = std::iter::once(previous.spec.source)
+            .chain(previous.overrides)
+            .collect();
+    }
+    agents.insert(handle.spec.metadata.name.clone(), handle);
+}
+
 fn is_agent_file(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| matches!(ext, "md" | "markdown" | "toml" | "json"))
+            .unwrap_or(false)
+}
+
+fn is_markdown(path: &Path) -> bool {
     path.extension()
         .and_then(|ext| ext.to_str())
         .map(|ext| matches!(ext, "md" | "markdown"))
@@ -256,4 +403,166 @@ Project reviewer instructions.
         assert_eq!(agent.spec.metadata.tools, vec!["apply_patch", "git_diff"]);
         assert_eq!(agent.spec.source, AgentSource::Project);
     }
+
+    #[test]
+    fn loads_only_packs_that_match_the_lockfile() {
+        let temp = tempfile::tempdir().unwrap();
+        let project_dir = temp.path().join("project");
+        let user_dir = temp.path().join("user");
+        for (pack, agent) in [
+            ("review-pack", "pack-reviewer"),
+            ("docs-pack", "documenter"),
+        ] {
+            let source = temp.path().join(pack);
+            fs::create_dir_all(&source).unwrap();
+            write(source.join("pack.toml"), format!("name = \"{pack}\"\n")).unwrap();
+            write(
+                source.join(format!("{agent}.md")),
+                format!("---\nname: {agent}\n---\nInstructions.\n"),
+            )
+            .unwrap();
+            crate::packs::PackStore::new(&project_dir)
+                .install(&crate::packs::PackSource::Path(source))
+                .unwrap();
+        }
+        let tampered = project_dir.join("packs/docs-pack");
+        write(
+            tampered.join("documenter.md"),
+            "---\nname: documenter\n---\nInjected.\n",
+        )
+        .unwrap();
+        fs::create_dir_all(project_dir.join("packs/stray-pack")).unwrap();
+
+        let mut registry = SubagentRegistry::new(&project_dir, &user_dir);
+        let snapshot = registry.reload().unwrap();
+        let names: Vec<&str> = snapshot
+            .agents
+            .iter()
+            .map(|handle| handle.spec.metadata.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["pack-reviewer"]);
+        assert_eq!(
+            snapshot.agents[0].spec.source_path.as_deref(),
+            Some(
+                project_dir
+                    .join("packs/review-pack/pack-reviewer.md")
+                    .as_path()
+            )
+        );
+
+        let errors: Vec<(&Path, bool)> = snapshot
+            .parse_errors
+            .iter()
+            .map(|err| (err.path.as_path(), err.message.contains("agents.lock")))
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                (tampered.as_path(), true),
+                (project_dir.join("packs/stray-pack").as_path(), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn merges_builtin_config_user_and_project_agents() {
+        let temp = tempfile::tempdir().unwrap();
+        let project_dir = temp.path().join("project");
+        let user_dir = temp.path().join("user");
+        fs::create_dir_all(&project_dir).unwrap();
+        fs::create_dir_all(&user_dir).unwrap();
+
+        write(
+            user_dir.join("reviewer.toml"),
+            "description = \"User reviewer\"\nname = \"reviewer\"\ninstructions_file = \"reviewer.txt\"\n",
+        )
+        .unwrap();
+        write(user_dir.join("reviewer.txt"), "Review like the user.").unwrap();
+        write(
+            project_dir.join("planner.json"),
+            r#"{"name": "planner", "tools": ["read_file"], "instructions": "Plan."}"#,
+        )
+        .unwrap();
+        write(
+            project_dir.join("planner.md"),
+            "---\nname: planner\n---\nDuplicate planner.",
+        )
+        .unwrap();
+
+        let config_path = temp.path().join("config.toml");
+        let definitions: BTreeMap<String, AgentDefinition> = toml::from_str(
+            r#"
+[reviewer]
+description = "Config reviewer"
+instructions = "Review like the config."
+
+[summarizer]
+model = "gpt-5"
+instructions = "Summarize."
+
+[broken]
+"#,
+        )
+        .unwrap();
+
+        let builtin = crate::SubagentBuilder::new("reviewer")
+            .instructions("Builtin reviewer.")
+            .source(AgentSource::Builtin)
+            .build()
+            .unwrap();
+        let mut registry = SubagentRegistry::new(&project_dir, &user_dir)
+            .with_builtin_agents(vec![builtin])
+            .with_config_agents(&config_path, definitions);
+        let snapshot = registry.reload().unwrap();
+
+        let summary: Vec<(&str, AgentSource, Vec<AgentSource>)> = snapshot
+            .agents
+            .iter()
+            .map(|handle| {
+                (
+                    handle.spec.metadata.name.as_str(),
+                    handle.spec.source,
+                    handle.overrides.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("planner", AgentSource::Project, vec![]),
+                (
+                    "reviewer",
+                    AgentSource::User,
+                    vec![AgentSource::Config, AgentSource::Builtin]
+                ),
+                ("summarizer", AgentSource::Config, vec![]),
+            ]
+        );
+        let reviewer = registry.get("reviewer").unwrap();
+        assert_eq!(reviewer.spec.instructions, "Review like the user.");
+        assert_eq!(
+            reviewer.spec.source_path.as_deref(),
+            Some(user_dir.join("reviewer.toml").as_path())
+        );
+
+        let errors: Vec<(&Path, &str)> = registry
+            .parse_errors()
+            .iter()
+            .map(|err| (err.path.as_path(), err.message.as_str()))
+            .collect();
+        let duplicate = format!(
+            "agent `planner` is already defined in {}",
+            project_dir.join("planner.json").display()
+        );
+        assert_eq!(
+            errors,
+            vec![
+                (
+                    config_path.as_path(),
+                    "[subagents.agents.broken]: missing required field: instructions"
+                ),
+                (project_dir.join("planner.md").as_path(), duplicate.as_str()),
+            ]
+        );
+    }
 }
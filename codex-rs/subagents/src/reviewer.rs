@@ -118,6 +118,7 @@ mod tests {
     use crate::pipeline::TestStatus;
     use crate::pipeline::merge_test_results;
     use crate::pipeline::plan_tests_for_changes;
+    use crate::project::ProjectLayout;
 
     #[test]
     fn highlights_blocked_tests() {
@@ -131,7 +132,7 @@ mod tests {
                 vec![],
             )],
         );
-        let plan = plan_tests_for_changes(&changes);
+        let plan = plan_tests_for_changes(&ProjectLayout::default(), &changes);
         let results = merge_test_results(
             &plan,
             vec![TestOutcome::new(
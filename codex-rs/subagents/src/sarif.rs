@@ -0,0 +1,440 @@
+//! SARIF 2.1.0 export of review results so code-scanning UIs can show them.
+//!
+//! Both the `/review` output ([`ReviewOutputEvent`]) and the reviewer
+//! subagent's [`ReviewFindings`] map onto a single [`SarifLog`]; each source
+//! becomes one run and each finding category one rule.
+
+use crate::pipeline::ProposedChanges;
+use crate::pipeline::ReviewFinding;
+use crate::pipeline::ReviewFindings;
+use crate::pipeline::Severity;
+use codex_protocol::protocol::ReviewOutputEvent;
+use serde::Deserialize;
+use serde::Serialize;
+use std::path::Path;
+
+pub const SARIF_VERSION: &str = "2.1.0";
+pub const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    pub schema: String,
+    pub version: String,
+    pub runs: Vec<SarifRun>,
+}
+
+impl Default for SarifLog {
+    fn default() -> Self {
+        Self {
+            schema: SARIF_SCHEMA.to_string(),
+            version: SARIF_VERSION.to_string(),
+            runs: Vec::new(),
+        }
+    }
+}
+
+impl SarifLog {
+    pub fn with_run(mut self, run: SarifRun) -> Self {
+        self.runs.push(run);
+        self
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub fn write_to(&self, path: &Path) -> anyhow::Result<()> {
+        std::fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SarifRun {
+    pub tool: SarifTool,
+    pub results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SarifTool {
+    pub driver: SarifDriver,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifDriver {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub information_uri: Option<String>,
+    pub rules: Vec<SarifRule>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRule {
+    pub id: String,
+    pub short_description: SarifMessage,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SarifMessage {
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SarifLevel {
+    Error,
+    Warning,
+    Note,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    pub rule_id: String,
+    pub level: SarifLevel,
+    pub message: SarifMessage,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub locations: Vec<SarifLocation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub properties: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifLocation {
+    pub physical_location: SarifPhysicalLocation,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifPhysicalLocation {
+    pub artifact_location: SarifArtifactLocation,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<SarifRegion>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SarifArtifactLocation {
+    pub uri: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRegion {
+    pub start_line: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<u32>,
+}
+
+impl SarifLocation {
+    /// `path` relative to `root` when it lies inside it, so viewers can match
+    /// results to repository files.
+    fn new(path: &Path, root: &Path, lines: Option<(u32, u32)>) -> Self {
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        let uri = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        let region = lines
+            .filter(|(start, _)| *start > 0)
+            .map(|(start, end)| SarifRegion {
+                start_line: start,
+                end_line: (end > start).then_some(end),
+            });
+        Self {
+            physical_location: SarifPhysicalLocation {
+                artifact_location: SarifArtifactLocation { uri },
+                region,
+            },
+        }
+    }
+}
+
+impl SarifRun {
+    fn new(tool_name: impl Into<String>, results: Vec<SarifResult>) -> Self {
+        let mut rules: Vec<SarifRule> = Vec::new();
+        for result in &results {
+            if !rules.iter().any(|rule| rule.id == result.rule_id) {
+                rules.push(SarifRule {
+                    id: result.rule_id.clone(),
+                    short_description: SarifMessage {
+                        text: rule_description(&result.rule_id),
+                    },
+                });
+            }
+        }
+        Self {
+            tool: SarifTool {
+                driver: SarifDriver {
+                    name: tool_name.into(),
+                    information_uri: Some("https://github.com/openai/codex".to_string()),
+                    rules,
+                },
+            },
+            results,
+        }
+    }
+
+    /// Convert `/review` output. Paths are made relative to `root`; the
+    /// finding's priority (`P0`–`P3`) is its rule.
+    pub fn from_review_output(output: &ReviewOutputEvent, root: &Path) -> Self {
+        let results = output
+            .findings
+            .iter()
+            .map(|finding| {
+                let location = &finding.code_location;
+                SarifResult {
+                    rule_id: priority_rule_id(finding.priority),
+                    level: priority_level(finding.priority),
+                    message: SarifMessage {
+                        text: if finding.body.is_empty() {
+                            finding.title.clone()
+                        } else {
+                            format!("{}\n\n{}", finding.title, finding.body)
+                        },
+                    },
+                    locations: vec![SarifLocation::new(
+                        &location.absolute_file_path,
+                        root,
+                        Some((location.line_range.start, location.line_range.end)),
+                    )],
+                    properties: Some(serde_json::json!({
+                        "confidence": finding.confidence_score,
+                    })),
+                }
+            })
+            .collect();
+        Self::new("codex-review", results)
+    }
+
+    /// Convert the reviewer subagent's findings. A finding tied to a
+    /// requirement is located at the files `changes` touched for it.
+    pub fn from_review_findings(
+        findings: &ReviewFindings,
+        changes: Option<&ProposedChanges>,
+        root: &Path,
+    ) -> Self {
+        let results = findings
+            .findings
+            .iter()
+            .map(|finding| {
+                let locations = finding
+                    .related_requirement
+                    .as_deref()
+                    .into_iter()
+                    .flat_map(|requirement_id| {
+                        changes
+                            .into_iter()
+                            .flat_map(|changes| changes.changes.iter())
+                            .filter(move |change| change.requirement_id == requirement_id)
+                    })
+                    .flat_map(|change| change.files.iter())
+                    .map(|file| SarifLocation::new(Path::new(&file.path), root, None))
+                    .collect();
+                SarifResult {
+                    rule_id: finding_rule_id(finding).to_string(),
+                    level: severity_level(&finding.severity),
+                    message: SarifMessage {
+                        text: match &finding.suggested_fix {
+                            Some(fix) => format!("{}\n\nSuggested fix: {fix}", finding.message),
+                            None => finding.message.clone(),
+                        },
+                    },
+                    locations,
+                    properties: finding
+                        .related_requirement
+                        .as_ref()
+                        .map(|id| serde_json::json!({ "requirement": id })),
+                }
+            })
+            .collect();
+        Self::new("codex-reviewer", results)
+    }
+
+    /// Read findings from a subagent's final message, which may hold either
+    /// [`ReviewFindings`] or `/review`-style JSON, possibly wrapped in prose.
+    /// Returns `None` when the message carries neither.
+    pub fn from_agent_message(agent_name: &str, message: &str, root: &Path) -> Option<Self> {
+        let json = match (message.find('{'), message.rfind('}')) {
+            (Some(start), Some(end)) if start < end => &message[start..=end],
+            _ => return None,
+        };
+        let mut run = if let Ok(findings) = serde_json::from_str::<ReviewFindings>(json) {
+            Self::from_review_findings(&findings, None, root)
+        } else if let Ok(output) = serde_json::from_str::<ReviewOutputEvent>(json) {
+            Self::from_review_output(&output, root)
+        } else {
+            return None;
+        };
+        run.tool.driver.name = format!("codex-subagent-{agent_name}");
+        Some(run)
+    }
+}
+
+fn severity_level(severity: &Severity) -> SarifLevel {
+    match severity {
+        Severity::Critical | Severity::High => SarifLevel::Error,
+        Severity::Medium => SarifLevel::Warning,
+        Severity::Low | Severity::Info => SarifLevel::Note,
+    }
+}
+
+fn priority_level(priority: i32) -> SarifLevel {
+    match priority {
+        i32::MIN..=1 => SarifLevel::Error,
+        2 => SarifLevel::Warning,
+        _ => SarifLevel::Note,
+    }
+}
+
+fn priority_rule_id(priority: i32) -> String {
+    format!("codex-review/P{}", priority.clamp(0, 3))
+}
+
+/// Categories match the findings `review_findings_from_results` produces;
+/// anything else is a general review comment.
+fn finding_rule_id(finding: &ReviewFinding) -> &'static str {
+    let message = finding.message.as_str();
+    if message.starts_with("Security-related requirement") {
+        "codex-reviewer/security"
+    } else if message.starts_with("Test ") && message.ends_with(" failed") {
+        "codex-reviewer/test-failed"
+    } else if message.starts_with("Test ") && message.ends_with(" did not run") {
+        "codex-reviewer/test-not-run"
+    } else {
+        "codex-reviewer/review"
+    }
+}
+
+fn rule_description(rule_id: &str) -> String {
+    match rule_id {
+        "codex-review/P0" => "Blocking issue".to_string(),
+        "codex-review/P1" => "Urgent issue".to_string(),
+        "codex-review/P2" => "Normal-priority issue".to_string(),
+        "codex-review/P3" => "Low-priority issue".to_string(),
+        "codex-reviewer/security" => "Security-sensitive change needs review".to_string(),
+        "codex-reviewer/test-failed" => "Test failed".to_string(),
+        "codex-reviewer/test-not-run" => "Test did not run".to_string(),
+        _ => "Review finding".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::ChangeFile;
+    use crate::pipeline::ChangeType;
+    use crate::pipeline::ProposedChange;
+    use codex_protocol::protocol::ReviewCodeLocation;
+    use codex_protocol::protocol::ReviewLineRange;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    #[test]
+    fn review_output_maps_priority_and_location() {
+        let output = ReviewOutputEvent {
+            findings: vec![codex_protocol::protocol::ReviewFinding {
+                title: "[P1] Off-by-one".to_string(),
+                body: "Loop skips the last item.".to_string(),
+                confidence_score: 0.8,
+                priority: 1,
+                code_location: ReviewCodeLocation {
+                    absolute_file_path: PathBuf::from("/repo/src/lib.rs"),
+                    line_range: ReviewLineRange { start: 10, end: 12 },
+                },
+            }],
+            ..Default::default()
+        };
+        let log =
+            SarifLog::default().with_run(SarifRun::from_review_output(&output, Path::new("/repo")));
+        let json: serde_json::Value =
+            serde_json::from_str(&log.to_json().expect("json")).expect("valid json");
+
+        assert_eq!(json["version"], "2.1.0");
+        let run = &json["runs"][0];
+        assert_eq!(run["tool"]["driver"]["rules"][0]["id"], "codex-review/P1");
+        let result = &run["results"][0];
+        assert_eq!(result["level"], "error");
+        assert_eq!(result["ruleId"], "codex-review/P1");
+        let location = &result["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/lib.rs");
+        assert_eq!(
+            location["region"],
+            serde_json::json!({ "startLine": 10, "endLine": 12 })
+        );
+    }
+
+    #[test]
+    fn review_findings_use_changed_files_as_locations() {
+        let findings = ReviewFindings::new(
+            "two findings",
+            vec![
+                ReviewFinding::new(
+                    Severity::High,
+                    "Security-related requirement REQ-001 needs dedicated review",
+                    Some("REQ-001".to_string()),
+                    None,
+                ),
+                ReviewFinding::new(Severity::Medium, "Test cargo test did not run", None, None),
+            ],
+        );
+        let changes = ProposedChanges::new(
+            "r",
+            vec![ProposedChange::new(
+                "REQ-001",
+                "Auth",
+                vec![ChangeFile::new("src/auth.rs", ChangeType::Modify, "")],
+                vec![],
+            )],
+        );
+        let run = SarifRun::from_review_findings(&findings, Some(&changes), Path::new("/repo"));
+
+        let summary: Vec<(&str, SarifLevel, Vec<&str>)> = run
+            .results
+            .iter()
+            .map(|result| {
+                (
+                    result.rule_id.as_str(),
+                    result.level,
+                    result
+                        .locations
+                        .iter()
+                        .map(|location| location.physical_location.artifact_location.uri.as_str())
+                        .collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "codex-reviewer/security",
+                    SarifLevel::Error,
+                    vec!["src/auth.rs"]
+                ),
+                ("codex-reviewer/test-not-run", SarifLevel::Warning, vec![]),
+            ]
+        );
+        assert_eq!(run.tool.driver.rules.len(), 2);
+    }
+
+    #[test]
+    fn agent_messages_may_wrap_findings_in_prose() {
+        let message = r#"Review done.
+{"summary": "one", "findings": [{"severity": "low", "message": "Rename helper"}]}"#;
+        let run = SarifRun::from_agent_message("reviewer", message, Path::new("/repo"))
+            .expect("findings");
+        assert_eq!(run.tool.driver.name, "codex-subagent-reviewer");
+        assert_eq!(run.results[0].level, SarifLevel::Note);
+        assert_eq!(run.results[0].rule_id, "codex-reviewer/review");
+
+        assert_eq!(
+            SarifRun::from_agent_message("reviewer", "Looks good to me.", Path::new("/repo")),
+            None
+        );
+    }
+}
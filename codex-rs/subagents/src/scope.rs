@@ -0,0 +1,121 @@
+//! Resolving an agent's `cwd` and `writable_paths` against the project root.
+//!
+//! The builder already rejects absolute paths and `..`; this checks the
+//! directories on disk, so a symlink cannot lead a child out of the project.
+
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::spec::SubagentMetadata;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AgentScope {
+    /// Absolute working directory, when the agent sets `cwd`.
+    pub cwd: Option<PathBuf>,
+    /// Absolute `writable_paths`.
+    pub writable_roots: Vec<PathBuf>,
+}
+
+impl AgentScope {
+    /// Fails when a path does not exist, is not a directory, or resolves
+    /// outside `project_root`.
+    pub fn resolve(metadata: &SubagentMetadata, project_root: &Path) -> io::Result<Self> {
+        let root = project_root.canonicalize()?;
+        let cwd = metadata
+            .cwd
+            .as_deref()
+            .map(|cwd| resolve_inside(&root, "cwd", cwd))
+            .transpose()?;
+        let writable_roots = metadata
+            .writable_paths
+            .iter()
+            .map(|path| resolve_inside(&root, "writable_paths", path))
+            .collect::<io::Result<Vec<_>>>()?;
+        Ok(Self {
+            cwd,
+            writable_roots,
+        })
+    }
+}
+
+fn resolve_inside(root: &Path, field: &str, relative: &Path) -> io::Result<PathBuf> {
+    let path = root.join(relative).canonicalize().map_err(|err| {
+        io::Error::new(
+            err.kind(),
+            format!("{field} `{}`: {err}", relative.display()),
+        )
+    })?;
+    if !path.starts_with(root) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "{field} `{}` resolves outside the project root {}",
+                relative.display(),
+                root.display()
+            ),
+        ));
+    }
+    if !path.is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{field} `{}` is not a directory", relative.display()),
+        ));
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn resolves_directories_inside_the_project() {
+        let project = TempDir::new().unwrap();
+        let root = project.path().canonicalize().unwrap();
+        std::fs::create_dir_all(root.join("web/src")).unwrap();
+        std::fs::write(root.join("README.md"), "").unwrap();
+        let metadata = SubagentMetadata::new("frontend-dev".to_string())
+            .cwd(Some(PathBuf::from("web")))
+            .writable_paths(vec![PathBuf::from("web/src")]);
+
+        assert_eq!(
+            AgentScope::resolve(&metadata, &root).unwrap(),
+            AgentScope {
+                cwd: Some(root.join("web")),
+                writable_roots: vec![root.join("web/src")],
+            }
+        );
+        assert_eq!(
+            AgentScope::resolve(&SubagentMetadata::new("plain".to_string()), &root).unwrap(),
+            AgentScope::default()
+        );
+
+        let missing = metadata.clone().cwd(Some(PathBuf::from("api")));
+        let err = AgentScope::resolve(&missing, &root).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+        let file = metadata.cwd(Some(PathBuf::from("README.md")));
+        let err = AgentScope::resolve(&file, &root).unwrap_err();
+        assert_eq!(err.to_string(), "cwd `README.md` is not a directory");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejects_symlinks_out_of_the_project() {
+        let project = TempDir::new().unwrap();
+        let outside = TempDir::new().unwrap();
+        std::os::unix::fs::symlink(outside.path(), project.path().join("escape")).unwrap();
+        let metadata = SubagentMetadata::new("frontend-dev".to_string())
+            .writable_paths(vec![PathBuf::from("escape")]);
+
+        let err = AgentScope::resolve(&metadata, project.path()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(
+            err.to_string()
+                .starts_with("writable_paths `escape` resolves outside the project root"),
+            "{err}"
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::context::ContextAttachment;
 use serde::Deserialize;
 use serde::Serialize;
 use serde_json::Value as JsonValue;
@@ -11,6 +12,8 @@ pub enum AgentSource {
     Project,
     User,
     Builtin,
+    /// Defined under `[subagents.agents]` in `config.toml` or a profile.
+    Config,
     Inline,
 }
 
@@ -20,6 +23,7 @@ impl AgentSource {
             AgentSource::Project => "project",
             AgentSource::User => "user",
             AgentSource::Builtin => "builtin",
+            AgentSource::Config => "config",
             AgentSource::Inline => "inline",
         }
     }
@@ -50,6 +54,25 @@ pub struct SubagentMetadata {
     pub tools: Vec<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub keywords: Vec<String>,
+    /// Agents this one may pass control to through the `handoff` tool.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub handoff_to: Vec<String>,
+    /// Offer `remember`/`recall` tools backed by the agent's notes file.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub memory: bool,
+    /// Inputs rendered into the child's first user message.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub context: Vec<ContextAttachment>,
+    /// Working directory for the child, relative to the project root.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<PathBuf>,
+    /// Replaces the workspace-write sandbox's extra writable roots; relative
+    /// to the project root.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub writable_paths: Vec<PathBuf>,
+    /// Variables added to the child's shell environment.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
 }
 
 impl SubagentMetadata {
@@ -61,6 +84,12 @@ impl SubagentMetadata {
             model_config: None,
             tools: Vec::new(),
             keywords: Vec::new(),
+            handoff_to: Vec::new(),
+            memory: false,
+            context: Vec::new(),
+            cwd: None,
+            writable_paths: Vec::new(),
+            env: BTreeMap::new(),
         }
     }
 
@@ -88,6 +117,36 @@ impl SubagentMetadata {
         self.keywords = keywords;
         self
     }
+
+    pub fn handoff_to(mut self, handoff_to: Vec<String>) -> Self {
+        self.handoff_to = handoff_to;
+        self
+    }
+
+    pub fn memory(mut self, memory: bool) -> Self {
+        self.memory = memory;
+        self
+    }
+
+    pub fn context(mut self, context: Vec<ContextAttachment>) -> Self {
+        self.context = context;
+        self
+    }
+
+    pub fn cwd(mut self, cwd: Option<PathBuf>) -> Self {
+        self.cwd = cwd;
+        self
+    }
+
+    pub fn writable_paths(mut self, paths: Vec<PathBuf>) -> Self {
+        self.writable_paths = paths;
+        self
+    }
+
+    pub fn env(mut self, env: BTreeMap<String, String>) -> Self {
+        self.env = env;
+        self
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -6,26 +6,16 @@ use crate::SubagentSpec;
 use crate::TaskContext;
 use crate::TaskContextError;
 use crate::TypedSubagent;
-use crate::pipeline::AcceptanceCriterion;
-use crate::pipeline::Requirement;
 use crate::pipeline::RequirementsSpec;
+use crate::spec_sources::SpecFormat;
+use crate::spec_sources::SpecSource;
+use crate::spec_sources::SpecSources;
 use anyhow::Result;
 use anyhow::anyhow;
-use once_cell::sync::Lazy;
-use regex_lite::Regex;
+use serde::Deserialize;
 use serde::Serialize;
 use std::borrow::Cow;
-use std::collections::HashSet;
-
-static REQUIREMENT_LINE: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"^-\s*\[(?P<id>[A-Z0-9_-]+)\]\s*(?P<summary>.+)$").expect("valid regex")
-});
-
-static ACCEPTANCE_LINE: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"^(?P<id>AC-[0-9]{3,})?\s*:?\s*(?P<text>.+)$").expect("valid regex"));
-
-static FILE_HINT_LINE: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"(?i)^file[s]?\s*:\s*(?P<files>.+)$").expect("valid regex"));
+use std::path::PathBuf;
 
 const SPEC_PARSER_PROMPT: &str = r#"
 You are the specification parser subagent. Read the user's request and produce a concise
@@ -40,25 +30,44 @@ Return JSON following this schema:
   `acceptance_criteria` (array of strings), and optional `file_hints` (array of strings).
 "#;
 
-#[derive(Default)]
-pub struct SpecParserSubagent;
+#[derive(Debug, Default)]
+pub struct SpecParserSubagent {
+    sources: SpecSources,
+}
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SpecParserSeed {
-    pub markdown: String,
+    pub text: String,
+    /// Where the spec was read from; its extension helps detect the format.
+    pub path: Option<PathBuf>,
+    /// Skip detection and parse as this format.
+    pub format: Option<SpecFormat>,
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub struct SpecParserOutput {
+    pub format: SpecFormat,
     pub spec: RequirementsSpec,
 }
 
 impl SpecParserSeed {
-    pub fn new(markdown: impl Into<String>) -> Self {
+    pub fn new(text: impl Into<String>) -> Self {
         Self {
-            markdown: markdown.into(),
+            text: text.into(),
+            path: None,
+            format: None,
         }
     }
+
+    pub fn path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    pub fn format(mut self, format: SpecFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
 }
 
 impl SpecParserSubagent {
@@ -75,6 +84,13 @@ impl SpecParserSubagent {
             .expect("valid spec parser definition")
     }
 
+    /// Accept another spec format; custom sources are tried before the
+    /// built-in ones.
+    pub fn with_source(mut self, source: Box<dyn SpecSource>) -> Self {
+        self.sources.register(source);
+        self
+    }
+
     fn load_seed(ctx: &TaskContext) -> Result<SpecParserSeed, anyhow::Error> {
         ctx.get_typed::<SpecParserSeed>()?
             .ok_or_else(|| anyhow!("SpecParserSeed not present in task context"))
@@ -96,18 +112,27 @@ impl TypedSubagent for SpecParserSubagent {
     }
 
     fn execute(&self, ctx: &mut TaskContext, input: Self::Input) -> Result<Self::Output> {
-        let parsed = parse_spec_markdown(&input.markdown).map_err(|err| {
-            let _ = ctx.push_diagnostic(
-                DiagnosticLevel::Error,
-                format!("Spec parsing failed: {err}"),
-            );
-            err
-        })?;
+        let (format, parsed) = self
+            .sources
+            .parse(&input.text, input.path.as_deref(), input.format)
+            .map_err(|err| {
+                let _ = ctx.push_diagnostic(
+                    DiagnosticLevel::Error,
+                    format!("Spec parsing failed: {err}"),
+                );
+                err
+            })?;
         ctx.push_diagnostic(
             DiagnosticLevel::Info,
-            format!("Parsed {} requirements", parsed.requirements.len()),
+            format!(
+                "Parsed {} requirements from {format} spec",
+                parsed.requirements.len()
+            ),
         )?;
-        Ok(SpecParserOutput { spec: parsed })
+        Ok(SpecParserOutput {
+            format,
+            spec: parsed,
+        })
     }
 
     fn finalize(&self, ctx: &mut TaskContext, output: Self::Output) -> Result<()> {
@@ -120,170 +145,6 @@ impl TypedSubagent for SpecParserSubagent {
     }
 }
 
-fn parse_spec_markdown(markdown: &str) -> Result<RequirementsSpec> {
-    let mut title = String::new();
-    let mut overview_lines = Vec::new();
-    let mut requirements = Vec::new();
-    let mut in_requirements_section = false;
-    let mut current: Option<RequirementBuilder> = None;
-    let mut seen_ids = HashSet::new();
-
-    for raw_line in markdown.lines() {
-        let line = raw_line.trim_end();
-        if line.starts_with('#') {
-            if line.starts_with("##") {
-                let heading = line.trim_start_matches('#').trim();
-                if heading.eq_ignore_ascii_case("requirements") {
-                    if let Some(builder) = current.take() {
-                        requirements.push(builder.finish()?);
-                    }
-                    in_requirements_section = true;
-                    continue;
-                }
-            } else if title.is_empty() {
-                title = line.trim_start_matches('#').trim().to_string();
-                continue;
-            }
-        }
-
-        if !in_requirements_section {
-            if !line.trim().is_empty() {
-                overview_lines.push(line.trim().to_string());
-            }
-            continue;
-        }
-
-        let trimmed = line.trim();
-
-        if let Some(captures) = REQUIREMENT_LINE.captures(trimmed) {
-            if let Some(builder) = current.take() {
-                requirements.push(builder.finish()?);
-            }
-            let id = captures
-                .name("id")
-                .expect("id capture")
-                .as_str()
-                .to_string();
-            validate_requirement_id(&id)?;
-            if !seen_ids.insert(id.clone()) {
-                return Err(anyhow!("Duplicate requirement id {id}"));
-            }
-            let summary = captures
-                .name("summary")
-                .expect("summary capture")
-                .as_str()
-                .trim()
-                .to_string();
-            current = Some(RequirementBuilder::new(id, summary));
-            continue;
-        }
-
-        if let Some(builder) = current.as_mut() {
-            if !trimmed.starts_with('-') {
-                continue;
-            }
-            let inner = trimmed.trim_start_matches('-').trim();
-
-            if let Some(file_caps) = FILE_HINT_LINE.captures(inner) {
-                let files = file_caps.name("files").expect("files").as_str();
-                let hints = files
-                    .split(',')
-                    .map(|f| f.trim().to_string())
-                    .filter(|f| !f.is_empty());
-                for hint in hints {
-                    builder.file_hints.push(hint);
-                }
-                continue;
-            }
-
-            if let Some(ac_caps) = ACCEPTANCE_LINE.captures(inner) {
-                let id = ac_caps.name("id").map(|m| m.as_str().to_string());
-                let text = ac_caps
-                    .name("text")
-                    .map(|m| m.as_str().trim().to_string())
-                    .unwrap_or_default();
-                if text.is_empty() {
-                    return Err(anyhow!("Acceptance criterion missing text"));
-                }
-                builder
-                    .acceptance_criteria
-                    .push(AcceptanceCriterion::new(id, text));
-                continue;
-            }
-
-            if !inner.is_empty() {
-                builder
-                    .acceptance_criteria
-                    .push(AcceptanceCriterion::new(None, inner.to_string()));
-            }
-        }
-    }
-
-    if let Some(builder) = current.take() {
-        requirements.push(builder.finish()?);
-    }
-
-    if requirements.is_empty() {
-        return Err(anyhow!("No requirements found"));
-    }
-
-    let overview = if overview_lines.is_empty() {
-        "No overview provided".to_string()
-    } else {
-        overview_lines.join(" ")
-    };
-
-    Ok(RequirementsSpec::new(
-        if title.is_empty() { "Untitled" } else { &title },
-        overview,
-        requirements,
-    ))
-}
-
-fn validate_requirement_id(id: &str) -> Result<()> {
-    if !id.starts_with("REQ-") {
-        return Err(anyhow!("Requirement id {id} must start with REQ-"));
-    }
-    let suffix = &id[4..];
-    if suffix.is_empty() || suffix.chars().any(|c| !c.is_ascii_digit()) {
-        return Err(anyhow!("Requirement id {id} must end in digits"));
-    }
-    Ok(())
-}
-
-struct RequirementBuilder {
-    id: String,
-    summary: String,
-    acceptance_criteria: Vec<AcceptanceCriterion>,
-    file_hints: Vec<String>,
-}
-
-impl RequirementBuilder {
-    fn new(id: String, summary: String) -> Self {
-        Self {
-            id,
-            summary,
-            acceptance_criteria: Vec::new(),
-            file_hints: Vec::new(),
-        }
-    }
-
-    fn finish(self) -> Result<Requirement> {
-        if self.acceptance_criteria.is_empty() {
-            return Err(anyhow!(
-                "Requirement {} missing acceptance criteria",
-                self.id
-            ));
-        }
-        Ok(Requirement::new(
-            self.id,
-            self.summary,
-            self.acceptance_criteria,
-            self.file_hints,
-        ))
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -309,7 +170,7 @@ The product team needs an authenticated export feature.
         let mut ctx = TaskContext::new();
         ctx.insert_typed(SpecParserSeed::new(SAMPLE.to_string()))
             .unwrap();
-        let agent = SpecParserSubagent;
+        let agent = SpecParserSubagent::default();
         let input = agent.prepare(&ctx).unwrap();
         let output = agent.execute(&mut ctx, input).unwrap();
         agent.finalize(&mut ctx, output.clone()).unwrap();
@@ -319,9 +180,17 @@ The product team needs an authenticated export feature.
     }
 
     #[test]
-    fn rejects_missing_acceptance_criteria() {
-        let markdown = "## Requirements\n- [REQ-123] Missing criteria";
-        let err = parse_spec_markdown(markdown).unwrap_err();
-        assert!(err.to_string().contains("missing acceptance"));
+    fn parses_gherkin_seed_detected_from_path() {
+        let mut ctx = TaskContext::new();
+        ctx.insert_typed(
+            SpecParserSeed::new("Feature: Export\n  Scenario: CSV\n    Then a file is written\n")
+                .path("specs/export.feature"),
+        )
+        .unwrap();
+        let agent = SpecParserSubagent::default();
+        let input = agent.prepare(&ctx).unwrap();
+        let output = agent.execute(&mut ctx, input).unwrap();
+        assert_eq!(output.format, SpecFormat::Gherkin);
+        assert_eq!(output.spec.requirements.len(), 1);
     }
 }
@@ -0,0 +1,296 @@
+use super::RequirementIds;
+use super::SpecFormat;
+use super::SpecSource;
+use super::has_extension;
+use crate::pipeline::AcceptanceCriterion;
+use crate::pipeline::Requirement;
+use crate::pipeline::RequirementsSpec;
+use anyhow::Result;
+use anyhow::anyhow;
+use std::path::Path;
+
+const SCENARIO_KEYWORDS: &[&str] = &[
+    "Scenario Outline:",
+    "Scenario Template:",
+    "Scenario:",
+    "Example:",
+];
+const STEP_KEYWORDS: &[&str] = &["Given ", "When ", "Then ", "And ", "But ", "* "];
+
+/// Gherkin `.feature` files. The feature (or each `Rule:`) becomes a
+/// requirement and every scenario becomes one of its acceptance criteria.
+/// Tags carry explicit IDs and file hints: `@REQ-012`, `@AC-003`,
+/// `@file:src/export.rs`.
+pub struct GherkinSpecSource;
+
+impl SpecSource for GherkinSpecSource {
+    fn format(&self) -> SpecFormat {
+        SpecFormat::Gherkin
+    }
+
+    fn detect(&self, text: &str, path: Option<&Path>) -> bool {
+        if has_extension(path, &["feature"]) {
+            return true;
+        }
+        text.lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with('@'))
+            .is_some_and(|line| line.starts_with("Feature:"))
+    }
+
+    fn parse(&self, text: &str) -> Result<RequirementsSpec> {
+        parse_gherkin(text)
+    }
+}
+
+#[derive(Default)]
+struct Tags {
+    id: Option<String>,
+    acceptance_id: Option<String>,
+    files: Vec<String>,
+}
+
+impl Tags {
+    fn parse(line: &str, into: &mut Tags) {
+        for tag in line
+            .split_whitespace()
+            .filter_map(|tag| tag.strip_prefix('@'))
+        {
+            if tag.starts_with("REQ-") {
+                into.id = Some(tag.to_string());
+            } else if tag.starts_with("AC-") {
+                into.acceptance_id = Some(tag.to_string());
+            } else if let Some(file) = tag.strip_prefix("file:") {
+                into.files.push(file.to_string());
+            }
+        }
+    }
+}
+
+struct PendingRequirement {
+    summary: String,
+    tags: Tags,
+    criteria: Vec<AcceptanceCriterion>,
+}
+
+struct PendingScenario {
+    name: String,
+    acceptance_id: Option<String>,
+    steps: Vec<String>,
+}
+
+impl PendingScenario {
+    fn finish(self) -> AcceptanceCriterion {
+        let text = if self.steps.is_empty() {
+            self.name
+        } else {
+            format!("{}: {}", self.name, self.steps.join("; "))
+        };
+        AcceptanceCriterion::new(self.acceptance_id, text)
+    }
+}
+
+fn parse_gherkin(text: &str) -> Result<RequirementsSpec> {
+    let mut title = String::new();
+    let mut overview_lines: Vec<String> = Vec::new();
+    let mut pending_tags = Tags::default();
+    let mut feature: Option<PendingRequirement> = None;
+    let mut rules: Vec<PendingRequirement> = Vec::new();
+    let mut scenario: Option<PendingScenario> = None;
+    let mut in_description = false;
+    let mut in_background = false;
+    let mut in_doc_string = false;
+
+    for raw_line in text.lines() {
+        let line = raw_line.trim();
+        if line.starts_with("\"\"\"") || line.starts_with("```") {
+            in_doc_string = !in_doc_string;
+            continue;
+        }
+        if in_doc_string || line.is_empty() || line.starts_with('#') || line.starts_with('|') {
+            continue;
+        }
+        if line.starts_with('@') {
+            Tags::parse(line, &mut pending_tags);
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix("Feature:") {
+            title = name.trim().to_string();
+            feature = Some(PendingRequirement {
+                summary: title.clone(),
+                tags: std::mem::take(&mut pending_tags),
+                criteria: Vec::new(),
+            });
+            in_description = true;
+            continue;
+        }
+        if let Some(name) = line.strip_prefix("Rule:") {
+            finish_scenario(&mut scenario, feature.as_mut(), &mut rules);
+            rules.push(PendingRequirement {
+                summary: name.trim().to_string(),
+                tags: std::mem::take(&mut pending_tags),
+                criteria: Vec::new(),
+            });
+            in_description = false;
+            in_background = false;
+            continue;
+        }
+        if line.starts_with("Background:") {
+            finish_scenario(&mut scenario, feature.as_mut(), &mut rules);
+            in_description = false;
+            in_background = true;
+            continue;
+        }
+        if let Some(name) = SCENARIO_KEYWORDS
+            .iter()
+            .find_map(|keyword| line.strip_prefix(keyword))
+        {
+            finish_scenario(&mut scenario, feature.as_mut(), &mut rules);
+            let tags = std::mem::take(&mut pending_tags);
+            scenario = Some(PendingScenario {
+                name: name.trim().to_string(),
+                acceptance_id: tags.acceptance_id,
+                steps: Vec::new(),
+            });
+            in_description = false;
+            in_background = false;
+            continue;
+        }
+        if line.starts_with("Examples:") || line.starts_with("Scenarios:") || in_background {
+            continue;
+        }
+        if STEP_KEYWORDS
+            .iter()
+            .any(|keyword| line.starts_with(keyword))
+        {
+            if let Some(scenario) = scenario.as_mut() {
+                scenario.steps.push(line.to_string());
+            }
+            continue;
+        }
+        if in_description {
+            overview_lines.push(line.to_string());
+        }
+    }
+    finish_scenario(&mut scenario, feature.as_mut(), &mut rules);
+
+    let feature = feature.ok_or_else(|| anyhow!("Gherkin spec has no Feature"))?;
+    let mut pending = Vec::new();
+    if rules.is_empty() || !feature.criteria.is_empty() {
+        pending.push(feature);
+    }
+    pending.extend(rules);
+
+    let mut ids = RequirementIds::reserving(
+        pending
+            .iter()
+            .filter_map(|requirement| requirement.tags.id.as_deref()),
+    );
+    let mut requirements = Vec::new();
+    for requirement in pending {
+        let id = ids.assign(requirement.tags.id.as_deref(), &requirement.summary)?;
+        if requirement.criteria.is_empty() {
+            return Err(anyhow!("Requirement {id} missing acceptance criteria"));
+        }
+        requirements.push(Requirement::new(
+            id,
+            requirement.summary,
+            requirement.criteria,
+            requirement.tags.files,
+        ));
+    }
+
+    let overview = if overview_lines.is_empty() {
+        "No overview provided".to_string()
+    } else {
+        overview_lines.join(" ")
+    };
+    Ok(RequirementsSpec::new(
+        if title.is_empty() { "Untitled" } else { &title },
+        overview,
+        requirements,
+    ))
+}
+
+/// Attach the open scenario to the innermost rule, or to the feature.
+fn finish_scenario(
+    scenario: &mut Option<PendingScenario>,
+    feature: Option<&mut PendingRequirement>,
+    rules: &mut [PendingRequirement],
+) {
+    let Some(scenario) = scenario.take() else {
+        return;
+    };
+    if let Some(owner) = rules.last_mut().or(feature) {
+        owner.criteria.push(scenario.finish());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const FEATURE: &str = r#"
+# language: en
+@REQ-010 @file:cli/src/export.rs
+Feature: Export reports
+  Users download their reports for offline use.
+
+  Background:
+    Given a signed-in user
+
+  Scenario: Export as CSV
+    When they run "codex export --csv"
+    Then a CSV file is written
+
+  @AC-007
+  Scenario Outline: Reject unknown formats
+    When they run "codex export --<format>"
+    Then the command fails
+    Examples:
+      | format |
+      | pdf    |
+
+  Rule: Exports are audited
+    Scenario: Audit entry
+      Then an audit event is recorded
+"#;
+
+    #[test]
+    fn maps_feature_rules_and_scenarios() {
+        let spec = parse_gherkin(FEATURE).expect("valid feature");
+        assert_eq!(spec.title, "Export reports");
+        assert_eq!(
+            spec.overview,
+            "Users download their reports for offline use."
+        );
+        assert_eq!(spec.requirements.len(), 2);
+
+        let export = &spec.requirements[0];
+        assert_eq!(export.id, "REQ-010");
+        assert_eq!(export.file_hints, vec!["cli/src/export.rs".to_string()]);
+        assert_eq!(
+            export.acceptance_criteria,
+            vec![
+                AcceptanceCriterion::new(
+                    None,
+                    "Export as CSV: When they run \"codex export --csv\"; Then a CSV file is written",
+                ),
+                AcceptanceCriterion::new(
+                    Some("AC-007".to_string()),
+                    "Reject unknown formats: When they run \"codex export --<format>\"; Then the command fails",
+                ),
+            ]
+        );
+
+        let audit = &spec.requirements[1];
+        assert!(audit.id.starts_with("REQ-"));
+        assert_eq!(audit.summary, "Exports are audited");
+        assert_eq!(
+            parse_gherkin(FEATURE).expect("valid feature").requirements[1].id,
+            audit.id
+        );
+    }
+}
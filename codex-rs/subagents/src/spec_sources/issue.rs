@@ -0,0 +1,242 @@
+use super::RequirementIds;
+use super::SpecFormat;
+use super::SpecSource;
+use super::markdown::parse_file_hints;
+use crate::pipeline::AcceptanceCriterion;
+use crate::pipeline::Requirement;
+use crate::pipeline::RequirementsSpec;
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex_lite::Regex;
+use std::path::Path;
+
+static CHECKLIST_ITEM: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^[-*+]\s+\[[ xX]\]\s+(?:(?P<id>AC-[0-9]{3,})\s*:?\s*)?(?P<text>.+)$")
+        .expect("valid regex")
+});
+
+/// Optional `REQ-###:` prefix on a section heading.
+static HEADING_ID: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\[?(?P<id>REQ-[0-9]+)\]?\s*:?\s*(?P<summary>.*)$").expect("valid regex")
+});
+
+/// GitHub-issue-style Markdown. Each heading that owns `- [ ]` checklist items
+/// becomes a requirement whose checklist items are its acceptance criteria;
+/// items before any heading belong to the issue title.
+pub struct IssueSpecSource;
+
+impl SpecSource for IssueSpecSource {
+    fn format(&self) -> SpecFormat {
+        SpecFormat::Issue
+    }
+
+    fn detect(&self, text: &str, _path: Option<&Path>) -> bool {
+        text.lines()
+            .any(|line| CHECKLIST_ITEM.is_match(line.trim()))
+    }
+
+    fn parse(&self, text: &str) -> Result<RequirementsSpec> {
+        parse_issue(text)
+    }
+}
+
+struct Section {
+    heading: Option<String>,
+    criteria: Vec<AcceptanceCriterion>,
+    file_hints: Vec<String>,
+}
+
+fn parse_issue(text: &str) -> Result<RequirementsSpec> {
+    let mut title = String::new();
+    let mut overview_lines: Vec<String> = Vec::new();
+    let mut sections = vec![Section {
+        heading: None,
+        criteria: Vec::new(),
+        file_hints: Vec::new(),
+    }];
+
+    for raw_line in text.lines() {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with("<!--") {
+            continue;
+        }
+        if line.starts_with('#') {
+            let heading = line.trim_start_matches('#').trim().to_string();
+            if title.is_empty() && line.starts_with("# ") {
+                title = heading;
+            } else {
+                sections.push(Section {
+                    heading: Some(heading),
+                    criteria: Vec::new(),
+                    file_hints: Vec::new(),
+                });
+            }
+            continue;
+        }
+
+        let Some(section) = sections.last_mut() else {
+            continue;
+        };
+        if let Some(captures) = CHECKLIST_ITEM.captures(line) {
+            let id = captures.name("id").map(|id| id.as_str().to_string());
+            let text = captures
+                .name("text")
+                .map(|text| text.as_str().trim())
+                .unwrap_or_default();
+            match parse_file_hints(text) {
+                Some(hints) => section.file_hints.extend(hints),
+                None => section.criteria.push(AcceptanceCriterion::new(id, text)),
+            }
+            continue;
+        }
+        let bullet = line.trim_start_matches(['-', '*', '+']).trim();
+        if let Some(hints) = parse_file_hints(bullet) {
+            section.file_hints.extend(hints);
+            continue;
+        }
+        overview_lines.push(line.to_string());
+    }
+
+    if title.is_empty() {
+        title = "Untitled".to_string();
+    }
+
+    // Sections that only group checklists under a generic heading (for example
+    // "Acceptance criteria") are named after the issue itself.
+    let mut pending = Vec::new();
+    for mut section in sections {
+        if section.criteria.is_empty() {
+            continue;
+        }
+        let heading = section
+            .heading
+            .take()
+            .filter(|heading| !is_generic_heading(heading))
+            .unwrap_or_else(|| title.clone());
+        let (explicit, summary) = match HEADING_ID.captures(&heading) {
+            Some(captures) => (
+                captures.name("id").map(|id| id.as_str().to_string()),
+                captures
+                    .name("summary")
+                    .map(|summary| summary.as_str().trim().to_string())
+                    .filter(|summary| !summary.is_empty())
+                    .unwrap_or_else(|| title.clone()),
+            ),
+            None => (None, heading),
+        };
+        pending.push((explicit, summary, section));
+    }
+
+    let mut ids = RequirementIds::reserving(
+        pending
+            .iter()
+            .filter_map(|(explicit, _, _)| explicit.as_deref()),
+    );
+    let mut requirements = Vec::new();
+    for (explicit, summary, section) in pending {
+        let id = ids.assign(explicit.as_deref(), &summary)?;
+        requirements.push(Requirement::new(
+            id,
+            summary,
+            section.criteria,
+            section.file_hints,
+        ));
+    }
+
+    let overview = if overview_lines.is_empty() {
+        "No overview provided".to_string()
+    } else {
+        overview_lines.join(" ")
+    };
+    Ok(RequirementsSpec::new(title, overview, requirements))
+}
+
+fn is_generic_heading(heading: &str) -> bool {
+    let heading = heading.to_lowercase();
+    [
+        "acceptance criteria",
+        "tasks",
+        "checklist",
+        "todo",
+        "definition of done",
+    ]
+    .iter()
+    .any(|generic| heading == *generic)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const ISSUE: &str = r#"
+# Export reports as CSV
+
+Users want to download their reports.
+
+<!-- Describe the acceptance criteria below -->
+### Acceptance criteria
+- [ ] `codex export --csv` writes a CSV file
+- [x] AC-004: Empty reports produce a header row
+
+### REQ-020: Audit exports
+- [ ] An audit event is recorded
+- files: core/src/audit.rs
+
+### Notes
+Nothing else.
+"#;
+
+    #[test]
+    fn maps_checklists_to_acceptance_criteria() {
+        let spec = parse_issue(ISSUE).expect("valid issue");
+        assert_eq!(spec.title, "Export reports as CSV");
+        assert_eq!(
+            spec.overview,
+            "Users want to download their reports. Nothing else."
+        );
+
+        let summaries: Vec<(&str, Vec<&str>, Vec<&str>)> = spec
+            .requirements
+            .iter()
+            .map(|requirement| {
+                (
+                    requirement.summary.as_str(),
+                    requirement
+                        .acceptance_criteria
+                        .iter()
+                        .map(|criterion| criterion.text.as_str())
+                        .collect(),
+                    requirement.file_hints.iter().map(String::as_str).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summaries,
+            vec![
+                (
+                    "Export reports as CSV",
+                    vec![
+                        "`codex export --csv` writes a CSV file",
+                        "Empty reports produce a header row",
+                    ],
+                    vec![],
+                ),
+                (
+                    "Audit exports",
+                    vec!["An audit event is recorded"],
+                    vec!["core/src/audit.rs"],
+                ),
+            ]
+        );
+        assert_eq!(
+            spec.requirements[0].acceptance_criteria[1].id.as_deref(),
+            Some("AC-004")
+        );
+        assert_eq!(spec.requirements[1].id, "REQ-020");
+        assert_eq!(
+            parse_issue(ISSUE).expect("valid issue").requirements[0].id,
+            spec.requirements[0].id
+        );
+    }
+}
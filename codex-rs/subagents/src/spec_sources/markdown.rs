@@ -0,0 +1,214 @@
+use super::RequirementIds;
+use super::SpecFormat;
+use super::SpecSource;
+use crate::pipeline::AcceptanceCriterion;
+use crate::pipeline::Requirement;
+use crate::pipeline::RequirementsSpec;
+use anyhow::Result;
+use anyhow::anyhow;
+use once_cell::sync::Lazy;
+use regex_lite::Regex;
+use std::path::Path;
+
+static REQUIREMENT_LINE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^-\s*\[(?P<id>[A-Z0-9_-]+)\]\s*(?P<summary>.+)$").expect("valid regex")
+});
+
+static ACCEPTANCE_LINE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(?P<id>AC-[0-9]{3,})?\s*:?\s*(?P<text>.+)$").expect("valid regex"));
+
+static FILE_HINT_LINE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)^file[s]?\s*:\s*(?P<files>.+)$").expect("valid regex"));
+
+/// The original spec layout: an H1 title, a `## Requirements` section, and
+/// `- [REQ-###] summary` bullets with nested acceptance criteria and `files:`
+/// hints.
+pub struct MarkdownSpecSource;
+
+impl SpecSource for MarkdownSpecSource {
+    fn format(&self) -> SpecFormat {
+        SpecFormat::Markdown
+    }
+
+    fn detect(&self, text: &str, _path: Option<&Path>) -> bool {
+        text.lines().map(str::trim).any(|line| {
+            (line.starts_with("##")
+                && line
+                    .trim_start_matches('#')
+                    .trim()
+                    .eq_ignore_ascii_case("requirements"))
+                || REQUIREMENT_LINE
+                    .captures(line)
+                    .and_then(|captures| captures.name("id"))
+                    .is_some_and(|id| id.as_str().starts_with("REQ-"))
+        })
+    }
+
+    fn parse(&self, text: &str) -> Result<RequirementsSpec> {
+        parse_spec_markdown(text)
+    }
+}
+
+/// Paths listed on a `files: a.rs, b.rs` line, shared by the Markdown-based
+/// sources.
+pub(crate) fn parse_file_hints(line: &str) -> Option<Vec<String>> {
+    let captures = FILE_HINT_LINE.captures(line)?;
+    let files = captures.name("files")?.as_str();
+    Some(
+        files
+            .split(',')
+            .map(|file| file.trim().trim_matches('`').to_string())
+            .filter(|file| !file.is_empty())
+            .collect(),
+    )
+}
+
+fn parse_spec_markdown(markdown: &str) -> Result<RequirementsSpec> {
+    let mut title = String::new();
+    let mut overview_lines = Vec::new();
+    let mut requirements = Vec::new();
+    let mut in_requirements_section = false;
+    let mut current: Option<RequirementBuilder> = None;
+    let mut ids = RequirementIds::default();
+
+    for raw_line in markdown.lines() {
+        let line = raw_line.trim_end();
+        if line.starts_with('#') {
+            if line.starts_with("##") {
+                let heading = line.trim_start_matches('#').trim();
+                if heading.eq_ignore_ascii_case("requirements") {
+                    if let Some(builder) = current.take() {
+                        requirements.push(builder.finish()?);
+                    }
+                    in_requirements_section = true;
+                    continue;
+                }
+            } else if title.is_empty() {
+                title = line.trim_start_matches('#').trim().to_string();
+                continue;
+            }
+        }
+
+        if !in_requirements_section {
+            if !line.trim().is_empty() {
+                overview_lines.push(line.trim().to_string());
+            }
+            continue;
+        }
+
+        let trimmed = line.trim();
+
+        if let Some(captures) = REQUIREMENT_LINE.captures(trimmed) {
+            if let Some(builder) = current.take() {
+                requirements.push(builder.finish()?);
+            }
+            let id = ids.explicit(captures.name("id").expect("id capture").as_str())?;
+            let summary = captures
+                .name("summary")
+                .expect("summary capture")
+                .as_str()
+                .trim()
+                .to_string();
+            current = Some(RequirementBuilder::new(id, summary));
+            continue;
+        }
+
+        if let Some(builder) = current.as_mut() {
+            if !trimmed.starts_with('-') {
+                continue;
+            }
+            let inner = trimmed.trim_start_matches('-').trim();
+
+            if let Some(hints) = parse_file_hints(inner) {
+                builder.file_hints.extend(hints);
+                continue;
+            }
+
+            if let Some(ac_caps) = ACCEPTANCE_LINE.captures(inner) {
+                let id = ac_caps.name("id").map(|m| m.as_str().to_string());
+                let text = ac_caps
+                    .name("text")
+                    .map(|m| m.as_str().trim().to_string())
+                    .unwrap_or_default();
+                if text.is_empty() {
+                    return Err(anyhow!("Acceptance criterion missing text"));
+                }
+                builder
+                    .acceptance_criteria
+                    .push(AcceptanceCriterion::new(id, text));
+                continue;
+            }
+
+            if !inner.is_empty() {
+                builder
+                    .acceptance_criteria
+                    .push(AcceptanceCriterion::new(None, inner.to_string()));
+            }
+        }
+    }
+
+    if let Some(builder) = current.take() {
+        requirements.push(builder.finish()?);
+    }
+
+    if requirements.is_empty() {
+        return Err(anyhow!("No requirements found"));
+    }
+
+    let overview = if overview_lines.is_empty() {
+        "No overview provided".to_string()
+    } else {
+        overview_lines.join(" ")
+    };
+
+    Ok(RequirementsSpec::new(
+        if title.is_empty() { "Untitled" } else { &title },
+        overview,
+        requirements,
+    ))
+}
+
+struct RequirementBuilder {
+    id: String,
+    summary: String,
+    acceptance_criteria: Vec<AcceptanceCriterion>,
+    file_hints: Vec<String>,
+}
+
+impl RequirementBuilder {
+    fn new(id: String, summary: String) -> Self {
+        Self {
+            id,
+            summary,
+            acceptance_criteria: Vec::new(),
+            file_hints: Vec::new(),
+        }
+    }
+
+    fn finish(self) -> Result<Requirement> {
+        if self.acceptance_criteria.is_empty() {
+            return Err(anyhow!(
+                "Requirement {} missing acceptance criteria",
+                self.id
+            ));
+        }
+        Ok(Requirement::new(
+            self.id,
+            self.summary,
+            self.acceptance_criteria,
+            self.file_hints,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_missing_acceptance_criteria() {
+        let markdown = "## Requirements\n- [REQ-123] Missing criteria";
+        let err = parse_spec_markdown(markdown).unwrap_err();
+        assert!(err.to_string().contains("missing acceptance"));
+    }
+}
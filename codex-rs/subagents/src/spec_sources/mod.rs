@@ -0,0 +1,280 @@
+//! Spec sources normalize product specs written in different formats into a
+//! [`RequirementsSpec`] for the spec-parser subagent.
+
+mod gherkin;
+mod issue;
+mod markdown;
+mod yaml;
+
+pub use gherkin::GherkinSpecSource;
+pub use issue::IssueSpecSource;
+pub use markdown::MarkdownSpecSource;
+pub use yaml::YamlSpecSource;
+
+use crate::pipeline::RequirementsSpec;
+use anyhow::Result;
+use anyhow::anyhow;
+use serde::Deserialize;
+use serde::Serialize;
+use sha1::Digest;
+use sha1::Sha1;
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum SpecFormat {
+    /// `## Requirements` section with `[REQ-###]` bullets.
+    Markdown,
+    /// Gherkin `.feature` files.
+    Gherkin,
+    /// GitHub-issue-style Markdown with `- [ ]` checklists.
+    Issue,
+    Yaml,
+}
+
+impl fmt::Display for SpecFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SpecFormat::Markdown => "markdown",
+            SpecFormat::Gherkin => "gherkin",
+            SpecFormat::Issue => "issue",
+            SpecFormat::Yaml => "yaml",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A spec format the spec-parser subagent understands.
+pub trait SpecSource: Send + Sync {
+    fn format(&self) -> SpecFormat;
+
+    /// Whether `text` (optionally read from `path`) looks like this format.
+    fn detect(&self, text: &str, path: Option<&Path>) -> bool;
+
+    fn parse(&self, text: &str) -> Result<RequirementsSpec>;
+}
+
+/// Ordered set of [`SpecSource`]s; the first source whose `detect` matches
+/// parses the input.
+pub struct SpecSources {
+    sources: Vec<Box<dyn SpecSource>>,
+}
+
+impl Default for SpecSources {
+    fn default() -> Self {
+        Self {
+            sources: vec![
+                Box::new(YamlSpecSource),
+                Box::new(GherkinSpecSource),
+                Box::new(MarkdownSpecSource),
+                Box::new(IssueSpecSource),
+            ],
+        }
+    }
+}
+
+impl fmt::Debug for SpecSources {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.sources.iter().map(|source| source.format()))
+            .finish()
+    }
+}
+
+impl SpecSources {
+    /// Register `source` ahead of the built-in formats.
+    pub fn register(&mut self, source: Box<dyn SpecSource>) {
+        self.sources.insert(0, source);
+    }
+
+    /// Pick the source for `text`, falling back to plain Markdown.
+    pub fn detect(&self, text: &str, path: Option<&Path>) -> SpecFormat {
+        self.sources
+            .iter()
+            .find(|source| source.detect(text, path))
+            .map_or(SpecFormat::Markdown, |source| source.format())
+    }
+
+    pub fn parse(
+        &self,
+        text: &str,
+        path: Option<&Path>,
+        format: Option<SpecFormat>,
+    ) -> Result<(SpecFormat, RequirementsSpec)> {
+        let format = format.unwrap_or_else(|| self.detect(text, path));
+        let source = self
+            .sources
+            .iter()
+            .find(|source| source.format() == format)
+            .ok_or_else(|| anyhow!("No spec source registered for {format} specs"))?;
+        let spec = source.parse(text)?;
+        if spec.requirements.is_empty() {
+            return Err(anyhow!("No requirements found"));
+        }
+        Ok((format, spec))
+    }
+}
+
+pub(crate) fn has_extension(path: Option<&Path>, extensions: &[&str]) -> bool {
+    path.and_then(Path::extension)
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            extensions
+                .iter()
+                .any(|candidate| ext.eq_ignore_ascii_case(candidate))
+        })
+}
+
+pub(crate) fn validate_requirement_id(id: &str) -> Result<()> {
+    let Some(suffix) = id.strip_prefix("REQ-") else {
+        return Err(anyhow!("Requirement id {id} must start with REQ-"));
+    };
+    if suffix.is_empty() || suffix.chars().any(|c| !c.is_ascii_digit()) {
+        return Err(anyhow!("Requirement id {id} must end in digits"));
+    }
+    Ok(())
+}
+
+/// Hands out requirement IDs for one spec. Generated IDs are derived from the
+/// requirement summary so re-parsing the same spec yields the same IDs, even
+/// when requirements are reordered.
+#[derive(Debug, Default)]
+pub(crate) struct RequirementIds {
+    used: HashSet<String>,
+    /// IDs the spec declares, kept free for `explicit` whatever the order.
+    reserved: HashSet<String>,
+}
+
+impl RequirementIds {
+    /// Start from every ID the spec declares, so a generated ID never takes
+    /// one that a later requirement names explicitly.
+    pub(crate) fn reserving<'a>(explicit: impl IntoIterator<Item = &'a str>) -> Self {
+        Self {
+            used: HashSet::new(),
+            reserved: explicit.into_iter().map(str::to_string).collect(),
+        }
+    }
+
+    /// Record an ID written in the spec itself.
+    pub(crate) fn explicit(&mut self, id: &str) -> Result<String> {
+        validate_requirement_id(id)?;
+        if !self.used.insert(id.to_string()) {
+            return Err(anyhow!("Duplicate requirement id {id}"));
+        }
+        Ok(id.to_string())
+    }
+
+    pub(crate) fn generate(&mut self, summary: &str) -> String {
+        let normalized = summary
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase();
+        let digest = Sha1::digest(normalized.as_bytes());
+        let mut number = u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]) % 10_000;
+        loop {
+            let id = format!("REQ-{number:04}");
+            if !self.reserved.contains(&id) && self.used.insert(id.clone()) {
+                return id;
+            }
+            number = (number + 1) % 10_000;
+        }
+    }
+
+    /// Use `explicit` when the spec provides an ID, otherwise generate one.
+    pub(crate) fn assign(&mut self, explicit: Option<&str>, summary: &str) -> Result<String> {
+        match explicit {
+            Some(id) => self.explicit(id),
+            None => Ok(self.generate(summary)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn detects_formats_from_path_and_content() {
+        let sources = SpecSources::default();
+        let cases = [
+            (
+                "Feature: Export\n  Scenario: works\n",
+                None,
+                SpecFormat::Gherkin,
+            ),
+            (
+                "# tagged\n@export\nFeature: Export\n",
+                None,
+                SpecFormat::Gherkin,
+            ),
+            ("title: Export\nrequirements: []\n", None, SpecFormat::Yaml),
+            (
+                "---\ntitle: Export\nrequirements: []\n",
+                None,
+                SpecFormat::Yaml,
+            ),
+            (
+                "---\ntitle: Export\n---\n## Requirements\n- [REQ-001] Export\n  - works\n",
+                None,
+                SpecFormat::Markdown,
+            ),
+            (
+                "---\ntitle: Export\n---\n### Acceptance criteria\n- [ ] works\n",
+                None,
+                SpecFormat::Issue,
+            ),
+            ("anything", Some("spec.yml"), SpecFormat::Yaml),
+            ("anything", Some("export.feature"), SpecFormat::Gherkin),
+            (
+                "## Requirements\n- [REQ-001] Export\n  - works\n",
+                None,
+                SpecFormat::Markdown,
+            ),
+            (
+                "### Acceptance criteria\n- [ ] works\n- [x] tested\n",
+                None,
+                SpecFormat::Issue,
+            ),
+            ("just some prose", None, SpecFormat::Markdown),
+        ];
+        for (text, path, expected) in cases {
+            assert_eq!(
+                sources.detect(text, path.map(Path::new)),
+                expected,
+                "input: {text:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn generated_ids_are_stable_and_unique() {
+        let mut first = RequirementIds::default();
+        let a = first.generate("Export  reports as CSV");
+        let b = first.generate("Retry failed uploads");
+
+        let mut second = RequirementIds::default();
+        assert_eq!(second.generate("Retry failed uploads"), b);
+        assert_eq!(second.generate("export reports as csv"), a);
+        assert_ne!(a, b);
+        assert!(validate_requirement_id(&a).is_ok());
+
+        let mut ids = RequirementIds::default();
+        let original = ids.generate("Same summary");
+        let duplicate = ids.generate("Same summary");
+        assert_ne!(original, duplicate);
+        assert!(ids.explicit(&original).is_err());
+    }
+
+    #[test]
+    fn generated_ids_skip_ids_declared_later() {
+        let taken = RequirementIds::default().generate("Export reports");
+        let mut ids = RequirementIds::reserving([taken.as_str()]);
+        let generated = ids.assign(None, "Export reports").unwrap();
+        assert_ne!(generated, taken);
+        assert_eq!(ids.assign(Some(&taken), "Import reports").unwrap(), taken);
+    }
+}
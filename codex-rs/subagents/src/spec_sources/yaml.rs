@@ -0,0 +1,169 @@
+use super::RequirementIds;
+use super::SpecFormat;
+use super::SpecSource;
+use super::has_extension;
+use crate::pipeline::AcceptanceCriterion;
+use crate::pipeline::Requirement;
+use crate::pipeline::RequirementsSpec;
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::anyhow;
+use serde::Deserialize;
+use std::path::Path;
+
+/// YAML specs:
+///
+/// ```yaml
+/// title: Export reports
+/// overview: Users download their reports.
+/// requirements:
+///   - summary: Export as CSV          # `id: REQ-###` is optional
+///     acceptance_criteria:
+///       - CSV file is written
+///       - { id: AC-002, text: Empty reports have a header row }
+///     files: [cli/src/export.rs]
+/// ```
+pub struct YamlSpecSource;
+
+impl SpecSource for YamlSpecSource {
+    fn format(&self) -> SpecFormat {
+        SpecFormat::Yaml
+    }
+
+    fn detect(&self, text: &str, path: Option<&Path>) -> bool {
+        if has_extension(path, &["yaml", "yml"]) {
+            return true;
+        }
+        if has_markdown_body(text) {
+            return false;
+        }
+        text.lines()
+            .map(str::trim_end)
+            .find(|line| !line.trim().is_empty() && !line.starts_with('#') && *line != "---")
+            .is_some_and(|line| {
+                ["title:", "overview:", "requirements:"]
+                    .iter()
+                    .any(|key| line.starts_with(key))
+            })
+    }
+
+    fn parse(&self, text: &str) -> Result<RequirementsSpec> {
+        let document: YamlSpec = serde_yaml::from_str(text).context("Invalid YAML spec")?;
+        let mut ids = RequirementIds::reserving(
+            document
+                .requirements
+                .iter()
+                .filter_map(|requirement| requirement.id.as_deref()),
+        );
+        let mut requirements = Vec::new();
+        for requirement in document.requirements {
+            let summary = requirement.summary.trim().to_string();
+            let id = ids.assign(requirement.id.as_deref(), &summary)?;
+            if requirement.acceptance_criteria.is_empty() {
+                return Err(anyhow!("Requirement {id} missing acceptance criteria"));
+            }
+            let criteria = requirement
+                .acceptance_criteria
+                .into_iter()
+                .map(|criterion| match criterion {
+                    YamlCriterion::Text(text) => AcceptanceCriterion::new(None, text),
+                    YamlCriterion::Detailed { id, text } => AcceptanceCriterion::new(id, text),
+                })
+                .collect();
+            requirements.push(Requirement::new(id, summary, criteria, requirement.files));
+        }
+        Ok(RequirementsSpec::new(
+            document.title.unwrap_or_else(|| "Untitled".to_string()),
+            document
+                .overview
+                .unwrap_or_else(|| "No overview provided".to_string()),
+            requirements,
+        ))
+    }
+}
+
+/// Whether `text` opens with a `---` block that is closed by another `---`
+/// and followed by more text, i.e. Markdown with YAML frontmatter.
+fn has_markdown_body(text: &str) -> bool {
+    let mut lines = text
+        .lines()
+        .map(str::trim_end)
+        .skip_while(|line| line.trim().is_empty());
+    lines.next() == Some("---")
+        && lines.by_ref().any(|line| line == "---")
+        && lines.any(|line| !line.trim().is_empty())
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct YamlSpec {
+    title: Option<String>,
+    overview: Option<String>,
+    #[serde(default)]
+    requirements: Vec<YamlRequirement>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct YamlRequirement {
+    id: Option<String>,
+    summary: String,
+    #[serde(default, alias = "acceptance")]
+    acceptance_criteria: Vec<YamlCriterion>,
+    #[serde(default, alias = "file_hints")]
+    files: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum YamlCriterion {
+    Text(String),
+    Detailed { id: Option<String>, text: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_yaml_specs() {
+        let yaml = r#"
+title: Export reports
+overview: Users download their reports.
+requirements:
+  - id: REQ-001
+    summary: Export as CSV
+    acceptance_criteria:
+      - CSV file is written
+      - { id: AC-002, text: Empty reports have a header row }
+    files: [cli/src/export.rs]
+  - summary: Audit exports
+    acceptance: [An audit event is recorded]
+"#;
+        let spec = YamlSpecSource.parse(yaml).expect("valid yaml");
+        assert_eq!(spec.title, "Export reports");
+        assert_eq!(
+            spec.requirements[0],
+            Requirement::new(
+                "REQ-001",
+                "Export as CSV",
+                vec![
+                    AcceptanceCriterion::new(None, "CSV file is written"),
+                    AcceptanceCriterion::new(
+                        Some("AC-002".to_string()),
+                        "Empty reports have a header row"
+                    ),
+                ],
+                vec!["cli/src/export.rs".to_string()],
+            )
+        );
+        let generated = &spec.requirements[1].id;
+        assert!(generated.starts_with("REQ-") && generated != "REQ-001");
+
+        let err = YamlSpecSource
+            .parse("requirements:\n  - id: REQ-1\n    summary: a\n    acceptance_criteria: [x]\n  - id: REQ-1\n    summary: b\n    acceptance_criteria: [y]\n")
+            .unwrap_err();
+        assert!(err.to_string().contains("Duplicate requirement id REQ-1"));
+    }
+}
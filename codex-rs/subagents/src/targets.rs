@@ -0,0 +1,301 @@
+//! Resolves the files a requirement should touch by searching the project with
+//! `codex-file-search`, which honors `.gitignore` like the `@` file picker.
+
+use crate::pipeline::ChangeFile;
+use crate::pipeline::ChangeType;
+use crate::pipeline::Requirement;
+use once_cell::sync::Lazy;
+use regex_lite::Regex;
+use std::collections::BTreeSet;
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+const HINT_SEARCH_RESULTS: NonZeroUsize = NonZeroUsize::new(8).unwrap();
+const KEYWORD_SEARCH_RESULTS: NonZeroUsize = NonZeroUsize::new(20).unwrap();
+const NUM_FILE_SEARCH_THREADS: NonZeroUsize = NonZeroUsize::new(2).unwrap();
+
+/// Keywords searched per requirement when no hints are given.
+const MAX_KEYWORDS: usize = 8;
+/// Ranked candidates kept per requirement when no hints are given.
+const MAX_CANDIDATES: usize = 3;
+/// Files larger than this are ranked on their path alone.
+const MAX_SYMBOL_SCAN_BYTES: u64 = 256 * 1024;
+
+const STOPWORDS: &[&str] = &[
+    "add", "all", "and", "any", "are", "can", "for", "from", "has", "have", "into", "its", "new",
+    "not", "now", "should", "that", "the", "their", "them", "then", "this", "use", "when", "with",
+    "will", "must", "able", "make", "user", "users", "support",
+];
+
+static DECLARATION_RE: Lazy<Regex> = Lazy::new(|| {
+    #[expect(clippy::expect_used)]
+    Regex::new(
+        r"\b(?:fn|struct|enum|trait|mod|type|const|static|class|def|func|function|interface)\s+([A-Za-z_][A-Za-z0-9_]*)",
+    )
+    .expect("valid declaration regex")
+});
+
+/// Files to change for `requirement`. Hints are resolved against `root`;
+/// without hints, files are ranked by how many requirement keywords appear in
+/// their path and declared symbols.
+pub(crate) fn resolve_change_files(root: &Path, requirement: &Requirement) -> Vec<ChangeFile> {
+    if requirement.file_hints.is_empty() {
+        return rank_candidate_files(root, requirement);
+    }
+
+    let mut files: Vec<ChangeFile> = Vec::new();
+    for hint in &requirement.file_hints {
+        let file = resolve_hint(root, hint, &requirement.id);
+        if !files.iter().any(|existing| existing.path == file.path) {
+            files.push(file);
+        }
+    }
+    files
+}
+
+fn resolve_hint(root: &Path, hint: &str, requirement_id: &str) -> ChangeFile {
+    let hint = hint.trim().trim_start_matches("./");
+    if root.join(hint).is_file() {
+        return ChangeFile::new(
+            hint,
+            ChangeType::Modify,
+            format!("Referenced by {requirement_id}"),
+        )
+        .with_confidence(100);
+    }
+
+    let file_name = Path::new(hint).file_name();
+    if let Some(path) = search(root, hint, HINT_SEARCH_RESULTS)
+        .into_iter()
+        .find(|path| Path::new(path).file_name() == file_name)
+    {
+        return ChangeFile::new(
+            path,
+            ChangeType::Modify,
+            format!("Resolved from `{hint}` referenced by {requirement_id}"),
+        )
+        .with_confidence(80);
+    }
+
+    ChangeFile::new(
+        hint,
+        ChangeType::Create,
+        format!("New file referenced by {requirement_id}"),
+    )
+    .with_confidence(60)
+}
+
+fn rank_candidate_files(root: &Path, requirement: &Requirement) -> Vec<ChangeFile> {
+    let keywords = requirement_keywords(requirement);
+    if keywords.is_empty() {
+        return Vec::new();
+    }
+
+    let candidates: BTreeSet<String> = keywords
+        .iter()
+        .flat_map(|keyword| search(root, keyword, KEYWORD_SEARCH_RESULTS))
+        .collect();
+
+    let mut ranked: Vec<(usize, usize, String)> = Vec::new();
+    for path in candidates {
+        let path_words = identifier_words(&path);
+        let symbol_words = declared_symbol_words(&root.join(&path));
+        let mut score = 0;
+        let mut matched = 0;
+        for keyword in &keywords {
+            let in_path = path_words.contains(keyword);
+            let in_symbols = symbol_words.contains(keyword);
+            score += usize::from(in_path) * 2 + usize::from(in_symbols);
+            matched += usize::from(in_path || in_symbols);
+        }
+        if score > 0 {
+            ranked.push((score, matched, path));
+        }
+    }
+    ranked.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.2.cmp(&b.2)));
+
+    ranked
+        .into_iter()
+        .take(MAX_CANDIDATES)
+        .map(|(_, matched, path)| {
+            let confidence = (matched * 100 / keywords.len()).min(100) as u8;
+            ChangeFile::new(
+                path,
+                ChangeType::Modify,
+                format!("Matches {matched} keywords from {}", requirement.id),
+            )
+            .with_confidence(confidence)
+        })
+        .collect()
+}
+
+fn search(root: &Path, pattern: &str, limit: NonZeroUsize) -> Vec<String> {
+    match codex_file_search::run(
+        pattern,
+        limit,
+        root,
+        Vec::new(),
+        NUM_FILE_SEARCH_THREADS,
+        Arc::new(AtomicBool::new(false)),
+        false,
+    ) {
+        Ok(results) => results
+            .matches
+            .into_iter()
+            .map(|file_match| file_match.path)
+            .collect(),
+        Err(err) => {
+            tracing::warn!("file search for `{pattern}` failed: {err}");
+            Vec::new()
+        }
+    }
+}
+
+fn requirement_keywords(requirement: &Requirement) -> Vec<String> {
+    let text = std::iter::once(requirement.summary.as_str())
+        .chain(
+            requirement
+                .acceptance_criteria
+                .iter()
+                .map(|criterion| criterion.text.as_str()),
+        )
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut keywords: Vec<String> = Vec::new();
+    for word in identifier_words(&text) {
+        if word.len() >= 3 && !STOPWORDS.contains(&word.as_str()) && !keywords.contains(&word) {
+            keywords.push(word);
+        }
+        if keywords.len() == MAX_KEYWORDS {
+            break;
+        }
+    }
+    keywords
+}
+
+/// Lowercase words from `text`, splitting on punctuation, `snake_case`, and
+/// `camelCase` boundaries.
+fn identifier_words(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    for token in text.split(|c: char| !c.is_ascii_alphanumeric()) {
+        let mut current = String::new();
+        let mut prev_lower = false;
+        for c in token.chars() {
+            if c.is_ascii_uppercase() && prev_lower && !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            prev_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+            current.push(c.to_ascii_lowercase());
+        }
+        if !current.is_empty() {
+            words.push(current);
+        }
+    }
+    words
+}
+
+fn declared_symbol_words(path: &Path) -> BTreeSet<String> {
+    let too_large = std::fs::metadata(path)
+        .map(|metadata| metadata.len() > MAX_SYMBOL_SCAN_BYTES)
+        .unwrap_or(true);
+    if too_large {
+        return BTreeSet::new();
+    }
+    let Ok(contents) = std::fs::read_to_string(path) else {
+        return BTreeSet::new();
+    };
+    DECLARATION_RE
+        .captures_iter(&contents)
+        .filter_map(|captures| captures.get(1))
+        .flat_map(|name| identifier_words(name.as_str()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::AcceptanceCriterion;
+    use crate::test_support::write_file;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn summary(files: &[ChangeFile]) -> Vec<(&str, ChangeType, Option<u8>)> {
+        files
+            .iter()
+            .map(|file| {
+                (
+                    file.path.as_str(),
+                    file.change_type.clone(),
+                    file.confidence,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn hints_resolve_to_existing_or_new_files() {
+        let dir = TempDir::new().expect("tempdir");
+        let root = dir.path();
+        write_file(root, "core/src/parser.rs", "pub fn parse() {}\n");
+        write_file(root, "core/src/lib.rs", "mod parser;\n");
+        write_file(root, ".gitignore", "target/\n");
+        write_file(root, "target/parser.rs", "");
+
+        let requirement = Requirement::new(
+            "REQ-001",
+            "Parse input",
+            vec![],
+            vec![
+                "core/src/lib.rs".to_string(),
+                "parser.rs".to_string(),
+                "core/src/lexer.rs".to_string(),
+            ],
+        );
+
+        assert_eq!(
+            summary(&resolve_change_files(root, &requirement)),
+            vec![
+                ("core/src/lib.rs", ChangeType::Modify, Some(100)),
+                ("core/src/parser.rs", ChangeType::Modify, Some(80)),
+                ("core/src/lexer.rs", ChangeType::Create, Some(60)),
+            ]
+        );
+    }
+
+    #[test]
+    fn ranks_candidates_by_path_and_symbol_overlap() {
+        let dir = TempDir::new().expect("tempdir");
+        let root = dir.path();
+        write_file(
+            root,
+            "src/auth/token_store.rs",
+            "pub struct TokenStore;\nfn refresh_token() {}\n",
+        );
+        write_file(root, "src/session.rs", "fn refresh_session_token() {}\n");
+        write_file(root, "src/render.rs", "fn draw() {}\n");
+
+        let requirement = Requirement::new(
+            "REQ-002",
+            "Refresh expired auth tokens",
+            vec![AcceptanceCriterion::new(
+                None,
+                "Token store keeps the new token",
+            )],
+            vec![],
+        );
+
+        assert_eq!(
+            requirement_keywords(&requirement),
+            vec![
+                "refresh", "expired", "auth", "tokens", "token", "store", "keeps"
+            ]
+        );
+        assert_eq!(
+            summary(&resolve_change_files(root, &requirement)),
+            vec![("src/auth/token_store.rs", ChangeType::Modify, Some(57))]
+        );
+    }
+}
@@ -1,5 +1,10 @@
+use crate::checkpoint::SlotCodecs;
+use crate::checkpoint::TaskContextCheckpoint;
 use crate::error::TaskContextError;
+use anyhow::Context;
+use serde::Deserialize;
 use serde::Serialize;
+use serde::de::DeserializeOwned;
 use std::any::Any;
 use std::any::TypeId;
 use std::any::type_name;
@@ -7,7 +12,7 @@ use std::collections::HashMap;
 use std::sync::RwLock;
 use time::OffsetDateTime;
 
-#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum DiagnosticLevel {
     Info,
@@ -15,20 +20,61 @@ pub enum DiagnosticLevel {
     Error,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiagnosticEntry {
     pub timestamp: OffsetDateTime,
     pub level: DiagnosticLevel,
     pub message: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskContextSnapshot {
     pub typed_slots: Vec<String>,
     pub scratchpads: HashMap<String, serde_json::Value>,
     pub diagnostics: Vec<DiagnosticEntry>,
 }
 
+impl TaskContextSnapshot {
+    /// Deserialize `field` of the JSON object stored in scratchpad `key`.
+    pub fn scratchpad_field<T: DeserializeOwned>(
+        &self,
+        key: &str,
+        field: &str,
+    ) -> anyhow::Result<Option<T>> {
+        let Some(value) = self
+            .scratchpads
+            .get(key)
+            .and_then(|output| output.get(field))
+        else {
+            return Ok(None);
+        };
+        serde_json::from_value(value.clone())
+            .map(Some)
+            .with_context(|| format!("invalid `{field}` in scratchpad `{key}`"))
+    }
+
+    /// Store `value` as `field` of the JSON object in scratchpad `key`,
+    /// creating the scratchpad when it is missing.
+    pub fn set_scratchpad_field<T: Serialize>(
+        &mut self,
+        key: &str,
+        field: &str,
+        value: &T,
+    ) -> anyhow::Result<()> {
+        let value = serde_json::to_value(value)
+            .with_context(|| format!("failed to serialize `{field}`"))?;
+        let output = self
+            .scratchpads
+            .entry(key.to_string())
+            .or_insert_with(|| serde_json::Value::Object(Default::default()));
+        let Some(object) = output.as_object_mut() else {
+            anyhow::bail!("scratchpad `{key}` is not a JSON object");
+        };
+        object.insert(field.to_string(), value);
+        Ok(())
+    }
+}
+
 struct TypedSlot {
     type_name: &'static str,
     value: RwLock<Box<dyn Any + Send + Sync + 'static>>,
@@ -217,6 +263,45 @@ impl TaskContext {
         })
     }
 
+    /// Serialize the context. Typed slots are written through `codecs`; slots
+    /// of other types are listed by type name only.
+    pub fn checkpoint(
+        &self,
+        codecs: &SlotCodecs,
+    ) -> Result<TaskContextCheckpoint, TaskContextError> {
+        let snapshot = self.snapshot()?;
+        let mut dropped_slots: Vec<String> = snapshot
+            .typed_slots
+            .into_iter()
+            .filter(|type_name| !codecs.handles(type_name))
+            .collect();
+        dropped_slots.sort();
+        Ok(TaskContextCheckpoint {
+            typed_slots: codecs.encode(self)?,
+            dropped_slots,
+            scratchpads: snapshot.scratchpads.into_iter().collect(),
+            diagnostics: snapshot.diagnostics,
+        })
+    }
+
+    pub fn from_checkpoint(
+        checkpoint: TaskContextCheckpoint,
+        codecs: &SlotCodecs,
+    ) -> Result<Self, TaskContextError> {
+        let ctx = Self::new();
+        for (name, value) in checkpoint.typed_slots {
+            codecs.decode(&ctx, &name, value)?;
+        }
+        *ctx.scratchpads
+            .write()
+            .map_err(|_| TaskContextError::Poisoned)? =
+            checkpoint.scratchpads.into_iter().collect();
+        *ctx.diagnostics
+            .write()
+            .map_err(|_| TaskContextError::Poisoned)? = checkpoint.diagnostics;
+        Ok(ctx)
+    }
+
     pub fn debug_dump(&self) -> Result<Option<String>, TaskContextError> {
         if std::env::var("CODEX_DEBUG_SUBAGENTS").is_err() {
             return Ok(None);
@@ -0,0 +1,119 @@
+use crate::pipeline::TestCaseResult;
+use crate::pipeline::TestCaseStatus;
+use anyhow::Context;
+use anyhow::Result;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JestReport {
+    test_results: Vec<JestFileResult>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JestFileResult {
+    name: String,
+    #[serde(default)]
+    assertion_results: Vec<JestAssertion>,
+    /// Set when the file itself failed to run, e.g. on a syntax error.
+    #[serde(default)]
+    message: Option<String>,
+    #[serde(default)]
+    status: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JestAssertion {
+    full_name: String,
+    status: String,
+    #[serde(default)]
+    duration: Option<f64>,
+    #[serde(default)]
+    failure_messages: Vec<String>,
+}
+
+pub(super) fn looks_like_jest(text: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(text)
+        .is_ok_and(|value| value.get("testResults").is_some())
+}
+
+pub(super) fn parse(text: &str) -> Result<Vec<TestCaseResult>> {
+    let report: JestReport = serde_json::from_str(text).context("Invalid Jest JSON report")?;
+    let mut cases = Vec::new();
+    for file in report.test_results {
+        if file.assertion_results.is_empty() && file.status.as_deref() == Some("failed") {
+            let mut case = TestCaseResult::new(&file.name, TestCaseStatus::Failed);
+            if let Some(message) = file.message.filter(|message| !message.is_empty()) {
+                case = case.message(message);
+            }
+            cases.push(case);
+            continue;
+        }
+        for assertion in file.assertion_results {
+            let status = match assertion.status.as_str() {
+                "passed" => TestCaseStatus::Passed,
+                "failed" => TestCaseStatus::Failed,
+                _ => TestCaseStatus::Skipped,
+            };
+            let mut case = TestCaseResult::new(assertion.full_name, status).suite(&file.name);
+            if let Some(duration) = assertion.duration {
+                case = case.duration_ms(duration.round() as u64);
+            }
+            if !assertion.failure_messages.is_empty() {
+                case = case.message(assertion.failure_messages.join("\n"));
+            }
+            cases.push(case);
+        }
+    }
+    Ok(cases)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_jest_assertions() {
+        let text = r#"{
+  "numTotalTests": 3,
+  "success": false,
+  "testResults": [
+    {
+      "name": "/repo/src/sum.test.js",
+      "status": "failed",
+      "assertionResults": [
+        { "fullName": "sum adds", "status": "passed", "duration": 3, "failureMessages": [] },
+        { "fullName": "sum rejects strings", "status": "failed", "duration": 7,
+          "failureMessages": ["Error: expect(received).toThrow()"] },
+        { "fullName": "sum handles bigint", "status": "pending", "failureMessages": [] }
+      ]
+    },
+    {
+      "name": "/repo/src/broken.test.js",
+      "status": "failed",
+      "message": "SyntaxError: Unexpected token",
+      "assertionResults": []
+    }
+  ]
+}"#;
+        let suite = "/repo/src/sum.test.js";
+        assert_eq!(
+            parse(text).expect("report"),
+            vec![
+                TestCaseResult::new("sum adds", TestCaseStatus::Passed)
+                    .suite(suite)
+                    .duration_ms(3),
+                TestCaseResult::new("sum rejects strings", TestCaseStatus::Failed)
+                    .suite(suite)
+                    .duration_ms(7)
+                    .message("Error: expect(received).toThrow()"),
+                TestCaseResult::new("sum handles bigint", TestCaseStatus::Skipped).suite(suite),
+                TestCaseResult::new("/repo/src/broken.test.js", TestCaseStatus::Failed)
+                    .message("SyntaxError: Unexpected token"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,318 @@
+use crate::pipeline::TestCaseResult;
+use crate::pipeline::TestCaseStatus;
+use crate::pipeline::TestOutcome;
+use crate::pipeline::TestResults;
+use crate::pipeline::TestStatus;
+use anyhow::Context;
+use anyhow::Result;
+use quick_xml::Reader;
+use quick_xml::escape::escape;
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::BytesStart;
+use quick_xml::events::Event;
+use std::fmt::Write as _;
+
+/// Parse JUnit XML. `testsuite` nesting is flattened; a case without a
+/// `classname` takes the name of its enclosing suite.
+pub(super) fn parse(text: &str) -> Result<Vec<TestCaseResult>> {
+    let mut reader = Reader::from_str(text);
+    let mut cases = Vec::new();
+    let mut suites: Vec<Option<String>> = Vec::new();
+    let mut current: Option<TestCaseResult> = None;
+    let mut in_message = false;
+    let mut body = String::new();
+
+    loop {
+        let event = reader.read_event().context("Invalid JUnit XML")?;
+        match event {
+            Event::Start(ref tag) | Event::Empty(ref tag) => {
+                let empty = matches!(event, Event::Empty(_));
+                match tag.local_name().as_ref() {
+                    b"testsuite" if !empty => suites.push(attribute(tag, "name")?),
+                    b"testcase" => {
+                        let case = start_case(tag, suites.iter().rev().flatten().next())?;
+                        if empty {
+                            cases.push(case);
+                        } else {
+                            current = Some(case);
+                        }
+                    }
+                    name @ (b"failure" | b"error" | b"skipped") => {
+                        if let Some(case) = current.as_mut() {
+                            case.status = if name == b"skipped" {
+                                TestCaseStatus::Skipped
+                            } else {
+                                TestCaseStatus::Failed
+                            };
+                            case.message = attribute(tag, "message")?;
+                            in_message = !empty;
+                            body.clear();
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Event::Text(text) if in_message => {
+                body.push_str(&text.decode()?);
+            }
+            Event::CData(data) if in_message => {
+                body.push_str(&data.decode()?);
+            }
+            Event::GeneralRef(reference) if in_message => {
+                if let Some(ch) = reference.resolve_char_ref()? {
+                    body.push(ch);
+                } else if let Some(resolved) = resolve_predefined_entity(&reference.decode()?) {
+                    body.push_str(resolved);
+                }
+            }
+            Event::End(tag) => match tag.local_name().as_ref() {
+                b"testsuite" => {
+                    suites.pop();
+                }
+                b"testcase" => cases.extend(current.take()),
+                b"failure" | b"error" | b"skipped" => {
+                    in_message = false;
+                    let body = body.trim();
+                    if let Some(case) = current.as_mut()
+                        && !body.is_empty()
+                    {
+                        case.message = Some(match case.message.take() {
+                            Some(message) if !body.contains(&message) => {
+                                format!("{message}\n{body}")
+                            }
+                            _ => body.to_string(),
+                        });
+                    }
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(cases)
+}
+
+fn start_case(tag: &BytesStart<'_>, suite: Option<&String>) -> Result<TestCaseResult> {
+    let name = attribute(tag, "name")?.unwrap_or_default();
+    let mut case = TestCaseResult::new(name, TestCaseStatus::Passed);
+    if let Some(suite) = attribute(tag, "classname")?.or_else(|| suite.cloned()) {
+        case = case.suite(suite);
+    }
+    if let Some(seconds) = attribute(tag, "time")?.and_then(|time| time.parse::<f64>().ok()) {
+        case = case.duration_ms((seconds * 1000.0).round() as u64);
+    }
+    Ok(case)
+}
+
+fn attribute(tag: &BytesStart<'_>, name: &str) -> Result<Option<String>> {
+    match tag.try_get_attribute(name)? {
+        Some(attribute) => Ok(Some(attribute.unescape_value()?.into_owned())),
+        None => Ok(None),
+    }
+}
+
+/// Render `results` as JUnit XML: one `<testsuite>` per test task. Tasks
+/// without imported cases are reported as a single case named after the
+/// task; blocked tasks are reported as skipped.
+pub fn to_junit_xml(results: &TestResults) -> String {
+    let suites: Vec<(&TestOutcome, Vec<TestCaseResult>)> = results
+        .outcomes
+        .iter()
+        .map(|outcome| (outcome, cases_for(outcome)))
+        .collect();
+    let all_cases = || suites.iter().flat_map(|(_, cases)| cases.iter());
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(xml, "<testsuites name=\"codex\" {}>", counts(all_cases()));
+    for (outcome, cases) in &suites {
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{}\" {}>",
+            escape(outcome.task_name.as_str()),
+            counts(cases.iter())
+        );
+        for case in cases {
+            let classname = case.suite.as_deref().unwrap_or(&outcome.task_name);
+            let _ = write!(
+                xml,
+                "    <testcase name=\"{}\" classname=\"{}\"",
+                escape(case.name.as_str()),
+                escape(classname)
+            );
+            if let Some(duration_ms) = case.duration_ms {
+                let _ = write!(xml, " time=\"{:.3}\"", duration_ms as f64 / 1000.0);
+            }
+            let message = case.message.as_deref().unwrap_or_default();
+            let summary = message.lines().next().unwrap_or_default();
+            match case.status {
+                TestCaseStatus::Passed => xml.push_str("/>\n"),
+                TestCaseStatus::Failed => {
+                    let _ = writeln!(
+                        xml,
+                        ">\n      <failure message=\"{}\">{}</failure>\n    </testcase>",
+                        escape(summary),
+                        escape(message)
+                    );
+                }
+                TestCaseStatus::Skipped => {
+                    let _ = writeln!(
+                        xml,
+                        ">\n      <skipped message=\"{}\"/>\n    </testcase>",
+                        escape(summary)
+                    );
+                }
+            }
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+fn cases_for(outcome: &TestOutcome) -> Vec<TestCaseResult> {
+    if !outcome.cases.is_empty() {
+        return outcome.cases.clone();
+    }
+    let status = match outcome.status {
+        TestStatus::Passed => TestCaseStatus::Passed,
+        TestStatus::Failed => TestCaseStatus::Failed,
+        TestStatus::Blocked => TestCaseStatus::Skipped,
+    };
+    let case = TestCaseResult::new(&outcome.task_name, status);
+    match &outcome.details {
+        Some(details) => vec![case.message(details)],
+        None => vec![case],
+    }
+}
+
+fn counts<'a>(cases: impl Iterator<Item = &'a TestCaseResult>) -> String {
+    let (mut tests, mut failures, mut skipped) = (0, 0, 0);
+    for case in cases {
+        tests += 1;
+        match case.status {
+            TestCaseStatus::Passed => {}
+            TestCaseStatus::Failed => failures += 1,
+            TestCaseStatus::Skipped => skipped += 1,
+        }
+    }
+    format!("tests=\"{tests}\" failures=\"{failures}\" skipped=\"{skipped}\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::TestPlan;
+    use crate::pipeline::TestTask;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_nextest_junit() {
+        let text = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="nextest-run" tests="3" failures="1" errors="0">
+    <testsuite name="demo" tests="3" disabled="0" errors="0" failures="1">
+        <testcase name="tests::adds" classname="demo" timestamp="2024-01-01T00:00:00Z" time="0.004">
+        </testcase>
+        <testcase name="tests::divides" classname="demo" time="0.010">
+            <failure type="test failure">thread 'tests::divides' panicked at src/lib.rs:9:5:
+attempt to divide by zero &amp; more</failure>
+        </testcase>
+        <testcase name="tests::slow" classname="demo" time="0">
+            <skipped/>
+        </testcase>
+    </testsuite>
+</testsuites>
+"#;
+        assert_eq!(
+            parse(text).expect("junit"),
+            vec![
+                TestCaseResult::new("tests::adds", TestCaseStatus::Passed)
+                    .suite("demo")
+                    .duration_ms(4),
+                TestCaseResult::new("tests::divides", TestCaseStatus::Failed)
+                    .suite("demo")
+                    .duration_ms(10)
+                    .message(
+                        "thread 'tests::divides' panicked at src/lib.rs:9:5:\nattempt to divide by zero & more"
+                    ),
+                TestCaseResult::new("tests::slow", TestCaseStatus::Skipped)
+                    .suite("demo")
+                    .duration_ms(0),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_pytest_junit() {
+        let text = r#"<?xml version="1.0" encoding="utf-8"?><testsuites><testsuite name="pytest" errors="0" failures="1" skipped="1" tests="3"><testcase classname="tests.test_export" name="test_csv" time="0.001" /><testcase classname="tests.test_export" name="test_header" time="0.002"><failure message="AssertionError: assert 'a' == 'b'">def test_header():
+&gt;       assert "a" == "b"</failure></testcase><testcase classname="tests.test_export" name="test_pdf" time="0.000"><skipped type="pytest.skip" message="not supported">skipped</skipped></testcase></testsuite></testsuites>"#;
+        let cases = parse(text).expect("junit");
+        assert_eq!(cases.len(), 3);
+        assert_eq!(cases[0].status, TestCaseStatus::Passed);
+        assert_eq!(cases[0].suite.as_deref(), Some("tests.test_export"));
+        assert_eq!(
+            cases[1].message.as_deref(),
+            Some(
+                "AssertionError: assert 'a' == 'b'\ndef test_header():\n>       assert \"a\" == \"b\""
+            )
+        );
+        assert_eq!(cases[2].status, TestCaseStatus::Skipped);
+        assert_eq!(cases[2].message.as_deref(), Some("not supported\nskipped"));
+    }
+
+    #[test]
+    fn exports_junit_and_round_trips() {
+        let plan = TestPlan::new(
+            "plan",
+            vec![
+                TestTask::new("cargo test -p demo", "cargo test -p demo", vec![]),
+                TestTask::new("pytest", "pytest", vec![]),
+            ],
+        );
+        let results = TestResults::new(
+            plan,
+            vec![
+                TestOutcome::from_cases(
+                    "cargo test -p demo",
+                    vec![
+                        TestCaseResult::new("adds", TestCaseStatus::Passed).duration_ms(4),
+                        TestCaseResult::new("divides", TestCaseStatus::Failed)
+                            .message("panicked: a < b\nbacktrace"),
+                    ],
+                ),
+                TestOutcome::new(
+                    "pytest",
+                    TestStatus::Blocked,
+                    Some("Execution deferred to interactive shell".to_string()),
+                ),
+            ],
+        );
+        let xml = to_junit_xml(&results);
+        assert_eq!(
+            xml,
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="codex" tests="3" failures="1" skipped="1">
+  <testsuite name="cargo test -p demo" tests="2" failures="1" skipped="0">
+    <testcase name="adds" classname="cargo test -p demo" time="0.004"/>
+    <testcase name="divides" classname="cargo test -p demo">
+      <failure message="panicked: a &lt; b">panicked: a &lt; b
+backtrace</failure>
+    </testcase>
+  </testsuite>
+  <testsuite name="pytest" tests="1" failures="0" skipped="1">
+    <testcase name="pytest" classname="pytest">
+      <skipped message="Execution deferred to interactive shell"/>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#
+        );
+
+        let cases = parse(&xml).expect("round trip");
+        assert_eq!(
+            cases[1].message.as_deref(),
+            Some("panicked: a < b\nbacktrace")
+        );
+        assert_eq!(cases[2].status, TestCaseStatus::Skipped);
+    }
+}
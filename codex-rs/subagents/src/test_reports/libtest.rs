@@ -0,0 +1,88 @@
+use crate::pipeline::TestCaseResult;
+use crate::pipeline::TestCaseStatus;
+use anyhow::Result;
+use anyhow::anyhow;
+use serde::Deserialize;
+
+/// One line of libtest's JSON output. Suite events and lines that are not
+/// JSON (cargo's own progress output) are ignored.
+#[derive(Deserialize)]
+struct LibtestEvent {
+    #[serde(rename = "type")]
+    kind: String,
+    event: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    exec_time: Option<f64>,
+    #[serde(default)]
+    stdout: Option<String>,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+pub(super) fn parse(text: &str) -> Result<Vec<TestCaseResult>> {
+    let mut cases = Vec::new();
+    let mut saw_event = false;
+    for line in text.lines().map(str::trim) {
+        if !line.starts_with('{') {
+            continue;
+        }
+        let Ok(event) = serde_json::from_str::<LibtestEvent>(line) else {
+            continue;
+        };
+        saw_event = true;
+        if event.kind != "test" {
+            continue;
+        }
+        let Some(name) = event.name else {
+            continue;
+        };
+        let status = match event.event.as_str() {
+            "ok" => TestCaseStatus::Passed,
+            "failed" | "timeout" => TestCaseStatus::Failed,
+            "ignored" => TestCaseStatus::Skipped,
+            _ => continue,
+        };
+        let mut case = TestCaseResult::new(name, status);
+        if let Some(seconds) = event.exec_time {
+            case = case.duration_ms((seconds * 1000.0).round() as u64);
+        }
+        if let Some(message) = event.stdout.or(event.message).filter(|m| !m.is_empty()) {
+            case = case.message(message);
+        }
+        cases.push(case);
+    }
+    if !saw_event {
+        return Err(anyhow!("No libtest JSON events found"));
+    }
+    Ok(cases)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_libtest_events_between_cargo_output() {
+        let text = r#"
+   Compiling demo v0.1.0
+{ "type": "suite", "event": "started", "test_count": 2 }
+{ "type": "test", "event": "started", "name": "tests::adds" }
+{ "type": "test", "name": "tests::adds", "event": "ok", "exec_time": 0.0021 }
+{ "type": "test", "name": "tests::divides", "event": "failed", "exec_time": 0.5, "stdout": "thread panicked at src/lib.rs:9:5:\nattempt to divide by zero\n" }
+{ "type": "suite", "event": "failed", "passed": 1, "failed": 1, "ignored": 0, "measured": 0, "filtered_out": 0, "exec_time": 0.5 }
+"#;
+        assert_eq!(
+            parse(text).expect("events"),
+            vec![
+                TestCaseResult::new("tests::adds", TestCaseStatus::Passed).duration_ms(2),
+                TestCaseResult::new("tests::divides", TestCaseStatus::Failed)
+                    .duration_ms(500)
+                    .message("thread panicked at src/lib.rs:9:5:\nattempt to divide by zero\n"),
+            ]
+        );
+        assert!(parse("error: could not compile `demo`").is_err());
+    }
+}
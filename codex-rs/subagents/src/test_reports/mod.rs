@@ -0,0 +1,194 @@
+//! Per-test-case interop for [`TestResults`]: import the reports test runners
+//! write into [`TestCaseResult`]s, and export results as JUnit XML for CI
+//! dashboards.
+
+mod jest;
+mod junit;
+mod libtest;
+
+pub use junit::to_junit_xml;
+
+use crate::pipeline::TestCaseResult;
+use crate::pipeline::TestOutcome;
+use crate::pipeline::TestResults;
+use anyhow::Result;
+use anyhow::anyhow;
+use serde::Deserialize;
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TestReportFormat {
+    /// `cargo test -- -Z unstable-options --format json`.
+    LibtestJson,
+    /// JUnit XML, as written by `cargo nextest` and `pytest --junitxml`.
+    Junit,
+    /// `jest --json`.
+    JestJson,
+}
+
+impl fmt::Display for TestReportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            TestReportFormat::LibtestJson => "libtest-json",
+            TestReportFormat::Junit => "junit",
+            TestReportFormat::JestJson => "jest-json",
+        };
+        f.write_str(name)
+    }
+}
+
+impl TestReportFormat {
+    pub fn detect(text: &str) -> Self {
+        let trimmed = text.trim_start();
+        if trimmed.starts_with('<') {
+            TestReportFormat::Junit
+        } else if trimmed.starts_with('{') && jest::looks_like_jest(trimmed) {
+            TestReportFormat::JestJson
+        } else {
+            TestReportFormat::LibtestJson
+        }
+    }
+}
+
+/// Parse a test runner report, detecting its format when `format` is `None`.
+pub fn parse_test_report(
+    text: &str,
+    format: Option<TestReportFormat>,
+) -> Result<Vec<TestCaseResult>> {
+    match format.unwrap_or_else(|| TestReportFormat::detect(text)) {
+        TestReportFormat::LibtestJson => libtest::parse(text),
+        TestReportFormat::Junit => junit::parse(text),
+        TestReportFormat::JestJson => jest::parse(text),
+    }
+}
+
+/// Expand the outcome of `task_name` into its individual test cases.
+pub fn import_test_outcome(
+    task_name: &str,
+    text: &str,
+    format: Option<TestReportFormat>,
+) -> Result<TestOutcome> {
+    Ok(TestOutcome::from_cases(
+        task_name,
+        parse_test_report(text, format)?,
+    ))
+}
+
+impl TestResults {
+    /// Replace the outcome of the planned task `task_name` with the cases
+    /// from its runner's report.
+    pub fn import_report(
+        &mut self,
+        task_name: &str,
+        text: &str,
+        format: Option<TestReportFormat>,
+    ) -> Result<()> {
+        if !self.plan.tasks.iter().any(|task| task.name == task_name) {
+            let planned: Vec<&str> = self
+                .plan
+                .tasks
+                .iter()
+                .map(|task| task.name.as_str())
+                .collect();
+            return Err(anyhow!(
+                "no test task named `{task_name}` in the plan (planned: {})",
+                planned.join(", ")
+            ));
+        }
+        let outcome = import_test_outcome(task_name, text, format)?;
+        match self
+            .outcomes
+            .iter_mut()
+            .find(|existing| existing.task_name == task_name)
+        {
+            Some(existing) => *existing = outcome,
+            None => self.outcomes.push(outcome),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::TestCaseStatus;
+    use crate::pipeline::TestPlan;
+    use crate::pipeline::TestStatus;
+    use crate::pipeline::TestTask;
+    use crate::pipeline::merge_test_results;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn detects_report_formats() {
+        assert_eq!(
+            TestReportFormat::detect("<?xml version=\"1.0\"?><testsuites/>"),
+            TestReportFormat::Junit
+        );
+        assert_eq!(
+            TestReportFormat::detect(r#"{"numTotalTests": 0, "testResults": []}"#),
+            TestReportFormat::JestJson
+        );
+        assert_eq!(
+            TestReportFormat::detect(r#"{ "type": "suite", "event": "started", "test_count": 1 }"#),
+            TestReportFormat::LibtestJson
+        );
+    }
+
+    #[test]
+    fn imported_outcome_names_failing_cases() {
+        let report = r#"
+{ "type": "suite", "event": "started", "test_count": 3 }
+{ "type": "test", "event": "ok", "name": "parses" }
+{ "type": "test", "event": "failed", "name": "renders", "stdout": "boom" }
+{ "type": "test", "event": "ignored", "name": "slow" }
+{ "type": "suite", "event": "failed", "passed": 1, "failed": 1, "ignored": 1 }
+"#;
+        let outcome = import_test_outcome("cargo test", report, None).expect("report");
+        assert_eq!(outcome.status, TestStatus::Failed);
+        assert_eq!(
+            outcome.details.as_deref(),
+            Some("1 of 3 tests failed: renders")
+        );
+        let statuses: Vec<TestCaseStatus> = outcome.cases.iter().map(|case| case.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                TestCaseStatus::Passed,
+                TestCaseStatus::Failed,
+                TestCaseStatus::Skipped
+            ]
+        );
+
+        let skipped = import_test_outcome(
+            "cargo test",
+            r#"{ "type": "test", "event": "ignored", "name": "slow" }"#,
+            None,
+        )
+        .expect("report");
+        assert_eq!(skipped.status, TestStatus::Blocked);
+    }
+
+    #[test]
+    fn imports_reports_into_planned_tasks() {
+        let plan = TestPlan::new("plan", vec![TestTask::new("unit", "cargo test", vec![])]);
+        let mut results = merge_test_results(&plan, vec![]);
+        results
+            .import_report(
+                "unit",
+                r#"{ "type": "test", "event": "ok", "name": "parses" }"#,
+                Some(TestReportFormat::LibtestJson),
+            )
+            .expect("import");
+        assert_eq!(results.outcomes.len(), 1);
+        assert_eq!(results.outcomes[0].status, TestStatus::Passed);
+        assert_eq!(results.outcomes[0].cases.len(), 1);
+
+        let err = results.import_report("lint", "", None).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "no test task named `lint` in the plan (planned: unit)"
+        );
+    }
+}
@@ -0,0 +1,10 @@
+//! Helpers shared by the crate's unit tests.
+
+use std::path::Path;
+
+/// Write `contents` to `root/path`, creating parent directories.
+pub(crate) fn write_file(root: &Path, path: &str, contents: &str) {
+    let path = root.join(path);
+    std::fs::create_dir_all(path.parent().expect("parent")).expect("create dir");
+    std::fs::write(path, contents).expect("write file");
+}
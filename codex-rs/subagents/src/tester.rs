@@ -13,6 +13,8 @@ use crate::pipeline::TestResults;
 use crate::pipeline::TestStatus;
 use crate::pipeline::merge_test_results;
 use crate::pipeline::plan_tests_for_changes;
+use crate::project::ProjectLayout;
+use crate::project::ProjectRoot;
 use anyhow::Result;
 use anyhow::anyhow;
 use serde::Serialize;
@@ -93,7 +95,8 @@ impl TypedSubagent for TesterSubagent {
     }
 
     fn execute(&self, ctx: &mut TaskContext, input: Self::Input) -> Result<Self::Output> {
-        let plan = plan_tests_for_changes(&input);
+        let project = ProjectLayout::detect(&ProjectRoot::resolve(ctx)?);
+        let plan = plan_tests_for_changes(&project, &input);
         ctx.push_diagnostic(
             DiagnosticLevel::Info,
             format!("Prepared {} test tasks", plan.tasks.len()),
@@ -135,14 +138,32 @@ mod tests {
             )],
         );
         ctx.insert_typed(changes.clone()).unwrap();
+        let project = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            project.path().join("Cargo.toml"),
+            "[package]\nname = \"demo\"\n",
+        )
+        .unwrap();
+        ctx.insert_typed(ProjectRoot(project.path().to_path_buf()))
+            .unwrap();
 
         let agent = TesterSubagent;
         let input = agent.prepare(&ctx).unwrap();
         let output = agent.execute(&mut ctx, input).unwrap();
+        assert_eq!(
+            output
+                .results
+                .plan
+                .tasks
+                .iter()
+                .map(|task| task.command.as_str())
+                .collect::<Vec<_>>(),
+            vec!["cargo test", "cargo fmt -- --check"]
+        );
         for outcome in &output.results.outcomes {
             assert!(matches!(outcome.status, TestStatus::Blocked));
         }
-        let plan = plan_tests_for_changes(&changes);
+        let plan = plan_tests_for_changes(&ProjectLayout::detect(project.path()), &changes);
         let seatbelt_outcomes = TesterSubagent::execute_plan_for_mode(&plan, Some("seatbelt"));
         for outcome in &seatbelt_outcomes {
             assert!(matches!(outcome.status, TestStatus::Blocked));
@@ -0,0 +1,1136 @@
+//! Declarative multi-agent workflows loaded from `.codex/workflows/*.yaml`.
+//!
+//! A workflow is an ordered list of steps. Each step either runs a single
+//! agent or fans out to several agents in parallel. Prompts can reference
+//! workflow inputs and earlier step results with `{{ inputs.<name> }}`,
+//! `{{ steps.<id>.output }}`, and `{{ steps.<id>.outcome }}`. Steps may be
+//! gated on another step's outcome (`when: steps.test.failed`) and may loop
+//! back to an earlier step a bounded number of times when they fail.
+//!
+//! [`WorkflowRun`] is a synchronous state machine: callers ask it for the next
+//! batch of agents to run, execute them however they like, and feed each
+//! result back with [`WorkflowRun::record`].
+
+use crate::error::WorkflowError;
+use crate::error::WorkflowParseError;
+use crate::json_text::json_objects;
+use crate::parser::validate_agent_name;
+use crate::pipeline::TestResults;
+use crate::pipeline::TestStatus;
+use once_cell::sync::Lazy;
+use regex_lite::Regex;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// Upper bound for `on_failure.max_loops` so a workflow cannot spin forever.
+pub const MAX_WORKFLOW_LOOPS: u32 = 10;
+
+static TEMPLATE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{\{\s*([A-Za-z0-9_.-]+)\s*\}\}").expect("compiled template regex"));
+
+static STEP_ID_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[a-z0-9][a-z0-9_-]*$").expect("compiled step id regex"));
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkflowDefinition {
+    /// Defaults to the file stem when omitted.
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub inputs: Vec<WorkflowInput>,
+    pub steps: Vec<WorkflowStep>,
+    /// Template rendered when the workflow finishes. Defaults to the output of
+    /// the last step that ran.
+    #[serde(default)]
+    pub output: Option<String>,
+    #[serde(skip)]
+    pub source_path: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkflowInput {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Inputs without a default are required.
+    #[serde(default)]
+    pub default: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkflowStep {
+    pub id: String,
+    #[serde(default)]
+    pub agent: Option<String>,
+    #[serde(default)]
+    pub prompt: Option<String>,
+    /// Agents to run concurrently instead of a single `agent`.
+    #[serde(default)]
+    pub parallel: Vec<WorkflowBranch>,
+    #[serde(default)]
+    pub when: Option<StepCondition>,
+    #[serde(default)]
+    pub on_failure: Option<LoopBack>,
+    /// Keep going after this step fails instead of stopping the workflow.
+    #[serde(default)]
+    pub continue_on_failure: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkflowBranch {
+    pub id: String,
+    pub agent: String,
+    #[serde(default)]
+    pub prompt: Option<String>,
+}
+
+/// Jump back to `goto` when the step fails, at most `max_loops` times.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LoopBack {
+    pub goto: String,
+    pub max_loops: u32,
+}
+
+/// Parsed form of `steps.<id>.<succeeded|failed|skipped>`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct StepCondition {
+    pub step: String,
+    pub status: StepStatus,
+}
+
+impl TryFrom<String> for StepCondition {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let invalid = || {
+            format!(
+                "invalid condition `{value}`; expected `steps.<id>.succeeded`, `steps.<id>.failed`, or `steps.<id>.skipped`"
+            )
+        };
+        let rest = value.trim().strip_prefix("steps.").ok_or_else(invalid)?;
+        let (step, status) = rest.rsplit_once('.').ok_or_else(invalid)?;
+        let status = match status {
+            "succeeded" => StepStatus::Succeeded,
+            "failed" => StepStatus::Failed,
+            "skipped" => StepStatus::Skipped,
+            _ => return Err(invalid()),
+        };
+        if step.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Self {
+            step: step.to_string(),
+            status,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StepStatus {
+    Succeeded,
+    Failed,
+    Skipped,
+}
+
+impl StepStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StepStatus::Succeeded => "succeeded",
+            StepStatus::Failed => "failed",
+            StepStatus::Skipped => "skipped",
+        }
+    }
+}
+
+enum TemplateRef<'a> {
+    Input(&'a str),
+    StepOutput(&'a str),
+    StepOutcome(&'a str),
+}
+
+fn parse_reference(raw: &str) -> Option<TemplateRef<'_>> {
+    if let Some(name) = raw.strip_prefix("inputs.") {
+        return Some(TemplateRef::Input(name));
+    }
+    let rest = raw.strip_prefix("steps.")?;
+    match rest.rsplit_once('.')? {
+        (id, "output") => Some(TemplateRef::StepOutput(id)),
+        (id, "outcome") => Some(TemplateRef::StepOutcome(id)),
+        _ => None,
+    }
+}
+
+impl WorkflowDefinition {
+    pub fn validate(&self) -> Result<(), WorkflowError> {
+        let invalid = |reason: String| WorkflowError::Invalid {
+            workflow: self.name.clone(),
+            reason,
+        };
+
+        validate_agent_name(&self.name).map_err(|err| invalid(err.to_string()))?;
+        if self.steps.is_empty() {
+            return Err(invalid("at least one step is required".to_string()));
+        }
+
+        let mut input_names = HashSet::new();
+        for input in &self.inputs {
+            if input.name.trim().is_empty() {
+                return Err(invalid("input names must be non-empty".to_string()));
+            }
+            if !input_names.insert(input.name.as_str()) {
+                return Err(invalid(format!("duplicate input `{}`", input.name)));
+            }
+        }
+
+        let mut step_ids = HashSet::new();
+        for id in self.step_ids() {
+            if !STEP_ID_RE.is_match(id) {
+                return Err(invalid(format!(
+                    "step id `{id}` must use lowercase letters, digits, hyphen, or underscore"
+                )));
+            }
+            if !step_ids.insert(id) {
+                return Err(invalid(format!("duplicate step id `{id}`")));
+            }
+        }
+
+        for (index, step) in self.steps.iter().enumerate() {
+            match (&step.agent, step.parallel.is_empty()) {
+                (Some(agent), true) if !agent.trim().is_empty() => {}
+                (None, false) => {
+                    if step.prompt.is_some() {
+                        return Err(invalid(format!(
+                            "step `{}` sets `prompt` on a parallel step; set it per branch",
+                            step.id
+                        )));
+                    }
+                }
+                _ => {
+                    return Err(invalid(format!(
+                        "step `{}` must set exactly one of `agent` or `parallel`",
+                        step.id
+                    )));
+                }
+            }
+            if step
+                .parallel
+                .iter()
+                .any(|branch| branch.agent.trim().is_empty())
+            {
+                return Err(invalid(format!(
+                    "parallel branches of step `{}` must name an agent",
+                    step.id
+                )));
+            }
+
+            if let Some(condition) = &step.when
+                && !step_ids.contains(condition.step.as_str())
+            {
+                return Err(invalid(format!(
+                    "step `{}` has a condition on unknown step `{}`",
+                    step.id, condition.step
+                )));
+            }
+
+            if let Some(loop_back) = &step.on_failure {
+                match self.steps.iter().position(|s| s.id == loop_back.goto) {
+                    Some(target) if target <= index => {}
+                    _ => {
+                        return Err(invalid(format!(
+                            "step `{}` can only loop back to itself or an earlier top-level step, not `{}`",
+                            step.id, loop_back.goto
+                        )));
+                    }
+                }
+                if loop_back.max_loops == 0 || loop_back.max_loops > MAX_WORKFLOW_LOOPS {
+                    return Err(invalid(format!(
+                        "step `{}` must set `max_loops` between 1 and {MAX_WORKFLOW_LOOPS}",
+                        step.id
+                    )));
+                }
+            }
+        }
+
+        let templates = self
+            .steps
+            .iter()
+            .flat_map(|step| {
+                std::iter::once(step.prompt.as_deref())
+                    .chain(step.parallel.iter().map(|branch| branch.prompt.as_deref()))
+            })
+            .chain(std::iter::once(self.output.as_deref()))
+            .flatten();
+        for template in templates {
+            for capture in TEMPLATE_RE.captures_iter(template) {
+                let raw = capture.get(1).map(|m| m.as_str()).unwrap_or_default();
+                let known = match parse_reference(raw) {
+                    Some(TemplateRef::Input(name)) => input_names.contains(name),
+                    Some(TemplateRef::StepOutput(id)) | Some(TemplateRef::StepOutcome(id)) => {
+                        step_ids.contains(id)
+                    }
+                    None => false,
+                };
+                if !known {
+                    return Err(invalid(format!("unknown template reference `{raw}`")));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Every step and branch id, in declaration order.
+    pub fn step_ids(&self) -> impl Iterator<Item = &str> {
+        self.steps.iter().flat_map(|step| {
+            std::iter::once(step.id.as_str())
+                .chain(step.parallel.iter().map(|branch| branch.id.as_str()))
+        })
+    }
+
+    /// Agent names referenced by the workflow, for checking against a registry
+    /// before running.
+    pub fn agent_names(&self) -> BTreeSet<&str> {
+        self.steps
+            .iter()
+            .flat_map(|step| {
+                step.agent
+                    .as_deref()
+                    .into_iter()
+                    .chain(step.parallel.iter().map(|branch| branch.agent.as_str()))
+            })
+            .collect()
+    }
+}
+
+pub fn parse_workflow_str(
+    contents: &str,
+    fallback_name: Option<&str>,
+) -> Result<WorkflowDefinition, WorkflowError> {
+    let mut workflow: WorkflowDefinition =
+        serde_yaml::from_str(contents).map_err(WorkflowError::InvalidYaml)?;
+    if workflow.name.trim().is_empty()
+        && let Some(name) = fallback_name
+    {
+        workflow.name = name.to_string();
+    }
+    workflow.validate()?;
+    Ok(workflow)
+}
+
+pub fn parse_workflow_file(path: &Path) -> Result<WorkflowDefinition, WorkflowError> {
+    let contents = fs::read_to_string(path).map_err(|source| WorkflowError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let stem = path.file_stem().and_then(|stem| stem.to_str());
+    let mut workflow = parse_workflow_str(&contents, stem)?;
+    workflow.source_path = Some(path.to_path_buf());
+    Ok(workflow)
+}
+
+/// Workflows discovered in the project and user directories. Project files
+/// override user files with the same name.
+#[derive(Debug, Clone, Default)]
+pub struct WorkflowCatalog {
+    pub workflows: Vec<WorkflowDefinition>,
+    pub parse_errors: Vec<WorkflowParseError>,
+}
+
+impl WorkflowCatalog {
+    pub fn load(project_dir: &Path, user_dir: &Path) -> Result<Self, WorkflowError> {
+        let mut workflows = BTreeMap::new();
+        let mut parse_errors = Vec::new();
+        for dir in [user_dir, project_dir] {
+            let entries = match fs::read_dir(dir) {
+                Ok(entries) => entries,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(source) => {
+                    return Err(WorkflowError::Io {
+                        path: dir.to_path_buf(),
+                        source,
+                    });
+                }
+            };
+            let mut paths: Vec<PathBuf> = entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| is_workflow_file(path))
+                .collect();
+            paths.sort();
+            for path in paths {
+                match parse_workflow_file(&path) {
+                    Ok(workflow) => {
+                        workflows.insert(workflow.name.clone(), workflow);
+                    }
+                    Err(err) => parse_errors.push(WorkflowParseError::new(path, err.to_string())),
+                }
+            }
+        }
+        Ok(Self {
+            workflows: workflows.into_values().collect(),
+            parse_errors,
+        })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&WorkflowDefinition> {
+        self.workflows
+            .iter()
+            .find(|workflow| workflow.name.eq_ignore_ascii_case(name))
+    }
+}
+
+fn is_workflow_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| matches!(ext, "yaml" | "yml"))
+        .unwrap_or(false)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StepResult {
+    pub status: StepStatus,
+    pub output: Option<String>,
+    pub error: Option<String>,
+}
+
+impl StepResult {
+    pub fn succeeded(output: Option<String>) -> Self {
+        Self {
+            status: StepStatus::Succeeded,
+            output,
+            error: None,
+        }
+    }
+
+    pub fn failed(error: impl Into<String>, output: Option<String>) -> Self {
+        Self {
+            status: StepStatus::Failed,
+            output,
+            error: Some(error.into()),
+        }
+    }
+
+    fn skipped() -> Self {
+        Self {
+            status: StepStatus::Skipped,
+            output: None,
+            error: None,
+        }
+    }
+}
+
+/// The failure an agent reports in its final message, for runs that finished
+/// without error. The last JSON object in the message that carries a verdict
+/// decides: `{"status": "failed"}` (or `"fail"`/`"error"`) fails the step,
+/// `{"status": "passed"}` (or `"succeeded"`/`"ok"`) passes it, and test results
+/// (a `TestResults`, or `{"results": TestResults}` as the tester writes them)
+/// fail it when any test did not pass.
+pub fn reported_failure(message: &str) -> Option<String> {
+    json_objects(message)
+        .into_iter()
+        .rev()
+        .filter_map(|object| serde_json::from_str::<serde_json::Value>(object).ok())
+        .find_map(|value| verdict(&value))
+        .and_then(Result::err)
+}
+
+fn verdict(value: &serde_json::Value) -> Option<Result<(), String>> {
+    if let Some(status) = value.get("status").and_then(serde_json::Value::as_str) {
+        return match status.trim().to_ascii_lowercase().as_str() {
+            "passed" | "pass" | "succeeded" | "success" | "ok" => Some(Ok(())),
+            "failed" | "fail" | "failure" | "error" => Some(Err(value
+                .get("summary")
+                .and_then(serde_json::Value::as_str)
+                .map(|summary| format!("agent reported failure: {summary}"))
+                .unwrap_or_else(|| "agent reported failure".to_string()))),
+            _ => None,
+        };
+    }
+    let results = value.get("results").unwrap_or(value);
+    let results = serde_json::from_value::<TestResults>(results.clone()).ok()?;
+    let failing = results
+        .outcomes
+        .iter()
+        .filter(|outcome| outcome.status != TestStatus::Passed)
+        .count();
+    Some(if failing == 0 {
+        Ok(())
+    } else {
+        Err(format!(
+            "{failing} of {} test(s) did not pass",
+            results.outcomes.len()
+        ))
+    })
+}
+
+/// An agent invocation the caller should run next.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedStep {
+    pub step_id: String,
+    pub agent: String,
+    pub prompt: Option<String>,
+    /// 1 on the first run, incremented each time a loop revisits the step.
+    pub attempt: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct WorkflowBatch {
+    /// Agents to run; more than one means they may run concurrently.
+    pub steps: Vec<PlannedStep>,
+    /// Steps whose `when` condition did not hold since the previous batch.
+    pub skipped: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StepRecord {
+    pub step_id: String,
+    pub agent: Option<String>,
+    pub attempt: u32,
+    #[serde(flatten)]
+    pub result: StepResult,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WorkflowReport {
+    pub workflow: String,
+    pub status: StepStatus,
+    pub steps: Vec<StepRecord>,
+    pub output: Option<String>,
+}
+
+pub struct WorkflowRun<'a> {
+    workflow: &'a WorkflowDefinition,
+    inputs: BTreeMap<String, String>,
+    cursor: usize,
+    results: HashMap<String, StepResult>,
+    attempts: HashMap<String, u32>,
+    loops_taken: HashMap<String, u32>,
+    pending: Vec<String>,
+    history: Vec<StepRecord>,
+    last_output: Option<String>,
+    halted: bool,
+}
+
+impl<'a> WorkflowRun<'a> {
+    pub fn new(
+        workflow: &'a WorkflowDefinition,
+        provided: BTreeMap<String, String>,
+    ) -> Result<Self, WorkflowError> {
+        workflow.validate()?;
+        if let Some(unknown) = provided
+            .keys()
+            .find(|key| !workflow.inputs.iter().any(|input| &input.name == *key))
+        {
+            return Err(WorkflowError::UnknownInput(unknown.clone()));
+        }
+        let mut inputs = BTreeMap::new();
+        for input in &workflow.inputs {
+            let value = provided
+                .get(&input.name)
+                .or(input.default.as_ref())
+                .ok_or_else(|| WorkflowError::MissingInput(input.name.clone()))?;
+            inputs.insert(input.name.clone(), value.clone());
+        }
+
+        Ok(Self {
+            workflow,
+            inputs,
+            cursor: 0,
+            results: HashMap::new(),
+            attempts: HashMap::new(),
+            loops_taken: HashMap::new(),
+            pending: Vec::new(),
+            history: Vec::new(),
+            last_output: None,
+            halted: false,
+        })
+    }
+
+    /// Rebuild a run from the history of an earlier one, such as the steps
+    /// saved in a [`crate::checkpoint::RunManifest`]. Recorded steps are
+    /// replayed instead of planned again, so the next batch is the first one
+    /// the earlier run did not finish.
+    pub fn resume(
+        workflow: &'a WorkflowDefinition,
+        provided: BTreeMap<String, String>,
+        history: &[StepRecord],
+    ) -> Result<Self, WorkflowError> {
+        let mut run = Self::new(workflow, provided)?;
+        // Skipped records are produced again while planning.
+        let mut replay: VecDeque<&StepRecord> = history
+            .iter()
+            .filter(|record| record.result.status != StepStatus::Skipped)
+            .collect();
+        while let Some(next) = replay.front() {
+            let Some(batch) = run.next_batch() else {
+                return Err(WorkflowError::ResumeMismatch(next.step_id.clone()));
+            };
+            for planned in &batch.steps {
+                let position = replay
+                    .iter()
+                    .take(batch.steps.len())
+                    .position(|record| record.step_id == planned.step_id)
+                    .ok_or_else(|| WorkflowError::ResumeMismatch(planned.step_id.clone()))?;
+                if let Some(record) = replay.remove(position) {
+                    run.record(&planned.step_id, record.result.clone())?;
+                }
+            }
+        }
+        Ok(run)
+    }
+
+    pub fn workflow(&self) -> &'a WorkflowDefinition {
+        self.workflow
+    }
+
+    /// Returns the next agents to run, or `None` once the workflow is done.
+    /// Every planned step must be reported through [`Self::record`] before the
+    /// following batch is produced.
+    pub fn next_batch(&mut self) -> Option<WorkflowBatch> {
+        if self.halted || !self.pending.is_empty() {
+            return None;
+        }
+
+        let workflow = self.workflow;
+        let mut skipped = Vec::new();
+        while let Some(step) = workflow.steps.get(self.cursor) {
+            if let Some(condition) = &step.when
+                && self.status_of(&condition.step) != condition.status
+            {
+                self.record_skipped(step);
+                skipped.push(step.id.clone());
+                self.cursor += 1;
+                continue;
+            }
+
+            let attempt = self.attempts.entry(step.id.clone()).or_insert(0);
+            *attempt += 1;
+            let attempt = *attempt;
+
+            let steps: Vec<PlannedStep> = match &step.agent {
+                Some(agent) => vec![PlannedStep {
+                    step_id: step.id.clone(),
+                    agent: agent.clone(),
+                    prompt: step.prompt.as_deref().map(|text| self.render(text)),
+                    attempt,
+                }],
+                None => step
+                    .parallel
+                    .iter()
+                    .map(|branch| PlannedStep {
+                        step_id: branch.id.clone(),
+                        agent: branch.agent.clone(),
+                        prompt: branch.prompt.as_deref().map(|text| self.render(text)),
+                        attempt,
+                    })
+                    .collect(),
+            };
+            self.pending = steps
+                .iter()
+                .map(|planned| planned.step_id.clone())
+                .collect();
+            return Some(WorkflowBatch { steps, skipped });
+        }
+
+        if skipped.is_empty() {
+            None
+        } else {
+            Some(WorkflowBatch {
+                steps: Vec::new(),
+                skipped,
+            })
+        }
+    }
+
+    /// Report the result of a step returned by [`Self::next_batch`].
+    pub fn record(&mut self, step_id: &str, result: StepResult) -> Result<(), WorkflowError> {
+        let Some(position) = self.pending.iter().position(|id| id == step_id) else {
+            return Err(WorkflowError::UnexpectedResult(step_id.to_string()));
+        };
+        self.pending.remove(position);
+
+        let workflow = self.workflow;
+        let Some(step) = workflow.steps.get(self.cursor) else {
+            return Err(WorkflowError::UnexpectedResult(step_id.to_string()));
+        };
+        let agent = match &step.agent {
+            Some(agent) => Some(agent.clone()),
+            None => step
+                .parallel
+                .iter()
+                .find(|branch| branch.id == step_id)
+                .map(|branch| branch.agent.clone()),
+        };
+        self.history.push(StepRecord {
+            step_id: step_id.to_string(),
+            agent,
+            attempt: self.attempts.get(&step.id).copied().unwrap_or(1),
+            result: result.clone(),
+        });
+        self.results.insert(step_id.to_string(), result);
+
+        if self.pending.is_empty() {
+            self.finish_step(step);
+        }
+        Ok(())
+    }
+
+    /// True once a failed step stopped the workflow.
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.pending.is_empty() && (self.halted || self.cursor >= self.workflow.steps.len())
+    }
+
+    pub fn history(&self) -> &[StepRecord] {
+        &self.history
+    }
+
+    pub fn into_report(self) -> WorkflowReport {
+        let status = if self.halted {
+            StepStatus::Failed
+        } else {
+            StepStatus::Succeeded
+        };
+        let output = match self.workflow.output.as_deref() {
+            Some(template) => Some(self.render(template)),
+            None => self.last_output.clone(),
+        };
+        WorkflowReport {
+            workflow: self.workflow.name.clone(),
+            status,
+            steps: self.history,
+            output,
+        }
+    }
+
+    fn finish_step(&mut self, step: &WorkflowStep) {
+        if !step.parallel.is_empty() {
+            let branches: Vec<(&str, Option<&StepResult>)> = step
+                .parallel
+                .iter()
+                .map(|branch| (branch.id.as_str(), self.results.get(&branch.id)))
+                .collect();
+            let failed: Vec<&str> = branches
+                .iter()
+                .filter(|(_, result)| {
+                    result.is_none_or(|result| result.status == StepStatus::Failed)
+                })
+                .map(|(id, _)| *id)
+                .collect();
+            let output = branches
+                .iter()
+                .map(|(id, result)| {
+                    let text = result
+                        .and_then(|result| result.output.as_deref())
+                        .unwrap_or_default();
+                    format!("### {id}\n{text}")
+                })
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            let aggregate = if failed.is_empty() {
+                StepResult::succeeded(Some(output))
+            } else {
+                StepResult::failed(
+                    format!("failed branches: {}", failed.join(", ")),
+                    Some(output),
+                )
+            };
+            self.results.insert(step.id.clone(), aggregate);
+        }
+
+        let result = self.results.get(&step.id);
+        self.last_output = result.and_then(|result| result.output.clone());
+        if result.is_some_and(|result| result.status != StepStatus::Failed) {
+            self.cursor += 1;
+            return;
+        }
+
+        if let Some(loop_back) = &step.on_failure {
+            let taken = self.loops_taken.entry(step.id.clone()).or_insert(0);
+            if *taken < loop_back.max_loops
+                && let Some(target) = self
+                    .workflow
+                    .steps
+                    .iter()
+                    .position(|candidate| candidate.id == loop_back.goto)
+            {
+                *taken += 1;
+                self.cursor = target;
+                return;
+            }
+        }
+
+        if step.continue_on_failure {
+            self.cursor += 1;
+        } else {
+            self.halted = true;
+        }
+    }
+
+    fn record_skipped(&mut self, step: &WorkflowStep) {
+        let ids = std::iter::once(step.id.as_str())
+            .chain(step.parallel.iter().map(|branch| branch.id.as_str()));
+        for id in ids {
+            self.results.insert(id.to_string(), StepResult::skipped());
+        }
+        self.history.push(StepRecord {
+            step_id: step.id.clone(),
+            agent: step.agent.clone(),
+            attempt: self.attempts.get(&step.id).copied().unwrap_or(0),
+            result: StepResult::skipped(),
+        });
+    }
+
+    fn status_of(&self, step_id: &str) -> StepStatus {
+        self.results
+            .get(step_id)
+            .map(|result| result.status)
+            .unwrap_or(StepStatus::Skipped)
+    }
+
+    fn render(&self, template: &str) -> String {
+        TEMPLATE_RE
+            .replace_all(template, |caps: &regex_lite::Captures<'_>| {
+                let raw = caps.get(1).map(|m| m.as_str()).unwrap_or_default();
+                match parse_reference(raw) {
+                    Some(TemplateRef::Input(name)) => {
+                        self.inputs.get(name).cloned().unwrap_or_default()
+                    }
+                    Some(TemplateRef::StepOutput(id)) => self
+                        .results
+                        .get(id)
+                        .and_then(|result| result.output.clone())
+                        .unwrap_or_default(),
+                    Some(TemplateRef::StepOutcome(id)) => self.status_of(id).as_str().to_string(),
+                    None => String::new(),
+                }
+            })
+            .into_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::TestOutcome;
+    use crate::pipeline::TestPlan;
+    use crate::pipeline::TestTask;
+    use pretty_assertions::assert_eq;
+
+    const FIX_LOOP: &str = r#"
+description: Triage, fix, and test until green
+inputs:
+  - name: issue
+steps:
+  - id: triage
+    agent: triage
+    prompt: "Triage: {{ inputs.issue }}"
+  - id: fix
+    agent: code-writer
+    prompt: "Plan: {{ steps.triage.output }}\nLast test run: {{ steps.test.output }}"
+  - id: test
+    agent: tester
+    on_failure:
+      goto: fix
+      max_loops: 3
+"#;
+
+    const REVIEW_FANOUT: &str = r#"
+name: review-fanout
+steps:
+  - id: plan
+    agent: planner
+  - id: reviews
+    parallel:
+      - id: security
+        agent: security-reviewer
+        prompt: "Review {{ steps.plan.output }}"
+      - id: perf
+        agent: perf-reviewer
+    continue_on_failure: true
+  - id: escalate
+    agent: lead
+    when: steps.reviews.failed
+  - id: summarize
+    agent: summarizer
+    when: steps.reviews.succeeded
+output: "{{ steps.reviews.outcome }}"
+"#;
+
+    fn inputs(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    fn run_batch(run: &mut WorkflowRun<'_>, result: impl Fn(&PlannedStep) -> StepResult) {
+        let batch = run.next_batch().expect("batch");
+        for planned in &batch.steps {
+            run.record(&planned.step_id, result(planned))
+                .expect("record");
+        }
+    }
+
+    #[test]
+    fn loops_back_until_tests_pass() {
+        let workflow = parse_workflow_str(FIX_LOOP, Some("fix-loop")).expect("parse");
+        assert_eq!(workflow.name, "fix-loop");
+        let mut run =
+            WorkflowRun::new(&workflow, inputs(&[("issue", "crash on start")])).expect("run");
+
+        let batch = run.next_batch().expect("triage");
+        assert_eq!(
+            batch.steps[0].prompt.as_deref(),
+            Some("Triage: crash on start")
+        );
+        run.record(
+            "triage",
+            StepResult::succeeded(Some("null config".to_string())),
+        )
+        .expect("record");
+
+        run_batch(&mut run, |_| StepResult::succeeded(None));
+        run_batch(&mut run, |_| {
+            StepResult::failed("tests failed", Some("2 failures".to_string()))
+        });
+
+        let retry = run.next_batch().expect("fix again");
+        assert_eq!(retry.steps[0].step_id, "fix");
+        assert_eq!(retry.steps[0].attempt, 2);
+        assert_eq!(
+            retry.steps[0].prompt.as_deref(),
+            Some("Plan: null config\nLast test run: 2 failures")
+        );
+        run.record("fix", StepResult::succeeded(None))
+            .expect("record");
+        run_batch(&mut run, |_| {
+            StepResult::succeeded(Some("all green".to_string()))
+        });
+
+        assert!(run.next_batch().is_none());
+        assert!(run.is_finished());
+        let report = run.into_report();
+        assert_eq!(report.status, StepStatus::Succeeded);
+        assert_eq!(report.output.as_deref(), Some("all green"));
+        assert_eq!(report.steps.len(), 5);
+    }
+
+    #[test]
+    fn stops_after_loop_budget_is_spent() {
+        let workflow = parse_workflow_str(FIX_LOOP, Some("fix-loop")).expect("parse");
+        let mut run = WorkflowRun::new(&workflow, inputs(&[("issue", "flaky")])).expect("run");
+        run_batch(&mut run, |_| StepResult::succeeded(None));
+        for _ in 0..4 {
+            run_batch(&mut run, |_| StepResult::succeeded(None));
+            run_batch(&mut run, |_| StepResult::failed("red", None));
+        }
+        assert!(run.next_batch().is_none());
+        assert_eq!(run.into_report().status, StepStatus::Failed);
+    }
+
+    #[test]
+    fn resume_replays_recorded_steps() {
+        let workflow = parse_workflow_str(FIX_LOOP, Some("fix-loop")).expect("parse");
+        let mut first = WorkflowRun::new(&workflow, inputs(&[("issue", "crash")])).expect("run");
+        run_batch(&mut first, |_| {
+            StepResult::succeeded(Some("null config".to_string()))
+        });
+        run_batch(&mut first, |_| StepResult::succeeded(None));
+        run_batch(&mut first, |_| {
+            StepResult::failed("tests failed", Some("2 failures".to_string()))
+        });
+
+        let mut resumed =
+            WorkflowRun::resume(&workflow, inputs(&[("issue", "crash")]), first.history())
+                .expect("resume");
+        assert_eq!(resumed.history(), first.history());
+        let batch = resumed.next_batch().expect("fix again");
+        assert_eq!(batch.steps[0].step_id, "fix");
+        assert_eq!(batch.steps[0].attempt, 2);
+        assert_eq!(
+            batch.steps[0].prompt.as_deref(),
+            Some("Plan: null config\nLast test run: 2 failures")
+        );
+
+        let fanout = parse_workflow_str(REVIEW_FANOUT, None).expect("parse");
+        let mut first = WorkflowRun::new(&fanout, BTreeMap::new()).expect("run");
+        run_batch(&mut first, |_| StepResult::succeeded(None));
+        run_batch(&mut first, |planned| match planned.step_id.as_str() {
+            "perf" => StepResult::failed("slow path", None),
+            _ => StepResult::succeeded(None),
+        });
+        let mut resumed =
+            WorkflowRun::resume(&fanout, BTreeMap::new(), first.history()).expect("resume");
+        let batch = resumed.next_batch().expect("escalate");
+        assert_eq!(batch.steps[0].step_id, "escalate");
+
+        let err = WorkflowRun::resume(&workflow, inputs(&[("issue", "crash")]), first.history())
+            .err()
+            .expect("history from another workflow");
+        assert!(matches!(err, WorkflowError::ResumeMismatch(step) if step == "triage"));
+    }
+
+    #[test]
+    fn parallel_branches_gate_later_steps() {
+        let workflow = parse_workflow_str(REVIEW_FANOUT, None).expect("parse");
+        let mut run = WorkflowRun::new(&workflow, BTreeMap::new()).expect("run");
+        run_batch(&mut run, |_| {
+            StepResult::succeeded(Some("plan.md".to_string()))
+        });
+
+        let batch = run.next_batch().expect("reviews");
+        let agents: Vec<&str> = batch.steps.iter().map(|s| s.agent.as_str()).collect();
+        assert_eq!(agents, vec!["security-reviewer", "perf-reviewer"]);
+        assert_eq!(batch.steps[0].prompt.as_deref(), Some("Review plan.md"));
+        run.record("perf", StepResult::failed("slow path", None))
+            .expect("record perf");
+        run.record("security", StepResult::succeeded(None))
+            .expect("record security");
+
+        let batch = run.next_batch().expect("escalate");
+        assert_eq!(batch.steps[0].step_id, "escalate");
+        run.record("escalate", StepResult::succeeded(None))
+            .expect("record");
+
+        let tail = run.next_batch().expect("skipped summarize");
+        assert_eq!(tail.steps, Vec::new());
+        assert_eq!(tail.skipped, vec!["summarize".to_string()]);
+        assert!(run.next_batch().is_none());
+
+        let report = run.into_report();
+        assert_eq!(report.status, StepStatus::Succeeded);
+        assert_eq!(report.output.as_deref(), Some("failed"));
+    }
+
+    #[test]
+    fn rejects_invalid_definitions() {
+        let forward_loop = r#"
+name: broken
+steps:
+  - id: test
+    agent: tester
+    on_failure: { goto: fix, max_loops: 2 }
+  - id: fix
+    agent: code-writer
+"#;
+        let err = parse_workflow_str(forward_loop, None).expect_err("forward loop");
+        assert!(err.to_string().contains("loop back"), "{err}");
+
+        let unknown_ref = r#"
+name: broken
+steps:
+  - id: fix
+    agent: code-writer
+    prompt: "{{ steps.triage.output }}"
+"#;
+        let err = parse_workflow_str(unknown_ref, None).expect_err("unknown ref");
+        assert!(err.to_string().contains("steps.triage.output"), "{err}");
+
+        let bad_condition = r#"
+name: broken
+steps:
+  - id: fix
+    agent: code-writer
+    when: fix passed
+"#;
+        assert!(parse_workflow_str(bad_condition, None).is_err());
+    }
+
+    #[test]
+    fn reads_failures_reported_in_the_final_message() {
+        assert_eq!(reported_failure("All good, nothing to report."), None);
+        assert_eq!(
+            reported_failure(
+                "Ran `cargo test`.\n```json\n{\"status\": \"failed\", \"summary\": \"2 tests fail\"}\n```"
+            ),
+            Some("agent reported failure: 2 tests fail".to_string())
+        );
+        // The last verdict wins.
+        assert_eq!(
+            reported_failure(
+                "{\"status\": \"failed\"} then after the fix {\"status\": \"passed\"}"
+            ),
+            None
+        );
+
+        let plan = TestPlan::new(
+            "plan",
+            vec![
+                TestTask::new("unit", "cargo test", vec![]),
+                TestTask::new("lint", "cargo clippy", vec![]),
+            ],
+        );
+        let results = TestResults::new(
+            plan,
+            vec![
+                TestOutcome::new("unit", TestStatus::Passed, None),
+                TestOutcome::new("lint", TestStatus::Failed, None),
+            ],
+        );
+        let message = serde_json::json!({ "results": results }).to_string();
+        assert_eq!(
+            reported_failure(&message),
+            Some("1 of 2 test(s) did not pass".to_string())
+        );
+    }
+
+    #[test]
+    fn requires_declared_inputs() {
+        let workflow = parse_workflow_str(FIX_LOOP, Some("fix-loop")).expect("parse");
+        assert!(matches!(
+            WorkflowRun::new(&workflow, BTreeMap::new()),
+            Err(WorkflowError::MissingInput(name)) if name == "issue"
+        ));
+        assert!(matches!(
+            WorkflowRun::new(&workflow, inputs(&[("issue", "x"), ("extra", "y")])),
+            Err(WorkflowError::UnknownInput(name)) if name == "extra"
+        ));
+    }
+
+    #[test]
+    fn catalog_prefers_project_workflows() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let project_dir = temp.path().join("project");
+        let user_dir = temp.path().join("user");
+        fs::create_dir_all(&project_dir).expect("project dir");
+        fs::create_dir_all(&user_dir).expect("user dir");
+        fs::write(
+            user_dir.join("review.yaml"),
+            "description: user\nsteps:\n  - id: one\n    agent: reviewer\n",
+        )
+        .expect("write user");
+        fs::write(
+            project_dir.join("review.yml"),
+            "description: project\nsteps:\n  - id: one\n    agent: reviewer\n",
+        )
+        .expect("write project");
+        fs::write(project_dir.join("broken.yaml"), "steps: []\n").expect("write broken");
+
+        let catalog = WorkflowCatalog::load(&project_dir, &user_dir).expect("load");
+        assert_eq!(catalog.workflows.len(), 1);
+        let review = catalog.get("review").expect("review workflow");
+        assert_eq!(review.description.as_deref(), Some("project"));
+        assert_eq!(catalog.parse_errors.len(), 1);
+    }
+}
@@ -14,6 +14,7 @@ use codex_core::ConversationManager;
 use codex_core::config::Config;
 use codex_core::config::persist_model_selection;
 use codex_core::model_family::find_family_for_model;
+use codex_core::protocol::EventMsg;
 use codex_core::protocol::TokenUsage;
 use codex_core::protocol_config_types::ReasoningEffort as ReasoningEffortConfig;
 use codex_protocol::mcp_protocol::ConversationId;
@@ -274,7 +275,19 @@ impl App {
                 self.chat_widget.on_commit_tick();
             }
             AppEvent::CodexEvent(event) => {
+                let subagent_event = matches!(
+                    event.msg,
+                    EventMsg::SubAgentStarted(_)
+                        | EventMsg::SubAgentMessage(_)
+                        | EventMsg::SubAgentActivity(_)
+                        | EventMsg::SubAgentHandoff(_)
+                        | EventMsg::SubAgentCompleted(_)
+                );
                 self.chat_widget.handle_codex_event(event);
+                if subagent_event && let Some(Overlay::Subagents(o)) = &mut self.overlay {
+                    o.set_runs(self.chat_widget.subagent_transcripts());
+                    tui.frame_requester().schedule_frame();
+                }
             }
             AppEvent::ConversationHistory(ev) => {
                 self.on_conversation_history_for_backtrack(tui, ev).await?;
@@ -364,6 +377,9 @@ impl App {
             AppEvent::OpenReviewCustomPrompt => {
                 self.chat_widget.show_review_custom_prompt();
             }
+            AppEvent::UseSubagent(agent_name) => {
+                self.chat_widget.prefill_use_command(&agent_name);
+            }
         }
         Ok(true)
     }
@@ -390,6 +406,18 @@ impl App {
                 self.overlay = Some(Overlay::new_transcript(self.transcript_cells.clone()));
                 tui.frame_requester().schedule_frame();
             }
+            KeyEvent {
+                code: KeyCode::Char('g'),
+                modifiers: crossterm::event::KeyModifiers::CONTROL,
+                kind: KeyEventKind::Press,
+                ..
+            } => {
+                let _ = tui.enter_alt_screen();
+                self.overlay = Some(Overlay::new_subagents(
+                    self.chat_widget.subagent_transcripts(),
+                ));
+                tui.frame_requester().schedule_frame();
+            }
             // Esc primes/advances backtracking only in normal (not working) mode
             // with an empty composer. In any other state, forward Esc so the
             // active UI (e.g. status indicator, modals, popups) handles it.
@@ -69,6 +69,7 @@ impl App {
             kind: KeyEventKind::Press | KeyEventKind::Repeat,
             ..
         }) = event
+            && !matches!(self.overlay, Some(Overlay::Subagents(_)))
         {
             // First Esc in transcript overlay: begin backtrack preview at latest user message.
             self.begin_overlay_backtrack_preview(tui);
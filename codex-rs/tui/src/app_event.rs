@@ -76,4 +76,7 @@ pub(crate) enum AppEvent {
 
     /// Open the custom prompt option from the review popup.
     OpenReviewCustomPrompt,
+
+    /// Prefill `/use <agent>` after picking an agent from the `/use` popup.
+    UseSubagent(String),
 }
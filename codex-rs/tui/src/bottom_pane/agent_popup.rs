@@ -0,0 +1,156 @@
+use codex_common::fuzzy_match::fuzzy_match;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::WidgetRef;
+
+use super::popup_consts::MAX_POPUP_ROWS;
+use super::scroll_state::ScrollState;
+use super::selection_popup_common::GenericDisplayRow;
+use super::selection_popup_common::render_rows;
+
+/// A registered subagent that can be mentioned as `@name` in the composer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AgentMention {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+/// Popup listing the agents whose names fuzzy-match the `@token` under the
+/// cursor.
+pub(crate) struct AgentPopup {
+    agents: Vec<AgentMention>,
+    query: String,
+    state: ScrollState,
+}
+
+impl AgentPopup {
+    pub(crate) fn new(agents: Vec<AgentMention>) -> Self {
+        let mut state = ScrollState::new();
+        state.clamp_selection(agents.len());
+        Self {
+            agents,
+            query: String::new(),
+            state,
+        }
+    }
+
+    /// Whether any of `agents` matches `query`.
+    pub(crate) fn any_match(agents: &[AgentMention], query: &str) -> bool {
+        agents
+            .iter()
+            .any(|agent| fuzzy_match(&agent.name, query).is_some())
+    }
+
+    pub(crate) fn set_query(&mut self, query: &str) {
+        if query == self.query {
+            return;
+        }
+        self.query = query.to_string();
+        let len = self.filtered().len();
+        self.state.reset();
+        self.state.clamp_selection(len);
+        self.state.ensure_visible(len, len.min(MAX_POPUP_ROWS));
+    }
+
+    /// Matching agents with the matched name positions, best match first.
+    fn filtered(&self) -> Vec<(&AgentMention, Vec<usize>)> {
+        let mut out: Vec<(&AgentMention, Vec<usize>, i32)> = self
+            .agents
+            .iter()
+            .filter_map(|agent| {
+                fuzzy_match(&agent.name, &self.query)
+                    .map(|(indices, score)| (agent, indices, score))
+            })
+            .collect();
+        out.sort_by(|a, b| a.2.cmp(&b.2).then_with(|| a.0.name.cmp(&b.0.name)));
+        out.into_iter()
+            .map(|(agent, indices, _)| (agent, indices))
+            .collect()
+    }
+
+    pub(crate) fn move_up(&mut self) {
+        let len = self.filtered().len();
+        self.state.move_up_wrap(len);
+        self.state.ensure_visible(len, len.min(MAX_POPUP_ROWS));
+    }
+
+    pub(crate) fn move_down(&mut self) {
+        let len = self.filtered().len();
+        self.state.move_down_wrap(len);
+        self.state.ensure_visible(len, len.min(MAX_POPUP_ROWS));
+    }
+
+    pub(crate) fn selected_agent(&self) -> Option<&str> {
+        let filtered = self.filtered();
+        self.state
+            .selected_idx
+            .and_then(|idx| filtered.get(idx))
+            .map(|(agent, _)| agent.name.as_str())
+    }
+
+    pub(crate) fn calculate_required_height(&self) -> u16 {
+        self.filtered().len().clamp(1, MAX_POPUP_ROWS) as u16
+    }
+}
+
+impl WidgetRef for &AgentPopup {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let rows: Vec<GenericDisplayRow> = self
+            .filtered()
+            .into_iter()
+            .map(|(agent, indices)| GenericDisplayRow {
+                name: format!("@{}", agent.name),
+                // Shift past the leading `@`.
+                match_indices: Some(indices.into_iter().map(|i| i + 1).collect()),
+                is_current: false,
+                description: agent.description.clone(),
+            })
+            .collect();
+        render_rows(
+            area,
+            buf,
+            &rows,
+            &self.state,
+            MAX_POPUP_ROWS,
+            "no matching agents",
+            false,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn agent(name: &str) -> AgentMention {
+        AgentMention {
+            name: name.to_string(),
+            description: None,
+        }
+    }
+
+    #[test]
+    fn filters_and_ranks_agents_by_fuzzy_match() {
+        let mut popup = AgentPopup::new(vec![
+            agent("code-writer"),
+            agent("reviewer"),
+            agent("release-verifier"),
+        ]);
+        popup.set_query("rev");
+        let names: Vec<&str> = popup
+            .filtered()
+            .into_iter()
+            .map(|(agent, _)| agent.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["reviewer", "release-verifier"]);
+        assert_eq!(popup.selected_agent(), Some("reviewer"));
+
+        popup.move_down();
+        assert_eq!(popup.selected_agent(), Some("release-verifier"));
+
+        popup.set_query("");
+        assert_eq!(popup.calculate_required_height(), 3);
+        assert!(!AgentPopup::any_match(&popup.agents, "xyz"));
+    }
+}
@@ -15,6 +15,8 @@ use ratatui::widgets::Block;
 use ratatui::widgets::StatefulWidgetRef;
 use ratatui::widgets::WidgetRef;
 
+use super::agent_popup::AgentMention;
+use super::agent_popup::AgentPopup;
 use super::chat_composer_history::ChatComposerHistory;
 use super::command_popup::CommandItem;
 use super::command_popup::CommandPopup;
@@ -72,6 +74,8 @@ pub(crate) struct ChatComposer {
     esc_backtrack_hint: bool,
     use_shift_enter_hint: bool,
     dismissed_file_popup_token: Option<String>,
+    /// `@token` for which the agent popup was dismissed in favour of files.
+    dismissed_agent_popup_token: Option<String>,
     current_file_query: Option<String>,
     pending_pastes: Vec<(String, String)>,
     token_usage_info: Option<TokenUsageInfo>,
@@ -84,6 +88,7 @@ pub(crate) struct ChatComposer {
     // When true, disables paste-burst logic and inserts characters immediately.
     disable_paste_burst: bool,
     custom_prompts: Vec<CustomPrompt>,
+    agent_mentions: Vec<AgentMention>,
 }
 
 /// Popup state – at most one can be visible at any time.
@@ -91,6 +96,7 @@ enum ActivePopup {
     None,
     Command(CommandPopup),
     File(FileSearchPopup),
+    Agent(AgentPopup),
 }
 
 const FOOTER_HINT_HEIGHT: u16 = 1;
@@ -115,6 +121,7 @@ impl ChatComposer {
             esc_backtrack_hint: false,
             use_shift_enter_hint,
             dismissed_file_popup_token: None,
+            dismissed_agent_popup_token: None,
             current_file_query: None,
             pending_pastes: Vec::new(),
             token_usage_info: None,
@@ -125,6 +132,7 @@ impl ChatComposer {
             paste_burst: PasteBurst::default(),
             disable_paste_burst: false,
             custom_prompts: Vec::new(),
+            agent_mentions: Vec::new(),
         };
         // Apply configuration via the setter to keep side-effects centralized.
         this.set_disable_paste_burst(disable_paste_burst);
@@ -139,6 +147,7 @@ impl ChatComposer {
                 ActivePopup::None => FOOTER_HINT_HEIGHT,
                 ActivePopup::Command(c) => c.calculate_required_height(width),
                 ActivePopup::File(c) => c.calculate_required_height(),
+                ActivePopup::Agent(c) => c.calculate_required_height(),
             }
     }
 
@@ -148,6 +157,7 @@ impl ChatComposer {
                 Constraint::Max(popup.calculate_required_height(area.width))
             }
             ActivePopup::File(popup) => Constraint::Max(popup.calculate_required_height()),
+            ActivePopup::Agent(popup) => Constraint::Max(popup.calculate_required_height()),
             ActivePopup::None => Constraint::Max(FOOTER_HINT_HEIGHT),
         };
         let mut area = area;
@@ -333,6 +343,7 @@ impl ChatComposer {
         let result = match &mut self.active_popup {
             ActivePopup::Command(_) => self.handle_key_event_with_slash_popup(key_event),
             ActivePopup::File(_) => self.handle_key_event_with_file_popup(key_event),
+            ActivePopup::Agent(_) => self.handle_key_event_with_agent_popup(key_event),
             ActivePopup::None => self.handle_key_event_without_popup(key_event),
         };
 
@@ -579,6 +590,53 @@ impl ChatComposer {
         }
     }
 
+    fn handle_key_event_with_agent_popup(&mut self, key_event: KeyEvent) -> (InputResult, bool) {
+        let ActivePopup::Agent(popup) = &mut self.active_popup else {
+            unreachable!();
+        };
+
+        match key_event {
+            KeyEvent {
+                code: KeyCode::Up, ..
+            } => {
+                popup.move_up();
+                (InputResult::None, true)
+            }
+            KeyEvent {
+                code: KeyCode::Down,
+                ..
+            } => {
+                popup.move_down();
+                (InputResult::None, true)
+            }
+            KeyEvent {
+                code: KeyCode::Esc, ..
+            } => {
+                // Fall back to file search for this token.
+                self.dismissed_agent_popup_token = Self::current_at_token(&self.textarea);
+                self.active_popup = ActivePopup::None;
+                self.sync_file_search_popup();
+                (InputResult::None, true)
+            }
+            KeyEvent {
+                code: KeyCode::Tab, ..
+            }
+            | KeyEvent {
+                code: KeyCode::Enter,
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => {
+                if let Some(name) = popup.selected_agent() {
+                    let mention = format!("@{name}");
+                    self.insert_selected_path(&mention);
+                }
+                self.active_popup = ActivePopup::None;
+                (InputResult::None, true)
+            }
+            input => self.handle_input_basic(input),
+        }
+    }
+
     fn is_image_path(path: &str) -> bool {
         let lower = path.to_ascii_lowercase();
         lower.ends_with(".png") || lower.ends_with(".jpg") || lower.ends_with(".jpeg")
@@ -1176,6 +1234,11 @@ impl ChatComposer {
         }
     }
 
+    /// Agents offered when the user types `@name`.
+    pub(crate) fn set_agent_mentions(&mut self, agents: Vec<AgentMention>) {
+        self.agent_mentions = agents;
+    }
+
     /// Synchronize `self.file_search_popup` with the current text in the textarea.
     /// Note this is only called when self.active_popup is NOT Command.
     fn sync_file_search_popup(&mut self) {
@@ -1185,10 +1248,27 @@ impl ChatComposer {
             None => {
                 self.active_popup = ActivePopup::None;
                 self.dismissed_file_popup_token = None;
+                self.dismissed_agent_popup_token = None;
                 return;
             }
         };
 
+        // Agent names take precedence over files until the popup is dismissed.
+        if self.dismissed_agent_popup_token.as_ref() != Some(&query)
+            && AgentPopup::any_match(&self.agent_mentions, &query)
+        {
+            match &mut self.active_popup {
+                ActivePopup::Agent(popup) => popup.set_query(&query),
+                _ => {
+                    let mut popup = AgentPopup::new(self.agent_mentions.clone());
+                    popup.set_query(&query);
+                    self.active_popup = ActivePopup::Agent(popup);
+                }
+            }
+            self.dismissed_agent_popup_token = None;
+            return;
+        }
+
         // If user dismissed popup for this exact query, don't reopen until text changes.
         if self.dismissed_file_popup_token.as_ref() == Some(&query) {
             return;
@@ -1251,6 +1331,9 @@ impl WidgetRef for ChatComposer {
             ActivePopup::File(popup) => {
                 popup.render_ref(popup_rect, buf);
             }
+            ActivePopup::Agent(popup) => {
+                popup.render_ref(popup_rect, buf);
+            }
             ActivePopup::None => {
                 let mut hint_rect = popup_rect;
                 hint_rect.x += 2;
@@ -1853,6 +1936,49 @@ mod tests {
         assert_eq!(composer.textarea.text(), "@");
     }
 
+    #[test]
+    fn at_agent_mention_completes_from_agent_popup() {
+        use crossterm::event::KeyCode;
+        use crossterm::event::KeyEvent;
+        use crossterm::event::KeyModifiers;
+
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+        composer.set_agent_mentions(vec![
+            AgentMention {
+                name: "reviewer".to_string(),
+                description: Some("Reviews diffs".to_string()),
+            },
+            AgentMention {
+                name: "tester".to_string(),
+                description: None,
+            },
+        ]);
+
+        type_chars_humanlike(&mut composer, &['@', 'r', 'v']);
+        assert!(matches!(composer.active_popup, ActivePopup::Agent(_)));
+        let (result, _) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
+        assert_eq!(result, InputResult::None);
+        assert_eq!(composer.textarea.text(), "@reviewer ");
+
+        // Esc falls back to file search for the same token.
+        type_chars_humanlike(&mut composer, &['@', 't']);
+        assert!(matches!(composer.active_popup, ActivePopup::Agent(_)));
+        composer.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(matches!(composer.active_popup, ActivePopup::File(_)));
+        let searched = std::iter::from_fn(|| rx.try_recv().ok())
+            .any(|ev| matches!(ev, AppEvent::StartFileSearch(query) if query == "t"));
+        assert!(searched, "expected a file search for the dismissed token");
+    }
+
     #[test]
     fn test_multiple_pastes_submission() {
         use crossterm::event::KeyCode;
@@ -11,6 +11,7 @@ use ratatui::widgets::Widget;
 use textwrap::wrap;
 
 use crate::app_event_sender::AppEventSender;
+use codex_common::fuzzy_match::fuzzy_match;
 
 use super::CancellationEvent;
 use super::bottom_pane_view::BottomPaneView;
@@ -45,6 +46,9 @@ pub(crate) struct SelectionViewParams {
     pub footer_hint: Option<String>,
     pub items: Vec<SelectionItem>,
     pub is_searchable: bool,
+    /// Match the search query as a subsequence of each item's name (falling
+    /// back to `search_value`) and rank items by match quality.
+    pub fuzzy_search: bool,
     pub search_placeholder: Option<String>,
     pub header: Vec<HeaderLine>,
 }
@@ -58,9 +62,12 @@ pub(crate) struct ListSelectionView {
     complete: bool,
     app_event_tx: AppEventSender,
     is_searchable: bool,
+    fuzzy_search: bool,
     search_query: String,
     search_placeholder: Option<String>,
     filtered_indices: Vec<usize>,
+    /// Name character positions matched by a fuzzy query, per visible row.
+    match_indices: Vec<Option<Vec<usize>>>,
     last_selected_actual_idx: Option<usize>,
     header: Vec<HeaderLine>,
 }
@@ -85,6 +92,7 @@ impl ListSelectionView {
             complete: false,
             app_event_tx,
             is_searchable: params.is_searchable,
+            fuzzy_search: params.fuzzy_search,
             search_query: String::new(),
             search_placeholder: if params.is_searchable {
                 params.search_placeholder
@@ -92,6 +100,7 @@ impl ListSelectionView {
                 None
             },
             filtered_indices: Vec::new(),
+            match_indices: Vec::new(),
             last_selected_actual_idx: None,
             header: params.header,
         };
@@ -118,7 +127,27 @@ impl ListSelectionView {
                     .flatten()
             });
 
-        if self.is_searchable && !self.search_query.is_empty() {
+        self.match_indices.clear();
+        if self.is_searchable && self.fuzzy_search && !self.search_query.is_empty() {
+            let mut matches: Vec<(usize, Option<Vec<usize>>, i32)> = self
+                .items
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, item)| {
+                    if let Some((indices, score)) = fuzzy_match(&item.name, &self.search_query) {
+                        return Some((idx, Some(indices), score));
+                    }
+                    let search_value = item.search_value.as_deref()?;
+                    fuzzy_match(search_value, &self.search_query)
+                        .map(|(_, score)| (idx, None, score))
+                })
+                .collect();
+            matches.sort_by_key(|(idx, indices, score)| (indices.is_none(), *score, *idx));
+            (self.filtered_indices, self.match_indices) = matches
+                .into_iter()
+                .map(|(idx, indices, _)| (idx, indices))
+                .unzip();
+        } else if self.is_searchable && !self.search_query.is_empty() {
             let query_lower = self.search_query.to_lowercase();
             self.filtered_indices = self
                 .items
@@ -179,10 +208,20 @@ impl ListSelectionView {
                         item.name.clone()
                     };
                     let n = visible_idx + 1;
-                    let display_name = format!("{prefix} {n}. {name_with_marker}");
+                    let row_prefix = format!("{prefix} {n}. ");
+                    let match_indices =
+                        self.match_indices
+                            .get(visible_idx)
+                            .cloned()
+                            .flatten()
+                            .map(|indices| {
+                                let offset = row_prefix.chars().count();
+                                indices.into_iter().map(|i| i + offset).collect()
+                            });
+                    let display_name = format!("{row_prefix}{name_with_marker}");
                     GenericDisplayRow {
                         name: display_name,
-                        match_indices: None,
+                        match_indices,
                         is_current: item.is_current,
                         description: item.description.clone(),
                     }
@@ -581,4 +620,41 @@ mod tests {
         let lines = render_lines(&view);
         assert!(lines.contains("▌ filters"));
     }
+
+    #[test]
+    fn fuzzy_search_ranks_by_match_quality() {
+        let (tx_raw, _rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx_raw);
+        let item = |name: &str, search_value: Option<&str>| SelectionItem {
+            name: name.to_string(),
+            description: None,
+            is_current: false,
+            actions: vec![],
+            dismiss_on_select: true,
+            search_value: search_value.map(str::to_string),
+        };
+        let mut view = ListSelectionView::new(
+            SelectionViewParams {
+                title: "Agents".to_string(),
+                items: vec![
+                    item("code-writer", Some("code-writer writes rust")),
+                    item("reviewer", None),
+                    item("release-verifier", None),
+                    item("triage", Some("triage sorts issues")),
+                ],
+                is_searchable: true,
+                fuzzy_search: true,
+                ..Default::default()
+            },
+            tx,
+        );
+
+        view.set_search_query("rev".to_string());
+        assert_eq!(view.filtered_indices, vec![1, 2]);
+        assert_eq!(view.build_rows()[0].match_indices, Some(vec![5, 6, 7]));
+
+        view.set_search_query("issues".to_string());
+        assert_eq!(view.filtered_indices, vec![3]);
+        assert_eq!(view.build_rows()[0].match_indices, None);
+    }
 }
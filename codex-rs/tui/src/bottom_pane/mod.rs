@@ -15,6 +15,8 @@ use ratatui::layout::Rect;
 use ratatui::widgets::WidgetRef;
 use std::time::Duration;
 
+mod agent_popup;
+pub(crate) use agent_popup::AgentMention;
 mod approval_overlay;
 pub(crate) use approval_overlay::ApprovalOverlay;
 pub(crate) use approval_overlay::ApprovalRequest;
@@ -376,6 +378,11 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    /// Update the agents offered by the composer's `@agent` popup.
+    pub(crate) fn set_agent_mentions(&mut self, agents: Vec<AgentMention>) {
+        self.composer.set_agent_mentions(agents);
+    }
+
     pub(crate) fn composer_is_empty(&self) -> bool {
         self.composer.is_empty()
     }
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::VecDeque;
 use std::path::PathBuf;
@@ -36,7 +37,9 @@ use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::RateLimitSnapshot;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::StreamErrorEvent;
+use codex_core::protocol::SubAgentActivityEvent;
 use codex_core::protocol::SubAgentCompletedEvent;
+use codex_core::protocol::SubAgentHandoffEvent;
 use codex_core::protocol::SubAgentMessageEvent;
 use codex_core::protocol::SubAgentOutcome;
 use codex_core::protocol::SubAgentStartedEvent;
@@ -48,12 +51,17 @@ use codex_core::protocol::TurnDiffEvent;
 use codex_core::protocol::UserMessageEvent;
 use codex_core::protocol::WebSearchBeginEvent;
 use codex_core::protocol::WebSearchEndEvent;
+use codex_core::subagents::ParentContext;
 use codex_core::subagents::SubagentInvocation;
 use codex_core::subagents::SubagentOrchestrator;
+use codex_core::subagents::subagent_registry;
 use codex_protocol::mcp_protocol::ConversationId;
+use codex_protocol::num_format::format_si_suffix;
 use codex_protocol::parse_command::ParsedCommand;
 use codex_subagents::RegistrySnapshot;
-use codex_subagents::SubagentRegistry;
+use codex_subagents::StepStatus;
+use codex_subagents::SubagentSpec;
+use codex_subagents::WorkflowCatalog;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
@@ -72,6 +80,7 @@ use tracing::debug;
 
 use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
+use crate::bottom_pane::AgentMention;
 use crate::bottom_pane::ApprovalRequest;
 use crate::bottom_pane::BottomPane;
 use crate::bottom_pane::BottomPaneParams;
@@ -95,6 +104,8 @@ use crate::history_cell::McpToolCallCell;
 use crate::history_cell::PatchEventType;
 use crate::history_cell::PlainHistoryCell;
 use crate::markdown::append_markdown;
+use crate::pager_overlay::SubagentRunStatus;
+use crate::pager_overlay::SubagentTranscript;
 use crate::slash_command::SlashCommand;
 use crate::status::RateLimitSnapshotDisplay;
 use crate::text_formatting::truncate_text;
@@ -272,6 +283,9 @@ pub(crate) struct ChatWidget {
     // Whether to add a final message separator after the last message
     needs_final_message_separator: bool,
     subagent_stats: SubagentStats,
+    // Recent messages and plan handed to subagents with `parent_last_messages`
+    // or `plan` context attachments.
+    parent_context: ParentContext,
 }
 
 struct UserMessage {
@@ -294,6 +308,21 @@ struct SubagentStats {
     total_completed: usize,
     total_failed: usize,
     active: HashMap<String, SubagentRun>,
+    /// Transcripts of recent runs in start order, shown by the Ctrl+G overlay.
+    transcripts: Vec<SubagentRunTranscript>,
+    /// Tokens used by every finished run, shown by `/status`.
+    token_usage: TokenUsage,
+}
+
+/// Oldest finished transcripts are dropped beyond this many runs.
+const MAX_SUBAGENT_TRANSCRIPTS: usize = 32;
+
+struct SubagentRunTranscript {
+    conversation_id: String,
+    agent_name: String,
+    status: SubagentRunStatus,
+    cells: Vec<Arc<dyn HistoryCell>>,
+    running_commands: HashMap<String, RunningCommand>,
 }
 
 impl From<String> for UserMessage {
@@ -340,6 +369,7 @@ impl ChatWidget {
         }
         // Ask codex-core to enumerate custom prompts for this session.
         self.submit_op(Op::ListCustomPrompts);
+        self.refresh_agent_mentions();
         if let Some(user_message) = self.initial_user_message.take() {
             self.submit_user_message(user_message);
         }
@@ -960,6 +990,7 @@ impl ChatWidget {
             ghost_snapshots_disabled: true,
             needs_final_message_separator: false,
             subagent_stats: SubagentStats::default(),
+            parent_context: ParentContext::default(),
         }
     }
 
@@ -1025,6 +1056,7 @@ impl ChatWidget {
             ghost_snapshots_disabled: true,
             needs_final_message_separator: false,
             subagent_stats: SubagentStats::default(),
+            parent_context: ParentContext::default(),
         }
     }
 
@@ -1149,8 +1181,10 @@ impl ChatWidget {
                 self.show_subagent_status();
             }
             SlashCommand::Use => {
-                self.bottom_pane.set_composer_text("/use ".to_string());
-                self.request_redraw();
+                self.open_subagent_picker();
+            }
+            SlashCommand::Workflow => {
+                self.show_workflow_list();
             }
             SlashCommand::Model => {
                 self.open_model_popup();
@@ -1324,14 +1358,9 @@ impl ChatWidget {
                     }
                     SlashCommand::Use => {
                         let mut parts = prompt_str.split_whitespace();
-                        let agent_name = match parts.next() {
-                            Some(name) => name,
-                            None => {
-                                self.add_error_message(
-                                    "Usage: /use <agent-name> [prompt]".to_string(),
-                                );
-                                return;
-                            }
+                        let Some(agent_name) = parts.next() else {
+                            self.open_subagent_picker();
+                            return;
                         };
                         let remaining_prompt = parts.collect::<Vec<_>>().join(" ");
                         let prompt = if remaining_prompt.is_empty() {
@@ -1339,7 +1368,24 @@ impl ChatWidget {
                         } else {
                             Some(remaining_prompt)
                         };
-                        self.handle_use_command(agent_name, prompt);
+                        if agent_name.contains(',') {
+                            let names: Vec<&str> = agent_name
+                                .split(',')
+                                .map(str::trim)
+                                .filter(|name| !name.is_empty())
+                                .collect();
+                            self.handle_use_many_command(&names, prompt);
+                        } else {
+                            self.handle_use_command(agent_name, prompt);
+                        }
+                        return;
+                    }
+                    SlashCommand::Workflow => {
+                        if prompt_str.is_empty() {
+                            self.show_workflow_list();
+                        } else {
+                            self.handle_workflow_command(prompt_str);
+                        }
                         return;
                     }
                     _ => {}
@@ -1347,6 +1393,24 @@ impl ChatWidget {
             }
         }
 
+        let (mentions, prompt) = self.split_agent_mentions(&text);
+        if !mentions.is_empty() {
+            if !image_paths.is_empty() {
+                self.add_error_message(
+                    "Agent mentions do not support image attachments.".to_string(),
+                );
+                return;
+            }
+            let prompt = (!prompt.is_empty()).then_some(prompt);
+            if let [agent_name] = mentions.as_slice() {
+                self.handle_use_command(agent_name, prompt);
+            } else {
+                let names: Vec<&str> = mentions.iter().map(String::as_str).collect();
+                self.handle_use_many_command(&names, prompt);
+            }
+            return;
+        }
+
         self.capture_ghost_snapshot();
 
         let mut items: Vec<InputItem> = Vec::new();
@@ -1458,6 +1522,7 @@ impl ChatWidget {
     /// `replay_initial_messages()`. Callers should treat `None` as a "fake" id
     /// that must not be used to correlate follow-up actions.
     fn dispatch_event_msg(&mut self, id: Option<String>, msg: EventMsg, from_replay: bool) {
+        self.parent_context.observe(&msg);
         match msg {
             EventMsg::AgentMessageDelta(_)
             | EventMsg::AgentReasoningDelta(_)
@@ -1545,6 +1610,8 @@ impl ChatWidget {
             EventMsg::SubAgentStarted(ev) => self.on_subagent_started(ev),
             EventMsg::SubAgentMessage(ev) => self.on_subagent_message(ev),
             EventMsg::SubAgentCompleted(ev) => self.on_subagent_completed(ev),
+            EventMsg::SubAgentHandoff(ev) => self.on_subagent_handoff(ev),
+            EventMsg::SubAgentActivity(ev) => self.on_subagent_activity(ev),
         }
     }
 
@@ -1684,6 +1751,7 @@ impl ChatWidget {
         self.add_to_history(crate::status::new_status_output(
             &self.config,
             usage_ref,
+            Some(&self.subagent_stats.token_usage),
             &self.conversation_id,
             self.rate_limit_snapshot.as_ref(),
         ));
@@ -1865,14 +1933,22 @@ impl ChatWidget {
 
         let spec = handle.spec.clone();
         let resolved_name = spec.metadata.name.clone();
+        let agents: Vec<SubagentSpec> = snapshot
+            .agents
+            .iter()
+            .map(|handle| handle.spec.clone())
+            .collect();
         let config = self.config.clone();
         let conversation_manager = self.conversation_manager.clone();
         let app_event_tx = self.app_event_tx.clone();
         let prompt_for_run = prompt;
+        let parent_context = self.parent_context.clone();
         let parent_submit_id = format!("subagent-{:016x}", rand::random::<u64>());
 
         tokio::spawn(async move {
-            let orchestrator = SubagentOrchestrator::new(conversation_manager);
+            let orchestrator = SubagentOrchestrator::new(conversation_manager)
+                .with_handoff_agents(agents)
+                .with_parent_context(parent_context);
             let invocation = SubagentInvocation {
                 spec: &spec,
                 parent_submit_id: parent_submit_id.clone(),
@@ -1898,10 +1974,371 @@ impl ChatWidget {
         });
     }
 
+    fn handle_use_many_command(&mut self, agent_names: &[&str], prompt: Option<String>) {
+        if !self.config.subagents.enabled {
+            self.add_error_message(
+                "Subagents feature is disabled in the current configuration.".to_string(),
+            );
+            return;
+        }
+
+        let snapshot = match self.load_subagent_snapshot() {
+            Ok(snapshot) => snapshot,
+            Err(err) => {
+                self.add_error_message(err);
+                return;
+            }
+        };
+
+        let mut specs = Vec::with_capacity(agent_names.len());
+        for agent_name in agent_names {
+            let Some(handle) = snapshot
+                .agents
+                .iter()
+                .find(|agent| agent.spec.metadata.name.eq_ignore_ascii_case(agent_name))
+            else {
+                self.add_error_message(format!("Subagent '{agent_name}' not found."));
+                return;
+            };
+            specs.push(handle.spec.clone());
+        }
+        let agents: Vec<SubagentSpec> = snapshot
+            .agents
+            .into_iter()
+            .map(|handle| handle.spec)
+            .collect();
+
+        let config = self.config.clone();
+        let conversation_manager = self.conversation_manager.clone();
+        let app_event_tx = self.app_event_tx.clone();
+        let parent_context = self.parent_context.clone();
+        let parent_submit_id = format!("subagent-{:016x}", rand::random::<u64>());
+
+        tokio::spawn(async move {
+            let orchestrator = SubagentOrchestrator::new(conversation_manager)
+                .with_handoff_agents(agents)
+                .with_parent_context(parent_context);
+            let invocations = specs
+                .iter()
+                .map(|spec| SubagentInvocation {
+                    spec,
+                    parent_submit_id: parent_submit_id.clone(),
+                })
+                .collect();
+
+            let batch = orchestrator
+                .run_parallel(&config, invocations, prompt, |msg| {
+                    app_event_tx.send(AppEvent::CodexEvent(Event {
+                        id: parent_submit_id.clone(),
+                        msg,
+                    }));
+                })
+                .await;
+
+            for entry in batch.runs {
+                if let Err(err) = entry.result {
+                    app_event_tx.send(AppEvent::CodexEvent(Event {
+                        id: parent_submit_id.clone(),
+                        msg: EventMsg::Error(ErrorEvent {
+                            message: format!(
+                                "Failed to run subagent '{}': {err}",
+                                entry.agent_name
+                            ),
+                        }),
+                    }));
+                }
+            }
+        });
+    }
+
+    fn handle_workflow_command(&mut self, args: &str) {
+        const USAGE: &str = "Usage: /workflow <name> [key=value ...]";
+        if !self.config.subagents.enabled {
+            self.add_error_message(
+                "Subagents feature is disabled in the current configuration.".to_string(),
+            );
+            return;
+        }
+
+        let Some(tokens) = shlex::split(args) else {
+            self.add_error_message(USAGE.to_string());
+            return;
+        };
+        let mut tokens = tokens.into_iter();
+        let Some(name) = tokens.next() else {
+            self.add_error_message(USAGE.to_string());
+            return;
+        };
+        let mut inputs = BTreeMap::new();
+        for token in tokens {
+            match token.split_once('=') {
+                Some((key, value)) if !key.is_empty() => {
+                    inputs.insert(key.to_string(), value.to_string());
+                }
+                _ => {
+                    self.add_error_message(USAGE.to_string());
+                    return;
+                }
+            }
+        }
+
+        let catalog = match self.load_workflow_catalog() {
+            Ok(catalog) => catalog,
+            Err(err) => {
+                self.add_error_message(err);
+                return;
+            }
+        };
+        let Some(workflow) = catalog.get(&name).cloned() else {
+            self.add_error_message(format!("Workflow '{name}' not found."));
+            return;
+        };
+        let snapshot = match self.load_subagent_snapshot() {
+            Ok(snapshot) => snapshot,
+            Err(err) => {
+                self.add_error_message(err);
+                return;
+            }
+        };
+        let agents: Vec<SubagentSpec> = snapshot
+            .agents
+            .into_iter()
+            .map(|handle| handle.spec)
+            .collect();
+
+        let config = self.config.clone();
+        let conversation_manager = self.conversation_manager.clone();
+        let app_event_tx = self.app_event_tx.clone();
+        let parent_context = self.parent_context.clone();
+        let parent_submit_id = format!("workflow-{:016x}", rand::random::<u64>());
+        self.add_info_message(format!("Running workflow {}", workflow.name), None);
+
+        tokio::spawn(async move {
+            let orchestrator = SubagentOrchestrator::new(conversation_manager)
+                .with_handoff_agents(agents.clone())
+                .with_parent_context(parent_context);
+            let result = orchestrator
+                .run_workflow(&config, &workflow, inputs, &agents, |msg| match msg {
+                    EventMsg::BackgroundEvent(BackgroundEventEvent { message }) => {
+                        app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                            history_cell::new_info_event(message, None),
+                        )));
+                    }
+                    msg => app_event_tx.send(AppEvent::CodexEvent(Event {
+                        id: parent_submit_id.clone(),
+                        msg,
+                    })),
+                })
+                .await;
+
+            match result {
+                Ok(report) if report.status == StepStatus::Failed => {
+                    app_event_tx.send(AppEvent::CodexEvent(Event {
+                        id: parent_submit_id,
+                        msg: EventMsg::Error(ErrorEvent {
+                            message: format!("Workflow '{}' failed", report.workflow),
+                        }),
+                    }));
+                }
+                Ok(report) => {
+                    app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                        history_cell::new_info_event(
+                            format!("Workflow {} completed", report.workflow),
+                            report.output,
+                        ),
+                    )));
+                }
+                Err(err) => {
+                    app_event_tx.send(AppEvent::CodexEvent(Event {
+                        id: parent_submit_id,
+                        msg: EventMsg::Error(ErrorEvent {
+                            message: format!("Failed to run workflow '{}': {err}", workflow.name),
+                        }),
+                    }));
+                }
+            }
+        });
+    }
+
+    fn show_workflow_list(&mut self) {
+        let catalog = match self.load_workflow_catalog() {
+            Ok(catalog) => catalog,
+            Err(err) => {
+                self.add_error_message(err);
+                return;
+            }
+        };
+
+        let mut lines: Vec<Line> = Vec::new();
+        lines.push(
+            vec![
+                "Workflows".bold(),
+                format!(" ({})", catalog.workflows.len()).dim(),
+            ]
+            .into(),
+        );
+        if catalog.workflows.is_empty() {
+            lines.push("  (none discovered in .codex/workflows)".into());
+        }
+        for workflow in &catalog.workflows {
+            lines.push(vec!["  • ".into(), workflow.name.clone().cyan().bold()].into());
+            if let Some(desc) = workflow.description.as_ref() {
+                lines.push(vec!["      ".into(), desc.clone().into()].into());
+            }
+            let steps: Vec<&str> = workflow.steps.iter().map(|step| step.id.as_str()).collect();
+            lines.push(vec!["      steps: ".dim(), steps.join(" → ").into()].into());
+        }
+        if !catalog.parse_errors.is_empty() {
+            lines.push("Parse errors".red().bold().into());
+            for err in &catalog.parse_errors {
+                lines.push(vec!["  • ".into(), err.path.display().to_string().into()].into());
+                lines.push(vec!["      ".into(), err.message.clone().into()].into());
+            }
+        }
+        self.add_to_history(PlainHistoryCell::new(lines));
+        self.request_redraw();
+    }
+
+    fn load_workflow_catalog(&self) -> Result<WorkflowCatalog, String> {
+        WorkflowCatalog::load(
+            &self.config.cwd.join(".codex/workflows"),
+            &self.config.codex_home.join("workflows"),
+        )
+        .map_err(|err| err.to_string())
+    }
+
+    /// Open a fuzzy-searchable list of registered agents; choosing one fills
+    /// the composer with `/use <name> `.
+    fn open_subagent_picker(&mut self) {
+        if !self.config.subagents.enabled {
+            self.add_error_message(
+                "Subagents feature is disabled in the current configuration.".to_string(),
+            );
+            return;
+        }
+        let snapshot = match self.load_subagent_snapshot() {
+            Ok(snapshot) => snapshot,
+            Err(err) => {
+                self.add_error_message(err);
+                return;
+            }
+        };
+        if snapshot.agents.is_empty() {
+            self.add_info_message("No subagents are registered.".to_string(), None);
+            return;
+        }
+
+        let items: Vec<SelectionItem> = snapshot
+            .agents
+            .iter()
+            .map(|handle| {
+                let metadata = &handle.spec.metadata;
+                let name = metadata.name.clone();
+                let mut details: Vec<String> = Vec::new();
+                if let Some(desc) = metadata.description.as_ref() {
+                    details.push(desc.clone());
+                }
+                if let Some(model) = metadata.model.as_ref() {
+                    details.push(model.clone());
+                }
+                details.push(handle.spec.source.describe().to_string());
+                let search_value = format!(
+                    "{name} {}",
+                    metadata.description.as_deref().unwrap_or_default()
+                );
+                let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+                    tx.send(AppEvent::UseSubagent(name.clone()));
+                })];
+                SelectionItem {
+                    name: metadata.name.clone(),
+                    description: Some(details.join(" · ")),
+                    is_current: false,
+                    actions,
+                    dismiss_on_select: true,
+                    search_value: Some(search_value),
+                }
+            })
+            .collect();
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: "Select a subagent".to_string(),
+            footer_hint: Some(STANDARD_POPUP_HINT_LINE.to_string()),
+            items,
+            is_searchable: true,
+            fuzzy_search: true,
+            search_placeholder: Some("Type to search agents".to_string()),
+            ..Default::default()
+        });
+    }
+
+    /// Fill the composer with `/use <agent_name> ` so the user can add a prompt.
+    pub(crate) fn prefill_use_command(&mut self, agent_name: &str) {
+        self.bottom_pane
+            .set_composer_text(format!("/use {agent_name} "));
+        self.request_redraw();
+    }
+
+    /// Offer registered agents to the composer's `@agent` completion.
+    fn refresh_agent_mentions(&mut self) {
+        if !self.config.subagents.enabled {
+            return;
+        }
+        match self.load_subagent_snapshot() {
+            Ok(snapshot) => {
+                let mentions = snapshot
+                    .agents
+                    .iter()
+                    .map(|handle| AgentMention {
+                        name: handle.spec.metadata.name.clone(),
+                        description: handle.spec.metadata.description.clone(),
+                    })
+                    .collect();
+                self.bottom_pane.set_agent_mentions(mentions);
+            }
+            Err(err) => tracing::warn!("failed to load subagents for @mentions: {err}"),
+        }
+    }
+
+    /// Split `@agent` mentions of registered agents out of `text`, returning
+    /// the agent names in order and the remaining prompt.
+    fn split_agent_mentions(&self, text: &str) -> (Vec<String>, String) {
+        if !self.config.subagents.enabled || !text.contains('@') {
+            return (Vec::new(), text.to_string());
+        }
+        let Ok(snapshot) = self.load_subagent_snapshot() else {
+            return (Vec::new(), text.to_string());
+        };
+        let mut mentions: Vec<String> = Vec::new();
+        let mut prompt = String::with_capacity(text.len());
+        let mut idx = 0;
+        while idx < text.len() {
+            let start = idx + (text[idx..].len() - text[idx..].trim_start().len());
+            let end = text[start..]
+                .find(char::is_whitespace)
+                .map_or(text.len(), |len| start + len);
+            let agent = text[start..end].strip_prefix('@').and_then(|name| {
+                snapshot
+                    .agents
+                    .iter()
+                    .find(|handle| handle.spec.metadata.name == name)
+            });
+            match agent {
+                Some(handle) => {
+                    prompt.push_str(&text[idx..start]);
+                    let name = handle.spec.metadata.name.clone();
+                    if !mentions.contains(&name) {
+                        mentions.push(name);
+                    }
+                }
+                None => prompt.push_str(&text[idx..end]),
+            }
+            idx = end;
+        }
+        (mentions, prompt.trim().to_string())
+    }
+
     fn load_subagent_snapshot(&self) -> Result<RegistrySnapshot, String> {
-        let project_dir = self.config.cwd.join(".codex/agents");
-        let user_dir = self.config.codex_home.join("agents");
-        let mut registry = SubagentRegistry::new(project_dir, user_dir);
+        let mut registry = subagent_registry(&self.config);
         registry.reload().cloned().map_err(|err| err.to_string())
     }
 
@@ -1922,9 +2359,16 @@ impl ChatWidget {
             for (idx, handle) in snapshot.agents.iter().enumerate() {
                 let metadata = &handle.spec.metadata;
                 lines.push(vec!["  • ".into(), metadata.name.clone().cyan().bold()].into());
-                lines.push(
-                    vec!["      source: ".dim(), handle.spec.source.describe().into()].into(),
-                );
+                let mut source = handle.spec.source.describe().to_string();
+                if !handle.overrides.is_empty() {
+                    let overridden: Vec<&str> = handle
+                        .overrides
+                        .iter()
+                        .map(|source| source.describe())
+                        .collect();
+                    source = format!("{source} (overrides {})", overridden.join(", "));
+                }
+                lines.push(vec!["      source: ".dim(), source.into()].into());
                 if let Some(desc) = metadata.description.as_ref() {
                     lines.push(vec!["      ".into(), desc.clone().into()].into());
                 }
@@ -2015,11 +2459,87 @@ impl ChatWidget {
         for line in event.message.lines() {
             lines.push(vec!["      ".into(), line.to_string().into()].into());
         }
-        let cell = PlainHistoryCell::new(lines.into_iter().collect());
-        self.add_to_history(cell);
+        self.subagent_stats
+            .push_transcript_cell(&event.sub_conversation_id, PlainHistoryCell::new(lines));
         self.request_redraw();
     }
 
+    /// File a tool call, patch, or approval from a subagent's conversation
+    /// under that run's transcript rather than the main history.
+    fn on_subagent_activity(&mut self, event: SubAgentActivityEvent) {
+        let conversation_id = event.sub_conversation_id;
+        let Some(run) = self.subagent_stats.transcript_mut(&conversation_id) else {
+            return;
+        };
+        let cell: Box<dyn HistoryCell> = match *event.msg {
+            EventMsg::ExecCommandBegin(ev) => {
+                run.running_commands.insert(
+                    ev.call_id,
+                    RunningCommand {
+                        command: ev.command,
+                        parsed_cmd: ev.parsed_cmd,
+                    },
+                );
+                return;
+            }
+            EventMsg::ExecCommandEnd(ev) => {
+                let (command, parsed) = match run.running_commands.remove(&ev.call_id) {
+                    Some(rc) => (rc.command, rc.parsed_cmd),
+                    None => (vec![ev.call_id.clone()], Vec::new()),
+                };
+                let mut cell = new_active_exec_command(ev.call_id.clone(), command, parsed);
+                cell.complete_call(
+                    &ev.call_id,
+                    CommandOutput {
+                        exit_code: ev.exit_code,
+                        stdout: ev.stdout,
+                        stderr: ev.stderr,
+                        formatted_output: ev.formatted_output,
+                    },
+                    ev.duration,
+                );
+                Box::new(cell)
+            }
+            EventMsg::ExecApprovalRequest(ev) => {
+                Box::new(history_cell::new_proposed_command(&ev.command))
+            }
+            EventMsg::ApplyPatchApprovalRequest(ev) => Box::new(history_cell::new_patch_event(
+                PatchEventType::ApprovalRequest,
+                ev.changes,
+                &self.config.cwd,
+                None,
+            )),
+            EventMsg::PatchApplyBegin(ev) => Box::new(history_cell::new_patch_event(
+                PatchEventType::ApplyBegin {
+                    auto_approved: ev.auto_approved,
+                },
+                ev.changes,
+                &self.config.cwd,
+                None,
+            )),
+            EventMsg::PatchApplyEnd(ev) if !ev.success => {
+                Box::new(history_cell::new_patch_apply_failure(ev.stderr))
+            }
+            EventMsg::McpToolCallEnd(ev) => {
+                let mut cell = history_cell::new_active_mcp_tool_call(ev.call_id, ev.invocation);
+                if let Some(extra) = cell.complete(ev.duration, ev.result) {
+                    run.cells.push(Arc::new(cell));
+                    extra
+                } else {
+                    Box::new(cell)
+                }
+            }
+            _ => return,
+        };
+        run.cells.push(cell.into());
+        self.request_redraw();
+    }
+
+    /// Transcripts of recent subagent runs, oldest first.
+    pub(crate) fn subagent_transcripts(&self) -> Vec<SubagentTranscript> {
+        self.subagent_stats.transcripts()
+    }
+
     fn on_subagent_completed(&mut self, event: SubAgentCompletedEvent) {
         let prior = self.subagent_stats.on_completed(
             &event.sub_conversation_id,
@@ -2027,6 +2547,9 @@ impl ChatWidget {
             event.error.clone(),
             event.duration_ms,
         );
+        if let Some(usage) = event.token_usage.as_ref() {
+            self.subagent_stats.token_usage.add_assign(usage);
+        }
         self.refresh_subagent_status_overlay();
 
         let status_span = match event.outcome {
@@ -2054,13 +2577,44 @@ impl ChatWidget {
         if let Some(duration) = duration {
             lines.push(vec!["      duration: ".dim(), format_duration(duration).into()].into());
         }
+        if let Some(usage) = event.token_usage.as_ref().filter(|usage| !usage.is_zero()) {
+            lines.push(
+                vec![
+                    "      tokens: ".dim(),
+                    format_si_suffix(usage.blended_total()).into(),
+                ]
+                .into(),
+            );
+        }
         if let Some(err) = event.error.as_ref() {
             lines.push(vec!["      error: ".red(), err.clone().into()].into());
         } else if let Some(run) = prior.as_ref().and_then(|r| r.last_message.as_ref()) {
             lines.push(vec!["      last: ".dim(), run.clone().into()].into());
         }
-        let cell = PlainHistoryCell::new(lines.into_iter().collect());
-        self.add_to_history(cell);
+        if let Some(target) = event.handoff_to {
+            lines.push(vec!["      handoff: ".dim(), target.cyan()].into());
+        }
+        self.subagent_stats.push_transcript_cell(
+            &event.sub_conversation_id,
+            PlainHistoryCell::new(lines.clone()),
+        );
+        self.add_to_history(PlainHistoryCell::new(lines));
+        self.request_redraw();
+    }
+
+    fn on_subagent_handoff(&mut self, event: SubAgentHandoffEvent) {
+        let lines: Vec<Line> = vec![
+            vec![
+                "subagent ".dim(),
+                event.agent_name.cyan().bold(),
+                " handing off to ".into(),
+                event.target_agent.cyan().bold(),
+            ]
+            .into(),
+            vec!["      note: ".dim(), event.note.into()].into(),
+        ];
+        self.subagent_stats
+            .push_transcript_cell(&event.sub_conversation_id, PlainHistoryCell::new(lines));
         self.request_redraw();
     }
 
@@ -2374,6 +2928,21 @@ impl SubagentStats {
         model: Option<String>,
     ) {
         self.total_started += 1;
+        if self.transcripts.len() >= MAX_SUBAGENT_TRANSCRIPTS
+            && let Some(idx) = self
+                .transcripts
+                .iter()
+                .position(|run| run.status != SubagentRunStatus::Running)
+        {
+            self.transcripts.remove(idx);
+        }
+        self.transcripts.push(SubagentRunTranscript {
+            conversation_id: conversation_id.to_string(),
+            agent_name: agent_name.clone(),
+            status: SubagentRunStatus::Running,
+            cells: Vec::new(),
+            running_commands: HashMap::new(),
+        });
         self.active.insert(
             conversation_id.to_string(),
             SubagentRun {
@@ -2386,6 +2955,54 @@ impl SubagentStats {
         );
     }
 
+    fn transcript_mut(
+        &mut self,
+        conversation_id: &ConversationId,
+    ) -> Option<&mut SubagentRunTranscript> {
+        let key = conversation_id.to_string();
+        self.transcripts
+            .iter_mut()
+            .rev()
+            .find(|run| run.conversation_id == key)
+    }
+
+    fn push_transcript_cell(
+        &mut self,
+        conversation_id: &ConversationId,
+        cell: impl HistoryCell + 'static,
+    ) {
+        if let Some(run) = self.transcript_mut(conversation_id) {
+            run.cells.push(Arc::new(cell));
+        }
+    }
+
+    /// Snapshot of every transcript; commands that have not finished yet are
+    /// appended as active exec cells.
+    fn transcripts(&self) -> Vec<SubagentTranscript> {
+        self.transcripts
+            .iter()
+            .map(|run| {
+                let mut cells = run.cells.clone();
+                let mut running: Vec<(&String, &RunningCommand)> =
+                    run.running_commands.iter().collect();
+                running.sort_by(|a, b| a.0.cmp(b.0));
+                for (call_id, command) in running {
+                    cells.push(Arc::new(new_active_exec_command(
+                        call_id.clone(),
+                        command.command.clone(),
+                        command.parsed_cmd.clone(),
+                    )));
+                }
+                SubagentTranscript {
+                    conversation_id: run.conversation_id.clone(),
+                    agent_name: run.agent_name.clone(),
+                    status: run.status,
+                    cells,
+                }
+            })
+            .collect()
+    }
+
     fn on_message(&mut self, conversation_id: &ConversationId, message: String) {
         if let Some(run) = self.active.get_mut(&conversation_id.to_string()) {
             run.last_message = Some(preview_text(&message));
@@ -2416,6 +3033,13 @@ impl SubagentStats {
             SubAgentOutcome::Error => self.total_failed += 1,
         }
         let key = conversation_id.to_string();
+        if let Some(transcript) = self.transcript_mut(conversation_id) {
+            transcript.status = match outcome {
+                SubAgentOutcome::Success => SubagentRunStatus::Succeeded,
+                SubAgentOutcome::Error => SubagentRunStatus::Failed,
+            };
+            transcript.running_commands.clear();
+        }
         let mut run = self.active.remove(&key);
         if let Some(existing) = run.as_mut() {
             if let Some(ms) = duration_ms {
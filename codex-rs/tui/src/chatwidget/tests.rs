@@ -33,6 +33,7 @@ use codex_core::protocol::ReviewLineRange;
 use codex_core::protocol::ReviewOutputEvent;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::StreamErrorEvent;
+use codex_core::protocol::SubAgentActivityEvent;
 use codex_core::protocol::SubAgentCompletedEvent;
 use codex_core::protocol::SubAgentMessageEvent;
 use codex_core::protocol::SubAgentOutcome;
@@ -355,6 +356,7 @@ fn make_chatwidget_manual() -> (
         ghost_snapshots_disabled: false,
         needs_final_message_separator: false,
         subagent_stats: SubagentStats::default(),
+        parent_context: ParentContext::default(),
     };
     (widget, rx, op_rx)
 }
@@ -441,6 +443,7 @@ fn subagent_events_render_in_history() {
             parent_submit_id: "cli".to_string(),
             sub_conversation_id: conversation_id,
             model: Some("gpt-5-codex".to_string()),
+            handoff_from: None,
         }),
     });
 
@@ -462,6 +465,13 @@ fn subagent_events_render_in_history() {
             error: None,
             model: Some("gpt-5-codex".to_string()),
             duration_ms: Some(1_234),
+            handoff_to: None,
+            token_usage: Some(TokenUsage {
+                input_tokens: 1_200,
+                output_tokens: 300,
+                total_tokens: 1_500,
+                ..TokenUsage::default()
+            }),
         }),
     });
 
@@ -474,6 +484,11 @@ fn subagent_events_render_in_history() {
         let rendered = lines_to_single_string(lines);
         rendered.contains("Work in progress")
     }));
+    assert!(cells.iter().any(|lines| {
+        let rendered = lines_to_single_string(lines);
+        rendered.contains("tokens: 1.50K")
+    }));
+    assert_eq!(widget.subagent_stats.token_usage.total_tokens, 1_500);
     assert!(cells.iter().any(|lines| {
         let rendered = lines_to_single_string(lines);
         rendered.contains("subagent tester completed")
@@ -484,6 +499,94 @@ fn subagent_events_render_in_history() {
     }));
 }
 
+#[test]
+fn subagent_activity_goes_to_run_transcript() {
+    let (mut widget, mut rx, _op_rx) = make_chatwidget_manual();
+    let conversation_id = ConversationId::default();
+    let activity = |msg: EventMsg| Event {
+        id: "sub-1".into(),
+        msg: EventMsg::SubAgentActivity(SubAgentActivityEvent {
+            agent_name: "tester".to_string(),
+            sub_conversation_id: conversation_id,
+            msg: Box::new(msg),
+        }),
+    };
+
+    widget.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::SubAgentStarted(SubAgentStartedEvent {
+            agent_name: "tester".to_string(),
+            parent_submit_id: "cli".to_string(),
+            sub_conversation_id: conversation_id,
+            model: None,
+            handoff_from: None,
+        }),
+    });
+    widget.handle_codex_event(activity(EventMsg::ExecCommandBegin(
+        ExecCommandBeginEvent {
+            call_id: "c1".to_string(),
+            command: vec!["cargo".to_string(), "test".to_string()],
+            cwd: PathBuf::from("/repo"),
+            parsed_cmd: Vec::new(),
+        },
+    )));
+
+    let running = widget.subagent_transcripts();
+    assert_eq!(running.len(), 1);
+    assert_eq!(running[0].status, SubagentRunStatus::Running);
+    assert_eq!(running[0].cells.len(), 1);
+
+    widget.handle_codex_event(activity(EventMsg::ExecCommandEnd(ExecCommandEndEvent {
+        call_id: "c1".to_string(),
+        stdout: "ok".to_string(),
+        stderr: String::new(),
+        aggregated_output: "ok".to_string(),
+        exit_code: 0,
+        duration: std::time::Duration::from_millis(5),
+        formatted_output: "ok".to_string(),
+    })));
+    widget.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::SubAgentMessage(SubAgentMessageEvent {
+            agent_name: "tester".to_string(),
+            sub_conversation_id: conversation_id,
+            message: "All tests pass".to_string(),
+        }),
+    });
+    widget.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::SubAgentCompleted(SubAgentCompletedEvent {
+            agent_name: "tester".to_string(),
+            sub_conversation_id: conversation_id,
+            outcome: SubAgentOutcome::Success,
+            error: None,
+            model: None,
+            duration_ms: Some(10),
+            handoff_to: None,
+            token_usage: None,
+        }),
+    });
+
+    let history: Vec<String> = drain_insert_history(&mut rx)
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect();
+    assert_eq!(history.len(), 2, "only start and completion: {history:?}");
+    assert!(history.iter().all(|cell| !cell.contains("cargo test")));
+
+    let transcripts = widget.subagent_transcripts();
+    assert_eq!(transcripts[0].status, SubagentRunStatus::Succeeded);
+    let transcript: Vec<String> = transcripts[0]
+        .cells
+        .iter()
+        .map(|cell| lines_to_single_string(&cell.transcript_lines()))
+        .collect();
+    assert_eq!(transcript.len(), 3);
+    assert!(transcript[0].contains("cargo test"));
+    assert!(transcript[1].contains("All tests pass"));
+    assert!(transcript[2].contains("subagent tester completed"));
+}
+
 fn write_agent(path: &Path, name: &str, description: &str, body: &str) {
     fs::write(
         path,
@@ -544,6 +647,55 @@ fn subagent_list_snapshot() {
     assert_snapshot!("subagent_list_snapshot", sanitized);
 }
 
+fn make_subagent_chatwidget() -> (
+    ChatWidget,
+    tokio::sync::mpsc::UnboundedReceiver<AppEvent>,
+    tempfile::TempDir,
+) {
+    let project_dir = tempdir().expect("project dir");
+    let (mut widget, rx, _op_rx) = make_chatwidget_manual();
+    widget.config.cwd = project_dir.path().to_path_buf();
+    widget.config.codex_home = project_dir.path().join("home");
+    widget.config.subagents.enabled = true;
+    (widget, rx, project_dir)
+}
+
+#[test]
+fn use_without_agent_opens_fuzzy_picker() {
+    let (mut widget, mut rx, _project_dir) = make_subagent_chatwidget();
+
+    widget.dispatch_command(SlashCommand::Use);
+    for c in "tst".chars() {
+        widget.handle_key_event(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+    }
+    widget.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+    let mut picked = None;
+    while let Ok(ev) = rx.try_recv() {
+        if let AppEvent::UseSubagent(name) = ev {
+            picked = Some(name);
+        }
+    }
+    assert_eq!(picked.as_deref(), Some("tester"));
+}
+
+#[test]
+fn agent_mentions_are_split_from_the_prompt() {
+    let (widget, _rx, _project_dir) = make_subagent_chatwidget();
+
+    assert_eq!(
+        widget.split_agent_mentions("@reviewer check\nsrc/lib.rs  @tester @reviewer"),
+        (
+            vec!["reviewer".to_string(), "tester".to_string()],
+            "check\nsrc/lib.rs".to_string()
+        )
+    );
+    assert_eq!(
+        widget.split_agent_mentions("ask @someone about user@reviewer"),
+        (Vec::new(), "ask @someone about user@reviewer".to_string())
+    );
+}
+
 #[test]
 fn test_rate_limit_warnings_monthly() {
     let mut state = RateLimitWarningState::default();
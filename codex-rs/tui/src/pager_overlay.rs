@@ -26,6 +26,7 @@ use ratatui::widgets::WidgetRef;
 pub(crate) enum Overlay {
     Transcript(TranscriptOverlay),
     Static(StaticOverlay),
+    Subagents(SubagentOverlay),
 }
 
 impl Overlay {
@@ -37,10 +38,15 @@ impl Overlay {
         Self::Static(StaticOverlay::with_title(lines, title))
     }
 
+    pub(crate) fn new_subagents(runs: Vec<SubagentTranscript>) -> Self {
+        Self::Subagents(SubagentOverlay::new(runs))
+    }
+
     pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
         match self {
             Overlay::Transcript(o) => o.handle_event(tui, event),
             Overlay::Static(o) => o.handle_event(tui, event),
+            Overlay::Subagents(o) => o.handle_event(tui, event),
         }
     }
 
@@ -48,6 +54,7 @@ impl Overlay {
         match self {
             Overlay::Transcript(o) => o.is_done(),
             Overlay::Static(o) => o.is_done(),
+            Overlay::Subagents(o) => o.is_done(),
         }
     }
 }
@@ -547,6 +554,225 @@ impl StaticOverlay {
     }
 }
 
+/// State of a subagent run as shown in the tab row of [`SubagentOverlay`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SubagentRunStatus {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+impl SubagentRunStatus {
+    fn icon(self) -> &'static str {
+        match self {
+            SubagentRunStatus::Running => "●",
+            SubagentRunStatus::Succeeded => "✓",
+            SubagentRunStatus::Failed => "✗",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            SubagentRunStatus::Running => "running",
+            SubagentRunStatus::Succeeded => "completed",
+            SubagentRunStatus::Failed => "failed",
+        }
+    }
+}
+
+/// Everything one subagent conversation has done so far.
+pub(crate) struct SubagentTranscript {
+    pub(crate) conversation_id: String,
+    pub(crate) agent_name: String,
+    pub(crate) status: SubagentRunStatus,
+    pub(crate) cells: Vec<Arc<dyn HistoryCell>>,
+}
+
+/// Pager with one tab per subagent run (keyed by `sub_conversation_id`).
+pub(crate) struct SubagentOverlay {
+    view: PagerView,
+    runs: Vec<SubagentTranscript>,
+    selected: usize,
+    is_done: bool,
+}
+
+impl SubagentOverlay {
+    pub(crate) fn new(runs: Vec<SubagentTranscript>) -> Self {
+        // Open on the most recent run that is still going, if any.
+        let selected = runs
+            .iter()
+            .rposition(|run| run.status == SubagentRunStatus::Running)
+            .unwrap_or_else(|| runs.len().saturating_sub(1));
+        let mut overlay = Self {
+            view: PagerView::new(Vec::new(), String::new(), usize::MAX),
+            runs,
+            selected,
+            is_done: false,
+        };
+        overlay.refresh_view(usize::MAX);
+        overlay
+    }
+
+    /// Replace the runs with a fresh copy, keeping the selected run and
+    /// following new output if the view was at the bottom.
+    pub(crate) fn set_runs(&mut self, runs: Vec<SubagentTranscript>) {
+        let selected_id = self
+            .runs
+            .get(self.selected)
+            .map(|run| run.conversation_id.clone());
+        let follow_bottom = self.view.is_scrolled_to_bottom();
+        let scroll_offset = if follow_bottom {
+            usize::MAX
+        } else {
+            self.view.scroll_offset
+        };
+        self.selected = selected_id
+            .and_then(|id| runs.iter().position(|run| run.conversation_id == id))
+            .unwrap_or_else(|| runs.len().saturating_sub(1));
+        self.runs = runs;
+        self.refresh_view(scroll_offset);
+    }
+
+    fn select(&mut self, index: usize) {
+        if index != self.selected && index < self.runs.len() {
+            self.selected = index;
+            self.refresh_view(usize::MAX);
+        }
+    }
+
+    fn refresh_view(&mut self, scroll_offset: usize) {
+        let (texts, title) = match self.runs.get(self.selected) {
+            Some(run) => {
+                let texts = if run.cells.is_empty() {
+                    vec![Text::from("No activity yet.".dim())]
+                } else {
+                    TranscriptOverlay::render_cells_to_texts(&run.cells, None)
+                };
+                let title = format!(
+                    "{} · {} · {}",
+                    run.agent_name,
+                    run.status.label(),
+                    run.conversation_id
+                );
+                (texts, title)
+            }
+            None => (
+                vec![Text::from("No subagent runs yet.".dim())],
+                "S U B A G E N T S".to_string(),
+            ),
+        };
+        self.view = PagerView::new(texts, title, scroll_offset);
+    }
+
+    fn render_tabs(&self, area: Rect, buf: &mut Buffer) {
+        let labels: Vec<String> = self
+            .runs
+            .iter()
+            .map(|run| format!(" {} {} ", run.status.icon(), run.agent_name))
+            .collect();
+        // Drop tabs from the left until the selected one fits.
+        let width = area.width as usize;
+        let mut start = 0;
+        while start < self.selected
+            && labels[start..=self.selected]
+                .iter()
+                .map(|label| label.chars().count() + 1)
+                .sum::<usize>()
+                + 2
+                > width
+        {
+            start += 1;
+        }
+        let mut spans: Vec<Span<'static>> =
+            vec![if start > 0 { "‹ ".dim() } else { "  ".into() }];
+        for (idx, label) in labels.into_iter().enumerate().skip(start) {
+            let span = match self.runs[idx].status {
+                SubagentRunStatus::Running => label.cyan(),
+                SubagentRunStatus::Succeeded => label.green(),
+                SubagentRunStatus::Failed => label.red(),
+            };
+            spans.push(if idx == self.selected {
+                span.reversed()
+            } else {
+                span
+            });
+            spans.push(" ".into());
+        }
+        Paragraph::new(Line::from(spans)).render_ref(area, buf);
+    }
+
+    fn render_hints(&self, area: Rect, buf: &mut Buffer) {
+        let line1 = Rect::new(area.x, area.y, area.width, 1);
+        let line2 = Rect::new(area.x, area.y.saturating_add(1), area.width, 1);
+        render_key_hints(line1, buf, PAGER_KEY_HINTS);
+        let pairs = [("←/→", "switch run"), ("q", "quit")];
+        render_key_hints(line2, buf, &pairs);
+    }
+
+    pub(crate) fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        let tabs = Rect::new(area.x, area.y, area.width, 1.min(area.height));
+        let top_h = area.height.saturating_sub(4);
+        let top = Rect::new(area.x, area.y.saturating_add(1), area.width, top_h);
+        let bottom = Rect::new(area.x, area.y + 1 + top_h, area.width, 3);
+        self.render_tabs(tabs, buf);
+        self.view.render(top, buf);
+        self.render_hints(bottom, buf);
+    }
+}
+
+impl SubagentOverlay {
+    pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
+        match event {
+            TuiEvent::Key(key_event) => match key_event {
+                KeyEvent {
+                    code: KeyCode::Char('q'),
+                    kind: KeyEventKind::Press,
+                    ..
+                }
+                | KeyEvent {
+                    code: KeyCode::Char('g' | 'c'),
+                    modifiers: crossterm::event::KeyModifiers::CONTROL,
+                    kind: KeyEventKind::Press,
+                    ..
+                } => {
+                    self.is_done = true;
+                    Ok(())
+                }
+                KeyEvent {
+                    code: KeyCode::Left | KeyCode::BackTab,
+                    kind: KeyEventKind::Press | KeyEventKind::Repeat,
+                    ..
+                } => {
+                    self.select(self.selected.saturating_sub(1));
+                    tui.frame_requester().schedule_frame();
+                    Ok(())
+                }
+                KeyEvent {
+                    code: KeyCode::Right | KeyCode::Tab,
+                    kind: KeyEventKind::Press | KeyEventKind::Repeat,
+                    ..
+                } => {
+                    self.select(self.selected.saturating_add(1));
+                    tui.frame_requester().schedule_frame();
+                    Ok(())
+                }
+                other => self.view.handle_key_event(tui, other),
+            },
+            TuiEvent::Draw => {
+                tui.draw(u16::MAX, |frame| {
+                    self.render(frame.area(), frame.buffer);
+                })?;
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+    pub(crate) fn is_done(&self) -> bool {
+        self.is_done
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -835,4 +1061,79 @@ mod tests {
             "wrapped length should grow or stay same after append"
         );
     }
+
+    fn subagent_run(
+        conversation_id: &str,
+        agent_name: &str,
+        status: SubagentRunStatus,
+        lines: &[&'static str],
+    ) -> SubagentTranscript {
+        SubagentTranscript {
+            conversation_id: conversation_id.to_string(),
+            agent_name: agent_name.to_string(),
+            status,
+            cells: lines
+                .iter()
+                .map(|line| {
+                    Arc::new(TestCell {
+                        lines: vec![Line::from(*line)],
+                    }) as Arc<dyn HistoryCell>
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn subagent_overlay_snapshot_basic() {
+        let mut overlay = SubagentOverlay::new(vec![
+            subagent_run(
+                "c1",
+                "reviewer",
+                SubagentRunStatus::Succeeded,
+                &["looked at the diff"],
+            ),
+            subagent_run(
+                "c2",
+                "tester",
+                SubagentRunStatus::Running,
+                &["• Ran cargo test", "all green"],
+            ),
+        ]);
+        let mut term = Terminal::new(TestBackend::new(50, 10)).expect("term");
+        term.draw(|f| overlay.render(f.area(), f.buffer_mut()))
+            .expect("draw");
+        assert_snapshot!(term.backend());
+    }
+
+    #[test]
+    fn subagent_overlay_keeps_selected_run_across_updates() {
+        let mut overlay = SubagentOverlay::new(vec![
+            subagent_run("c1", "reviewer", SubagentRunStatus::Running, &["a"]),
+            subagent_run("c2", "tester", SubagentRunStatus::Running, &["b"]),
+        ]);
+        assert_eq!(overlay.selected, 1);
+        overlay.select(0);
+        assert_eq!(overlay.view.title, "reviewer · running · c1");
+
+        overlay.set_runs(vec![
+            subagent_run("c1", "reviewer", SubagentRunStatus::Succeeded, &["a", "b"]),
+            subagent_run("c2", "tester", SubagentRunStatus::Running, &["b"]),
+            subagent_run("c3", "planner", SubagentRunStatus::Running, &[]),
+        ]);
+        assert_eq!(overlay.selected, 0);
+        assert_eq!(overlay.view.title, "reviewer · completed · c1");
+        assert_eq!(overlay.view.texts.len(), 2);
+
+        overlay.select(5);
+        assert_eq!(overlay.selected, 0);
+    }
+
+    #[test]
+    fn subagent_overlay_without_runs_says_so() {
+        let mut overlay = SubagentOverlay::new(Vec::new());
+        let area = Rect::new(0, 0, 40, 8);
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+        assert!(buffer_to_text(&buf, area).contains("No subagent runs yet."));
+    }
 }
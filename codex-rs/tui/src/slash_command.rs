@@ -17,6 +17,7 @@ pub enum SlashCommand {
     Review,
     Agents,
     Use,
+    Workflow,
     New,
     Init,
     Compact,
@@ -41,7 +42,8 @@ impl SlashCommand {
             SlashCommand::Compact => "summarize conversation to prevent hitting the context limit",
             SlashCommand::Review => "review my current changes and find issues",
             SlashCommand::Agents => "list available subagents and their metadata",
-            SlashCommand::Use => "run a specific subagent by name",
+            SlashCommand::Use => "run one or more subagents by name",
+            SlashCommand::Workflow => "list or run a subagent workflow",
             SlashCommand::Undo => "restore the workspace to the last Codex snapshot",
             SlashCommand::Quit => "exit Codex",
             SlashCommand::Diff => "show git diff (including untracked files)",
@@ -74,6 +76,7 @@ impl SlashCommand {
             | SlashCommand::Approvals
             | SlashCommand::Review
             | SlashCommand::Use
+            | SlashCommand::Workflow
             | SlashCommand::Logout => false,
             SlashCommand::Agents
             | SlashCommand::SubagentStatus
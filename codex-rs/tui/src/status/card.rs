@@ -36,6 +36,16 @@ pub(crate) struct StatusTokenUsageData {
     output: u64,
 }
 
+impl From<&TokenUsage> for StatusTokenUsageData {
+    fn from(usage: &TokenUsage) -> Self {
+        Self {
+            total: usage.blended_total(),
+            input: usage.non_cached_input(),
+            output: usage.output_tokens,
+        }
+    }
+}
+
 #[derive(Debug)]
 struct StatusHistoryCell {
     model_name: String,
@@ -47,17 +57,22 @@ struct StatusHistoryCell {
     account: Option<StatusAccountDisplay>,
     session_id: Option<String>,
     token_usage: StatusTokenUsageData,
+    /// Share of `token_usage` used by subagents, when any have run.
+    subagent_token_usage: Option<StatusTokenUsageData>,
     rate_limits: StatusRateLimitData,
 }
 
+/// `subagent_usage` is added to `usage` for the total and also shown on its
+/// own line.
 pub(crate) fn new_status_output(
     config: &Config,
     usage: &TokenUsage,
+    subagent_usage: Option<&TokenUsage>,
     session_id: &Option<ConversationId>,
     rate_limits: Option<&RateLimitSnapshotDisplay>,
 ) -> CompositeHistoryCell {
     let command = PlainHistoryCell::new(vec!["/status".magenta().into()]);
-    let card = StatusHistoryCell::new(config, usage, session_id, rate_limits);
+    let card = StatusHistoryCell::new(config, usage, subagent_usage, session_id, rate_limits);
 
     CompositeHistoryCell::new(vec![Box::new(command), Box::new(card)])
 }
@@ -66,6 +81,7 @@ impl StatusHistoryCell {
     fn new(
         config: &Config,
         usage: &TokenUsage,
+        subagent_usage: Option<&TokenUsage>,
         session_id: &Option<ConversationId>,
         rate_limits: Option<&RateLimitSnapshotDisplay>,
     ) -> Self {
@@ -84,11 +100,13 @@ impl StatusHistoryCell {
         let agents_summary = compose_agents_summary(config);
         let account = compose_account_display(config);
         let session_id = session_id.as_ref().map(std::string::ToString::to_string);
-        let token_usage = StatusTokenUsageData {
-            total: usage.blended_total(),
-            input: usage.non_cached_input(),
-            output: usage.output_tokens,
-        };
+        let subagent_usage = subagent_usage.filter(|usage| !usage.is_zero());
+        let mut total_usage = usage.clone();
+        if let Some(subagent_usage) = subagent_usage {
+            total_usage.add_assign(subagent_usage);
+        }
+        let token_usage = StatusTokenUsageData::from(&total_usage);
+        let subagent_token_usage = subagent_usage.map(StatusTokenUsageData::from);
         let rate_limits = compose_rate_limit_data(rate_limits);
 
         Self {
@@ -101,14 +119,15 @@ impl StatusHistoryCell {
             account,
             session_id,
             token_usage,
+            subagent_token_usage,
             rate_limits,
         }
     }
 
-    fn token_usage_spans(&self) -> Vec<Span<'static>> {
-        let total_fmt = format_tokens_compact(self.token_usage.total);
-        let input_fmt = format_tokens_compact(self.token_usage.input);
-        let output_fmt = format_tokens_compact(self.token_usage.output);
+    fn token_usage_spans(usage: &StatusTokenUsageData) -> Vec<Span<'static>> {
+        let total_fmt = format_tokens_compact(usage.total);
+        let input_fmt = format_tokens_compact(usage.input);
+        let output_fmt = format_tokens_compact(usage.output);
 
         vec![
             Span::from(total_fmt),
@@ -235,6 +254,9 @@ impl HistoryCell for StatusHistoryCell {
             push_label(&mut labels, &mut seen, "Session");
         }
         push_label(&mut labels, &mut seen, "Token usage");
+        if self.subagent_token_usage.is_some() {
+            push_label(&mut labels, &mut seen, "Subagents");
+        }
         self.collect_rate_limit_labels(&mut seen, &mut labels);
 
         let formatter = FieldFormatter::from_labels(labels.iter().map(String::as_str));
@@ -264,7 +286,14 @@ impl HistoryCell for StatusHistoryCell {
         }
 
         lines.push(Line::from(Vec::<Span<'static>>::new()));
-        lines.push(formatter.line("Token usage", self.token_usage_spans()));
+        let mut usage_spans = Self::token_usage_spans(&self.token_usage);
+        if let Some(subagent_usage) = self.subagent_token_usage.as_ref() {
+            usage_spans.push(Span::from(" including subagents").dim());
+            lines.push(formatter.line("Token usage", usage_spans));
+            lines.push(formatter.line("Subagents", Self::token_usage_spans(subagent_usage)));
+        } else {
+            lines.push(formatter.line("Token usage", usage_spans));
+        }
 
         lines.extend(self.rate_limit_lines(available_inner_width, &formatter));
 
@@ -185,7 +185,15 @@ impl WidgetRef for StatusIndicatorWidget {
         let mut lines: Vec<Line<'static>> = Vec::new();
         lines.push(Line::from(spans));
         if let Some(summary) = &self.subagent_summary {
-            lines.push(Line::from(format!(" ↳ {summary}").dim()));
+            lines.push(
+                Line::from(vec![
+                    format!(" ↳ {summary}").into(),
+                    "   ".into(),
+                    key_hint::ctrl('G'),
+                    " view".into(),
+                ])
+                .dim(),
+            );
         }
         if !self.queued_messages.is_empty() {
             lines.push(Line::from(""));
@@ -298,6 +306,7 @@ mod tests {
             .expect("draw");
         let backend_debug = format!("{:?}", terminal.backend());
         assert!(backend_debug.contains("Subagents: 1 active"));
+        assert!(backend_debug.contains(" view"));
     }
 
     #[test]